@@ -14,6 +14,10 @@ set -e
 ~/.cargo/bin/hyperfine 'taskset -ac {cpu_count} /tmp/heca/release/heca --print=regular list 3766 --years 17000 --show yom-tov,minor-holidays,chol,special-parshas' --parameter-list cpu_count 1,0-32 --warmup 5 --export-markdown /tmp/2.md -u millisecond
 ~/.cargo/bin/hyperfine 'taskset -ac {cpu_count} /tmp/heca/release/heca --print=json list 3766 --years 17000 --show yom-tov,minor-holidays,chol,special-parshas --no-sort' --parameter-list cpu_count 1,0-32 --warmup 5 --export-markdown /tmp/3.md -u millisecond
 ~/.cargo/bin/hyperfine 'taskset -ac {cpu_count} /tmp/heca/release/heca --print=json list 3766 --years 17000 --show yom-tov,minor-holidays,chol,special-parshas' --parameter-list cpu_count 1,0-32 --warmup 5 --export-markdown /tmp/4.md -u millisecond
+~/.cargo/bin/hyperfine 'taskset -ac {cpu_count} /tmp/heca/release/heca --print=regular list 5780 --years 100 --events daf-yomi --no-sort' --parameter-list cpu_count 1,0-32 --warmup 5 --export-markdown /tmp/7.md -u millisecond
+~/.cargo/bin/hyperfine 'taskset -ac {cpu_count} /tmp/heca/release/heca --print=json list 5780 --years 100 --events daf-yomi' --parameter-list cpu_count 1,0-32 --warmup 5 --export-markdown /tmp/8.md -u millisecond
+~/.cargo/bin/hyperfine 'taskset -ac {cpu_count} /tmp/heca/release/heca --print=json list 5000 --years 1000 --show yom-tov,minor-holidays,chol,special-parshas' --parameter-list cpu_count 1,0-32 --warmup 5 --export-markdown /tmp/9.md -u millisecond
+~/.cargo/bin/hyperfine 'taskset -ac {cpu_count} /tmp/heca/release/heca --print=ics list 5000 --years 1000 --show yom-tov,minor-holidays,chol,special-parshas' --parameter-list cpu_count 1,0-32 --warmup 5 --export-markdown /tmp/10.md -u millisecond
 ~/.cargo/bin/hyperfine 'taskset -ac 1 hebcal 3766 --years 17000' --warmup 5 --export-markdown /tmp/5.md -u millisecond
 ~/.cargo/bin/hyperfine 'taskset -ac 0-32 hebcal 3766 --years 17000' --warmup 5 --export-markdown /tmp/6.md -u millisecond
 
@@ -0,0 +1,303 @@
+//! Generates the functions in `prelude::print` from the locale tables in `locales/`.
+//!
+//! Every file in `locales/` holds the names of the events in one language. `en.toml` is the
+//! reference locale: other locales fall back to it for any name they don't translate (which
+//! the tests in `prelude::print` report).
+//!
+//! Names of numbered days, such as the days of Sukkos or of the Omer, aren't written out one
+//! by one. The `numbered` table holds one template per event instead, such as
+//! `Sukkos = { days = 7, name = "{ordinal} day of Sukkos" }` under `[numbered.yom_tov]`, which
+//! names `YomTov::Sukkos1` to `YomTov::Sukkos7`. `{ordinal}` is replaced by the day counted in
+//! the locale's language, and `{number}` by its digits. Other locales may give just the name,
+//! and take the number of days from the reference locale.
+//!
+//! The files in `locales/short/` hold the abbreviated names printed with `list --short-names`,
+//! in the same format. They only abbreviate some of the names, and don't fall back to another
+//! locale: the rest are printed in full.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[path = "src/gematria/mod.rs"]
+#[allow(dead_code)]
+mod gematria;
+#[path = "src/prelude/numbers.rs"]
+mod numbers;
+
+const REFERENCE_LOCALE: &str = "en";
+
+/// The tables in every locale file, with the function generated for them and the type they name.
+const TABLES: &[(&str, &str)] = &[
+    ("minor_days", "MinorDays"),
+    ("month", "HebrewMonth"),
+    ("yom_tov", "YomTov"),
+    ("chol", "Chol"),
+    ("parsha", "Parsha"),
+    ("special_parsha", "SpecialParsha"),
+    ("special_shabbos", "SpecialShabbos"),
+    ("zman", "Zman"),
+    ("davening_change", "DaveningChange"),
+];
+
+struct Locale {
+    name: String,
+    language: String,
+    tables: BTreeMap<String, toml::value::Table>,
+    numbered: toml::value::Table,
+}
+
+fn read_locale(path: &Path) -> Locale {
+    let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+    let mut locale: toml::value::Table = toml::from_str(&fs::read_to_string(path).unwrap())
+        .unwrap_or_else(|e| panic!("Can't parse {}: {}", path.display(), e));
+    let language = match locale.remove("language") {
+        Some(toml::Value::String(language)) => language,
+        _ => panic!("{} must set `language`", path.display()),
+    };
+    let numbered = match locale.remove("numbered") {
+        Some(toml::Value::Table(numbered)) => numbered,
+        Some(_) => panic!("`numbered` in {} must be a table", path.display()),
+        None => toml::value::Table::new(),
+    };
+    let mut tables = BTreeMap::new();
+    for (table, values) in locale {
+        if !TABLES.iter().any(|(t, _)| *t == table) {
+            panic!("{} has an unknown table `{}`", path.display(), table);
+        }
+        match values {
+            toml::Value::Table(values) => tables.insert(table, values),
+            _ => panic!("`{}` in {} must be a table", table, path.display()),
+        };
+    }
+    Locale {
+        name,
+        language,
+        tables,
+        numbered,
+    }
+}
+
+/// Counts a day in the locale's language.
+fn ordinal(language: &str, number: u32) -> String {
+    match language {
+        "Hebrew" => gematria::hebrew_numeral(number),
+        _ => numbers::english_ordinal(number),
+    }
+}
+
+/// Adds the names of every numbered day in `locale` to its tables. `days` holds the number of
+/// days of every numbered event, keyed by table and event.
+fn expand_numbered(locale: &mut Locale, days: &mut BTreeMap<(String, String), u32>) {
+    for (table, events) in &locale.numbered {
+        if !TABLES.iter().any(|(t, _)| t == table) {
+            panic!("{} has an unknown table `numbered.{}`", locale.name, table);
+        }
+        let events = events
+            .as_table()
+            .unwrap_or_else(|| panic!("`numbered.{}` in {} must be a table", table, locale.name));
+        for (event, value) in events {
+            let key = (table.clone(), event.clone());
+            let name = match value {
+                toml::Value::String(name) => name,
+                toml::Value::Table(value) => {
+                    let count = value
+                        .get("days")
+                        .and_then(|x| x.as_integer())
+                        .unwrap_or_else(|| {
+                            panic!(
+                                "numbered.{}.{} in {} needs `days`",
+                                table, event, locale.name
+                            )
+                        }) as u32;
+                    if *days.entry(key.clone()).or_insert(count) != count {
+                        panic!(
+                            "numbered.{}.{} in {} has a different number of days",
+                            table, event, locale.name
+                        );
+                    }
+                    value
+                        .get("name")
+                        .and_then(|x| x.as_str())
+                        .unwrap_or_else(|| {
+                            panic!(
+                                "numbered.{}.{} in {} needs `name`",
+                                table, event, locale.name
+                            )
+                        })
+                }
+                _ => panic!(
+                    "numbered.{}.{} in {} must be a string or a table",
+                    table, event, locale.name
+                ),
+            };
+            let count = *days.get(&key).unwrap_or_else(|| {
+                panic!(
+                    "numbered.{}.{} in {} isn't in locales/{}.toml",
+                    table, event, locale.name, REFERENCE_LOCALE
+                )
+            });
+            let values = locale.tables.entry(table.clone()).or_default();
+            for number in 1..=count {
+                let value = name
+                    .replace("{ordinal}", &ordinal(&locale.language, number))
+                    .replace("{number}", &number.to_string());
+                let day = format!("{}{}", event, number);
+                if values
+                    .insert(day.clone(), toml::Value::String(value))
+                    .is_some()
+                {
+                    panic!(
+                        "{}.{} in {} is also numbered.{}.{}",
+                        table, day, locale.name, table, event
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn read_locales(dir: &str) -> Vec<Locale> {
+    println!("cargo:rerun-if-changed={}", dir);
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .map(|path| {
+            println!("cargo:rerun-if-changed={}", path.display());
+            read_locale(&path)
+        })
+        .collect()
+}
+
+/// Writes a function for every table, named `prefix` and the table, which returns the name of an
+/// event in a language. With `fallback`, it returns the name in the reference language for the
+/// events a locale doesn't name; without it, it returns an `Option`.
+fn write_tables(out: &mut String, locales: &[Locale], prefix: &str, fallback: Option<&str>) {
+    for (table, ty) in TABLES {
+        let returns = match fallback {
+            Some(_) => "&'static str",
+            None => "Option<&'static str>",
+        };
+        // A table no locale names anything in, like the short names of the parshiyos, can't
+        // match on the key at all.
+        if fallback.is_none()
+            && locales
+                .iter()
+                .all(|locale| !locale.tables.contains_key(*table))
+        {
+            writeln!(
+                out,
+                "fn {}{}(_key: {}, _language: Language) -> {} {{",
+                prefix, table, ty, returns
+            )
+            .unwrap();
+            writeln!(out, "    None").unwrap();
+            writeln!(out, "}}").unwrap();
+            continue;
+        }
+        writeln!(
+            out,
+            "fn {}{}(key: {}, language: Language) -> {} {{",
+            prefix, table, ty, returns
+        )
+        .unwrap();
+        writeln!(out, "    match language {{").unwrap();
+        for locale in locales {
+            let values = match locale.tables.get(*table).filter(|x| !x.is_empty()) {
+                Some(values) => values,
+                None => {
+                    match fallback {
+                        Some(reference_language) => writeln!(
+                            out,
+                            "        Language::{} => {}{}(key, Language::{}),",
+                            locale.language, prefix, table, reference_language
+                        ),
+                        None => writeln!(out, "        Language::{} => None,", locale.language),
+                    }
+                    .unwrap();
+                    continue;
+                }
+            };
+            writeln!(out, "        Language::{} => match key {{", locale.language).unwrap();
+            for (key, value) in values {
+                let value = value.as_str().unwrap_or_else(|| {
+                    panic!("{}.{} in {} must be a string", table, key, locale.name)
+                });
+                match fallback {
+                    Some(_) => writeln!(out, "            {}::{} => {:?},", ty, key, value),
+                    None => writeln!(out, "            {}::{} => Some({:?}),", ty, key, value),
+                }
+                .unwrap();
+            }
+            match fallback {
+                Some(_) if locale.name == REFERENCE_LOCALE => {}
+                Some(reference_language) => {
+                    writeln!(out, "            #[allow(unreachable_patterns)]").unwrap();
+                    writeln!(
+                        out,
+                        "            _ => {}{}(key, Language::{}),",
+                        prefix, table, reference_language
+                    )
+                    .unwrap();
+                }
+                None => {
+                    writeln!(out, "            #[allow(unreachable_patterns)]").unwrap();
+                    writeln!(out, "            _ => None,").unwrap();
+                }
+            }
+            writeln!(out, "        }},").unwrap();
+        }
+        // Short names may not be given for every language.
+        if fallback.is_none() {
+            writeln!(out, "        #[allow(unreachable_patterns)]").unwrap();
+            writeln!(out, "        _ => None,").unwrap();
+        }
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut locales = read_locales("locales");
+    // The reference locale comes first, so the other locales can fall back to it.
+    locales.sort_by_key(|locale| (locale.name != REFERENCE_LOCALE, locale.name.clone()));
+    if locales.first().map(|locale| locale.name.as_str()) != Some(REFERENCE_LOCALE) {
+        panic!("locales/{}.toml is missing", REFERENCE_LOCALE);
+    }
+    let reference_language = locales[0].language.clone();
+    let mut days = BTreeMap::new();
+    for locale in &mut locales {
+        expand_numbered(locale, &mut days);
+    }
+
+    let mut short_locales = read_locales("locales/short");
+    for locale in &mut short_locales {
+        expand_numbered(locale, &mut days);
+    }
+
+    let mut out = String::new();
+    write_tables(&mut out, &locales, "", Some(&reference_language));
+    write_tables(&mut out, &short_locales, "short_", None);
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("locale.rs");
+    fs::write(path, out).unwrap();
+
+    // The commit heca is built from, for `heca --print json --version`. Builds outside of a git
+    // checkout, such as from crates.io, don't have one.
+    if Path::new(".git").exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        println!("cargo:rerun-if-changed=.git/refs/heads");
+        let hash = std::process::Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success());
+        if let Some(hash) = hash {
+            let hash = String::from_utf8_lossy(&hash.stdout);
+            println!("cargo:rustc-env=HECA_GIT_HASH={}", hash.trim());
+        }
+    }
+}
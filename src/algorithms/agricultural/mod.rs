@@ -0,0 +1,112 @@
+//! The dates of the agricultural laws of Eretz Yisrael: the Shmita year, the new year of trees
+//! for orlah and maaser, the removal and confession of maasros, and the season of bikurim.
+
+use crate::args::types::{DayVal, Language, Name};
+use heca_lib::prelude::HebrewMonth;
+use heca_lib::HebrewYear;
+use serde::Deserialize;
+use std::io::Write;
+use std::num::NonZeroI8;
+
+/// Every seventh year, counted from year 0, is a Shmita year, like 5782 and 5789.
+pub fn year_of_shmita_cycle(year: u64) -> u64 {
+    match year % 7 {
+        0 => 7,
+        year => year,
+    }
+}
+
+pub fn get(year: &HebrewYear) -> Vec<DayVal> {
+    let cycle_year = year_of_shmita_cycle(year.year());
+    let day = |month, day, name| DayVal {
+        day: year
+            .get_hebrew_date(month, NonZeroI8::new(day).unwrap())
+            .unwrap()
+            .into(),
+        name: Name::Agricultural(name),
+        candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
+    };
+    let mut days = vec![];
+    if cycle_year == 7 {
+        days.push(day(HebrewMonth::Tishrei, 1, Agricultural::ShmitaBegins));
+    }
+    days.push(day(
+        HebrewMonth::Tishrei,
+        15,
+        Agricultural::BikurimReadingEnds,
+    ));
+    days.push(day(HebrewMonth::Kislev, 25, Agricultural::BikurimEnd));
+    days.push(day(HebrewMonth::Shvat, 15, Agricultural::NewYearForTrees));
+    // Maasros are removed from the house before Pesach of the 4th and 7th years.
+    if cycle_year == 4 || cycle_year == 7 {
+        days.push(day(HebrewMonth::Nissan, 14, Agricultural::BiurMaaser));
+        days.push(day(HebrewMonth::Nissan, 21, Agricultural::ViduyMaaser));
+    }
+    days.push(day(HebrewMonth::Sivan, 6, Agricultural::BikurimBegin));
+    if cycle_year == 7 {
+        days.push(day(HebrewMonth::Elul, 29, Agricultural::ShmitaEnds));
+    }
+    days
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum Agricultural {
+    ShmitaBegins,
+    /// The last day of Shmita, when loans are released unless a pruzbul was written.
+    ShmitaEnds,
+    /// Tu BiShvat, when the years of orlah and the maaser of fruit are counted from.
+    NewYearForTrees,
+    BiurMaaser,
+    ViduyMaaser,
+    BikurimBegin,
+    /// Sukkos, after which bikurim are brought without Mikra Bikurim.
+    BikurimReadingEnds,
+    BikurimEnd,
+}
+
+impl Agricultural {
+    /// Whether it's observed from the night, like the dates of the year, or during the day.
+    pub fn starts_at_night(&self) -> bool {
+        !matches!(
+            self,
+            Self::ShmitaEnds | Self::BiurMaaser | Self::ViduyMaaser
+        )
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        let p = match language {
+            Language::English => match self {
+                Self::ShmitaBegins => lock.write(b"Start of the Shmita Year").ok()?,
+                Self::ShmitaEnds => lock
+                    .write(b"End of the Shmita Year (Loans Released)")
+                    .ok()?,
+                Self::NewYearForTrees => {
+                    lock.write(b"New Year of Trees for Orlah and Maaser").ok()?
+                }
+                Self::BiurMaaser => lock.write(b"Biur Maasros").ok()?,
+                Self::ViduyMaaser => lock.write(b"Viduy Maaser").ok()?,
+                Self::BikurimBegin => lock.write(b"Bikurim Begin").ok()?,
+                Self::BikurimReadingEnds => {
+                    lock.write(b"Bikurim Brought Without Mikra Bikurim").ok()?
+                }
+                Self::BikurimEnd => lock.write(b"Bikurim End").ok()?,
+            },
+            Language::Hebrew => match self {
+                Self::ShmitaBegins => lock.write("תחילת שנת השמיטה".as_bytes()).ok()?,
+                Self::ShmitaEnds => lock.write("סוף שנת השמיטה (שמיטת כספים)".as_bytes()).ok()?,
+                Self::NewYearForTrees => lock
+                    .write("ראש השנה לאילן לערלה ולמעשרות".as_bytes())
+                    .ok()?,
+                Self::BiurMaaser => lock.write("ביעור מעשרות".as_bytes()).ok()?,
+                Self::ViduyMaaser => lock.write("וידוי מעשרות".as_bytes()).ok()?,
+                Self::BikurimBegin => lock.write("תחילת הבאת ביכורים".as_bytes()).ok()?,
+                Self::BikurimReadingEnds => lock.write("ביכורים בלא קריאה".as_bytes()).ok()?,
+                Self::BikurimEnd => lock.write("סוף הבאת ביכורים".as_bytes()).ok()?,
+            },
+        };
+        Some(p)
+    }
+}
@@ -0,0 +1,231 @@
+//! Arithmetic on Hebrew dates: the length of a year or month, moving a date by months, and the
+//! same date in another year, such as an anniversary or a yahrzeit.
+//!
+//! A date doesn't always exist in the year or month it's moved to. Adar is one month in a regular
+//! year and two in a leap year, so where a date in Adar goes is decided by an [`AdarPolicy`].
+//! Cheshvan and Kislev have 29 or 30 days, depending on the year, and Adar 1 always has 30 while
+//! Adar has 29, so where the 30th of a month goes when it has 29 days is decided by a
+//! [`MissingDayPolicy`].
+
+use heca_lib::prelude::{ConversionError, HebrewMonth};
+use heca_lib::{HebrewDate, HebrewYear};
+use std::num::NonZeroI8;
+
+/// Which Adar of a leap year a date in Adar of a regular year moves to. Dates in Adar 1 or
+/// Adar 2 move to Adar in a regular year, and stay in their own Adar in a leap year.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AdarPolicy {
+    /// Adar 2, the month Purim is in. A bar mitzvah of a boy born in Adar is in Adar 2.
+    Adar2,
+    /// Adar 1, where many keep the yahrzeit of someone who died in Adar.
+    Adar1,
+}
+
+/// Where the 30th of a month moves to in a year when the month has 29 days.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MissingDayPolicy {
+    /// The 29th, the last day of the month.
+    LastDay,
+    /// The 1st of the month after it, the day after the 29th.
+    NextMonth,
+}
+
+/// The months of the year, in order.
+pub fn months(year: u64) -> Result<&'static [HebrewMonth], ConversionError> {
+    use HebrewMonth::*;
+    Ok(if HebrewYear::new(year)?.is_leap_year() {
+        &[
+            Tishrei, Cheshvan, Kislev, Teves, Shvat, Adar1, Adar2, Nissan, Iyar, Sivan, Tammuz, Av,
+            Elul,
+        ]
+    } else {
+        &[
+            Tishrei, Cheshvan, Kislev, Teves, Shvat, Adar, Nissan, Iyar, Sivan, Tammuz, Av, Elul,
+        ]
+    })
+}
+
+/// The amount of days in the year: 353 to 355 in a regular year, and 383 to 385 in a leap year.
+pub fn days_in_year(year: u64) -> Result<i64, ConversionError> {
+    let rosh_hashanah = |year: u64| -> Result<chrono::DateTime<chrono::Utc>, ConversionError> {
+        Ok(HebrewDate::from_ymd(year, HebrewMonth::Tishrei, NonZeroI8::new(1).unwrap())?.into())
+    };
+    Ok((rosh_hashanah(year + 1)? - rosh_hashanah(year)?).num_days())
+}
+
+/// The amount of days in the month of the year, 29 or 30. Fails if the month isn't in the year,
+/// like Adar 1 in a regular year.
+pub fn days_in_month(year: u64, month: HebrewMonth) -> Result<i8, ConversionError> {
+    HebrewDate::from_ymd(year, month, NonZeroI8::new(1).unwrap())?;
+    Ok(
+        match HebrewDate::from_ymd(year, month, NonZeroI8::new(30).unwrap()) {
+            Ok(_) => 30,
+            Err(_) => 29,
+        },
+    )
+}
+
+/// Returns the day of the month, or where it moves to if the month is too short for it.
+fn day_in_month(
+    year: u64,
+    month: HebrewMonth,
+    day: i8,
+    missing_day: MissingDayPolicy,
+) -> Result<HebrewDate, ConversionError> {
+    let days = days_in_month(year, month)?;
+    if day <= days {
+        return HebrewDate::from_ymd(year, month, NonZeroI8::new(day).unwrap());
+    }
+    match missing_day {
+        MissingDayPolicy::LastDay => {
+            HebrewDate::from_ymd(year, month, NonZeroI8::new(days).unwrap())
+        }
+        MissingDayPolicy::NextMonth => add_hebrew_months(
+            HebrewDate::from_ymd(year, month, NonZeroI8::new(1).unwrap())?,
+            1,
+            missing_day,
+        ),
+    }
+}
+
+/// Moves the date by `months` months, forwards or backwards. Months are counted as they are in
+/// the calendar, so in a leap year Adar 1 and Adar 2 are two months: a month after the 10th of
+/// Shvat is the 10th of Adar 1 in a leap year, and of Adar in a regular year.
+pub fn add_hebrew_months(
+    date: HebrewDate,
+    months: i64,
+    missing_day: MissingDayPolicy,
+) -> Result<HebrewDate, ConversionError> {
+    let mut year = date.year();
+    let mut index = self::months(year)?
+        .iter()
+        .position(|x| *x == date.month())
+        .unwrap() as i64
+        + months;
+    while index < 0 {
+        year = year.checked_sub(1).ok_or(ConversionError::YearTooSmall)?;
+        index += self::months(year)?.len() as i64;
+    }
+    while index >= self::months(year)?.len() as i64 {
+        index -= self::months(year)?.len() as i64;
+        year += 1;
+    }
+    day_in_month(
+        year,
+        self::months(year)?[index as usize],
+        date.day().get(),
+        missing_day,
+    )
+}
+
+/// Returns the same date in another year, like the anniversary of a date.
+pub fn same_date_in_year(
+    date: HebrewDate,
+    year: u64,
+    adar: AdarPolicy,
+    missing_day: MissingDayPolicy,
+) -> Result<HebrewDate, ConversionError> {
+    let leap_year = HebrewYear::new(year)?.is_leap_year();
+    let month = match (date.month(), leap_year, adar) {
+        (HebrewMonth::Adar1, false, _) | (HebrewMonth::Adar2, false, _) => HebrewMonth::Adar,
+        (HebrewMonth::Adar, true, AdarPolicy::Adar1) => HebrewMonth::Adar1,
+        (HebrewMonth::Adar, true, AdarPolicy::Adar2) => HebrewMonth::Adar2,
+        (month, _, _) => month,
+    };
+    day_in_month(year, month, date.day().get(), missing_day)
+}
+
+/// Returns the same date in the next year.
+pub fn same_date_next_year(
+    date: HebrewDate,
+    adar: AdarPolicy,
+    missing_day: MissingDayPolicy,
+) -> Result<HebrewDate, ConversionError> {
+    same_date_in_year(date, date.year() + 1, adar, missing_day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn date(year: u64, month: HebrewMonth, day: i8) -> HebrewDate {
+        HebrewDate::from_ymd(year, month, NonZeroI8::new(day).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn lengths() {
+        // 5784 is a short leap year, 5785 a full year, 5786 a regular year and 5787 a full leap
+        // year.
+        assert_eq!(days_in_year(5784).unwrap(), 383);
+        assert_eq!(days_in_year(5785).unwrap(), 355);
+        assert_eq!(days_in_year(5786).unwrap(), 354);
+        assert_eq!(days_in_year(5787).unwrap(), 385);
+        assert_eq!(days_in_month(5785, HebrewMonth::Cheshvan).unwrap(), 30);
+        assert_eq!(days_in_month(5786, HebrewMonth::Cheshvan).unwrap(), 29);
+        assert_eq!(days_in_month(5784, HebrewMonth::Adar1).unwrap(), 30);
+        assert_eq!(days_in_month(5785, HebrewMonth::Adar).unwrap(), 29);
+        assert!(days_in_month(5785, HebrewMonth::Adar1).is_err());
+        assert_eq!(months(5784).unwrap().len(), 13);
+        assert_eq!(months(5785).unwrap().len(), 12);
+    }
+
+    #[test]
+    fn months_are_added() {
+        use MissingDayPolicy::*;
+        let shvat = date(5784, HebrewMonth::Shvat, 10);
+        assert_eq!(
+            add_hebrew_months(shvat, 1, LastDay).unwrap(),
+            date(5784, HebrewMonth::Adar1, 10)
+        );
+        assert_eq!(
+            add_hebrew_months(shvat, 13, LastDay).unwrap(),
+            date(5785, HebrewMonth::Shvat, 10)
+        );
+        assert_eq!(
+            add_hebrew_months(shvat, -5, LastDay).unwrap(),
+            date(5783, HebrewMonth::Elul, 10)
+        );
+        let adar1 = date(5784, HebrewMonth::Adar1, 30);
+        assert_eq!(
+            add_hebrew_months(adar1, 13, LastDay).unwrap(),
+            date(5785, HebrewMonth::Adar, 29)
+        );
+        assert_eq!(
+            add_hebrew_months(adar1, 13, NextMonth).unwrap(),
+            date(5785, HebrewMonth::Nissan, 1)
+        );
+    }
+
+    #[test]
+    fn same_date() {
+        use AdarPolicy::*;
+        use MissingDayPolicy::*;
+        let purim = date(5786, HebrewMonth::Adar, 14);
+        assert_eq!(
+            same_date_next_year(purim, Adar2, LastDay).unwrap(),
+            date(5787, HebrewMonth::Adar2, 14)
+        );
+        assert_eq!(
+            same_date_next_year(purim, Adar1, LastDay).unwrap(),
+            date(5787, HebrewMonth::Adar1, 14)
+        );
+        assert_eq!(
+            same_date_next_year(date(5784, HebrewMonth::Adar1, 7), Adar2, LastDay).unwrap(),
+            date(5785, HebrewMonth::Adar, 7)
+        );
+        // Cheshvan of 5785 has 30 days, and Cheshvan of 5786 has 29.
+        let cheshvan = date(5785, HebrewMonth::Cheshvan, 30);
+        assert_eq!(
+            same_date_next_year(cheshvan, Adar2, LastDay).unwrap(),
+            date(5786, HebrewMonth::Cheshvan, 29)
+        );
+        assert_eq!(
+            same_date_next_year(cheshvan, Adar2, NextMonth).unwrap(),
+            date(5786, HebrewMonth::Kislev, 1)
+        );
+        assert_eq!(
+            same_date_in_year(date(5786, HebrewMonth::Kislev, 30), 5784, Adar2, LastDay).unwrap(),
+            date(5784, HebrewMonth::Kislev, 29)
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use crate::args::types::{DayVal, Language, Name};
 use heca_lib::prelude::HebrewMonth;
 use heca_lib::HebrewYear;
+use serde::Deserialize;
 use std::convert::TryInto;
-use std::io::{BufWriter, StdoutLock, Write};
+use std::io::Write;
 use std::num::NonZeroI8;
 
 pub fn get(year: &HebrewYear) -> Vec<DayVal> {
@@ -27,6 +28,9 @@ fn get_yud_kislev(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::ChabadHoliday(ChabadHoliday::YudKislev),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -43,6 +47,9 @@ fn get_yud_tes_kislev(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::ChabadHoliday(ChabadHoliday::YudTesKislev),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -59,6 +66,9 @@ fn get_chof_kislev(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::ChabadHoliday(ChabadHoliday::ChofKislev),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -75,6 +85,9 @@ fn get_yud_beis_tammuz(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::ChabadHoliday(ChabadHoliday::YudBeisTammuz),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -91,11 +104,14 @@ fn get_yud_gimmel_tammuz(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::ChabadHoliday(ChabadHoliday::YudGimmelTammuz),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum ChabadHoliday {
     YudKislev,
     YudTesKislev,
@@ -105,11 +121,7 @@ pub enum ChabadHoliday {
 }
 
 impl ChabadHoliday {
-    pub fn pretty_print(
-        &self,
-        lock: &mut BufWriter<StdoutLock<'_>>,
-        language: Language,
-    ) -> Option<usize> {
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
         let p = match language {
             Language::English => match self {
                 Self::YudKislev => lock.write(b"Yud Kislev").ok()?,
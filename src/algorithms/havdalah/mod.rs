@@ -0,0 +1,42 @@
+use crate::algorithms::candle_lighting::City;
+use crate::algorithms::solar::{local_time, Sun};
+use crate::args::types::{Havdalah, HavdalahOpinion, PolarFallback};
+use chrono::prelude::*;
+use chrono::Duration;
+
+/// Returns the time of havdalah on the civil date according to each of the opinions. Near the
+/// poles, an opinion's time may not exist on that day; it's then `None`, unless the fallback says
+/// otherwise.
+pub fn get(
+    sun: &dyn Sun,
+    city: &City,
+    date: NaiveDate,
+    opinions: &[HavdalahOpinion],
+    polar_fallback: PolarFallback,
+) -> Vec<Havdalah> {
+    let sunset = sun.sunset(city, date);
+    opinions
+        .iter()
+        .map(|opinion| {
+            let time = match opinion.after_sunset() {
+                Some(minutes) => sunset.map(|sunset| sunset + Duration::minutes(minutes)),
+                None => sun
+                    .evening_depression(
+                        city.latitude,
+                        city.longitude,
+                        date,
+                        opinion.degrees_below_horizon(),
+                    )
+                    .or_else(|| match polar_fallback {
+                        PolarFallback::None => None,
+                        PolarFallback::Midnight => Some(sun.solar_midnight(city.longitude, date)),
+                    })
+                    .map(|time| local_time(&city.time_zone, time)),
+            };
+            Havdalah {
+                opinion: *opinion,
+                time,
+            }
+        })
+        .collect()
+}
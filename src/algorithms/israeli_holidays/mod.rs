@@ -2,8 +2,9 @@ use crate::args::types::{DayVal, Language, Name};
 
 use heca_lib::prelude::{HebrewMonth, MonthSchedule};
 use heca_lib::HebrewYear;
+use serde::Deserialize;
 use std::convert::TryInto;
-use std::io::{BufWriter, StdoutLock, Write};
+use std::io::Write;
 use std::num::NonZeroI8;
 
 pub fn get(year: &HebrewYear, exact_days: bool) -> Vec<DayVal> {
@@ -28,6 +29,9 @@ fn get_yom_haaliyah(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::IsraeliHoliday(IsraeliHoliday::YomHaAliyah),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -44,6 +48,9 @@ fn get_yom_yerushalayim(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::IsraeliHoliday(IsraeliHoliday::YomYerushalayim),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -60,6 +67,9 @@ fn get_sigd(year: &HebrewYear) -> Option<DayVal> {
                 .unwrap(),
             name: Name::IsraeliHoliday(IsraeliHoliday::Sigd),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -93,6 +103,9 @@ fn get_yom_hashoah(year: &HebrewYear, exact_days: bool) -> Option<DayVal> {
                 .unwrap(),
             name: Name::IsraeliHoliday(IsraeliHoliday::YomHaShoah),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         })
     }
 }
@@ -132,6 +145,9 @@ fn get_yom_haatzmaut_and_yom_hazikaron(year: &HebrewYear, exact_days: bool) -> V
             .unwrap(),
         name: Name::IsraeliHoliday(IsraeliHoliday::YomHaZikaron),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     };
     let yom_haatzmaut = DayVal {
         day: year
@@ -141,11 +157,14 @@ fn get_yom_haatzmaut_and_yom_hazikaron(year: &HebrewYear, exact_days: bool) -> V
             .unwrap(),
         name: Name::IsraeliHoliday(IsraeliHoliday::YomHaAtzmaut),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     };
     vec![yom_hazikaron, yom_haatzmaut]
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum IsraeliHoliday {
     YomHaZikaron,
     YomHaAtzmaut,
@@ -156,11 +175,7 @@ pub enum IsraeliHoliday {
 }
 
 impl IsraeliHoliday {
-    pub fn pretty_print(
-        &self,
-        lock: &mut BufWriter<StdoutLock<'_>>,
-        language: Language,
-    ) -> Option<usize> {
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
         let p = match language {
             Language::English => match self {
                 Self::YomHaAtzmaut => lock.write(b"Yom HaAtzmaut").ok()?,
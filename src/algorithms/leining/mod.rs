@@ -0,0 +1,1328 @@
+//! The aliyos, maftir and haftarah of every parsha, and the readings which replace the maftir
+//! and haftarah on special Shabbosos. Verses are numbered as in Hebrew chumashim, which differ
+//! from English translations in a few chapters.
+
+use crate::algorithms::reading_cycle::{split_parsha, Book};
+use crate::args::types::Language;
+use heca_lib::prelude::{Chol, Parsha, SpecialParsha};
+use serde::{Serialize, Serializer};
+
+/// The amount of verses in every chapter of the books of the Torah.
+const GENESIS: [u8; 50] = [
+    31, 25, 24, 26, 32, 22, 24, 22, 29, 32, 32, 20, 18, 24, 21, 16, 27, 33, 38, 18, 34, 24, 20, 67,
+    34, 35, 46, 22, 35, 43, 54, 33, 20, 31, 29, 43, 36, 30, 23, 23, 57, 38, 34, 34, 28, 34, 31, 22,
+    33, 26,
+];
+const EXODUS: [u8; 40] = [
+    22, 25, 22, 31, 23, 30, 29, 28, 35, 29, 10, 51, 22, 31, 27, 36, 16, 27, 25, 23, 37, 30, 33, 18,
+    40, 37, 21, 43, 46, 38, 18, 35, 23, 35, 35, 38, 29, 31, 43, 38,
+];
+const LEVITICUS: [u8; 27] = [
+    17, 16, 17, 35, 26, 23, 38, 36, 24, 20, 47, 8, 59, 57, 33, 34, 16, 30, 37, 27, 24, 33, 44, 23,
+    55, 46, 34,
+];
+const NUMBERS: [u8; 36] = [
+    54, 34, 51, 49, 31, 27, 89, 26, 23, 36, 35, 16, 33, 45, 41, 35, 28, 32, 22, 29, 35, 41, 30, 25,
+    19, 65, 23, 31, 39, 17, 54, 42, 56, 29, 34, 13,
+];
+const DEUTERONOMY: [u8; 34] = [
+    46, 37, 29, 49, 30, 25, 26, 20, 29, 22, 32, 31, 19, 29, 23, 22, 20, 22, 21, 20, 23, 29, 26, 22,
+    19, 19, 26, 69, 28, 20, 30, 52, 29, 12,
+];
+
+fn chapters(book: Book) -> &'static [u8] {
+    match book {
+        Book::Genesis => &GENESIS,
+        Book::Exodus => &EXODUS,
+        Book::Leviticus => &LEVITICUS,
+        Book::Numbers => &NUMBERS,
+        Book::Deuteronomy => &DEUTERONOMY,
+    }
+}
+
+/// A book of Nevi'im, which haftaros are read from.
+#[derive(Debug, Clone, Copy)]
+pub enum Navi {
+    Joshua,
+    Judges,
+    ISamuel,
+    IISamuel,
+    IKings,
+    IIKings,
+    Isaiah,
+    Jeremiah,
+    Ezekiel,
+    Hosea,
+    Joel,
+    Amos,
+    Micah,
+    Zechariah,
+    Malachi,
+}
+
+impl Navi {
+    pub fn english(self) -> &'static str {
+        match self {
+            Navi::Joshua => "Joshua",
+            Navi::Judges => "Judges",
+            Navi::ISamuel => "I Samuel",
+            Navi::IISamuel => "II Samuel",
+            Navi::IKings => "I Kings",
+            Navi::IIKings => "II Kings",
+            Navi::Isaiah => "Isaiah",
+            Navi::Jeremiah => "Jeremiah",
+            Navi::Ezekiel => "Ezekiel",
+            Navi::Hosea => "Hosea",
+            Navi::Joel => "Joel",
+            Navi::Amos => "Amos",
+            Navi::Micah => "Micah",
+            Navi::Zechariah => "Zechariah",
+            Navi::Malachi => "Malachi",
+        }
+    }
+
+    pub fn hebrew(self) -> &'static str {
+        match self {
+            Navi::Joshua => "יהושע",
+            Navi::Judges => "שופטים",
+            Navi::ISamuel => "שמואל א",
+            Navi::IISamuel => "שמואל ב",
+            Navi::IKings => "מלכים א",
+            Navi::IIKings => "מלכים ב",
+            Navi::Isaiah => "ישעיהו",
+            Navi::Jeremiah => "ירמיהו",
+            Navi::Ezekiel => "יחזקאל",
+            Navi::Hosea => "הושע",
+            Navi::Joel => "יואל",
+            Navi::Amos => "עמוס",
+            Navi::Micah => "מיכה",
+            Navi::Zechariah => "זכריה",
+            Navi::Malachi => "מלאכי",
+        }
+    }
+}
+
+/// The book a range of verses is in.
+#[derive(Debug, Clone, Copy)]
+pub enum Sefer {
+    Torah(Book),
+    Navi(Navi),
+}
+
+impl Sefer {
+    pub fn name(self, language: Language) -> &'static str {
+        match (self, language) {
+            (Sefer::Torah(book), Language::English) => book.english(),
+            (Sefer::Torah(book), Language::Hebrew) => book.hebrew(),
+            (Sefer::Navi(navi), Language::English) => navi.english(),
+            (Sefer::Navi(navi), Language::Hebrew) => navi.hebrew(),
+        }
+    }
+}
+
+/// A range of verses in one book, like Exodus 30:11-30:16, as chapters and verses.
+#[derive(Debug, Clone, Copy)]
+pub struct Verses {
+    pub sefer: Sefer,
+    pub from: (u8, u8),
+    pub to: (u8, u8),
+}
+
+impl Verses {
+    /// Reads a range written like `30:11-30:16`, or a single verse like `3:4`.
+    fn new(sefer: Sefer, range: &str) -> Self {
+        let verse = |verse: &str| {
+            let (chapter, verse) = verse.split_once(':').unwrap();
+            (chapter.parse().unwrap(), verse.parse().unwrap())
+        };
+        let (from, to) = range.split_once('-').unwrap_or((range, range));
+        Self {
+            sefer,
+            from: verse(from),
+            to: verse(to),
+        }
+    }
+
+    fn torah(book: Book, range: &str) -> Self {
+        Self::new(Sefer::Torah(book), range)
+    }
+
+    fn navi(navi: Navi, range: &str) -> Self {
+        Self::new(Sefer::Navi(navi), range)
+    }
+
+    /// The amount of verses in the range. Only verses of the Torah are counted.
+    pub fn count(&self) -> Option<u16> {
+        let book = match self.sefer {
+            Sefer::Torah(book) => book,
+            Sefer::Navi(_) => return None,
+        };
+        let chapters = chapters(book);
+        let before = |(chapter, verse): (u8, u8)| -> u16 {
+            chapters[..chapter as usize - 1]
+                .iter()
+                .map(|&x| u16::from(x))
+                .sum::<u16>()
+                + u16::from(verse)
+        };
+        Some(before(self.to) - before(self.from) + 1)
+    }
+
+    /// The chapters and verses, like `30:11-30:16`.
+    pub fn range(&self) -> String {
+        if self.from == self.to {
+            format!("{}:{}", self.from.0, self.from.1)
+        } else {
+            format!(
+                "{}:{}-{}:{}",
+                self.from.0, self.from.1, self.to.0, self.to.1
+            )
+        }
+    }
+}
+
+impl Serialize for Verses {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&format!(
+            "{} {}",
+            self.sefer.name(Language::English),
+            self.range()
+        ))
+    }
+}
+
+/// The aliyos of a single parsha, its maftir and its haftarah, as read by Ashkenazim.
+#[derive(Debug)]
+struct Leining {
+    book: Book,
+    aliyos: [&'static str; 7],
+    maftir: &'static str,
+    haftarah: &'static [(Navi, &'static str)],
+}
+
+macro_rules! leining {
+    ($name:ident, $book:ident, $aliyos:expr, $maftir:expr, [$(($navi:ident, $range:expr)),*]) => {
+        const $name: Leining = Leining {
+            book: Book::$book,
+            aliyos: $aliyos,
+            maftir: $maftir,
+            haftarah: &[$((Navi::$navi, $range)),*],
+        };
+    };
+}
+
+leining!(
+    BEREISHIS,
+    Genesis,
+    [
+        "1:1-2:3",
+        "2:4-2:19",
+        "2:20-3:21",
+        "3:22-4:18",
+        "4:19-4:22",
+        "4:23-5:24",
+        "5:25-6:8"
+    ],
+    "6:5-6:8",
+    [(Isaiah, "42:5-43:10")]
+);
+leining!(
+    NOACH,
+    Genesis,
+    [
+        "6:9-6:22",
+        "7:1-7:16",
+        "7:17-8:14",
+        "8:15-9:7",
+        "9:8-9:17",
+        "9:18-10:32",
+        "11:1-11:32"
+    ],
+    "11:29-11:32",
+    [(Isaiah, "54:1-55:5")]
+);
+leining!(
+    LECH_LECHA,
+    Genesis,
+    [
+        "12:1-12:13",
+        "12:14-13:4",
+        "13:5-13:18",
+        "14:1-14:20",
+        "14:21-15:6",
+        "15:7-16:16",
+        "17:1-17:27"
+    ],
+    "17:24-17:27",
+    [(Isaiah, "40:27-41:16")]
+);
+leining!(
+    VAYEIRA,
+    Genesis,
+    [
+        "18:1-18:14",
+        "18:15-18:33",
+        "19:1-19:20",
+        "19:21-21:4",
+        "21:5-21:21",
+        "21:22-21:34",
+        "22:1-22:24"
+    ],
+    "22:20-22:24",
+    [(IIKings, "4:1-4:37")]
+);
+leining!(
+    CHAYEI_SARA,
+    Genesis,
+    [
+        "23:1-23:16",
+        "23:17-24:9",
+        "24:10-24:26",
+        "24:27-24:52",
+        "24:53-24:67",
+        "25:1-25:11",
+        "25:12-25:18"
+    ],
+    "25:16-25:18",
+    [(IKings, "1:1-1:31")]
+);
+leining!(
+    TOLDOS,
+    Genesis,
+    [
+        "25:19-26:5",
+        "26:6-26:12",
+        "26:13-26:22",
+        "26:23-26:29",
+        "26:30-27:27",
+        "27:28-28:4",
+        "28:5-28:9"
+    ],
+    "28:7-28:9",
+    [(Malachi, "1:1-2:7")]
+);
+leining!(
+    VAYETZEI,
+    Genesis,
+    [
+        "28:10-28:22",
+        "29:1-29:17",
+        "29:18-30:13",
+        "30:14-30:27",
+        "30:28-31:16",
+        "31:17-31:42",
+        "31:43-32:3"
+    ],
+    "32:1-32:3",
+    [(Hosea, "12:13-14:10")]
+);
+leining!(
+    VAYISHLACH,
+    Genesis,
+    [
+        "32:4-32:13",
+        "32:14-32:30",
+        "32:31-33:5",
+        "33:6-33:20",
+        "34:1-35:12",
+        "35:13-36:19",
+        "36:20-36:43"
+    ],
+    "36:40-36:43",
+    [(Hosea, "11:7-12:12")]
+);
+leining!(
+    VAYESHEV,
+    Genesis,
+    [
+        "37:1-37:11",
+        "37:12-37:22",
+        "37:23-37:36",
+        "38:1-38:30",
+        "39:1-39:6",
+        "39:7-39:23",
+        "40:1-40:23"
+    ],
+    "40:20-40:23",
+    [(Amos, "2:6-3:8")]
+);
+leining!(
+    MIKETZ,
+    Genesis,
+    [
+        "41:1-41:14",
+        "41:15-41:38",
+        "41:39-41:52",
+        "41:53-42:18",
+        "42:19-43:15",
+        "43:16-43:29",
+        "43:30-44:17"
+    ],
+    "44:14-44:17",
+    [(IKings, "3:15-4:1")]
+);
+leining!(
+    VAYIGASH,
+    Genesis,
+    [
+        "44:18-44:30",
+        "44:31-45:7",
+        "45:8-45:18",
+        "45:19-45:27",
+        "45:28-46:27",
+        "46:28-47:10",
+        "47:11-47:27"
+    ],
+    "47:25-47:27",
+    [(Ezekiel, "37:15-37:28")]
+);
+leining!(
+    VAYECHI,
+    Genesis,
+    [
+        "47:28-48:9",
+        "48:10-48:16",
+        "48:17-48:22",
+        "49:1-49:18",
+        "49:19-49:26",
+        "49:27-50:20",
+        "50:21-50:26"
+    ],
+    "50:23-50:26",
+    [(IKings, "2:1-2:12")]
+);
+leining!(
+    SHEMOS,
+    Exodus,
+    [
+        "1:1-1:17",
+        "1:18-2:10",
+        "2:11-2:25",
+        "3:1-3:15",
+        "3:16-4:17",
+        "4:18-4:31",
+        "5:1-6:1"
+    ],
+    "5:22-6:1",
+    [(Isaiah, "27:6-28:13"), (Isaiah, "29:22-29:23")]
+);
+leining!(
+    VAEIRA,
+    Exodus,
+    [
+        "6:2-6:13",
+        "6:14-6:28",
+        "6:29-7:7",
+        "7:8-8:6",
+        "8:7-8:18",
+        "8:19-9:16",
+        "9:17-9:35"
+    ],
+    "9:33-9:35",
+    [(Ezekiel, "28:25-29:21")]
+);
+leining!(
+    BO,
+    Exodus,
+    [
+        "10:1-10:11",
+        "10:12-10:23",
+        "10:24-11:3",
+        "11:4-12:20",
+        "12:21-12:28",
+        "12:29-12:51",
+        "13:1-13:16"
+    ],
+    "13:14-13:16",
+    [(Jeremiah, "46:13-46:28")]
+);
+leining!(
+    BESHALACH,
+    Exodus,
+    [
+        "13:17-14:8",
+        "14:9-14:14",
+        "14:15-14:25",
+        "14:26-15:26",
+        "15:27-16:10",
+        "16:11-16:36",
+        "17:1-17:16"
+    ],
+    "17:14-17:16",
+    [(Judges, "4:4-5:31")]
+);
+leining!(
+    YISRO,
+    Exodus,
+    [
+        "18:1-18:12",
+        "18:13-18:23",
+        "18:24-18:27",
+        "19:1-19:6",
+        "19:7-19:19",
+        "19:20-20:14",
+        "20:15-20:23"
+    ],
+    "20:19-20:23",
+    [(Isaiah, "6:1-7:6"), (Isaiah, "9:5-9:6")]
+);
+leining!(
+    MISHPATIM,
+    Exodus,
+    [
+        "21:1-21:19",
+        "21:20-22:3",
+        "22:4-22:26",
+        "22:27-23:5",
+        "23:6-23:19",
+        "23:20-23:25",
+        "23:26-24:18"
+    ],
+    "24:16-24:18",
+    [(Jeremiah, "34:8-34:22"), (Jeremiah, "33:25-33:26")]
+);
+leining!(
+    TERUMAH,
+    Exodus,
+    [
+        "25:1-25:16",
+        "25:17-25:30",
+        "25:31-25:40",
+        "26:1-26:14",
+        "26:15-26:30",
+        "26:31-26:37",
+        "27:1-27:19"
+    ],
+    "27:17-27:19",
+    [(IKings, "5:26-6:13")]
+);
+leining!(
+    TETZAVEH,
+    Exodus,
+    [
+        "27:20-28:12",
+        "28:13-28:30",
+        "28:31-28:43",
+        "29:1-29:18",
+        "29:19-29:37",
+        "29:38-29:46",
+        "30:1-30:10"
+    ],
+    "30:8-30:10",
+    [(Ezekiel, "43:10-43:27")]
+);
+leining!(
+    KI_SISA,
+    Exodus,
+    [
+        "30:11-31:17",
+        "31:18-33:11",
+        "33:12-33:16",
+        "33:17-33:23",
+        "34:1-34:9",
+        "34:10-34:26",
+        "34:27-34:35"
+    ],
+    "34:33-34:35",
+    [(IKings, "18:1-18:39")]
+);
+leining!(
+    VAYAKHEL,
+    Exodus,
+    [
+        "35:1-35:20",
+        "35:21-35:29",
+        "35:30-36:7",
+        "36:8-36:19",
+        "36:20-37:16",
+        "37:17-37:29",
+        "38:1-38:20"
+    ],
+    "38:18-38:20",
+    [(IKings, "7:40-7:50")]
+);
+leining!(
+    PIKUDEI,
+    Exodus,
+    [
+        "38:21-38:31",
+        "39:1-39:21",
+        "39:22-39:32",
+        "39:33-39:43",
+        "40:1-40:16",
+        "40:17-40:27",
+        "40:28-40:38"
+    ],
+    "40:34-40:38",
+    [(IKings, "7:51-8:21")]
+);
+leining!(
+    VAYIKRA,
+    Leviticus,
+    [
+        "1:1-1:13",
+        "1:14-2:6",
+        "2:7-2:16",
+        "3:1-3:17",
+        "4:1-4:26",
+        "4:27-5:10",
+        "5:11-5:26"
+    ],
+    "5:24-5:26",
+    [(Isaiah, "43:21-44:23")]
+);
+leining!(
+    TZAV,
+    Leviticus,
+    [
+        "6:1-6:11",
+        "6:12-6:23",
+        "7:1-7:10",
+        "7:11-7:37",
+        "7:38-8:13",
+        "8:14-8:21",
+        "8:22-8:36"
+    ],
+    "8:33-8:36",
+    [(Jeremiah, "7:21-8:3"), (Jeremiah, "9:22-9:23")]
+);
+leining!(
+    SHEMINI,
+    Leviticus,
+    [
+        "9:1-9:16",
+        "9:17-9:23",
+        "9:24-10:11",
+        "10:12-10:15",
+        "10:16-10:20",
+        "11:1-11:32",
+        "11:33-11:47"
+    ],
+    "11:45-11:47",
+    [(IISamuel, "6:1-7:17")]
+);
+leining!(
+    TAZRIYA,
+    Leviticus,
+    [
+        "12:1-13:5",
+        "13:6-13:17",
+        "13:18-13:23",
+        "13:24-13:28",
+        "13:29-13:39",
+        "13:40-13:54",
+        "13:55-13:59"
+    ],
+    "13:56-13:59",
+    [(IIKings, "4:42-5:19")]
+);
+leining!(
+    METZORAH,
+    Leviticus,
+    [
+        "14:1-14:12",
+        "14:13-14:20",
+        "14:21-14:32",
+        "14:33-14:57",
+        "15:1-15:15",
+        "15:16-15:28",
+        "15:29-15:33"
+    ],
+    "15:31-15:33",
+    [(IIKings, "7:3-7:20")]
+);
+leining!(
+    ACHAREI_MOS,
+    Leviticus,
+    [
+        "16:1-16:17",
+        "16:18-16:24",
+        "16:25-16:34",
+        "17:1-17:7",
+        "17:8-17:16",
+        "18:1-18:21",
+        "18:22-18:30"
+    ],
+    "18:28-18:30",
+    [(Ezekiel, "22:1-22:19")]
+);
+leining!(
+    KEDOSHIM,
+    Leviticus,
+    [
+        "19:1-19:14",
+        "19:15-19:22",
+        "19:23-19:32",
+        "19:33-19:37",
+        "20:1-20:7",
+        "20:8-20:22",
+        "20:23-20:27"
+    ],
+    "20:25-20:27",
+    [(Amos, "9:7-9:15")]
+);
+leining!(
+    EMOR,
+    Leviticus,
+    [
+        "21:1-21:15",
+        "21:16-22:16",
+        "22:17-22:33",
+        "23:1-23:22",
+        "23:23-23:32",
+        "23:33-23:44",
+        "24:1-24:23"
+    ],
+    "24:21-24:23",
+    [(Ezekiel, "44:15-44:31")]
+);
+leining!(
+    BEHAR,
+    Leviticus,
+    [
+        "25:1-25:13",
+        "25:14-25:18",
+        "25:19-25:24",
+        "25:25-25:28",
+        "25:29-25:38",
+        "25:39-25:46",
+        "25:47-26:2"
+    ],
+    "25:55-26:2",
+    [(Jeremiah, "32:6-32:27")]
+);
+leining!(
+    BECHUKOSAI,
+    Leviticus,
+    [
+        "26:3-26:5",
+        "26:6-26:9",
+        "26:10-26:46",
+        "27:1-27:15",
+        "27:16-27:21",
+        "27:22-27:28",
+        "27:29-27:34"
+    ],
+    "27:32-27:34",
+    [(Jeremiah, "16:19-17:14")]
+);
+leining!(
+    BAMIDBAR,
+    Numbers,
+    [
+        "1:1-1:19",
+        "1:20-1:54",
+        "2:1-2:34",
+        "3:1-3:13",
+        "3:14-3:39",
+        "3:40-3:51",
+        "4:1-4:20"
+    ],
+    "4:17-4:20",
+    [(Hosea, "2:1-2:22")]
+);
+leining!(
+    NASO,
+    Numbers,
+    [
+        "4:21-4:37",
+        "4:38-4:49",
+        "5:1-5:10",
+        "5:11-6:27",
+        "7:1-7:41",
+        "7:42-7:71",
+        "7:72-7:89"
+    ],
+    "7:87-7:89",
+    [(Judges, "13:2-13:25")]
+);
+leining!(
+    BEHAALOSCHA,
+    Numbers,
+    [
+        "8:1-8:14",
+        "8:15-8:26",
+        "9:1-9:14",
+        "9:15-10:10",
+        "10:11-10:34",
+        "10:35-11:29",
+        "11:30-12:16"
+    ],
+    "12:14-12:16",
+    [(Zechariah, "2:14-4:7")]
+);
+leining!(
+    SHLACH,
+    Numbers,
+    [
+        "13:1-13:20",
+        "13:21-14:7",
+        "14:8-14:25",
+        "14:26-15:7",
+        "15:8-15:16",
+        "15:17-15:26",
+        "15:27-15:41"
+    ],
+    "15:37-15:41",
+    [(Joshua, "2:1-2:24")]
+);
+leining!(
+    KORACH,
+    Numbers,
+    [
+        "16:1-16:13",
+        "16:14-16:19",
+        "16:20-17:8",
+        "17:9-17:15",
+        "17:16-17:24",
+        "17:25-18:20",
+        "18:21-18:32"
+    ],
+    "18:30-18:32",
+    [(ISamuel, "11:14-12:22")]
+);
+leining!(
+    CHUKAS,
+    Numbers,
+    [
+        "19:1-19:17",
+        "19:18-20:6",
+        "20:7-20:13",
+        "20:14-20:21",
+        "20:22-21:9",
+        "21:10-21:20",
+        "21:21-22:1"
+    ],
+    "21:34-22:1",
+    [(Judges, "11:1-11:33")]
+);
+leining!(
+    BALAK,
+    Numbers,
+    [
+        "22:2-22:12",
+        "22:13-22:20",
+        "22:21-22:38",
+        "22:39-23:12",
+        "23:13-23:26",
+        "23:27-24:13",
+        "24:14-25:9"
+    ],
+    "25:7-25:9",
+    [(Micah, "5:6-6:8")]
+);
+leining!(
+    PINCHAS,
+    Numbers,
+    [
+        "25:10-26:4",
+        "26:5-26:51",
+        "26:52-27:5",
+        "27:6-27:14",
+        "27:15-27:23",
+        "28:1-28:15",
+        "28:16-30:1"
+    ],
+    "29:35-30:1",
+    [(IKings, "18:46-19:21")]
+);
+leining!(
+    MATOS,
+    Numbers,
+    [
+        "30:2-30:17",
+        "31:1-31:12",
+        "31:13-31:24",
+        "31:25-31:41",
+        "31:42-31:54",
+        "32:1-32:19",
+        "32:20-32:42"
+    ],
+    "32:39-32:42",
+    [(Jeremiah, "1:1-2:3")]
+);
+leining!(
+    MAASEI,
+    Numbers,
+    [
+        "33:1-33:10",
+        "33:11-33:49",
+        "33:50-34:15",
+        "34:16-34:29",
+        "35:1-35:8",
+        "35:9-35:34",
+        "36:1-36:13"
+    ],
+    "36:11-36:13",
+    [(Jeremiah, "2:4-2:28"), (Jeremiah, "3:4")]
+);
+leining!(
+    DEVARIM,
+    Deuteronomy,
+    [
+        "1:1-1:11",
+        "1:12-1:21",
+        "1:22-1:38",
+        "1:39-2:1",
+        "2:2-2:30",
+        "2:31-3:14",
+        "3:15-3:22"
+    ],
+    "3:20-3:22",
+    [(Isaiah, "1:1-1:27")]
+);
+leining!(
+    VAESCHANAN,
+    Deuteronomy,
+    [
+        "3:23-4:4",
+        "4:5-4:40",
+        "4:41-4:49",
+        "5:1-5:18",
+        "5:19-6:3",
+        "6:4-6:25",
+        "7:1-7:11"
+    ],
+    "7:9-7:11",
+    [(Isaiah, "40:1-40:26")]
+);
+leining!(
+    EIKEV,
+    Deuteronomy,
+    [
+        "7:12-8:10",
+        "8:11-9:3",
+        "9:4-9:29",
+        "10:1-10:11",
+        "10:12-11:9",
+        "11:10-11:21",
+        "11:22-11:25"
+    ],
+    "11:22-11:25",
+    [(Isaiah, "49:14-51:3")]
+);
+leining!(
+    REEH,
+    Deuteronomy,
+    [
+        "11:26-12:10",
+        "12:11-12:28",
+        "12:29-13:19",
+        "14:1-14:21",
+        "14:22-14:29",
+        "15:1-15:18",
+        "15:19-16:17"
+    ],
+    "16:13-16:17",
+    [(Isaiah, "54:11-55:5")]
+);
+leining!(
+    SHOFTIM,
+    Deuteronomy,
+    [
+        "16:18-17:13",
+        "17:14-17:20",
+        "18:1-18:5",
+        "18:6-18:13",
+        "18:14-19:13",
+        "19:14-20:9",
+        "20:10-21:9"
+    ],
+    "21:7-21:9",
+    [(Isaiah, "51:12-52:12")]
+);
+leining!(
+    KI_SEITZEI,
+    Deuteronomy,
+    [
+        "21:10-21:21",
+        "21:22-22:7",
+        "22:8-23:7",
+        "23:8-23:24",
+        "23:25-24:4",
+        "24:5-24:13",
+        "24:14-25:19"
+    ],
+    "25:17-25:19",
+    [(Isaiah, "54:1-54:10")]
+);
+leining!(
+    KI_SAVOH,
+    Deuteronomy,
+    [
+        "26:1-26:11",
+        "26:12-26:15",
+        "26:16-26:19",
+        "27:1-27:10",
+        "27:11-28:6",
+        "28:7-28:69",
+        "29:1-29:8"
+    ],
+    "29:6-29:8",
+    [(Isaiah, "60:1-60:22")]
+);
+leining!(
+    NITZAVIM,
+    Deuteronomy,
+    [
+        "29:9-29:11",
+        "29:12-29:14",
+        "29:15-29:28",
+        "30:1-30:6",
+        "30:7-30:10",
+        "30:11-30:14",
+        "30:15-30:20"
+    ],
+    "30:18-30:20",
+    [(Isaiah, "61:10-63:9")]
+);
+leining!(
+    VAYELECH,
+    Deuteronomy,
+    [
+        "31:1-31:3",
+        "31:4-31:6",
+        "31:7-31:9",
+        "31:10-31:13",
+        "31:14-31:19",
+        "31:20-31:24",
+        "31:25-31:30"
+    ],
+    "31:28-31:30",
+    [(Isaiah, "55:6-56:8")]
+);
+leining!(
+    HAAZINU,
+    Deuteronomy,
+    [
+        "32:1-32:6",
+        "32:7-32:12",
+        "32:13-32:18",
+        "32:19-32:28",
+        "32:29-32:39",
+        "32:40-32:43",
+        "32:44-32:52"
+    ],
+    "32:48-32:52",
+    [(IISamuel, "22:1-22:51")]
+);
+
+fn get_leining(parsha: Parsha) -> &'static Leining {
+    match parsha {
+        Parsha::Vayelech => &VAYELECH,
+        Parsha::Haazinu => &HAAZINU,
+        Parsha::Bereishis => &BEREISHIS,
+        Parsha::Noach => &NOACH,
+        Parsha::LechLecha => &LECH_LECHA,
+        Parsha::Vayeira => &VAYEIRA,
+        Parsha::ChayeiSara => &CHAYEI_SARA,
+        Parsha::Toldos => &TOLDOS,
+        Parsha::Vayetzei => &VAYETZEI,
+        Parsha::Vayishlach => &VAYISHLACH,
+        Parsha::Vayeshev => &VAYESHEV,
+        Parsha::Miketz => &MIKETZ,
+        Parsha::Vayigash => &VAYIGASH,
+        Parsha::Vayechi => &VAYECHI,
+        Parsha::Shemos => &SHEMOS,
+        Parsha::Vaeira => &VAEIRA,
+        Parsha::Bo => &BO,
+        Parsha::Beshalach => &BESHALACH,
+        Parsha::Yisro => &YISRO,
+        Parsha::Mishpatim => &MISHPATIM,
+        Parsha::Terumah => &TERUMAH,
+        Parsha::Tetzaveh => &TETZAVEH,
+        Parsha::KiSisa => &KI_SISA,
+        Parsha::Vayakhel => &VAYAKHEL,
+        Parsha::Pikudei => &PIKUDEI,
+        Parsha::Vayikra => &VAYIKRA,
+        Parsha::Tzav => &TZAV,
+        Parsha::Shemini => &SHEMINI,
+        Parsha::Tazriya => &TAZRIYA,
+        Parsha::Metzorah => &METZORAH,
+        Parsha::AchareiMos => &ACHAREI_MOS,
+        Parsha::Kedoshim => &KEDOSHIM,
+        Parsha::Emor => &EMOR,
+        Parsha::Behar => &BEHAR,
+        Parsha::Bechukosai => &BECHUKOSAI,
+        Parsha::Bamidbar => &BAMIDBAR,
+        Parsha::Naso => &NASO,
+        Parsha::Behaaloscha => &BEHAALOSCHA,
+        Parsha::Shlach => &SHLACH,
+        Parsha::Korach => &KORACH,
+        Parsha::Chukas => &CHUKAS,
+        Parsha::Balak => &BALAK,
+        Parsha::Pinchas => &PINCHAS,
+        Parsha::Matos => &MATOS,
+        Parsha::Maasei => &MAASEI,
+        Parsha::Devarim => &DEVARIM,
+        Parsha::Vaeschanan => &VAESCHANAN,
+        Parsha::Eikev => &EIKEV,
+        Parsha::Reeh => &REEH,
+        Parsha::Shoftim => &SHOFTIM,
+        Parsha::KiSeitzei => &KI_SEITZEI,
+        Parsha::KiSavoh => &KI_SAVOH,
+        Parsha::Nitzavim => &NITZAVIM,
+        Parsha::VayakhelPikudei
+        | Parsha::TazriyaMetzorah
+        | Parsha::AchareiMosKedoshim
+        | Parsha::BeharBechukosai
+        | Parsha::ChukasBalak
+        | Parsha::MatosMaasei
+        | Parsha::NitzavimVayelech => unreachable!("{:?} is two parshiyos", parsha),
+    }
+}
+
+/// The whole reading of the parsha, from the first aliyah until the end of the last. Doubled
+/// parshiyos are read one after the other.
+pub fn reading(parsha: Parsha) -> Verses {
+    let parshiyos = split_parsha(parsha);
+    let (first, last) = (
+        get_leining(parshiyos[0]),
+        get_leining(*parshiyos.last().unwrap()),
+    );
+    let book = Sefer::Torah(first.book);
+    Verses {
+        sefer: book,
+        from: Verses::new(book, first.aliyos[0]).from,
+        to: Verses::new(book, last.aliyos[6]).to,
+    }
+}
+
+/// The seven aliyos of the parsha. Doubled parshiyos are divided differently than either of
+/// their parshiyos, which isn't recorded, so they have none.
+pub fn aliyos(parsha: Parsha) -> Option<Vec<Verses>> {
+    match split_parsha(parsha)[..] {
+        [parsha] => {
+            let leining = get_leining(parsha);
+            Some(
+                leining
+                    .aliyos
+                    .iter()
+                    .map(|x| Verses::torah(leining.book, x))
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// The seven aliyos of Vezos Haberachah, as it's divided when it's read on Simchas Torah.
+pub fn vezos_haberachah_aliyos() -> Vec<Verses> {
+    [
+        "33:1-33:7",
+        "33:8-33:12",
+        "33:13-33:17",
+        "33:18-33:21",
+        "33:22-33:26",
+        "33:27-33:29",
+        "34:1-34:12",
+    ]
+    .iter()
+    .map(|x| Verses::torah(Book::Deuteronomy, x))
+    .collect()
+}
+
+/// The maftir of the parsha. A doubled parsha ends with the maftir of its second parsha.
+pub fn maftir(parsha: Parsha) -> Verses {
+    let leining = get_leining(*split_parsha(parsha).last().unwrap());
+    Verses::torah(leining.book, leining.maftir)
+}
+
+/// The haftarah of the parsha. Doubled parshiyos read the haftarah of the second parsha,
+/// besides Nitzavim-Vayelech, which reads the last of the seven haftaros of consolation.
+pub fn haftarah(parsha: Parsha) -> Vec<Verses> {
+    let parshiyos = split_parsha(parsha);
+    let parsha = match parsha {
+        Parsha::NitzavimVayelech => Parsha::Nitzavim,
+        _ => *parshiyos.last().unwrap(),
+    };
+    navi(get_leining(parsha).haftarah)
+}
+
+fn navi(ranges: &[(Navi, &str)]) -> Vec<Verses> {
+    ranges
+        .iter()
+        .map(|(navi, range)| Verses::navi(*navi, range))
+        .collect()
+}
+
+/// The maftir read from a second sefer Torah on a special parsha.
+pub fn special_parsha_maftir(special: SpecialParsha) -> Verses {
+    match special {
+        SpecialParsha::Shekalim => Verses::torah(Book::Exodus, "30:11-30:16"),
+        SpecialParsha::Zachor => Verses::torah(Book::Deuteronomy, "25:17-25:19"),
+        SpecialParsha::Parah => Verses::torah(Book::Numbers, "19:1-19:22"),
+        SpecialParsha::HaChodesh => Verses::torah(Book::Exodus, "12:1-12:20"),
+    }
+}
+
+pub fn special_parsha_haftarah(special: SpecialParsha) -> Vec<Verses> {
+    match special {
+        SpecialParsha::Shekalim => navi(&[(Navi::IIKings, "12:1-12:17")]),
+        SpecialParsha::Zachor => navi(&[(Navi::ISamuel, "15:2-15:34")]),
+        SpecialParsha::Parah => navi(&[(Navi::Ezekiel, "36:16-36:38")]),
+        SpecialParsha::HaChodesh => navi(&[(Navi::Ezekiel, "45:16-46:18")]),
+    }
+}
+
+/// The maftir of Shabbos Rosh Chodesh or Shabbos Chanukah, which is read from a second sefer
+/// Torah. On Chanukah, it's the offering of the nasi of the day.
+pub fn chol_maftir(chol: Chol) -> Option<Verses> {
+    let range = match chol {
+        Chol::Chanukah1 => "7:1-7:17",
+        Chol::Chanukah2 => "7:18-7:23",
+        Chol::Chanukah3 => "7:24-7:29",
+        Chol::Chanukah4 => "7:30-7:35",
+        Chol::Chanukah5 => "7:36-7:41",
+        Chol::Chanukah6 => "7:42-7:47",
+        Chol::Chanukah7 => "7:48-7:53",
+        Chol::Chanukah8 => "7:54-8:4",
+        Chol::TzomGedalia
+        | Chol::TenTeves
+        | Chol::TaanisEsther
+        | Chol::Purim
+        | Chol::ShushanPurim
+        | Chol::SeventeenTammuz
+        | Chol::NineAv => return None,
+        // Every day of Rosh Chodesh.
+        _ => "28:9-28:15",
+    };
+    Some(Verses::torah(Book::Numbers, range))
+}
+
+/// The haftarah of Shabbos Chanukah. If Chanukah has two Shabbosos, the second is on its last
+/// day, and reads about the vessels Shlomo made for the Beis Hamikdash.
+pub fn chanukah_haftarah(last_day: bool) -> Vec<Verses> {
+    if last_day {
+        navi(&[(Navi::IKings, "7:40-7:50")])
+    } else {
+        navi(&[(Navi::Zechariah, "2:14-4:7")])
+    }
+}
+
+pub fn rosh_chodesh_haftarah() -> Vec<Verses> {
+    navi(&[(Navi::Isaiah, "66:1-66:24")])
+}
+
+/// The haftarah of the Shabbos before Rosh Chodesh, which starts "tomorrow is the new moon".
+pub fn machar_chodesh_haftarah() -> Vec<Verses> {
+    navi(&[(Navi::ISamuel, "20:18-20:42")])
+}
+
+pub fn shabbos_shuva_haftarah() -> Vec<Verses> {
+    navi(&[(Navi::Hosea, "14:2-14:10"), (Navi::Joel, "2:15-2:27")])
+}
+
+pub fn shabbos_hagadol_haftarah() -> Vec<Verses> {
+    navi(&[(Navi::Malachi, "3:4-3:24")])
+}
+
+/// The first two of the three haftaros of affliction, read on the Shabbosos before Shabbos
+/// Chazon, whichever parshiyos they are. `weeks_before` is 2 for the first and 1 for the second.
+pub fn three_weeks_haftarah(weeks_before: i64) -> Vec<Verses> {
+    match weeks_before {
+        2 => navi(MATOS.haftarah),
+        _ => navi(MAASEI.haftarah),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aliyos_follow_each_other() {
+        for leining in &[
+            &BEREISHIS,
+            &NOACH,
+            &LECH_LECHA,
+            &VAYEIRA,
+            &CHAYEI_SARA,
+            &TOLDOS,
+            &VAYETZEI,
+            &VAYISHLACH,
+            &VAYESHEV,
+            &MIKETZ,
+            &VAYIGASH,
+            &VAYECHI,
+            &SHEMOS,
+            &VAEIRA,
+            &BO,
+            &BESHALACH,
+            &YISRO,
+            &MISHPATIM,
+            &TERUMAH,
+            &TETZAVEH,
+            &KI_SISA,
+            &VAYAKHEL,
+            &PIKUDEI,
+            &VAYIKRA,
+            &TZAV,
+            &SHEMINI,
+            &TAZRIYA,
+            &METZORAH,
+            &ACHAREI_MOS,
+            &KEDOSHIM,
+            &EMOR,
+            &BEHAR,
+            &BECHUKOSAI,
+            &BAMIDBAR,
+            &NASO,
+            &BEHAALOSCHA,
+            &SHLACH,
+            &KORACH,
+            &CHUKAS,
+            &BALAK,
+            &PINCHAS,
+            &MATOS,
+            &MAASEI,
+            &DEVARIM,
+            &VAESCHANAN,
+            &EIKEV,
+            &REEH,
+            &SHOFTIM,
+            &KI_SEITZEI,
+            &KI_SAVOH,
+            &NITZAVIM,
+            &VAYELECH,
+            &HAAZINU,
+        ] {
+            let aliyos: Vec<Verses> = leining
+                .aliyos
+                .iter()
+                .map(|x| Verses::torah(leining.book, x))
+                .collect();
+            for pair in aliyos.windows(2) {
+                let next = Verses {
+                    sefer: pair[0].sefer,
+                    from: pair[0].to,
+                    to: pair[1].from,
+                };
+                assert_eq!(next.count(), Some(2), "{:?}", leining);
+            }
+            let maftir = Verses::torah(leining.book, leining.maftir);
+            assert_eq!(maftir.to, aliyos[6].to, "{:?}", leining);
+            assert!(maftir.count().unwrap() >= 3, "{:?}", leining);
+        }
+    }
+
+    #[test]
+    fn verse_counts() {
+        assert_eq!(reading(Parsha::Bereishis).count(), Some(146));
+        assert_eq!(reading(Parsha::VayakhelPikudei).count(), Some(214));
+        // Chapters of different lengths than in English translations.
+        assert_eq!(reading(Parsha::Pinchas).count(), Some(169));
+        assert_eq!(reading(Parsha::KiSavoh).count(), Some(122));
+        assert_eq!(
+            aliyos(Parsha::Bechukosai)
+                .unwrap()
+                .iter()
+                .map(|x| x.count().unwrap())
+                .collect::<Vec<_>>(),
+            [3, 4, 37, 15, 6, 7, 6]
+        );
+    }
+}
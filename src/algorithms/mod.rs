@@ -1,4 +1,11 @@
+pub mod agricultural;
+pub mod arithmetic;
 pub mod candle_lighting;
 pub mod chabad_holidays;
+pub mod havdalah;
 pub mod israeli_holidays;
+pub mod leining;
+pub mod reading_cycle;
+pub mod restrictions;
 pub mod shabbos_mevarchim;
+pub mod solar;
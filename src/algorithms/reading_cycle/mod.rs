@@ -0,0 +1,599 @@
+use crate::args::types::{Language, ReadingCycle};
+use heca_lib::prelude::{HebrewMonth, Parsha};
+use heca_lib::HebrewDate;
+use serde::ser::*;
+use serde::{Serialize, Serializer};
+use std::io::Write;
+
+/// The first year of a triennial cycle. Every third year after it starts a new cycle.
+const FIRST_TRIENNIAL_YEAR: i64 = 5744;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Book {
+    Genesis,
+    Exodus,
+    Leviticus,
+    Numbers,
+    Deuteronomy,
+}
+
+impl Book {
+    pub fn english(self) -> &'static str {
+        match self {
+            Book::Genesis => "Genesis",
+            Book::Exodus => "Exodus",
+            Book::Leviticus => "Leviticus",
+            Book::Numbers => "Numbers",
+            Book::Deuteronomy => "Deuteronomy",
+        }
+    }
+
+    pub fn hebrew(self) -> &'static str {
+        match self {
+            Book::Genesis => "בראשית",
+            Book::Exodus => "שמות",
+            Book::Leviticus => "ויקרא",
+            Book::Numbers => "במדבר",
+            Book::Deuteronomy => "דברים",
+        }
+    }
+}
+
+/// The verses of a single parsha, along with the thirds read in each year of the triennial cycle.
+#[derive(Debug)]
+struct ParshaVerses {
+    book: Book,
+    full_kriah: &'static str,
+    triennial: [&'static str; 3],
+}
+
+macro_rules! parsha_verses {
+    ($name:ident, $book:ident, $full_kriah:expr, [$year1:expr, $year2:expr, $year3:expr]) => {
+        const $name: ParshaVerses = ParshaVerses {
+            book: Book::$book,
+            full_kriah: $full_kriah,
+            triennial: [$year1, $year2, $year3],
+        };
+    };
+}
+
+parsha_verses!(
+    BEREISHIS,
+    Genesis,
+    "1:1-6:8",
+    ["1:1-2:3", "2:4-3:21", "3:22-6:8"]
+);
+parsha_verses!(
+    NOACH,
+    Genesis,
+    "6:9-11:32",
+    ["6:9-8:14", "8:15-10:32", "11:1-11:32"]
+);
+parsha_verses!(
+    LECH_LECHA,
+    Genesis,
+    "12:1-17:27",
+    ["12:1-13:18", "14:1-15:21", "16:1-17:27"]
+);
+parsha_verses!(
+    VAYEIRA,
+    Genesis,
+    "18:1-22:24",
+    ["18:1-18:33", "19:1-20:18", "21:1-22:24"]
+);
+parsha_verses!(
+    CHAYEI_SARA,
+    Genesis,
+    "23:1-25:18",
+    ["23:1-24:9", "24:10-24:52", "24:53-25:18"]
+);
+parsha_verses!(
+    TOLDOS,
+    Genesis,
+    "25:19-28:9",
+    ["25:19-26:22", "26:23-27:27", "27:28-28:9"]
+);
+parsha_verses!(
+    VAYETZEI,
+    Genesis,
+    "28:10-32:3",
+    ["28:10-30:13", "30:14-31:16", "31:17-32:3"]
+);
+parsha_verses!(
+    VAYISHLACH,
+    Genesis,
+    "32:4-36:43",
+    ["32:4-33:20", "34:1-35:15", "35:16-36:43"]
+);
+parsha_verses!(
+    VAYESHEV,
+    Genesis,
+    "37:1-40:23",
+    ["37:1-37:36", "38:1-38:30", "39:1-40:23"]
+);
+parsha_verses!(
+    MIKETZ,
+    Genesis,
+    "41:1-44:17",
+    ["41:1-41:52", "41:53-43:15", "43:16-44:17"]
+);
+parsha_verses!(
+    VAYIGASH,
+    Genesis,
+    "44:18-47:27",
+    ["44:18-45:27", "45:28-46:27", "46:28-47:27"]
+);
+parsha_verses!(
+    VAYECHI,
+    Genesis,
+    "47:28-50:26",
+    ["47:28-48:22", "49:1-49:26", "49:27-50:26"]
+);
+parsha_verses!(
+    SHEMOS,
+    Exodus,
+    "1:1-6:1",
+    ["1:1-2:25", "3:1-4:17", "4:18-6:1"]
+);
+parsha_verses!(
+    VAEIRA,
+    Exodus,
+    "6:2-9:35",
+    ["6:2-7:7", "7:8-8:15", "8:16-9:35"]
+);
+parsha_verses!(
+    BO,
+    Exodus,
+    "10:1-13:16",
+    ["10:1-11:3", "11:4-12:28", "12:29-13:16"]
+);
+parsha_verses!(
+    BESHALACH,
+    Exodus,
+    "13:17-17:16",
+    ["13:17-15:26", "15:27-16:36", "17:1-17:16"]
+);
+parsha_verses!(
+    YISRO,
+    Exodus,
+    "18:1-20:23",
+    ["18:1-18:27", "19:1-19:25", "20:1-20:23"]
+);
+parsha_verses!(
+    MISHPATIM,
+    Exodus,
+    "21:1-24:18",
+    ["21:1-22:3", "22:4-23:19", "23:20-24:18"]
+);
+parsha_verses!(
+    TERUMAH,
+    Exodus,
+    "25:1-27:19",
+    ["25:1-25:40", "26:1-26:30", "26:31-27:19"]
+);
+parsha_verses!(
+    TETZAVEH,
+    Exodus,
+    "27:20-30:10",
+    ["27:20-28:30", "28:31-29:18", "29:19-30:10"]
+);
+parsha_verses!(
+    KI_SISA,
+    Exodus,
+    "30:11-34:35",
+    ["30:11-31:17", "31:18-33:11", "33:12-34:35"]
+);
+parsha_verses!(
+    VAYAKHEL,
+    Exodus,
+    "35:1-38:20",
+    ["35:1-35:29", "35:30-37:16", "37:17-38:20"]
+);
+parsha_verses!(
+    PIKUDEI,
+    Exodus,
+    "38:21-40:38",
+    ["38:21-39:21", "39:22-39:43", "40:1-40:38"]
+);
+parsha_verses!(
+    VAYIKRA,
+    Leviticus,
+    "1:1-5:26",
+    ["1:1-2:16", "3:1-4:26", "4:27-5:26"]
+);
+parsha_verses!(
+    TZAV,
+    Leviticus,
+    "6:1-8:36",
+    ["6:1-7:10", "7:11-7:38", "8:1-8:36"]
+);
+parsha_verses!(
+    SHEMINI,
+    Leviticus,
+    "9:1-11:47",
+    ["9:1-9:23", "9:24-10:20", "11:1-11:47"]
+);
+parsha_verses!(
+    TAZRIYA,
+    Leviticus,
+    "12:1-13:59",
+    ["12:1-13:5", "13:6-13:28", "13:29-13:59"]
+);
+parsha_verses!(
+    METZORAH,
+    Leviticus,
+    "14:1-15:33",
+    ["14:1-14:20", "14:21-14:57", "15:1-15:33"]
+);
+parsha_verses!(
+    ACHAREI_MOS,
+    Leviticus,
+    "16:1-18:30",
+    ["16:1-16:24", "16:25-17:16", "18:1-18:30"]
+);
+parsha_verses!(
+    KEDOSHIM,
+    Leviticus,
+    "19:1-20:27",
+    ["19:1-19:22", "19:23-19:37", "20:1-20:27"]
+);
+parsha_verses!(
+    EMOR,
+    Leviticus,
+    "21:1-24:23",
+    ["21:1-22:16", "22:17-23:22", "23:23-24:23"]
+);
+parsha_verses!(
+    BEHAR,
+    Leviticus,
+    "25:1-26:2",
+    ["25:1-25:18", "25:19-25:38", "25:39-26:2"]
+);
+parsha_verses!(
+    BECHUKOSAI,
+    Leviticus,
+    "26:3-27:34",
+    ["26:3-26:46", "27:1-27:15", "27:16-27:34"]
+);
+parsha_verses!(
+    BAMIDBAR,
+    Numbers,
+    "1:1-4:20",
+    ["1:1-1:54", "2:1-3:13", "3:14-4:20"]
+);
+parsha_verses!(
+    NASO,
+    Numbers,
+    "4:21-7:89",
+    ["4:21-5:10", "5:11-6:27", "7:1-7:89"]
+);
+parsha_verses!(
+    BEHAALOSCHA,
+    Numbers,
+    "8:1-12:16",
+    ["8:1-9:14", "9:15-10:34", "10:35-12:16"]
+);
+parsha_verses!(
+    SHLACH,
+    Numbers,
+    "13:1-15:41",
+    ["13:1-13:33", "14:1-14:25", "14:26-15:41"]
+);
+parsha_verses!(
+    KORACH,
+    Numbers,
+    "16:1-18:32",
+    ["16:1-16:35", "17:1-17:24", "17:25-18:32"]
+);
+parsha_verses!(
+    CHUKAS,
+    Numbers,
+    "19:1-22:1",
+    ["19:1-20:6", "20:7-21:9", "21:10-22:1"]
+);
+parsha_verses!(
+    BALAK,
+    Numbers,
+    "22:2-25:9",
+    ["22:2-22:38", "22:39-23:26", "23:27-25:9"]
+);
+parsha_verses!(
+    PINCHAS,
+    Numbers,
+    "25:10-30:1",
+    ["25:10-26:51", "26:52-28:15", "28:16-30:1"]
+);
+parsha_verses!(
+    MATOS,
+    Numbers,
+    "30:2-32:42",
+    ["30:2-31:12", "31:13-31:54", "32:1-32:42"]
+);
+parsha_verses!(
+    MAASEI,
+    Numbers,
+    "33:1-36:13",
+    ["33:1-33:49", "33:50-35:8", "35:9-36:13"]
+);
+parsha_verses!(
+    DEVARIM,
+    Deuteronomy,
+    "1:1-3:22",
+    ["1:1-1:21", "1:22-2:1", "2:2-3:22"]
+);
+parsha_verses!(
+    VAESCHANAN,
+    Deuteronomy,
+    "3:23-7:11",
+    ["3:23-4:40", "4:41-6:3", "6:4-7:11"]
+);
+parsha_verses!(
+    EIKEV,
+    Deuteronomy,
+    "7:12-11:25",
+    ["7:12-8:20", "9:1-10:11", "10:12-11:25"]
+);
+parsha_verses!(
+    REEH,
+    Deuteronomy,
+    "11:26-16:17",
+    ["11:26-12:28", "12:29-14:29", "15:1-16:17"]
+);
+parsha_verses!(
+    SHOFTIM,
+    Deuteronomy,
+    "16:18-21:9",
+    ["16:18-18:5", "18:6-19:13", "19:14-21:9"]
+);
+parsha_verses!(
+    KI_SEITZEI,
+    Deuteronomy,
+    "21:10-25:19",
+    ["21:10-22:7", "22:8-23:25", "23:26-25:19"]
+);
+parsha_verses!(
+    KI_SAVOH,
+    Deuteronomy,
+    "26:1-29:8",
+    ["26:1-26:15", "26:16-27:10", "27:11-29:8"]
+);
+parsha_verses!(
+    NITZAVIM,
+    Deuteronomy,
+    "29:9-30:20",
+    ["29:9-29:28", "30:1-30:6", "30:7-30:20"]
+);
+parsha_verses!(
+    VAYELECH,
+    Deuteronomy,
+    "31:1-31:30",
+    ["31:1-31:6", "31:7-31:13", "31:14-31:30"]
+);
+parsha_verses!(
+    HAAZINU,
+    Deuteronomy,
+    "32:1-32:52",
+    ["32:1-32:12", "32:13-32:28", "32:29-32:52"]
+);
+
+fn get_verses(parsha: Parsha) -> &'static [ParshaVerses] {
+    match parsha {
+        Parsha::Vayelech => &[VAYELECH],
+        Parsha::Haazinu => &[HAAZINU],
+        Parsha::Bereishis => &[BEREISHIS],
+        Parsha::Noach => &[NOACH],
+        Parsha::LechLecha => &[LECH_LECHA],
+        Parsha::Vayeira => &[VAYEIRA],
+        Parsha::ChayeiSara => &[CHAYEI_SARA],
+        Parsha::Toldos => &[TOLDOS],
+        Parsha::Vayetzei => &[VAYETZEI],
+        Parsha::Vayishlach => &[VAYISHLACH],
+        Parsha::Vayeshev => &[VAYESHEV],
+        Parsha::Miketz => &[MIKETZ],
+        Parsha::Vayigash => &[VAYIGASH],
+        Parsha::Vayechi => &[VAYECHI],
+        Parsha::Shemos => &[SHEMOS],
+        Parsha::Vaeira => &[VAEIRA],
+        Parsha::Bo => &[BO],
+        Parsha::Beshalach => &[BESHALACH],
+        Parsha::Yisro => &[YISRO],
+        Parsha::Mishpatim => &[MISHPATIM],
+        Parsha::Terumah => &[TERUMAH],
+        Parsha::Tetzaveh => &[TETZAVEH],
+        Parsha::KiSisa => &[KI_SISA],
+        Parsha::VayakhelPikudei => &[VAYAKHEL, PIKUDEI],
+        Parsha::Vayakhel => &[VAYAKHEL],
+        Parsha::Pikudei => &[PIKUDEI],
+        Parsha::Vayikra => &[VAYIKRA],
+        Parsha::Tzav => &[TZAV],
+        Parsha::Shemini => &[SHEMINI],
+        Parsha::TazriyaMetzorah => &[TAZRIYA, METZORAH],
+        Parsha::Tazriya => &[TAZRIYA],
+        Parsha::Metzorah => &[METZORAH],
+        Parsha::AchareiMosKedoshim => &[ACHAREI_MOS, KEDOSHIM],
+        Parsha::AchareiMos => &[ACHAREI_MOS],
+        Parsha::Kedoshim => &[KEDOSHIM],
+        Parsha::Emor => &[EMOR],
+        Parsha::BeharBechukosai => &[BEHAR, BECHUKOSAI],
+        Parsha::Behar => &[BEHAR],
+        Parsha::Bechukosai => &[BECHUKOSAI],
+        Parsha::Bamidbar => &[BAMIDBAR],
+        Parsha::Naso => &[NASO],
+        Parsha::Behaaloscha => &[BEHAALOSCHA],
+        Parsha::Shlach => &[SHLACH],
+        Parsha::Korach => &[KORACH],
+        Parsha::ChukasBalak => &[CHUKAS, BALAK],
+        Parsha::Chukas => &[CHUKAS],
+        Parsha::Balak => &[BALAK],
+        Parsha::Pinchas => &[PINCHAS],
+        Parsha::MatosMaasei => &[MATOS, MAASEI],
+        Parsha::Matos => &[MATOS],
+        Parsha::Maasei => &[MAASEI],
+        Parsha::Devarim => &[DEVARIM],
+        Parsha::Vaeschanan => &[VAESCHANAN],
+        Parsha::Eikev => &[EIKEV],
+        Parsha::Reeh => &[REEH],
+        Parsha::Shoftim => &[SHOFTIM],
+        Parsha::KiSeitzei => &[KI_SEITZEI],
+        Parsha::KiSavoh => &[KI_SAVOH],
+        Parsha::NitzavimVayelech => &[NITZAVIM, VAYELECH],
+        Parsha::Nitzavim => &[NITZAVIM],
+    }
+}
+
+/// Splits a doubled parsha into the parshiyos it's made of.
+pub fn split_parsha(parsha: Parsha) -> Vec<Parsha> {
+    match parsha {
+        Parsha::VayakhelPikudei => vec![Parsha::Vayakhel, Parsha::Pikudei],
+        Parsha::TazriyaMetzorah => vec![Parsha::Tazriya, Parsha::Metzorah],
+        Parsha::AchareiMosKedoshim => vec![Parsha::AchareiMos, Parsha::Kedoshim],
+        Parsha::BeharBechukosai => vec![Parsha::Behar, Parsha::Bechukosai],
+        Parsha::ChukasBalak => vec![Parsha::Chukas, Parsha::Balak],
+        Parsha::MatosMaasei => vec![Parsha::Matos, Parsha::Maasei],
+        Parsha::NitzavimVayelech => vec![Parsha::Nitzavim, Parsha::Vayelech],
+        parsha => vec![parsha],
+    }
+}
+
+/// Returns the doubled parsha made of `parshiyos`, the reverse of [`split_parsha`].
+pub fn join_parshiyos(parshiyos: &[Parsha]) -> Option<Parsha> {
+    match parshiyos {
+        [parsha] => Some(*parsha),
+        [_, _] => [
+            Parsha::VayakhelPikudei,
+            Parsha::TazriyaMetzorah,
+            Parsha::AchareiMosKedoshim,
+            Parsha::BeharBechukosai,
+            Parsha::ChukasBalak,
+            Parsha::MatosMaasei,
+            Parsha::NitzavimVayelech,
+        ]
+        .iter()
+        .copied()
+        .find(|&doubled| split_parsha(doubled) == parshiyos),
+        _ => None,
+    }
+}
+
+/// Returns the year (1, 2 or 3) of the triennial cycle that the reading on `day` belongs to.
+///
+/// The reading year starts on Simchas Torah, so Vayelech and Haazinu read in Tishrei
+/// finish the previous year's cycle.
+fn get_triennial_year(parsha: Parsha, day: HebrewDate) -> u8 {
+    let mut year = day.year() as i64;
+    if day.month() == HebrewMonth::Tishrei
+        && (parsha == Parsha::Vayelech || parsha == Parsha::Haazinu)
+    {
+        year -= 1;
+    }
+    ((year - FIRST_TRIENNIAL_YEAR).rem_euclid(3) + 1) as u8
+}
+
+#[derive(Debug, Clone)]
+pub struct TorahPortion {
+    verses: &'static [ParshaVerses],
+    triennial_year: Option<u8>,
+}
+
+impl TorahPortion {
+    pub fn new(parsha: Parsha, day: HebrewDate, reading_cycle: ReadingCycle) -> Self {
+        Self {
+            verses: get_verses(parsha),
+            triennial_year: match reading_cycle {
+                ReadingCycle::Annual => None,
+                ReadingCycle::Triennial => Some(get_triennial_year(parsha, day)),
+            },
+        }
+    }
+
+    /// Returns the portion of `parsha` in the given year of the triennial cycle, as read back
+    /// from JSON output.
+    pub fn with_triennial_year(parsha: Parsha, triennial_year: Option<u8>) -> Self {
+        Self {
+            verses: get_verses(parsha),
+            triennial_year,
+        }
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        let mut p = lock.write(b" (").ok()?;
+        p += print_verses(
+            lock,
+            self.verses.iter().map(|x| (x.book, x.full_kriah)),
+            language,
+        )?;
+        if let Some(triennial_year) = self.triennial_year {
+            p += match language {
+                Language::English => lock.write(b"; triennial year ").ok()?,
+                Language::Hebrew => lock.write("; שנה ".as_bytes()).ok()?,
+            };
+            let mut year_arr = [b'\0'; 1];
+            let count = itoa::write(&mut year_arr[..], triennial_year).unwrap();
+            p += lock.write(&year_arr[..count]).ok()?;
+            if language == Language::Hebrew {
+                p += lock.write(" במחזור התלת-שנתי".as_bytes()).ok()?;
+            }
+            p += lock.write(b": ").ok()?;
+            p += print_verses(
+                lock,
+                self.verses
+                    .iter()
+                    .map(|x| (x.book, x.triennial[triennial_year as usize - 1])),
+                language,
+            )?;
+        }
+        p += lock.write(b")").ok()?;
+        Some(p)
+    }
+}
+
+fn print_verses<W: Write>(
+    lock: &mut W,
+    verses: impl Iterator<Item = (Book, &'static str)>,
+    language: Language,
+) -> Option<usize> {
+    let mut p = 0;
+    for (index, (book, range)) in verses.enumerate() {
+        if index != 0 {
+            p += lock.write(b", ").ok()?;
+        }
+        p += match language {
+            Language::English => lock.write(book.english().as_bytes()).ok()?,
+            Language::Hebrew => lock.write(book.hebrew().as_bytes()).ok()?,
+        };
+        p += lock.write(b" ").ok()?;
+        p += lock.write(range.as_bytes()).ok()?;
+    }
+    Some(p)
+}
+
+impl Serialize for TorahPortion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TorahPortion", 3)?;
+        let full_kriah: Vec<String> = self
+            .verses
+            .iter()
+            .map(|x| format!("{} {}", x.book.english(), x.full_kriah))
+            .collect();
+        state.serialize_field("fullKriah", &full_kriah)?;
+        if let Some(triennial_year) = self.triennial_year {
+            let triennial: Vec<String> = self
+                .verses
+                .iter()
+                .map(|x| {
+                    format!(
+                        "{} {}",
+                        x.book.english(),
+                        x.triennial[triennial_year as usize - 1]
+                    )
+                })
+                .collect();
+            state.serialize_field("triennialYear", &triennial_year)?;
+            state.serialize_field("triennial", &triennial)?;
+        }
+        state.end()
+    }
+}
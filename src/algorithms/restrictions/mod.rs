@@ -0,0 +1,107 @@
+use crate::args::types::{Language, SefirahCustom};
+use crate::list::providers::is_yom_tov_day;
+use crate::prelude::print::torah_reading;
+use heca_lib::prelude::*;
+use heca_lib::HebrewDate;
+use serde::Serialize;
+
+/// Why a wedding isn't held on a day.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", content = "day")]
+pub enum Restriction {
+    Shabbos,
+    YomTov(YomTov),
+    CholHamoed(YomTov),
+    Fast(Chol),
+    /// From 17 Tammuz until 10 Av, as Ashkenazim keep the mourning of 9 Av until midday of 10 Av.
+    ThreeWeeks,
+    /// The days of Sefiras HaOmer the custom keeps the mourning on.
+    Sefirah,
+}
+
+impl Restriction {
+    pub fn name(self, language: Language) -> &'static str {
+        match (self, language) {
+            (Restriction::Shabbos, Language::English) => "Shabbos",
+            (Restriction::Shabbos, Language::Hebrew) => "שבת",
+            (Restriction::YomTov(yt), language) | (Restriction::CholHamoed(yt), language) => {
+                torah_reading(TorahReading::YomTov(yt), language)
+            }
+            (Restriction::Fast(chol), language) => {
+                torah_reading(TorahReading::Chol(chol), language)
+            }
+            (Restriction::ThreeWeeks, Language::English) => "the Three Weeks",
+            (Restriction::ThreeWeeks, Language::Hebrew) => "בין המצרים",
+            (Restriction::Sefirah, Language::English) => "Sefiras HaOmer",
+            (Restriction::Sefirah, Language::Hebrew) => "ספירת העומר",
+        }
+    }
+}
+
+/// The day of the Omer a date is on, counting from 16 Nissan.
+fn omer_day(date: HebrewDate) -> Option<i8> {
+    let day = date.day().get();
+    let omer = match date.month() {
+        HebrewMonth::Nissan => day - 15,
+        HebrewMonth::Iyar => day + 15,
+        HebrewMonth::Sivan => day + 44,
+        _ => return None,
+    };
+    if (1..=49).contains(&omer) {
+        Some(omer)
+    } else {
+        None
+    }
+}
+
+/// The periods of mourning a day is in, from its date. `date` is the date of the day, not of the
+/// night before it.
+pub fn periods(date: HebrewDate, sefirah: SefirahCustom) -> Vec<Restriction> {
+    let day = date.day().get();
+    let three_weeks = match date.month() {
+        HebrewMonth::Tammuz => day >= 17,
+        HebrewMonth::Av => day <= 10,
+        _ => false,
+    };
+    // Lag BaOmer is the 33rd day, and weddings can be held from the three days before Shavuos,
+    // which start on the 47th.
+    let sefirah = omer_day(date).is_some_and(|omer| match sefirah {
+        SefirahCustom::FirstDays => omer < 33,
+        SefirahCustom::LastDays => (15..47).contains(&omer) && omer != 33,
+        SefirahCustom::Whole => omer < 47 && omer != 33,
+    });
+    let mut restrictions = vec![];
+    if three_weeks {
+        restrictions.push(Restriction::ThreeWeeks);
+    }
+    if sefirah {
+        restrictions.push(Restriction::Sefirah);
+    }
+    restrictions
+}
+
+/// Returns the restriction a Torah reading of the day brings, if there's one: Yom Tov, Chol
+/// Hamoed and the fasts. Yom Kippur is a Yom Tov.
+pub fn reading(reading: TorahReading, location: Location) -> Option<Restriction> {
+    match reading {
+        TorahReading::YomTov(yt) if is_yom_tov_day(yt, location) => Some(Restriction::YomTov(yt)),
+        TorahReading::YomTov(yt) => match yt {
+            // These are Yom Tov outside Israel, and aren't listed in Israel.
+            YomTov::Pesach8 | YomTov::Shavuos2 | YomTov::SimchasTorah => None,
+            yt => Some(Restriction::CholHamoed(yt)),
+        },
+        TorahReading::Chol(chol)
+            if matches!(
+                chol,
+                Chol::TzomGedalia
+                    | Chol::TenTeves
+                    | Chol::TaanisEsther
+                    | Chol::SeventeenTammuz
+                    | Chol::NineAv
+            ) =>
+        {
+            Some(Restriction::Fast(chol))
+        }
+        _ => None,
+    }
+}
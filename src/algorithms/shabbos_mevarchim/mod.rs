@@ -6,7 +6,7 @@ use heca_lib::{HebrewDate, HebrewYear};
 use serde::ser::*;
 use serde::{Serialize, Serializer};
 use std::convert::TryInto;
-use std::io::{BufWriter, StdoutLock, Write};
+use std::io::Write;
 use std::num::NonZeroI8;
 
 pub fn get(year: &HebrewYear) -> Vec<DayVal> {
@@ -94,21 +94,13 @@ pub fn get(year: &HebrewYear) -> Vec<DayVal> {
         })
         .collect();
     for num in shabbos_mevarchim {
-        let cur_month = num.1;
-        let molad = year.get_molad(cur_month).unwrap();
-        let molad_day = molad.get_day_utc();
         v.push(DayVal {
             day: num.0,
-            name: Name::ShabbosMevarchim(ShabbosMevarchim {
-                hebrew_month: cur_month,
-                gregorian_month: molad_day.month(),
-                gregorian_dow: molad_day.weekday(),
-                gregorian_day: molad_day.day(),
-                hour: molad_day.hour(),
-                minute: molad_day.minute(),
-                chalakim: molad.get_chalakim(),
-            }),
+            name: Name::ShabbosMevarchim(ShabbosMevarchim::new(year, num.1).unwrap()),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         });
     }
     v
@@ -152,11 +144,23 @@ impl Serialize for ShabbosMevarchim {
 }
 
 impl ShabbosMevarchim {
-    pub fn pretty_print(
-        &self,
-        lock: &mut BufWriter<StdoutLock<'_>>,
-        language: Language,
-    ) -> Option<usize> {
+    /// Returns the molad of `hebrew_month` as announced in `year`, or `None` if the year doesn't
+    /// have that month.
+    pub fn new(year: &HebrewYear, hebrew_month: HebrewMonth) -> Option<Self> {
+        let molad = year.get_molad(hebrew_month).ok()?;
+        let molad_day = molad.get_day_utc();
+        Some(ShabbosMevarchim {
+            hebrew_month,
+            gregorian_month: molad_day.month(),
+            gregorian_dow: molad_day.weekday(),
+            gregorian_day: molad_day.day(),
+            hour: molad_day.hour(),
+            minute: molad_day.minute(),
+            chalakim: molad.get_chalakim(),
+        })
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
         let p = match language {
             Language::English => {
                 let mut p = lock.write(b"Shabbos Mevarchim ").ok()?;
@@ -0,0 +1,170 @@
+use crate::algorithms::candle_lighting::City;
+use crate::args::types::SolarAlgorithm;
+use chrono::prelude::*;
+use chrono::Duration;
+use chrono::TimeZone as _;
+use chrono_tz::{Tz, TZ_VARIANTS};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::OnceLock;
+use zmanim::prelude::tz::TimeZone;
+
+mod noaa;
+mod usno;
+
+pub use noaa::Noaa;
+pub use usno::Usno;
+
+/// The Julian day of the Unix epoch.
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2_440_587.5;
+/// How far below the horizon the center of the sun is at sunrise and sunset, because of
+/// refraction and the size of the sun.
+const SUNRISE_DEPRESSION: f64 = 0.833;
+
+fn sin(degrees: f64) -> f64 {
+    (degrees * PI / 180.0).sin()
+}
+
+fn cos(degrees: f64) -> f64 {
+    (degrees * PI / 180.0).cos()
+}
+
+fn tan(degrees: f64) -> f64 {
+    (degrees * PI / 180.0).tan()
+}
+
+/// The Julian day of midnight UTC at the start of `date`.
+fn julian_day(date: NaiveDate) -> f64 {
+    date.and_hms(0, 0, 0).timestamp() as f64 / 86400.0 + UNIX_EPOCH_JULIAN_DAY
+}
+
+fn from_julian_day(julian_day: f64) -> DateTime<Utc> {
+    let timestamp = ((julian_day - UNIX_EPOCH_JULIAN_DAY) * 86400.0).round() as i64;
+    Utc.timestamp(timestamp, 0)
+}
+
+/// Returns how long before and after solar noon the center of the sun is `degrees` below the
+/// horizon, as a fraction of a day. Returns `None` if the sun doesn't go that far down (or
+/// doesn't come up) on that day, which happens near the poles.
+fn hour_angle(latitude: f64, declination: f64, degrees: f64) -> Option<f64> {
+    let cos_hour_angle =
+        (sin(-degrees) - sin(latitude) * sin(declination)) / (cos(latitude) * cos(declination));
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    Some(cos_hour_angle.acos() * 180.0 / PI / 360.0)
+}
+
+/// Whether a time is before or after solar noon.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Side {
+    Morning,
+    Evening,
+}
+
+/// An algorithm for where the sun is, which sunrise, sunset and every zman are calculated from.
+/// They differ by up to a minute or so, which matters to anyone comparing the times to the tables
+/// their shul uses.
+pub trait Sun: Sync {
+    /// Returns when the center of the sun is `degrees` below the horizon on the `side` of `date`.
+    /// Returns `None` if the sun doesn't go that far down (or doesn't come up) on that day, which
+    /// happens near the poles.
+    fn depression(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+        degrees: f64,
+        side: Side,
+    ) -> Option<DateTime<Utc>>;
+
+    /// Returns solar noon on `date`, when the sun is highest.
+    fn transit(&self, longitude: f64, date: NaiveDate) -> DateTime<Utc>;
+
+    /// Returns when the center of the sun is `degrees` below the horizon in the morning of `date`.
+    fn morning_depression(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+        degrees: f64,
+    ) -> Option<DateTime<Utc>> {
+        self.depression(latitude, longitude, date, degrees, Side::Morning)
+    }
+
+    /// Returns when the center of the sun is `degrees` below the horizon in the evening of `date`.
+    fn evening_depression(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+        degrees: f64,
+    ) -> Option<DateTime<Utc>> {
+        self.depression(latitude, longitude, date, degrees, Side::Evening)
+    }
+
+    /// Returns midnight after `date`, when the sun is lowest. This is what a zman based on a
+    /// depression angle falls back to on days the sun doesn't go that far down.
+    fn solar_midnight(&self, longitude: f64, date: NaiveDate) -> DateTime<Utc> {
+        self.transit(longitude, date) + Duration::hours(12)
+    }
+
+    /// Returns the time of sunrise in the city on `date`, or `None` if the sun doesn't rise on
+    /// that day.
+    fn sunrise(&self, city: &City, date: NaiveDate) -> Option<DateTime<FixedOffset>> {
+        let sunrise =
+            self.morning_depression(city.latitude, city.longitude, date, SUNRISE_DEPRESSION)?;
+        Some(local_time(&city.time_zone, sunrise))
+    }
+
+    /// Returns the time of sunset in the city on `date`, or `None` if the sun doesn't set on
+    /// that day.
+    fn sunset(&self, city: &City, date: NaiveDate) -> Option<DateTime<FixedOffset>> {
+        let sunset =
+            self.evening_depression(city.latitude, city.longitude, date, SUNRISE_DEPRESSION)?;
+        Some(local_time(&city.time_zone, sunset))
+    }
+}
+
+impl SolarAlgorithm {
+    pub fn sun(self) -> &'static dyn Sun {
+        match self {
+            SolarAlgorithm::Noaa => &Noaa,
+            SolarAlgorithm::Usno => &Usno,
+        }
+    }
+}
+
+/// Returns the time zone of the tz database the `zmanim` crate's time zone is. Its time zones
+/// are nested enums whose variants are the words of the name, so `America/Port-au-Prince` is
+/// `America(PortAuPrince)`, and they're matched by their letters and digits.
+pub fn tz(time_zone: &TimeZone) -> Tz {
+    static TIME_ZONES: OnceLock<HashMap<String, Tz>> = OnceLock::new();
+    let letters = |name: &str| -> String {
+        name.chars()
+            .filter(|x| x.is_ascii_alphanumeric())
+            .map(|x| x.to_ascii_lowercase())
+            .collect()
+    };
+    let time_zones = TIME_ZONES.get_or_init(|| {
+        TZ_VARIANTS
+            .iter()
+            .map(|tz| (letters(tz.name()), *tz))
+            .collect()
+    });
+    *time_zones
+        .get(&letters(&format!("{:?}", time_zone)))
+        .unwrap_or(&Tz::UTC)
+}
+
+/// Returns the time it is in the time zone at `instant`, with the offset in effect then.
+pub fn local_time(time_zone: &TimeZone, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+    instant.with_timezone(&utc_offset(time_zone, instant))
+}
+
+/// Returns the time zone's offset from UTC at `instant`.
+pub fn utc_offset(time_zone: &TimeZone, instant: DateTime<Utc>) -> FixedOffset {
+    tz(time_zone)
+        .offset_from_utc_datetime(&instant.naive_utc())
+        .fix()
+}
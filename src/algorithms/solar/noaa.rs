@@ -0,0 +1,74 @@
+//! The algorithm of NOAA's solar calculator, from Meeus' Astronomical Algorithms. This is the
+//! default, and what most Jewish calendar software uses.
+
+use super::{cos, from_julian_day, hour_angle, julian_day, sin, tan, Side, Sun};
+use chrono::prelude::*;
+use std::f64::consts::PI;
+
+/// The Julian day of the J2000 epoch.
+const J2000: f64 = 2_451_545.0;
+
+pub struct Noaa;
+
+/// Returns the equation of time (how far ahead of the mean sun the sun is, in minutes) and the
+/// sun's declination at `julian_day`.
+fn position(julian_day: f64) -> (f64, f64) {
+    let century = (julian_day - J2000) / 36525.0;
+    let mean_longitude =
+        (280.46646 + century * (36000.76983 + century * 0.0003032)).rem_euclid(360.0);
+    let mean_anomaly = 357.52911 + century * (35999.05029 - 0.0001537 * century);
+    let eccentricity = 0.016708634 - century * (0.000042037 + 0.0000001267 * century);
+    let center = sin(mean_anomaly) * (1.914602 - century * (0.004817 + 0.000014 * century))
+        + sin(2.0 * mean_anomaly) * (0.019993 - 0.000101 * century)
+        + sin(3.0 * mean_anomaly) * 0.000289;
+    let omega = 125.04 - 1934.136 * century;
+    let apparent_longitude = mean_longitude + center - 0.00569 - 0.00478 * sin(omega);
+    let mean_obliquity = 23.0
+        + (26.0 + (21.448 - century * (46.815 + century * (0.00059 - century * 0.001813))) / 60.0)
+            / 60.0;
+    let obliquity = mean_obliquity + 0.00256 * cos(omega);
+    let declination = (sin(obliquity) * sin(apparent_longitude)).asin() * 180.0 / PI;
+    let y = tan(obliquity / 2.0).powi(2);
+    let equation_of_time = y * sin(2.0 * mean_longitude) - 2.0 * eccentricity * sin(mean_anomaly)
+        + 4.0 * eccentricity * y * sin(mean_anomaly) * cos(2.0 * mean_longitude)
+        - 0.5 * y * y * sin(4.0 * mean_longitude)
+        - 1.25 * eccentricity * eccentricity * sin(2.0 * mean_anomaly);
+    (equation_of_time * 180.0 / PI * 4.0, declination)
+}
+
+/// Returns solar noon on the day starting at `midnight`, as a Julian day.
+fn transit(longitude: f64, midnight: f64) -> f64 {
+    let noon = midnight + 0.5 - longitude / 360.0;
+    let (equation_of_time, _) = position(noon);
+    noon - equation_of_time / 1440.0
+}
+
+impl Sun for Noaa {
+    fn depression(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+        degrees: f64,
+        side: Side,
+    ) -> Option<DateTime<Utc>> {
+        let midnight = julian_day(date);
+        let noon = transit(longitude, midnight);
+        // The sun's position is first taken at noon, and then again at the time that gives.
+        let mut time = noon;
+        for _ in 0..2 {
+            let (equation_of_time, declination) = position(time);
+            let hour_angle = hour_angle(latitude, declination, degrees)?;
+            let noon = midnight + 0.5 - longitude / 360.0 - equation_of_time / 1440.0;
+            time = match side {
+                Side::Morning => noon - hour_angle,
+                Side::Evening => noon + hour_angle,
+            };
+        }
+        Some(from_julian_day(time))
+    }
+
+    fn transit(&self, longitude: f64, date: NaiveDate) -> DateTime<Utc> {
+        from_julian_day(transit(longitude, julian_day(date)))
+    }
+}
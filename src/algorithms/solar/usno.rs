@@ -0,0 +1,66 @@
+//! The sunrise algorithm of the US Naval Observatory's Almanac for Computers (1990), which some
+//! older printed tables use.
+
+use super::{from_julian_day, hour_angle, julian_day, sin, tan, Side, Sun};
+use chrono::prelude::*;
+use std::f64::consts::PI;
+
+pub struct Usno;
+
+/// Returns the sun's right ascension, in hours, and declination at `time`, which is a day of the
+/// year with a fraction.
+fn position(time: f64) -> (f64, f64) {
+    let mean_anomaly = 0.9856 * time - 3.289;
+    let longitude =
+        (mean_anomaly + 1.916 * sin(mean_anomaly) + 0.020 * sin(2.0 * mean_anomaly) + 282.634)
+            .rem_euclid(360.0);
+    let right_ascension = (0.91764 * tan(longitude)).atan() * 180.0 / PI;
+    // The right ascension is in the same quadrant as the longitude.
+    let right_ascension = right_ascension.rem_euclid(360.0) + (longitude / 90.0).floor() * 90.0
+        - (right_ascension.rem_euclid(360.0) / 90.0).floor() * 90.0;
+    let declination = (0.39782 * sin(longitude)).asin() * 180.0 / PI;
+    (right_ascension / 15.0, declination)
+}
+
+/// Returns the time, in hours after midnight UTC of `date`, at which the sun is `hour_angle`
+/// hours west of the meridian. `local_hour` is roughly the local time of that.
+fn time(
+    longitude: f64,
+    date: NaiveDate,
+    local_hour: f64,
+    hour_angle: impl Fn(f64) -> Option<f64>,
+) -> Option<f64> {
+    let longitude_hours = longitude / 15.0;
+    let day = date.ordinal() as f64 + (local_hour - longitude_hours) / 24.0;
+    let (right_ascension, declination) = position(day);
+    let local_time = hour_angle(declination)? + right_ascension - 0.06571 * day - 6.622;
+    let utc = local_time - longitude_hours;
+    // The algorithm only gives the time of day, so it's put on the day it was looked for on.
+    let expected = local_hour - longitude_hours;
+    Some(utc - ((utc - expected) / 24.0).round() * 24.0)
+}
+
+impl Sun for Usno {
+    fn depression(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        date: NaiveDate,
+        degrees: f64,
+        side: Side,
+    ) -> Option<DateTime<Utc>> {
+        let (local_hour, sign) = match side {
+            Side::Morning => (6.0, -1.0),
+            Side::Evening => (18.0, 1.0),
+        };
+        let hours = time(longitude, date, local_hour, |declination| {
+            Some(sign * hour_angle(latitude, declination, degrees)? * 24.0)
+        })?;
+        Some(from_julian_day(julian_day(date) + hours / 24.0))
+    }
+
+    fn transit(&self, longitude: f64, date: NaiveDate) -> DateTime<Utc> {
+        let hours = time(longitude, date, 12.0, |_| Some(0.0)).unwrap();
+        from_julian_day(julian_day(date) + hours / 24.0)
+    }
+}
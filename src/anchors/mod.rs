@@ -0,0 +1,197 @@
+use crate::args::types::{
+    AppError, DailyStudy, Language, MainArgs, OutputType, PolarFallback, SolarAlgorithm,
+    Transliteration, VerifyAnchorsArgs,
+};
+use crate::list::providers::{DailyStudies, TorahReadings};
+use crate::prelude::{
+    print_output, write_data, write_error, CountingWriter, EventProvider, Printable, Runnable,
+};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::{HebrewMonth, Location, TorahReadingType};
+use heca_lib::{HebrewDate, HebrewYear};
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::io::Write;
+use std::num::NonZeroI8;
+
+/// A fact about the calendar which was checked against other sources (printed calendars,
+/// hebcal and the Daf Yomi schedule). Gregorian dates are civil days, not the night before.
+enum Anchor {
+    /// The Hebrew date (day, month, year) falls on the Gregorian date.
+    HebrewDate((i8, HebrewMonth, u64), (i32, u32, u32)),
+    /// The Daf Yomi on the Gregorian date, in English with the Sephardi transliteration.
+    DafYomi((i32, u32, u32), &'static str),
+    /// The parsha read in Chutz La'aretz on the Shabbos on the Gregorian date.
+    Parsha((i32, u32, u32), &'static str),
+}
+
+const ANCHORS: &[Anchor] = &[
+    Anchor::HebrewDate((1, HebrewMonth::Tishrei, 5781), (2020, 9, 19)),
+    Anchor::HebrewDate((1, HebrewMonth::Tishrei, 5784), (2023, 9, 16)),
+    Anchor::HebrewDate((25, HebrewMonth::Kislev, 5784), (2023, 12, 8)),
+    Anchor::HebrewDate((14, HebrewMonth::Adar2, 5784), (2024, 3, 24)),
+    Anchor::HebrewDate((15, HebrewMonth::Nissan, 5784), (2024, 4, 23)),
+    Anchor::HebrewDate((10, HebrewMonth::Tishrei, 5785), (2024, 10, 12)),
+    Anchor::DafYomi((1975, 6, 23), "Niddah 73"),
+    Anchor::DafYomi((1975, 6, 24), "Berakhot 2"),
+    Anchor::DafYomi((2012, 8, 3), "Berakhot 2"),
+    Anchor::DafYomi((2020, 1, 5), "Berakhot 2"),
+    Anchor::Parsha((2023, 10, 14), "Bereishis"),
+];
+
+#[derive(Serialize)]
+struct Check {
+    anchor: String,
+    ok: bool,
+    got: String,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Checks(Vec<Check>);
+
+fn format_date((year, month, day): (i32, u32, u32)) -> String {
+    format!("{}-{:02}-{:02}", year, month, day)
+}
+
+/// Returns the night an event on the civil date is listed on, and the Hebrew year it's in.
+fn night_of(date: (i32, u32, u32)) -> (DateTime<Utc>, HebrewYear) {
+    let night = Utc.ymd(date.0, date.1, date.2).and_hms(18, 0, 0) - Duration::days(1);
+    let year = HebrewYear::new(HebrewDate::try_from(night).unwrap().year()).unwrap();
+    (night, year)
+}
+
+/// Returns the name of the event the provider lists on the civil date, if any.
+fn event_on(provider: &dyn EventProvider, date: (i32, u32, u32)) -> String {
+    let (night, year) = night_of(date);
+    match provider.get(&year).into_iter().find(|x| x.day == night) {
+        Some(day_val) => {
+            let mut name = vec![];
+            day_val
+                .name
+                .pretty_print(&mut name, Language::English, Transliteration::Sephardi)
+                .unwrap();
+            String::from_utf8(name).unwrap()
+        }
+        None => "nothing".into(),
+    }
+}
+
+impl Anchor {
+    fn describe(&self) -> String {
+        match self {
+            Anchor::HebrewDate((day, month, year), gregorian) => format!(
+                "{} {} {} is {}",
+                day,
+                crate::prelude::hebrew_month_english(*month),
+                year,
+                format_date(*gregorian)
+            ),
+            Anchor::DafYomi(gregorian, daf) => {
+                format!("Daf Yomi on {} is {}", format_date(*gregorian), daf)
+            }
+            Anchor::Parsha(gregorian, parsha) => {
+                format!("The parsha on {} is {}", format_date(*gregorian), parsha)
+            }
+        }
+    }
+
+    fn check(&self) -> Check {
+        let (ok, got) = match self {
+            Anchor::HebrewDate((day, month, year), gregorian) => {
+                let got = HebrewDate::from_ymd(*year, *month, NonZeroI8::new(*day).unwrap())
+                    .map(|date| {
+                        let night: DateTime<Utc> = date.into();
+                        let civil = night + Duration::days(1);
+                        format_date((civil.year(), civil.month(), civil.day()))
+                    })
+                    .unwrap_or_else(|e| format!("{:?}", e));
+                (got == format_date(*gregorian), got)
+            }
+            Anchor::DafYomi(gregorian, daf) => {
+                let provider = DailyStudies {
+                    events: vec![DailyStudy::DafYomi],
+                    location: Location::Chul,
+                };
+                let got = event_on(&provider, *gregorian);
+                (got == *daf, got)
+            }
+            Anchor::Parsha(gregorian, parsha) => {
+                let provider = TorahReadings {
+                    events: vec![TorahReadingType::Shabbos],
+                    location: Location::Chul,
+                    yom_tov_observance: Location::Chul,
+                    city: None,
+                    reading_cycle: None,
+                    havdalah: vec![],
+                    polar_fallback: PolarFallback::None,
+                    solar_algorithm: SolarAlgorithm::Noaa,
+                };
+                let got = event_on(&provider, *gregorian);
+                (got == *parsha, got)
+            }
+        };
+        Check {
+            anchor: self.describe(),
+            ok,
+            got,
+        }
+    }
+}
+
+fn check_anchors() -> Checks {
+    Checks(ANCHORS.iter().map(Anchor::check).collect())
+}
+
+/// Panics if any anchor doesn't hold. Debug builds run this on startup.
+pub fn assert_anchors() {
+    for check in check_anchors().0 {
+        assert!(check.ok, "{}, but heca says {}", check.anchor, check.got);
+    }
+}
+
+impl Checks {
+    fn pretty_print<W: Write>(&self, lock: &mut W) -> std::io::Result<()> {
+        for check in &self.0 {
+            if check.ok {
+                writeln!(lock, "ok: {}", check.anchor)?;
+            } else {
+                writeln!(lock, "FAILED: {} (got {})", check.anchor, check.got)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Printable for Checks {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(&mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for VerifyAnchorsArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let checks = check_anchors();
+        print_output(&checks, args)?;
+        let failed = checks.0.iter().filter(|check| !check.ok).count();
+        if failed != 0 {
+            return Err(AppError::AnchorMismatch(format!(
+                "{} of {} anchors don't match",
+                failed,
+                checks.0.len()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,14 @@
+use crate::args::prelude::{get_location, parse_date, Config};
+use crate::args::types::{AppError, BarMitzvahArgs, Command, Language};
+use clap::ArgMatches;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+) -> Result<Command, AppError> {
+    Ok(Command::BarMitzvah(BarMitzvahArgs {
+        date: parse_date(matches.value_of("Date").unwrap())?,
+        location: get_location(matches, config, language)?,
+    }))
+}
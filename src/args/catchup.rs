@@ -0,0 +1,56 @@
+use crate::args::digest::today;
+use crate::args::prelude::{get_location, get_today_time_zone, parse_gregorian_date, Config};
+use crate::args::types::{AppError, CatchupArgs, Command, DailyStudy, Language, RambamChapters};
+use crate::prelude::Warnings;
+use clap::ArgMatches;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+    warnings: Warnings,
+) -> Result<Command, AppError> {
+    let study = match matches.value_of("Study").unwrap() {
+        "daf-yomi" => DailyStudy::DafYomi,
+        "yerushalmi-yomi" => DailyStudy::YerushalmiYomi,
+        "chumash" => DailyStudy::Chumash,
+        "rambam-3-chapters" => DailyStudy::Rambam(RambamChapters::Three),
+        "rambam-1-chapter" => DailyStudy::Rambam(RambamChapters::One),
+        x => unreachable!("{}", x),
+    };
+    let behind = matches.value_of("Behind").unwrap();
+    let behind = behind.parse().map_err(|_| {
+        AppError::ArgUndefinedError(format!("--behind must be a number of days, not {}", behind))
+    })?;
+    let pace = parse_pace(matches.value_of("Pace").unwrap())?;
+    let date = match matches.value_of("Date") {
+        Some(date) => parse_gregorian_date(date)?,
+        None => today(
+            matches,
+            get_today_time_zone(matches, None, warnings)?.as_ref(),
+        )?,
+    };
+    Ok(Command::Catchup(CatchupArgs {
+        study,
+        location: get_location(matches, config, language)?,
+        behind,
+        pace,
+        date,
+    }))
+}
+
+/// Reads a pace like `2/day`, or just `2`. Learning one day of the cycle a day never catches up.
+fn parse_pace(pace: &str) -> Result<u32, AppError> {
+    let days = pace.strip_suffix("/day").unwrap_or(pace);
+    match days.parse() {
+        Ok(days) if days > 1 => Ok(days),
+        Ok(_) => Err(AppError::ArgUndefinedError(format!(
+            "A pace of {} never catches up. Learn at least 2/day",
+            pace
+        ))),
+        Err(_) => Err(AppError::ArgUndefinedError(format!(
+            "--pace must be like 2/day, not {}",
+            pace
+        ))),
+    }
+}
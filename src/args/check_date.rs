@@ -0,0 +1,26 @@
+use crate::args::prelude::{get_location, parse_date, str_to_sefirah_custom, Config};
+use crate::args::types::{
+    AppError, CheckDateArgs, CheckedOccasion, Command, Language, SefirahCustom,
+};
+use clap::ArgMatches;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+) -> Result<Command, AppError> {
+    let occasion = match matches.value_of("For").unwrap() {
+        "wedding" => CheckedOccasion::Wedding,
+        x => unreachable!("{}", x),
+    };
+    Ok(Command::CheckDate(CheckDateArgs {
+        date: parse_date(matches.value_of("Date").unwrap())?,
+        occasion,
+        location: get_location(matches, config, language)?,
+        sefirah: matches
+            .value_of("Sefirah")
+            .and_then(str_to_sefirah_custom)
+            .or(config.sefirah)
+            .unwrap_or(SefirahCustom::FirstDays),
+    }))
+}
@@ -1,7 +1,9 @@
 use crate::args::prelude::{parse_hebrew, ConfigDateFmt, ConfigDateType};
 use crate::args::types::{AppError, Command, ConvertArgs, ConvertType, Language};
 use crate::args::DATE_TOKEN;
+use crate::prelude::constants::{check_gregorian_year, check_hebrew_year};
 use chrono::prelude::*;
+use heca_lib::prelude::Location;
 use heca_lib::HebrewDate;
 
 pub fn parse_options(
@@ -9,6 +11,7 @@ pub fn parse_options(
     language: Language,
     datefmt: ConfigDateFmt,
     date_type: ConfigDateType,
+    location: Location,
 ) -> Result<Command, AppError> {
     let sp = date.split(&DATE_TOKEN[..]).collect::<Vec<&str>>();
     if sp.len() != 3 {
@@ -16,22 +19,28 @@ pub fn parse_options(
     }
 
     Ok(match date_type {
-        ConfigDateType::Hebrew => parse_convert_hebrew(&sp, language)?,
-        ConfigDateType::Gregorian => parse_convert_gregorian(&sp, datefmt, language)?,
+        ConfigDateType::Hebrew => parse_convert_hebrew(&sp, language, location)?,
+        ConfigDateType::Gregorian => parse_convert_gregorian(&sp, datefmt, language, location)?,
         ConfigDateType::Fuzzy => {
             if sp[1].parse::<u8>().is_ok() {
-                parse_convert_gregorian(&sp, datefmt, language)?
+                parse_convert_gregorian(&sp, datefmt, language, location)?
             } else {
-                parse_convert_hebrew(&sp, language)?
+                parse_convert_hebrew(&sp, language, location)?
             }
         }
     })
 }
 
-pub fn parse_convert_hebrew(sp: &[&str], language: Language) -> Result<Command, AppError> {
+pub fn parse_convert_hebrew(
+    sp: &[&str],
+    language: Language,
+    location: Location,
+) -> Result<Command, AppError> {
     let (day, month, year) = parse_hebrew(sp)?;
+    check_hebrew_year(year.unwrap() as i64)?;
     Ok(Command::Convert(ConvertArgs {
         language,
+        location,
         date: ConvertType::Hebrew(HebrewDate::from_ymd(year.unwrap(), month, day)?),
     }))
 }
@@ -40,6 +49,7 @@ pub fn parse_convert_gregorian(
     sp: &[&str],
     format: ConfigDateFmt,
     language: Language,
+    location: Location,
 ) -> Result<Command, AppError> {
     let (day, month, year) = match format {
         ConfigDateFmt::ISO | ConfigDateFmt::B => {
@@ -81,8 +91,10 @@ pub fn parse_convert_gregorian(
             (day, month, year)
         }
     };
+    check_gregorian_year(year as i64)?;
     Ok(Command::Convert(ConvertArgs {
         language,
+        location,
         date: ConvertType::Gregorian(
             Utc.ymd_opt(year, month, day)
                 .single()
@@ -0,0 +1,105 @@
+use crate::args::list::shown_events;
+use crate::args::location::get_city;
+use crate::args::prelude::{
+    get_location, get_solar_algorithm, get_today, get_today_time_zone, parse_gregorian_date, Config,
+};
+use crate::args::types::{
+    AppError, Command, DigestArgs, HavdalahOpinion, Language, ListArgs, PolarFallback, WeekStart,
+    YearType, ZMANIM,
+};
+use crate::prelude::system::{date_in, now};
+use crate::prelude::Warnings;
+use chrono::Duration;
+use chrono::{Datelike, NaiveDate, Utc};
+use clap::ArgMatches;
+use zmanim::prelude::tz::TimeZone;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+    warnings: Warnings,
+) -> Result<Command, AppError> {
+    let list = list_args(matches, config, language, warnings)?;
+    let date = match matches.value_of("Date") {
+        Some(date) => parse_gregorian_date(date)?,
+        None => {
+            let time_zone = get_today_time_zone(matches, list.city.as_ref(), warnings)?;
+            today(matches, time_zone.as_ref())?
+        }
+    };
+    let week = date - Duration::days(date.weekday().num_days_from_sunday() as i64);
+    let zmanim = matches
+        .values_of("Zmanim")
+        .unwrap()
+        .map(|name| ZMANIM.iter().find(|(x, _)| *x == name).unwrap().1)
+        .collect();
+    Ok(Command::Digest(DigestArgs {
+        week,
+        list,
+        zmanim,
+        to: matches.value_of("To").map(String::from),
+        from: matches.value_of("From").map(String::from),
+    }))
+}
+
+/// Returns the date it is now (or at `--today`) where `--time-zone`, the city, or the system says,
+/// or in UTC.
+pub(crate) fn today(
+    matches: &ArgMatches<'_>,
+    time_zone: Option<&TimeZone>,
+) -> Result<NaiveDate, AppError> {
+    let now = now(get_today(matches)?, time_zone);
+    Ok(match time_zone {
+        Some(time_zone) => date_in(time_zone, now),
+        None => now.naive_utc().date(),
+    })
+}
+
+/// Returns the arguments to list the events of `--show` with, like `heca list` would list them by
+/// default, for the commands which only print a few days with `ListArgs::list_between`.
+pub(crate) fn list_args(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+    warnings: Warnings,
+) -> Result<ListArgs, AppError> {
+    let shown = matches.values_of("Events").unwrap().collect();
+    let location = get_location(matches, config, language)?;
+    Ok(ListArgs {
+        // `list_between` lists the days it's given whatever the year is.
+        year: YearType::Gregorian(Utc::today().year() as u64),
+        location,
+        yom_tov_observance: location,
+        events: shown_events(shown, &config.custom_days, WeekStart::Sunday),
+        amnt_years: 1,
+        no_sort: false,
+        exact_days: config.exact_days.unwrap_or(false),
+        categories: None,
+        excluded_categories: vec![],
+        city: get_city(matches, config)?,
+        reading_cycle: None,
+        merge_spans: false,
+        merge_rosh_chodesh: false,
+        strict: false,
+        month: None,
+        only_shabbos: false,
+        havdalah: vec![HavdalahOpinion::Minutes42],
+        polar_fallback: PolarFallback::None,
+        solar_algorithm: get_solar_algorithm(matches),
+        icons: false,
+        fields: None,
+        short_names: false,
+        headers: None,
+        summary: false,
+        meta: false,
+        check: false,
+        warn_shabbos_conflicts: false,
+        provenance: false,
+        links: None,
+        alarms: vec![],
+        routes: vec![],
+        append_since_last_run: None,
+        warnings,
+    })
+}
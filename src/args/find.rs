@@ -0,0 +1,69 @@
+use crate::args::digest::today;
+use crate::args::prelude::{get_location, Config};
+use crate::args::types::{AppError, Command, FindArgs, Language};
+use crate::prelude::constants::check_hebrew_year;
+use crate::prelude::night_of;
+use chrono::Weekday;
+use clap::ArgMatches;
+use heca_lib::HebrewDate;
+use std::convert::TryFrom;
+
+/// How many years are searched if `--until` isn't given.
+const DEFAULT_YEARS: u64 = 1000;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+) -> Result<Command, AppError> {
+    let year = |name: &str| -> Result<Option<u64>, AppError> {
+        matches
+            .value_of(name)
+            .map(|year| {
+                year.parse()
+                    .map_err(|_| AppError::YearIsNotANumber(year.into()))
+            })
+            .transpose()
+    };
+    let from = match year("From")? {
+        Some(from) => from,
+        None => HebrewDate::try_from(night_of(today(matches, None)?))?.year(),
+    };
+    let until = year("Until")?.unwrap_or(from + DEFAULT_YEARS);
+    check_hebrew_year(from as i64)?;
+    check_hebrew_year(until as i64)?;
+    if until < from {
+        return Err(AppError::InvalidYearRange(format!(
+            "--until {} is before --from {}",
+            until, from
+        )));
+    }
+    let limit = matches.value_of("Limit").unwrap();
+    let limit = match limit.parse() {
+        Ok(0) | Err(_) => {
+            return Err(AppError::ArgUndefinedError(format!(
+                "--limit must be a number of at least 1, not {}",
+                limit
+            )))
+        }
+        Ok(limit) => limit,
+    };
+    let weekday = matches.value_of("Weekday").map(|x| match x {
+        "sunday" => Weekday::Sun,
+        "monday" => Weekday::Mon,
+        "tuesday" => Weekday::Tue,
+        "wednesday" => Weekday::Wed,
+        "thursday" => Weekday::Thu,
+        "friday" => Weekday::Fri,
+        "shabbos" | "saturday" => Weekday::Sat,
+        x => unreachable!("{}", x),
+    });
+    Ok(Command::Find(FindArgs {
+        event: matches.value_of("Event").unwrap().into(),
+        weekday,
+        limit,
+        from,
+        until,
+        location: get_location(matches, config, language)?,
+    }))
+}
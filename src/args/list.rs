@@ -1,9 +1,15 @@
-use crate::algorithms::candle_lighting::{City, CITIES};
-use crate::args::prelude::{str_to_location, Config};
+use crate::args::location::get_city;
+use crate::args::prelude::{
+    get_location, get_polar_fallback, get_solar_algorithm, str_to_location, str_to_month, Config,
+};
 use crate::args::types::{
-    AppError, Command, CustomHoliday, DailyStudy, Event, Language, ListArgs, MinorHoliday,
-    RambamChapters, YearType,
+    Alarm, AlarmTarget, AlarmTime, AppError, Category, Command, CustomHoliday, DailyStudy, Event,
+    HavdalahOpinion, Headers, Language, LinkFormat, ListArgs, MinorHoliday, MonthFilter,
+    OutputType, RambamChapters, ReadingCycle, Route, TimeOfDay, WeekStart, YearType,
 };
+use crate::args::{CATEGORIES, EVENTS, FIELDS};
+use crate::prelude::{Warning, Warnings};
+use chrono::{Duration, NaiveTime};
 use clap::ArgMatches;
 use heca_lib::prelude::{Location, TorahReadingType};
 
@@ -15,17 +21,40 @@ pub fn parse_options(
     config: &Config,
     language: Language,
     custom_days: &[CustomHoliday],
+    warnings: Warnings,
 ) -> Result<Command, AppError> {
     let year_num = matches
         .value_of("Year")
         .unwrap()
         .parse()
-        .expect("The supplied year must be a number");
-    let amnt_years = matches
+        .map_err(|_| AppError::YearIsNotANumber(matches.value_of("Year").unwrap().into()))?;
+    let amnt_years: i64 = matches
         .value_of("AmountYears")
         .unwrap()
         .parse()
-        .expect("Amount of years must be a number");
+        .map_err(|_| AppError::YearIsNotANumber(matches.value_of("AmountYears").unwrap().into()))?;
+    let until: Option<u64> = match matches.value_of("Until") {
+        Some(until) => Some(
+            until
+                .parse()
+                .map_err(|_| AppError::YearIsNotANumber(until.into()))?,
+        ),
+        None => None,
+    };
+    if let Some(until) = until {
+        // Like the fuzzy year type, years after 3000 are taken to be Hebrew.
+        if (year_num > 3000) != (until > 3000) {
+            let calendar = |year| if year > 3000 { "Hebrew" } else { "Gregorian" };
+            return Err(AppError::InvalidYearRange(format!(
+                "--until {} looks like a {} year, but {} is a {} year. Pass --until a year of the same calendar, or use --years",
+                until,
+                calendar(until),
+                year_num,
+                calendar(year_num)
+            )));
+        }
+    }
+    let (first_year, amnt_years) = get_year_range(year_num, amnt_years, until)?;
 
     let year = if let Some(year_type) = matches.value_of("YearType") {
         match year_type {
@@ -59,6 +88,13 @@ pub fn parse_options(
         YearType::Gregorian(year_num)
     };
 
+    let year = match year {
+        YearType::Hebrew(_) => YearType::Hebrew(first_year),
+        YearType::Gregorian(_) => YearType::Gregorian(first_year),
+    };
+
+    let month = get_month(matches, &year, amnt_years)?;
+
     let no_sort = if matches.occurrences_of("NoSort") > 0 {
         true
     } else if let Some(no_sort) = env::var_os("HECA_NOSORT") {
@@ -81,73 +117,218 @@ pub fn parse_options(
         }
     };
 
-    let location = if let Some(location) = matches.value_of("Location") {
-        str_to_location(location)?
-    } else if let Some(location) = env::var_os("LOC") {
-        let location: String = location.into_string().unwrap();
-        str_to_location(&location)?
-    } else if let Some(location) = env::var_os("HECA_LOCATION") {
-        let location: String = location.into_string().unwrap();
-        str_to_location(&location)?
-    } else if let Some(location) = &config.location {
-        *location
-    } else if language == Language::Hebrew {
-        Location::Israel
-    } else {
-        Location::Chul
+    let preset = matches.value_of("Preset");
+
+    let passed_location = matches
+        .value_of("Location")
+        .map(str_to_location)
+        .transpose()?;
+    let location = match (passed_location, preset) {
+        (None, Some("orthodox-diaspora")) => Location::Chul,
+        (None, Some("israel")) => Location::Israel,
+        (Some(Location::Israel), Some("orthodox-diaspora")) => {
+            return Err(AppError::ConflictingArgs(
+                "--preset orthodox-diaspora lists a Chutz La'aretz calendar, but --location Israel was passed. Use --preset israel".into(),
+            ))
+        }
+        (Some(Location::Chul), Some("israel")) => {
+            return Err(AppError::ConflictingArgs(
+                "--preset israel lists an Israeli calendar, but --location Chul was passed. Use --preset orthodox-diaspora".into(),
+            ))
+        }
+        _ => get_location(matches, config, language)?,
+    };
+    let yom_tov_observance = match matches.value_of("YomTovObservance") {
+        Some(observance) if observance.eq_ignore_ascii_case("israel") => Location::Israel,
+        Some(_) => Location::Chul,
+        None => location,
     };
 
-    let city = if let Some(city) = matches.value_of("City") {
-        Some(String::from(city))
-    } else if let Some(city) = env::var_os("HECA_CITY") {
-        Some(city.to_string_lossy().into_owned())
-    } else if let Some(city) = &config.default_city {
-        Some(city.clone())
-    } else {
-        None
+    let city = get_city(matches, config)?;
+
+    let reading_cycle = match matches.value_of("ReadingCycle") {
+        Some("annual") => Some(ReadingCycle::Annual),
+        Some("triennial") => Some(ReadingCycle::Triennial),
+        Some(x) => unreachable!("{}", x),
+        None => None,
     };
 
-    let city: Option<City> = if let Some(city) = city {
-        let res = config
-            .cities
-            .as_ref()
-            .and_then(|config_city_vec| {
-                config_city_vec
-                    .iter()
-                    .find(|config_city| config_city.name == city)
-                    .and_then(|x| Some(x.clone()))
-            })
-            .and_then(|config_city| {
-                Some(City {
-                    name: config_city.name.into(),
-                    time_zone: config_city.time_zone.clone(),
-                    latitude: config_city.latitude,
-                    longitude: config_city.longitude,
-                    candlelighting_to_sunset: config_city.light_candles_before_shkiya,
-                })
+    let havdalah = matches
+        .values_of("HavdalahOpinion")
+        .map(|x| {
+            x.map(|opinion| match opinion {
+                "42" => HavdalahOpinion::Minutes42,
+                "50" => HavdalahOpinion::Minutes50,
+                "72" => HavdalahOpinion::Minutes72,
+                "8.5" => HavdalahOpinion::Degrees8_5,
+                "rabbeinu-tam" => HavdalahOpinion::RabbeinuTam,
+                x => unreachable!("{}", x),
             })
-            .or_else(|| {
-                CITIES.iter().find(|x| city == x.name).and_then(|x| {
-                    Some(City {
-                        candlelighting_to_sunset: x.candlelighting_to_sunset.clone(),
-                        latitude: x.latitude.clone(),
-                        longitude: x.longitude.clone(),
-                        name: x.name.clone(),
-                        time_zone: x.time_zone.clone(),
-                    })
-                })
-            });
-        match res {
-            Some(res) => Some(res),
-            None => return Err(AppError::CityNotFound(city)),
+            .collect()
+        })
+        .unwrap_or_else(|| vec![HavdalahOpinion::Minutes42]);
+
+    let week_start = match matches.value_of("WeekStart") {
+        Some("shabbos") => WeekStart::Shabbos,
+        _ => WeekStart::Sunday,
+    };
+
+    // Unsorted events are only grouped by the Hebrew year they're generated in.
+    if no_sort {
+        if let Some(headers @ ("gregorian-year" | "month")) = matches.value_of("Headers") {
+            return Err(AppError::ConflictingArgs(format!(
+                "--headers {} can't be used with --no-sort (or HECA_NOSORT=1), which lists the events of every Hebrew year in the order they're generated. Use --headers hebrew-year",
+                headers
+            )));
         }
+    }
+
+    let merge_spans = matches.occurrences_of("MergeSpans") > 0;
+    let strict = matches.occurrences_of("Strict") > 0;
+
+    let categories = matches
+        .values_of("Category")
+        .map(|x| x.map(str_to_category).collect());
+    let excluded_categories = matches
+        .values_of("ExcludeCategory")
+        .map(|x| x.map(str_to_category).collect())
+        .unwrap_or_default();
+
+    // --show defaults to the events of the config file, or else to yom-tov, which shouldn't be
+    // added to a preset unless asked for.
+    let shown: Vec<&str> = if let (0, Some(show)) = (matches.occurrences_of("Events"), &config.show)
+    {
+        show.iter().map(String::as_str).collect()
+    } else if preset.is_none() || matches.occurrences_of("Events") > 0 {
+        matches
+            .values_of("Events")
+            .unwrap_or_else(|| panic!("{}, {}", file!(), line!()))
+            .collect()
     } else {
-        None
+        vec![]
+    };
+    let (times, mut shown) = split_times(shown)?;
+    if !times.is_empty() && city.is_none() {
+        return Err(AppError::ArgUndefinedError(
+            "zmanim need a city. Pass --city, --coordinates or --address, or set HECA_CITY or default-city in the \
+             config file"
+                .into(),
+        ));
+    }
+    let only_shabbos = matches.occurrences_of("OnlyShabbos") > 0;
+    let only_shabbos_events: &[&str] = if only_shabbos {
+        &[
+            "shabbos",
+            "yom-tov",
+            "chol",
+            "special-parshas",
+            "shabbos-mevarchim",
+        ]
+    } else {
+        &[]
     };
+    for event in preset
+        .map(get_preset)
+        .unwrap_or(&[])
+        .iter()
+        .chain(only_shabbos_events)
+    {
+        if !shown.contains(event) {
+            shown.push(event);
+        }
+    }
+    if shown.contains(&"agricultural") && location == Location::Chul {
+        return Err(AppError::ArgUndefinedError(
+            "The agricultural dates are only listed in Israel. Pass --location Israel".into(),
+        ));
+    }
+    if shown.contains(&"all") {
+        shown = EVENTS.to_vec();
+    }
+    if strict {
+        check_unused_args(matches, &shown, city.is_some())?;
+    }
 
-    let events = matches
-        .values_of("Events")
-        .unwrap_or_else(|| panic!("{}, {}", file!(), line!()))
+    let events = shown_events(shown, custom_days, week_start)
+        .into_iter()
+        .chain(times.into_iter().map(Event::Zman))
+        .collect::<Vec<Event>>();
+    if events.iter().any(|x| matches!(x, Event::CustomHoliday(_))) {
+        if strict && !config.custom_day_conflicts.is_empty() {
+            return Err(AppError::ConfigError(
+                config.custom_day_conflicts.join("\n"),
+            ));
+        }
+        for conflict in &config.custom_day_conflicts {
+            warnings.warn(Warning::CustomHolidayConflict(conflict.clone()))?;
+        }
+    }
+
+    Ok(Command::List(ListArgs {
+        city,
+        year,
+        location,
+        yom_tov_observance,
+        events,
+        amnt_years,
+        no_sort,
+        exact_days,
+        categories,
+        excluded_categories,
+        reading_cycle,
+        merge_spans,
+        merge_rosh_chodesh: matches.occurrences_of("MergeRoshChodesh") > 0,
+        strict,
+        month,
+        only_shabbos,
+        havdalah,
+        polar_fallback: get_polar_fallback(matches),
+        solar_algorithm: get_solar_algorithm(matches),
+        icons: matches.occurrences_of("Icons") > 0,
+        fields: matches
+            .values_of("Fields")
+            .map(|x| x.map(parse_field).collect())
+            .transpose()?,
+        short_names: matches.occurrences_of("ShortNames") > 0,
+        headers: match matches.value_of("Headers") {
+            Some("hebrew-year") => Some(Headers::HebrewYear),
+            Some("gregorian-year") => Some(Headers::GregorianYear),
+            Some("month") => Some(Headers::Month),
+            _ => None,
+        },
+        summary: matches.occurrences_of("Summary") > 0,
+        meta: matches.occurrences_of("Meta") > 0,
+        check: matches.occurrences_of("Check") > 0,
+        warn_shabbos_conflicts: matches.occurrences_of("WarnShabbosConflicts") > 0,
+        provenance: matches.is_present("Provenance"),
+        links: match matches.value_of("Links") {
+            Some("csv") => Some(LinkFormat::Csv),
+            Some("json") => Some(LinkFormat::Json),
+            _ => None,
+        },
+        alarms: matches
+            .values_of("Alarm")
+            .map(|x| x.map(parse_alarm).collect())
+            .unwrap_or_else(|| Ok(vec![]))?,
+        routes: matches
+            .values_of("Route")
+            .map(|x| x.map(parse_route).collect())
+            .unwrap_or_else(|| Ok(vec![]))?,
+        append_since_last_run: matches
+            .value_of("AppendSinceLastRun")
+            .map(std::path::PathBuf::from),
+        warnings,
+    }))
+}
+
+/// Returns the events to list for the names passed to `--show`, other than times.
+pub(crate) fn shown_events(
+    shown: Vec<&str>,
+    custom_days: &[CustomHoliday],
+    week_start: WeekStart,
+) -> Vec<Event> {
+    shown
+        .into_iter()
         .flat_map(|x| match x {
             "yom-tov" => vec![Event::TorahReadingType(TorahReadingType::YomTov)],
             "chol" => vec![Event::TorahReadingType(TorahReadingType::Chol)],
@@ -159,8 +340,11 @@ pub fn parse_options(
                 .map(|x| Event::CustomHoliday(x.clone()))
                 .collect(),
             "minor-holidays" => vec![Event::MinorHoliday(MinorHoliday::Minor)],
+            "avos-ubanim" => vec![Event::MinorHoliday(MinorHoliday::AvosUbanim)],
             "daf-yomi" => vec![Event::DailyStudy(DailyStudy::DafYomi)],
+            "daf-hashavua" => vec![Event::DailyStudy(DailyStudy::DafHashavua(week_start))],
             "yerushalmi-yomi" => vec![Event::DailyStudy(DailyStudy::YerushalmiYomi)],
+            "chumash" => vec![Event::DailyStudy(DailyStudy::Chumash)],
             "rambam-3-chapters" => {
                 vec![Event::DailyStudy(DailyStudy::Rambam(RambamChapters::Three))]
             }
@@ -168,18 +352,308 @@ pub fn parse_options(
 
             "israeli-holidays" => vec![Event::IsraeliHolidays],
             "chabad-holidays" => vec![Event::ChabadHolidays],
+            "agricultural" => vec![Event::Agricultural],
+            "davening-changes" => vec![Event::DaveningChanges],
 
             "shabbos-mevarchim" => vec![Event::ShabbosMevarchim],
             _ => unreachable!("{}", x),
         })
-        .collect::<Vec<Event>>();
-    Ok(Command::List(ListArgs {
-        city,
-        year,
-        location,
-        events,
-        amnt_years,
-        no_sort,
-        exact_days,
-    }))
+        .collect()
+}
+
+/// Takes the times out of `--show`. `zmanim:` starts a list of times, so that
+/// `--show yom-tov,zmanim:candles,havdalah` lists Yom Tov, candle lighting and havdalah.
+fn split_times(shown: Vec<&str>) -> Result<(Vec<TimeOfDay>, Vec<&str>), AppError> {
+    let mut times = vec![];
+    let mut events = vec![];
+    let mut in_times = false;
+    for value in shown {
+        let time = match value.strip_prefix("zmanim:") {
+            Some(name) => {
+                in_times = true;
+                TimeOfDay::from_arg_name(name)
+            }
+            None => TimeOfDay::from_arg_name(value),
+        };
+        match time {
+            Some(_) if !in_times => {
+                return Err(AppError::ArgUndefinedError(format!(
+                    "{} is a time. List it with --show zmanim:{}",
+                    value, value
+                )))
+            }
+            Some(time) if !times.contains(&time) => times.push(time),
+            Some(_) => {}
+            None => events.push(value),
+        }
+    }
+    Ok((times, events))
+}
+
+/// With `--strict`, fails on arguments which wouldn't change the list, since they were probably
+/// passed along with the wrong events.
+fn check_unused_args(
+    matches: &ArgMatches<'_>,
+    shown: &[&str],
+    has_city: bool,
+) -> Result<(), AppError> {
+    let unused = |arg: &str, event: &str, does: &str| {
+        if matches.occurrences_of(arg) > 0 && !shown.contains(&event) {
+            Err(AppError::ConflictingArgs(format!(
+                "{} {}. Add {} to --show, or don't pass it",
+                does, event, event
+            )))
+        } else {
+            Ok(())
+        }
+    };
+    unused(
+        "ExactDays",
+        "israeli-holidays",
+        "--exact-days only changes the dates of",
+    )?;
+    unused("ReadingCycle", "shabbos", "--reading-cycle only annotates")?;
+    unused(
+        "WeekStart",
+        "daf-hashavua",
+        "--week-start only changes the days of",
+    )?;
+    if matches.occurrences_of("HavdalahOpinion") > 0 && !has_city {
+        return Err(AppError::ConflictingArgs(
+            "--havdalah-opinion needs a city. Pass --city, --coordinates or --address".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Parses `--month` and `--gmonth`, which only make sense for a single year of the same kind.
+fn get_month(
+    matches: &ArgMatches<'_>,
+    year: &YearType,
+    amnt_years: u64,
+) -> Result<Option<MonthFilter>, AppError> {
+    let month = if let Some(month) = matches.value_of("Month") {
+        let month = str_to_month(month)
+            .or_else(|| str_to_month(&month.to_lowercase()))
+            .ok_or_else(|| AppError::MonthNotParsed(month.to_owned()))?;
+        if let YearType::Gregorian(_) = year {
+            return Err(AppError::ArgUndefinedError(
+                "--month needs a Hebrew year. Use --gmonth for a Gregorian month".into(),
+            ));
+        }
+        MonthFilter::Hebrew(month)
+    } else if let Some(month) = matches.value_of("GregorianMonth") {
+        let month = match month.parse() {
+            Ok(month) if (1..=12).contains(&month) => month,
+            _ => return Err(AppError::MonthNotParsed(month.to_owned())),
+        };
+        if let YearType::Hebrew(_) = year {
+            return Err(AppError::ArgUndefinedError(
+                "--gmonth needs a Gregorian year. Use --month for a Hebrew month".into(),
+            ));
+        }
+        MonthFilter::Gregorian(month)
+    } else {
+        return Ok(None);
+    };
+    if amnt_years != 1 {
+        return Err(AppError::InvalidYearRange(
+            "a single month can only be listed in a single year".into(),
+        ));
+    }
+    Ok(Some(month))
+}
+
+/// Returns the first year and the amount of years to generate. Ranges going backwards from
+/// the given year (`--years -10` or `--until` with an earlier year) are turned around so
+/// that they always go forwards.
+fn get_year_range(year: u64, amnt_years: i64, until: Option<u64>) -> Result<(u64, u64), AppError> {
+    if let Some(until) = until {
+        if until >= year {
+            Ok((year, until - year + 1))
+        } else {
+            Ok((until, year - until + 1))
+        }
+    } else if amnt_years > 0 {
+        Ok((year, amnt_years as u64))
+    } else if amnt_years < 0 {
+        let amnt_years = amnt_years.unsigned_abs();
+        match (year + 1).checked_sub(amnt_years) {
+            Some(first_year) if first_year > 0 => Ok((first_year, amnt_years)),
+            _ => Err(AppError::InvalidYearRange(format!(
+                "cannot go back {} years from year {}",
+                amnt_years, year
+            ))),
+        }
+    } else {
+        Err(AppError::InvalidYearRange(
+            "the amount of years must not be 0".into(),
+        ))
+    }
+}
+
+fn get_preset(preset: &str) -> &'static [&'static str] {
+    match preset {
+        "orthodox-diaspora" => &[
+            "yom-tov",
+            "shabbos",
+            "special-parshas",
+            "chol",
+            "minor-holidays",
+            "omer",
+            "custom-holidays",
+            "shabbos-mevarchim",
+        ],
+        "israel" => &[
+            "yom-tov",
+            "shabbos",
+            "special-parshas",
+            "chol",
+            "minor-holidays",
+            "omer",
+            "custom-holidays",
+            "israeli-holidays",
+            "shabbos-mevarchim",
+        ],
+        "daily-study" => &[
+            "daf-yomi",
+            "yerushalmi-yomi",
+            "chumash",
+            "rambam-3-chapters",
+            "rambam-1-chapter",
+        ],
+        x => unreachable!("{}", x),
+    }
+}
+
+/// Reads an alarm passed to `--alarm`, like `candles:-18m` or `daf-yomi:20:00`: the events it's
+/// added to, followed by an offset in minutes, hours or days, or by a time of the day.
+fn parse_alarm(alarm: &str) -> Result<Alarm, AppError> {
+    let error = || {
+        AppError::ArgUndefinedError(format!(
+            "Cannot read the alarm \"{}\". It's written like \"candles:-18m\", \"yom-tov:-1d\" or \"daf-yomi:20:00\"",
+            alarm
+        ))
+    };
+    let (target, when) = alarm.split_once(':').ok_or_else(error)?;
+    let target = match target {
+        "all" => AlarmTarget::All,
+        "candles" => AlarmTarget::CandleLighting,
+        "havdalah" => AlarmTarget::Havdalah,
+        "daf-yomi" => AlarmTarget::DailyStudy("DafYomi"),
+        "daf-hashavua" => AlarmTarget::DailyStudy("DafHashavua"),
+        "yerushalmi-yomi" => AlarmTarget::DailyStudy("Yerushalmi"),
+        "chumash" => AlarmTarget::DailyStudy("Chumash"),
+        "rambam-3-chapters" => AlarmTarget::DailyStudy("Rambam3Chapters"),
+        "rambam-1-chapter" => AlarmTarget::DailyStudy("Rambam1Chapter"),
+        x => AlarmTarget::Category(parse_category(x).ok_or_else(error)?),
+    };
+    let when = match NaiveTime::parse_from_str(when, "%H:%M") {
+        Ok(time) => AlarmTime::At(time),
+        Err(_) => {
+            let sign = match when.get(..1) {
+                Some("-") => -1,
+                Some("+") => 1,
+                _ => return Err(error()),
+            };
+            let unit = when[1..].chars().last().ok_or_else(error)?;
+            let amount: i64 = when[1..when.len() - unit.len_utf8()]
+                .parse()
+                .map_err(|_| error())?;
+            AlarmTime::Offset(match unit {
+                'm' => Duration::minutes(sign * amount),
+                'h' => Duration::hours(sign * amount),
+                'd' => Duration::days(sign * amount),
+                _ => return Err(error()),
+            })
+        }
+    };
+    Ok(Alarm { target, when })
+}
+
+/// Reads a category written like the events of `--show` (`yom-tov`), like `--category`
+/// (`yom_tov`), or `zmanim`.
+fn parse_category(category: &str) -> Option<Category> {
+    let category = match category {
+        "zmanim" => String::from("zman"),
+        x => x.replace('-', "_"),
+    };
+    if CATEGORIES.contains(&category.as_str()) {
+        Some(str_to_category(&category))
+    } else {
+        None
+    }
+}
+
+/// Reads a field of `--fields`, which is a field of the events in JSON or a field inside one,
+/// like `name.en`.
+fn parse_field(field: &str) -> Result<String, AppError> {
+    let top = field.split('.').next().unwrap();
+    if FIELDS.contains(&top) {
+        Ok(field.into())
+    } else {
+        Err(AppError::ArgUndefinedError(format!(
+            "Unknown field \"{}\". The fields are {}",
+            field,
+            FIELDS.join(", ")
+        )))
+    }
+}
+
+/// Reads a route, like `zmanim=>zmanim.ics`. The file is written in the format its extension
+/// says: `.ics`, `.json`, `.cbor`, `.msgpack`, `.parquet` (with the `parquet` feature), or `.txt`
+/// for the printed list.
+fn parse_route(route: &str) -> Result<Route, AppError> {
+    let error = || {
+        AppError::ArgUndefinedError(format!(
+            "Cannot read the route \"{}\". It's written like \"zmanim=>zmanim.ics\" or \"daily-study=>study.json\"",
+            route
+        ))
+    };
+    let (category, path) = route.split_once("=>").ok_or_else(error)?;
+    let category = parse_category(category.trim()).ok_or_else(error)?;
+    let path = std::path::PathBuf::from(path.trim());
+    let extension = path
+        .extension()
+        .and_then(|x| x.to_str())
+        .map(str::to_ascii_lowercase);
+    let output_type = match extension.as_deref() {
+        Some("ics") => OutputType::ICS,
+        Some("json") => OutputType::JSON,
+        Some("cbor") => OutputType::CBOR,
+        Some("msgpack") => OutputType::MessagePack,
+        #[cfg(feature = "parquet")]
+        Some("parquet") => OutputType::Parquet,
+        Some("txt") => OutputType::Regular,
+        _ => {
+            return Err(AppError::ArgUndefinedError(format!(
+                "Cannot tell which format to write {} in. Its name has to end with .ics, .json, .cbor, .msgpack{} or .txt",
+                path.display(),
+                if cfg!(feature = "parquet") { ", .parquet" } else { "" }
+            )))
+        }
+    };
+    Ok(Route {
+        category,
+        path,
+        output_type,
+    })
+}
+
+fn str_to_category(category: &str) -> Category {
+    match category {
+        "yom_tov" => Category::YomTov,
+        "chol" => Category::Chol,
+        "fast" => Category::Fast,
+        "rosh_chodesh" => Category::RoshChodesh,
+        "parsha" => Category::Parsha,
+        "special_parsha" => Category::SpecialParsha,
+        "minor" => Category::Minor,
+        "custom" => Category::Custom,
+        "daily_study" => Category::DailyStudy,
+        "agricultural" => Category::Agricultural,
+        "davening_changes" => Category::DaveningChanges,
+        "zman" => Category::Zman,
+        x => unreachable!("{}", x),
+    }
 }
@@ -0,0 +1,295 @@
+//! Where candle lighting, havdalah and the zmanim are calculated for: a built-in city, a city in
+//! the config file, coordinates or an address.
+
+use crate::algorithms::candle_lighting::{City, CITIES};
+use crate::args::prelude::Config;
+use crate::args::types::AppError;
+use clap::ArgMatches;
+use std::convert::TryFrom;
+use std::process;
+use zmanim::prelude::tz::TimeZone;
+
+/// Returns the place passed to `--city`, `--coordinates` or `--address`, or else the city set in
+/// `HECA_CITY` or the config file's default.
+pub fn get_city(matches: &ArgMatches<'_>, config: &Config) -> Result<Option<City>, AppError> {
+    let time_zone = match matches.value_of("TimeZone") {
+        Some(time_zone) => Some(
+            TimeZone::try_from(time_zone)
+                .map_err(|_| AppError::InvalidTimeZone(time_zone.into()))?,
+        ),
+        None => None,
+    };
+    if let Some(coordinates) = matches.value_of("Coordinates") {
+        let (latitude, longitude) = parse_coordinates(coordinates)?;
+        let name = format!("{:.4},{:.4}", latitude, longitude);
+        return Ok(Some(at_coordinates(name, latitude, longitude, time_zone)));
+    }
+    if let Some(address) = matches.value_of("Address") {
+        let city = match &config.geocoder {
+            Some(program) => CommandGeocoder { program }.locate(address, time_zone)?,
+            None => KnownCities { config }.locate(address, time_zone)?,
+        };
+        return city
+            .map(Some)
+            .ok_or_else(|| AppError::AddressNotFound(address.into()));
+    }
+
+    let city = if let Some(city) = matches.value_of("City") {
+        Some(String::from(city))
+    } else {
+        config.default_city.clone()
+    };
+    match city {
+        Some(city) => match find_city(config, |name| name == city) {
+            Some(res) => Ok(Some(res)),
+            None => Err(AppError::CityNotFound(city)),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Returns the first city in the config file, or else the first built-in city, whose name
+/// matches. It lights candles as many minutes before sunset as the config file's `candle-lighting`
+/// sets for it, if it does.
+fn find_city(config: &Config, matches: impl Fn(&str) -> bool) -> Option<City> {
+    let config_city = config
+        .cities
+        .iter()
+        .flatten()
+        .find(|config_city| matches(&config_city.name))
+        .map(|config_city| City {
+            name: config_city.name.clone().into(),
+            time_zone: config_city.time_zone.clone(),
+            latitude: config_city.latitude,
+            longitude: config_city.longitude,
+            candlelighting_to_sunset: config_city.light_candles_before_shkiya,
+        });
+    config_city
+        .or_else(|| {
+            CITIES.iter().find(|x| matches(&x.name)).map(|x| City {
+                candlelighting_to_sunset: x.candlelighting_to_sunset,
+                latitude: x.latitude,
+                longitude: x.longitude,
+                name: x.name.clone(),
+                time_zone: x.time_zone.clone(),
+            })
+        })
+        .map(|mut city| {
+            if let Some(&minutes) = config.candle_lighting.get(city.name.as_ref()) {
+                city.candlelighting_to_sunset = minutes;
+            }
+            city
+        })
+}
+
+/// Returns a place at the coordinates. Unless a time zone is given, it has the time zone of the
+/// nearest built-in city; it always lights candles as long before sunset as that city does.
+fn at_coordinates(
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    time_zone: Option<TimeZone>,
+) -> City {
+    let nearest = CITIES
+        .iter()
+        .min_by(|a, b| {
+            let distance =
+                |city: &City| distance(latitude, longitude, city.latitude, city.longitude);
+            distance(a).partial_cmp(&distance(b)).unwrap()
+        })
+        .unwrap();
+    City {
+        name: name.into(),
+        time_zone: time_zone.unwrap_or_else(|| nearest.time_zone.clone()),
+        latitude,
+        longitude,
+        candlelighting_to_sunset: nearest.candlelighting_to_sunset,
+    }
+}
+
+/// Returns the angle between two places, as seen from the center of the earth, in radians.
+fn distance(latitude1: f64, longitude1: f64, latitude2: f64, longitude2: f64) -> f64 {
+    let (latitude1, latitude2) = (latitude1.to_radians(), latitude2.to_radians());
+    let longitude = (longitude1 - longitude2).to_radians();
+    let cos =
+        latitude1.sin() * latitude2.sin() + latitude1.cos() * latitude2.cos() * longitude.cos();
+    cos.clamp(-1.0, 1.0).acos()
+}
+
+/// Parses a latitude and longitude, in decimal degrees (`40.69,-73.99` or `40.69 -73.99`), or in
+/// degrees, minutes and seconds with the hemispheres (`40°41'N 73°59'W`). With hemispheres, the
+/// longitude may come first.
+pub fn parse_coordinates(text: &str) -> Result<(f64, f64), AppError> {
+    let error = || AppError::CannotParseCoordinates(text.into());
+    let upper = text.trim().to_uppercase();
+    let (first, second) = match upper.find(['N', 'S', 'E', 'W']) {
+        Some(index) => upper.split_at(index + 1),
+        None => match upper.split_once(',') {
+            Some(parts) => parts,
+            None => {
+                let parts: Vec<&str> = upper.split_whitespace().collect();
+                match parts[..] {
+                    [first, second] => (first, second),
+                    _ => return Err(error()),
+                }
+            }
+        },
+    };
+    let second = second.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    let first = parse_angle(first).ok_or_else(error)?;
+    let second = parse_angle(second).ok_or_else(error)?;
+    let is_longitude = |hemisphere: Option<char>| matches!(hemisphere, Some('E') | Some('W'));
+    let is_latitude = |hemisphere: Option<char>| matches!(hemisphere, Some('N') | Some('S'));
+    let ((latitude, latitude_hemisphere), (longitude, longitude_hemisphere)) =
+        if is_longitude(first.1) || is_latitude(second.1) {
+            (second, first)
+        } else {
+            (first, second)
+        };
+    if is_longitude(latitude_hemisphere)
+        || is_latitude(longitude_hemisphere)
+        || !(-90.0..=90.0).contains(&latitude)
+        || !(-180.0..=180.0).contains(&longitude)
+    {
+        return Err(error());
+    }
+    Ok((latitude, longitude))
+}
+
+/// Parses an angle in decimal degrees, or in degrees and minutes, with or without seconds,
+/// followed by an optional hemisphere. Returns the angle, which is negative to the south and
+/// west, and the hemisphere.
+fn parse_angle(text: &str) -> Option<(f64, Option<char>)> {
+    let text = text.trim();
+    let (text, hemisphere) = match text.chars().last()? {
+        c @ ('N' | 'S' | 'E' | 'W') => (&text[..text.len() - 1], Some(c)),
+        _ => (text, None),
+    };
+    let numbers = text
+        .split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse::<f64>().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    let (degrees, minutes, seconds) = match numbers[..] {
+        [degrees] => (degrees, 0.0, 0.0),
+        [degrees, minutes] => (degrees, minutes, 0.0),
+        [degrees, minutes, seconds] => (degrees, minutes, seconds),
+        _ => return None,
+    };
+    if !(0.0..60.0).contains(&minutes) || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+    let angle = degrees.abs() + minutes / 60.0 + seconds / 3600.0;
+    let negative = degrees.is_sign_negative() || matches!(hemisphere, Some('S') | Some('W'));
+    Some((if negative { -angle } else { angle }, hemisphere))
+}
+
+/// Finds where an address is, for `--address`.
+pub trait Geocoder {
+    /// Returns the place at the address, or `None` if it can't be found. `time_zone` is the time
+    /// zone passed to `--time-zone`, if any.
+    fn locate(&self, address: &str, time_zone: Option<TimeZone>) -> Result<Option<City>, AppError>;
+}
+
+/// Looks up addresses in the cities of the config file and the built-in cities, which works
+/// offline. Each part of the address, separated by commas, is looked up in turn, so that
+/// `Golders Green, London` is found as London. Case, spaces and punctuation are ignored.
+pub struct KnownCities<'a> {
+    pub config: &'a Config,
+}
+
+impl Geocoder for KnownCities<'_> {
+    fn locate(&self, address: &str, time_zone: Option<TimeZone>) -> Result<Option<City>, AppError> {
+        let normalize = |name: &str| -> String {
+            name.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        for part in address.split(',') {
+            let part = normalize(part);
+            if part.is_empty() {
+                continue;
+            }
+            if let Some(mut city) = find_city(self.config, |name| normalize(name) == part) {
+                if let Some(time_zone) = time_zone {
+                    city.time_zone = time_zone;
+                }
+                return Ok(Some(city));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Runs the program set as `geocoder` in the config file with the address as its argument. It
+/// prints the coordinates on the first line, in any format `--coordinates` takes, and may print
+/// the time zone on the second. It prints nothing, or fails, if it can't find the address.
+pub struct CommandGeocoder<'a> {
+    pub program: &'a str,
+}
+
+impl Geocoder for CommandGeocoder<'_> {
+    fn locate(&self, address: &str, time_zone: Option<TimeZone>) -> Result<Option<City>, AppError> {
+        let output = process::Command::new(self.program)
+            .arg(address)
+            .output()
+            .map_err(|e| {
+                AppError::ConfigError(format!("Cannot run geocoder {}: {}", self.program, e))
+            })?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines().map(str::trim).filter(|x| !x.is_empty());
+        let (latitude, longitude) = match lines.next() {
+            Some(coordinates) => parse_coordinates(coordinates)?,
+            None => return Ok(None),
+        };
+        let time_zone = match (time_zone, lines.next()) {
+            (Some(time_zone), _) => Some(time_zone),
+            (None, Some(time_zone)) => Some(
+                TimeZone::try_from(time_zone)
+                    .map_err(|_| AppError::InvalidTimeZone(time_zone.into()))?,
+            ),
+            (None, None) => None,
+        };
+        Ok(Some(at_coordinates(
+            address.into(),
+            latitude,
+            longitude,
+            time_zone,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn coordinate_formats() {
+        let brooklyn = (40.0 + 41.0 / 60.0, -(73.0 + 59.0 / 60.0));
+        for text in &[
+            "40.683333,-73.983333",
+            "40.683333 -73.983333",
+            "40°41'N 73°59'W",
+            "40°41′N, 73°59′W",
+            "73°59'W 40°41'N",
+            "40 41 0 n 73 59 0 w",
+        ] {
+            let (latitude, longitude) = parse_coordinates(text).unwrap();
+            assert!((latitude - brooklyn.0).abs() < 1e-5, "{}", text);
+            assert!((longitude - brooklyn.1).abs() < 1e-5, "{}", text);
+        }
+        for text in &[
+            "40.68",
+            "91,0",
+            "40°41'E 73°59'W",
+            "40°61'N 73°59'W",
+            "north",
+        ] {
+            assert!(parse_coordinates(text).is_err(), "{}", text);
+        }
+    }
+}
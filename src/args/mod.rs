@@ -1,22 +1,106 @@
-use clap::{App, Arg, ArgMatches, SubCommand};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 
+mod bar_mitzvah;
+mod catchup;
+mod check_date;
 mod convert;
+mod digest;
+mod find;
 mod list;
+mod location;
+mod plan_siyum;
 pub(crate) mod prelude;
+mod slug;
+mod status_bar;
 pub mod types;
+mod zmanim;
 
-use crate::args::prelude::{Config, ConfigDateFmt, ConfigDateType};
+use crate::args::prelude::{
+    get_hebrew_year_range, get_location, get_today, str_to_month, str_to_transliteration, Config,
+    ConfigDateFmt, ConfigDateType,
+};
 use crate::args::types::*;
+use crate::prelude::ascii::locale_is_not_utf8;
+use crate::prelude::system::system_language;
+use crate::prelude::Warnings;
+use clap::ErrorKind;
 use std::env;
+use std::ffi::OsString;
 
 const DATE_TOKEN: [char; 8] = ['-', '/', '_', '\\', '.', ',', '=', ' '];
+const EVENTS: &[&str] = &[
+    "yom-tov",
+    "shabbos",
+    "special-parshas",
+    "chol",
+    "minor-holidays",
+    "omer",
+    "custom-holidays",
+    "daf-yomi",
+    "daf-hashavua",
+    "yerushalmi-yomi",
+    "chumash",
+    "rambam-3-chapters",
+    "rambam-1-chapter",
+    "israeli-holidays",
+    "chabad-holidays",
+    "agricultural",
+    "davening-changes",
+    "shabbos-mevarchim",
+    "avos-ubanim",
+];
+const CATEGORIES: &[&str] = &[
+    "yom_tov",
+    "chol",
+    "fast",
+    "rosh_chodesh",
+    "parsha",
+    "special_parsha",
+    "minor",
+    "custom",
+    "daily_study",
+    "agricultural",
+    "davening_changes",
+    "zman",
+];
+/// The fields of an event in JSON, which `list --fields` selects from. The fields inside them
+/// (like `name.en` or `topic.masechta`) depend on the event, so they aren't checked.
+const FIELDS: &[&str] = &[
+    "day",
+    "type",
+    "name",
+    "topic",
+    "count",
+    "month",
+    "molad",
+    "end",
+    "nights",
+    "special",
+    "events",
+    "units",
+    "candleLighting",
+    "candleLightingMinutes",
+    "havdalah",
+    "torahPortion",
+    "time",
+    "opinion",
+    "category",
+    "timeRange",
+    "uid",
+    "source",
+];
 
-pub fn build_args<I, T>(_args: I, output_type: OutputType) -> Result<MainArgs, AppError>
+pub fn build_args<I, T>(args: I, output_type: OutputType) -> Result<MainArgs, AppError>
 where
     I: IntoIterator<Item = T>,
     T: Into<std::ffi::OsString> + Clone,
 {
-    parse_args(App::new("Hebrew calendar program")
+    // Times are listed with `--show zmanim:candles,havdalah`, which is split into
+    // `zmanim:candles` and `havdalah`.
+    let times: Vec<String> = TimeOfDay::arg_names()
+        .map(|name| format!("zmanim:{}", name))
+        .collect();
+    let app = App::new("Hebrew calendar program")
                    .version(env!("CARGO_PKG_VERSION"))
                    .about("Heca is a fast utility to convert and list dates in the Hebrew Calendar.")
                    .arg(Arg::with_name("configfile")
@@ -24,17 +108,532 @@ where
                        .help("Sets a custom config file.")
                        .takes_value(true)
                        .required(false))
+                   .arg(Arg::with_name("Profile")
+                       .long("profile")
+                       .help("Use the settings of this profile of the config file, like \"shul\" for [profiles.shul]")
+                       .takes_value(true)
+                       .required(false))
                    .arg(Arg::with_name("type")
                             .long("print")
-                            .help(r#"Set output type. Options are ["regular", "pretty", "json"]"#)
+                            .help(r#"Set output type. Options are ["regular", "pretty", "json", "ics", "cbor", "msgpack"]"#)
                             .takes_value(true)
                             .required(false), )
+                   .arg(Arg::with_name("out")
+                       .long("out")
+                       .help("Write the output to this file instead of stdout")
+                       .takes_value(true)
+                       .global(true)
+                       .required(false))
+                   .arg(Arg::with_name("Ascii")
+                       .long("ascii")
+                       .help("Print only ASCII, for terminals which can't show Hebrew. Hebrew names are printed in English")
+                       .global(true)
+                       .required(false))
+                   .arg(Arg::with_name("Today")
+                       .long("today")
+                       .help("The time it is now, like 2025-03-11T20:00, for commands which print today's day, so that they print the same day every time. Without an offset (like +02:00), it's the time in the time zone today is found in. Defaults to HECA_TODAY, or the system clock")
+                       .takes_value(true)
+                       .global(true)
+                       .required(false))
+                   .arg(Arg::with_name("DenyWarnings")
+                       .long("deny-warnings")
+                       .help("Fail instead of warning when something is left out of the output")
+                       .global(true)
+                       .required(false))
                    .arg(Arg::with_name("language")
                        .long("language")
-                       .help("Set language")
-                       .possible_values(&["en_US", "he_IL"])
+                       .help("Set language. Pass two languages (for example \"en,he\") to print every event in both")
+                       .possible_values(&["en_US", "he_IL", "en", "he"])
+                       .takes_value(true)
+                       .use_delimiter(true)
+                       .max_values(2)
+                       .number_of_values(1)
+                       .multiple(true)
+                       .required(false))
+                   .arg(Arg::with_name("transliteration")
+                       .long("transliteration")
+                       .help("Set how masechta names are written in English output")
+                       .possible_values(&["sephardi", "ashkenazi", "abbreviated", "hebrew"])
                        .takes_value(true)
                        .required(false))
+                   .subcommand(SubCommand::with_name("limits")
+                       .about("Prints the range of years heca supports"))
+                   .subcommand(SubCommand::with_name("self-test")
+                       .about("Checks heca's output against built-in known-good calendars"))
+                   .subcommand(SubCommand::with_name("verify-anchors")
+                       .about("Checks heca against a table of externally verified dates"))
+                   .subcommand(SubCommand::with_name("zmanim")
+                       .about("Prints the zmanim of every day of a Gregorian year in a city")
+                       .arg(Arg::with_name("Year")
+                           .long("year")
+                           .help("The Gregorian year")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("City")
+                           .long("city")
+                           .help("Can be a built-in city or a city specified in the config file.")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Coordinates")
+                           .long("coordinates")
+                           .help("The latitude and longitude to calculate the zmanim for, like \"40.69,-73.99\" or \"40°41'N 73°59'W\", instead of a city")
+                           .takes_value(true)
+                           .allow_hyphen_values(true)
+                           .required(false)
+                           .conflicts_with_all(&["City", "Address"]))
+                       .arg(Arg::with_name("Address")
+                           .long("address")
+                           .help("An address to calculate the zmanim for, instead of a city. It's found in the built-in cities and the cities of the config file, or with the geocoder set in the config file")
+                           .takes_value(true)
+                           .required(false)
+                           .conflicts_with("City"))
+                       .arg(Arg::with_name("TimeZone")
+                           .long("time-zone")
+                           .help("The time zone of --coordinates or --address, like \"America/New_York\". Defaults to that of the nearest built-in city")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Zmanim")
+                           .long("zmanim")
+                           .help("Which zmanim to print, in this order. Defaults to all of them")
+                           .takes_value(true)
+                           .multiple(true)
+                           .use_delimiter(true)
+                           .required(false)
+                           .possible_values(&ZMANIM.iter().map(|(name, _)| *name).collect::<Vec<&str>>()))
+                       .arg(Arg::with_name("Table")
+                           .long("table")
+                           .help("Print a table with a row for every day and a column for every zman, aligned (the default) or as CSV")
+                           .takes_value(true)
+                           .min_values(0)
+                           .required(false)
+                           .possible_values(&["pretty", "csv"]))
+                       .arg(Arg::with_name("PolarFallback")
+                           .long("polar-fallback")
+                           .help("What alos, misheyakir and tzeis are on days the sun doesn't go far enough below the horizon. By default they don't occur; \"midnight\" uses the time the sun is lowest")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["none", "midnight"]))
+                       .arg(Arg::with_name("SolarAlgorithm")
+                           .long("solar-algorithm")
+                           .help("The algorithm sunrise, sunset and the zmanim are calculated with, to match the tables you compare them to")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["noaa", "usno"])
+                           .default_value("noaa")))
+                   .subcommand(SubCommand::with_name("table")
+                       .about("Prints the Gregorian date of Rosh Hashanah, the length of the year and whether it's a leap year, for a range of Hebrew years")
+                       .arg(Arg::with_name("From")
+                           .long("from")
+                           .help("The first Hebrew year")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("To")
+                           .long("to")
+                           .help("The last Hebrew year")
+                           .takes_value(true)
+                           .required(true)))
+                   .subcommand(SubCommand::with_name("year-info")
+                       .about("Prints the keviyah and length of a Hebrew year, whether it's a leap year, and the dates of Rosh Hashanah and Pesach")
+                       .arg(Arg::with_name("Year")
+                           .help("The Hebrew year")
+                           .required(true)
+                           .takes_value(true))
+                       .arg(Arg::with_name("Explain")
+                           .long("explain")
+                           .help("Explains the day of Rosh Hashanah from the molad and the dechiyos, and why Cheshvan and Kislev have their lengths"))
+                       .arg(Arg::with_name("Doubled")
+                           .long("doubled")
+                           .help("Lists which parshiyos are read together, and why"))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for the Israeli parshiyos or the Chutz La'aretz parshiyos?")
+                           .takes_value(true)
+                           .required(false)
+                           .requires("Doubled")
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"])))
+                   .subcommand(SubCommand::with_name("month-info")
+                       .about("Prints the length of a Hebrew month, its Rosh Chodesh, its molad, and the Gregorian dates it spans")
+                       .arg(Arg::with_name("Year")
+                           .help("The Hebrew year")
+                           .required(true)
+                           .takes_value(true))
+                       .arg(Arg::with_name("Month")
+                           .help("The Hebrew month, such as Adar1")
+                           .required(true)
+                           .takes_value(true))
+                       .arg(Arg::with_name("Explain")
+                           .long("explain")
+                           .help("Explains the day of Rosh Hashanah of the year from the molad and the dechiyos, and why Cheshvan and Kislev have their lengths")))
+                   .subcommand(SubCommand::with_name("stats")
+                       .about("Prints how often Rosh Hashanah falls on each day of the week, the earliest and latest dates of Rosh Hashanah and Pesach, and how often each kind of year happens, over a range of Hebrew years")
+                       .arg(Arg::with_name("From")
+                           .long("from")
+                           .help("The first Hebrew year")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("To")
+                           .long("to")
+                           .help("The last Hebrew year")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("Event")
+                           .long("event")
+                           .help("Counts how often an event falls on each day of the week instead, with the event named like in find, such as yom-kippur")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("By")
+                           .long("by")
+                           .help("What the days of the event are counted by. Defaults to weekday")
+                           .takes_value(true)
+                           .required(false)
+                           .requires("Event")
+                           .possible_values(&["weekday"]))
+                       .arg(Arg::with_name("Csv")
+                           .long("csv")
+                           .help("Prints the counts of the event as CSV")
+                           .requires("Event"))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for the Israeli holidays or the Chutz La'aretz holidays?")
+                           .takes_value(true)
+                           .required(false)
+                           .requires("Event")
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"])))
+                   .subcommand(SubCommand::with_name("diff-calendars")
+                       .about("Prints the events added, removed, moved and changed between two calendars printed with --print=json")
+                       .arg(Arg::with_name("Old")
+                           .help("The calendar to compare against")
+                           .required(true)
+                           .takes_value(true))
+                       .arg(Arg::with_name("New")
+                           .help("The calendar to compare")
+                           .required(true)
+                           .takes_value(true)))
+                   .subcommand(SubCommand::with_name("merge")
+                       .about("Merges calendars printed with --print=json, removing duplicates")
+                       .arg(Arg::with_name("Files")
+                           .help("The calendars to merge. When an event is in several of them, it's taken from the last one")
+                           .required(true)
+                           .multiple(true)
+                           .takes_value(true))
+                       .arg(Arg::with_name("Provenance")
+                           .long("provenance")
+                           .help("Adds the calendar each event was taken from, like \"merge:family.json#12\", to the events which weren't listed with list --provenance")
+                           .takes_value(false)
+                           .required(false)))
+                   .subcommand(SubCommand::with_name("slug")
+                       .about("Prints a filesystem-safe name for the week's parsha or the day's daf, for organizing recordings of shiurim")
+                       .arg(Arg::with_name("Date")
+                           .long("date")
+                           .help("The Gregorian date, as yyyy-mm-dd")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("Series")
+                           .long("series")
+                           .help("Name the date after the parsha of the week (the default) or the day's Daf Yomi")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["parsha", "daf-yomi"]))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for the Israeli parsha or the Chutz La'aretz parsha?")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"])))
+                   .subcommand(SubCommand::with_name("digest")
+                       .about("Prints an email with the parsha, events, zmanim and daily study of a week, which can be piped into sendmail")
+                       .arg(Arg::with_name("Week")
+                           .long("week")
+                           .help("Print the week, from Sunday to Shabbos, of --date")
+                           .required(true))
+                       .arg(Arg::with_name("Date")
+                           .long("date")
+                           .help("A Gregorian date in the week, as yyyy-mm-dd. Defaults to today")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for an Israeli calendar or a Chutz La'aretz calendar?")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"]))
+                       .arg(Arg::with_name("City")
+                           .long("city")
+                           .help("The city for candle lighting, havdalah and the zmanim. Can be a built-in city or a city specified in the config file.")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Coordinates")
+                           .long("coordinates")
+                           .help("The latitude and longitude for candle lighting, havdalah and the zmanim, like \"40.69,-73.99\" or \"40°41'N 73°59'W\", instead of a city")
+                           .takes_value(true)
+                           .allow_hyphen_values(true)
+                           .required(false)
+                           .conflicts_with_all(&["City", "Address"]))
+                       .arg(Arg::with_name("Address")
+                           .long("address")
+                           .help("An address for candle lighting, havdalah and the zmanim, instead of a city. It's found in the built-in cities and the cities of the config file, or with the geocoder set in the config file")
+                           .takes_value(true)
+                           .required(false)
+                           .conflicts_with("City"))
+                       .arg(Arg::with_name("TimeZone")
+                           .long("time-zone")
+                           .help("The time zone of --coordinates or --address, like \"America/New_York\". Defaults to that of the nearest built-in city")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Events")
+                           .long("show")
+                           .help("What events and daily study to print")
+                           .takes_value(true)
+                           .multiple(true)
+                           .required(false)
+                           .use_delimiter(true)
+                           .possible_values(EVENTS)
+                           .default_value("yom-tov,shabbos,special-parshas,chol,minor-holidays,daf-yomi"))
+                       .arg(Arg::with_name("Zmanim")
+                           .long("zmanim")
+                           .help("Which zmanim to print for every day, in this order")
+                           .takes_value(true)
+                           .multiple(true)
+                           .use_delimiter(true)
+                           .required(false)
+                           .possible_values(&ZMANIM.iter().map(|(name, _)| *name).collect::<Vec<&str>>())
+                           .default_value("alos,sunrise,sof-zman-shma,chatzos,sunset,tzeis"))
+                       .arg(Arg::with_name("SolarAlgorithm")
+                           .long("solar-algorithm")
+                           .help("The algorithm candle lighting, havdalah and the zmanim are calculated with, to match the tables you compare them to")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["noaa", "usno"])
+                           .default_value("noaa"))
+                       .arg(Arg::with_name("To")
+                           .long("to")
+                           .help("The address to send the email to, printed as its To header")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("From")
+                           .long("from")
+                           .help("The address the email is from, printed as its From header")
+                           .takes_value(true)
+                           .required(false)))
+                   .subcommand(SubCommand::with_name("status-bar")
+                       .alias("today")
+                       .about("Prints today's Hebrew date and events on one line for Polybar, or with --print json for Waybar")
+                       .arg(Arg::with_name("Date")
+                           .long("date")
+                           .help("The Gregorian date to print, as yyyy-mm-dd. Defaults to today, which starts at sunset with a city")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Watch")
+                           .long("watch")
+                           .help("Keep running, and print the day again at midnight and at sunset")
+                           .takes_value(false)
+                           .required(false)
+                           .conflicts_with("Date"))
+                       .arg(Arg::with_name("Exec")
+                           .long("exec")
+                           .help("Run this program with the output on its stdin, instead of printing it")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for an Israeli calendar or a Chutz La'aretz calendar?")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"]))
+                       .arg(Arg::with_name("City")
+                           .long("city")
+                           .help("The city for candle lighting and havdalah. Can be a built-in city or a city specified in the config file.")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Coordinates")
+                           .long("coordinates")
+                           .help("The latitude and longitude for candle lighting and havdalah, like \"40.69,-73.99\" or \"40°41'N 73°59'W\", instead of a city")
+                           .takes_value(true)
+                           .allow_hyphen_values(true)
+                           .required(false)
+                           .conflicts_with_all(&["City", "Address"]))
+                       .arg(Arg::with_name("Address")
+                           .long("address")
+                           .help("An address for candle lighting and havdalah, instead of a city. It's found in the built-in cities and the cities of the config file, or with the geocoder set in the config file")
+                           .takes_value(true)
+                           .required(false)
+                           .conflicts_with("City"))
+                       .arg(Arg::with_name("TimeZone")
+                           .long("time-zone")
+                           .help("The time zone of --coordinates or --address, like \"America/New_York\". Defaults to that of the nearest built-in city")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Events")
+                           .long("show")
+                           .help("What events and daily study to print")
+                           .takes_value(true)
+                           .multiple(true)
+                           .required(false)
+                           .use_delimiter(true)
+                           .possible_values(EVENTS)
+                           .default_value("yom-tov,shabbos,special-parshas,chol,minor-holidays,omer"))
+                       .arg(Arg::with_name("SolarAlgorithm")
+                           .long("solar-algorithm")
+                           .help("The algorithm candle lighting and havdalah are calculated with")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["noaa", "usno"])
+                           .default_value("noaa")))
+                   .subcommand(SubCommand::with_name("catchup")
+                       .about("Plans how to catch up with a daily study after falling behind, and prints what to learn every day until you're back on schedule")
+                       .arg(Arg::with_name("Study")
+                           .long("study")
+                           .help("The daily study to catch up with")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["daf-yomi", "yerushalmi-yomi", "chumash", "rambam-3-chapters", "rambam-1-chapter"])
+                           .default_value("daf-yomi"))
+                       .arg(Arg::with_name("Behind")
+                           .long("behind")
+                           .help("How many days of the cycle you're behind, like 12 if the last daf you learned was 13 days ago")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("Pace")
+                           .long("pace")
+                           .help("How many days of the cycle to learn every day until you catch up, like \"2/day\"")
+                           .takes_value(true)
+                           .required(false)
+                           .default_value("2/day"))
+                       .arg(Arg::with_name("Date")
+                           .long("date")
+                           .help("The Gregorian date to start on, as yyyy-mm-dd. Defaults to today")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("TimeZone")
+                           .long("time-zone")
+                           .help("The time zone today is the date in, like \"America/New_York\". Defaults to the system's")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you learning the Israeli or the Chutz La'aretz Chumash schedule?")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"])))
+                   .subcommand(SubCommand::with_name("plan-siyum")
+                       .about("Plans how many dapim to learn every time to finish a masechta by a date, and prints them as events")
+                       .arg(Arg::with_name("Masechta")
+                           .long("masechta")
+                           .help("The masechta to finish, like \"Kesubos\", \"Ketubot\" or \"כתובות\"")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("By")
+                           .long("by")
+                           .help("The last day to learn on, as a Hebrew date like \"5786-Nissan-1\" or a Gregorian date as yyyy-mm-dd")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("Days")
+                           .long("days")
+                           .help("The days of the week to learn on")
+                           .takes_value(true)
+                           .multiple(true)
+                           .use_delimiter(true)
+                           .required(false)
+                           .possible_values(&["sun", "mon", "tue", "wed", "thu", "fri", "sat"])
+                           .default_value("sun,mon,tue,wed,thu,fri,sat"))
+                       .arg(Arg::with_name("From")
+                           .long("from")
+                           .help("The Gregorian date to start on, as yyyy-mm-dd. Defaults to today")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("StartDaf")
+                           .long("start-daf")
+                           .help("The daf to start from, if some of the masechta was already learned")
+                           .takes_value(true)
+                           .required(false)
+                           .default_value("2"))
+                       .arg(Arg::with_name("TimeZone")
+                           .long("time-zone")
+                           .help("The time zone today is the date in, like \"America/New_York\". Defaults to the system's")
+                           .takes_value(true)
+                           .required(false)))
+                   .subcommand(SubCommand::with_name("bar-mitzvah")
+                       .about("Prints the parsha, aliyos, maftir and haftarah of a bar mitzvah, and when the parsha was last read to practice it")
+                       .arg(Arg::with_name("Date")
+                           .long("date")
+                           .help("The date of the bar mitzvah, as a Hebrew date like \"5786-Adar-3\" or a Gregorian date as yyyy-mm-dd. A weekday reads on the Shabbos after it")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for the Israeli parsha or the Chutz La'aretz parsha?")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"])))
+                   .subcommand(SubCommand::with_name("check-date")
+                       .about("Checks a date for restrictions, like the Three Weeks for a wedding, and prints the nearest dates without them")
+                       .arg(Arg::with_name("Date")
+                           .help("The date, as a Hebrew date like \"5786-Tammuz-28\" or a Gregorian date as yyyy-mm-dd")
+                           .required(true))
+                       .arg(Arg::with_name("For")
+                           .long("for")
+                           .help("What the date is for")
+                           .takes_value(true)
+                           .required(true)
+                           .possible_values(&["wedding"]))
+                       .arg(Arg::with_name("Sefirah")
+                           .long("sefirah")
+                           .help("The days of Sefiras HaOmer weddings aren't held on: from Pesach until Lag BaOmer, from Rosh Chodesh Iyar until the three days before Shavuos, or from Pesach until then. Defaults to the sefirah set in the config file, or first-days")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["first-days", "last-days", "whole"]))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for the Israeli holidays or the Chutz La'aretz holidays?")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"])))
+                   .subcommand(SubCommand::with_name("batch")
+                       .about("Reads a request on every line of stdin, like {\"cmd\":\"list\",\"year\":5785,\"show\":\"shabbos\"}, and prints each response on a line of its own, with --print json, cbor or msgpack"))
+                   .subcommand(SubCommand::with_name("find")
+                       .about("Finds the next years an event falls on a day of the week, like the next times Erev Pesach is on Shabbos")
+                       .arg(Arg::with_name("Event")
+                           .long("event")
+                           .help("The event, named like it's printed in English or in Hebrew, or like its JSON name, such as erev-pesach, \"1st day of Pesach\" or Pesach1")
+                           .takes_value(true)
+                           .required(true))
+                       .arg(Arg::with_name("Weekday")
+                           .long("weekday")
+                           .help("The day of the week of the day of the event (not of the night before it). Defaults to any day")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["sunday", "monday", "tuesday", "wednesday", "thursday", "friday", "shabbos", "saturday"]))
+                       .arg(Arg::with_name("Limit")
+                           .long("limit")
+                           .help("How many times to find")
+                           .takes_value(true)
+                           .required(false)
+                           .default_value("10"))
+                       .arg(Arg::with_name("From")
+                           .long("from")
+                           .help("The Hebrew year to search from. Defaults to this year")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Until")
+                           .long("until")
+                           .help("The last Hebrew year to search. Defaults to 1000 years after --from")
+                           .takes_value(true)
+                           .required(false))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Are you looking for the Israeli holidays or the Chutz La'aretz holidays?")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"])))
                    .subcommand(SubCommand::with_name("convert")
                        .about("Converts Hebrew to Gregorian and back")
                        .arg(Arg::with_name("DateFormat")
@@ -51,6 +650,13 @@ where
                            .takes_value(true)
                            .required(false)
                            .default_value("fuzzy"))
+                       .arg(Arg::with_name("Location")
+                           .long("location")
+                           .help("Look up the parsha and holidays of the date in Israel or in Chutz La'aretz. Only used in JSON output")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["Chul", "Israel"]))
                        .arg(Arg::with_name("Date")
                            .required(true)
                            .takes_value(true)))
@@ -64,6 +670,97 @@ where
                        .arg(Arg::with_name("NoSort")
                            .long("no-sort")
                            .help("Don't sort output"))
+                       .arg(
+                           Arg::with_name("Strict")
+                               .long("strict")
+                               .help("Fail if a custom holiday doesn't exist in one of the years listed, or if custom holidays in the config file conflict, instead of warning about it.")
+                       )
+                       .arg(
+                           Arg::with_name("MergeSpans")
+                               .long("merge-spans")
+                               .help("Print multi-day observances (Pesach, Sukkos, Chanukah and the Omer) as a single event spanning all of their days.")
+                       )
+                       .arg(
+                           Arg::with_name("MergeRoshChodesh")
+                               .long("merge-rosh-chodesh")
+                               .help("Print a two-day Rosh Chodesh as a single event spanning both days, named like \"Rosh Chodesh Kislev (2 days)\".")
+                       )
+                       .arg(
+                           Arg::with_name("OnlyShabbos")
+                               .long("only-shabbos")
+                               .help("Print a single line for every Shabbos, with the parsha, the special Shabbosos and the other events on it, and candle lighting and havdalah times if a city was given.")
+                       )
+                       .arg(Arg::with_name("Headers")
+                           .long("headers")
+                           .help("Prints a header before the events of every Hebrew year, Gregorian year or Hebrew month, so long lists are easy to scan. Only changes the pretty output")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["hebrew-year", "gregorian-year", "month"]))
+                       .arg(Arg::with_name("Summary")
+                           .long("summary")
+                           .help("Prints how many events of every category were listed and the dates the list covers after it, or, in JSON, CBOR and MessagePack, prints an object with the events in \"events\" and the summary in \"meta\"")
+                           .takes_value(false)
+                           .required(false))
+                       .arg(Arg::with_name("Meta")
+                           .long("meta")
+                           .help("Adds a hash of the events, the version of heca and the arguments it was run with, so that feeds which didn't change can be skipped: in \"meta\" in JSON, CBOR and MessagePack (like --summary), and as X-WR-HASH and X-WR-PARAMETERS in iCalendar")
+                           .takes_value(false)
+                           .required(false))
+                       .arg(Arg::with_name("Check")
+                           .long("check")
+                           .help("Reads the config file, the city and the other arguments and prints what would be listed (the location, the years and dates, and how many events of every category) instead of the events, for quick feedback when editing the custom days")
+                           .takes_value(false)
+                           .required(false))
+                       .arg(Arg::with_name("WarnShabbosConflicts")
+                           .long("warn-shabbos-conflicts")
+                           .help("After the list, prints the custom days from the config file which land on Shabbos or Yom Tov, such as a siyum or a community dinner which has to be moved that year. With --print json, cbor, msgpack, ics or parquet they're printed as warnings instead")
+                           .takes_value(false)
+                           .required(false))
+                       .arg(Arg::with_name("Links")
+                           .long("links")
+                           .help("Prints the daily study with a link to its text on Sefaria instead of the list, as CSV (the date, the name and the link) or as JSON (the events with a \"links\" field), to open the text straight from a calendar or spreadsheet")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["csv", "json"]))
+                       .arg(Arg::with_name("Alarm")
+                           .long("alarm")
+                           .help("Adds a reminder to events in iCalendar, like \"candles:-18m\" (18 minutes before candle lighting), \"yom-tov:-1d\" or \"daf-yomi:20:00\". The events are \"candles\", \"havdalah\", \"all\", a category or a daily study, and the reminder is an offset (+ or - and minutes, hours or days, like \"-2h\") or a time of the day. Can be passed more than once")
+                           .takes_value(true)
+                           .multiple(true)
+                           .number_of_values(1)
+                           .required(false))
+                       .arg(Arg::with_name("Route")
+                           .long("route")
+                           .help("Writes the events of a category to a file instead of the output, like \"zmanim=>zmanim.ics\" or \"daily-study=>study.json\", in the format of its extension (ics, json, cbor, msgpack or txt). The other events are printed as usual. Can be passed more than once")
+                           .takes_value(true)
+                           .multiple(true)
+                           .number_of_values(1)
+                           .required(false)
+                           .conflicts_with_all(&["Check", "Links"]))
+                       .arg(Arg::with_name("AppendSinceLastRun")
+                           .long("append-since-last-run")
+                           .help("Only prints the days after the last day printed by the runs before, which is kept in this state file (and is created on the first run), for cron jobs which mustn't send the same events again")
+                           .takes_value(true)
+                           .value_name("STATE")
+                           .required(false)
+                           .conflicts_with_all(&["Check", "Summary"]))
+                       .arg(Arg::with_name("ShortNames")
+                           .long("short-names")
+                           .help("Prints abbreviated names, like \"RH 1\", \"Chanukah 3\" and \"Ber. 12\", for small screens")
+                           .takes_value(false)
+                           .required(false))
+                       .arg(Arg::with_name("Fields")
+                           .long("fields")
+                           .help("Only print these fields of every event in JSON, like \"day,name.en,category\". A field inside another is named after both, joined by a dot")
+                           .takes_value(true)
+                           .multiple(true)
+                           .use_delimiter(true)
+                           .required(false))
+                       .arg(
+                           Arg::with_name("Icons")
+                               .long("icons")
+                               .help("Print an emoji before Yom Tov (🎉), daily study (📖), candle lighting (🕯) and havdalah (🍷), in the printed list and in the summary of calendar events.")
+                       )
                        .arg(
                            Arg::with_name("ExactDays")
                                .long("exact-days")
@@ -74,18 +771,74 @@ where
                            .help("Are you looking for an Israeli calendar or a Chutz La'aretz calendar?")
                            .takes_value(true)
                            .required(false)
+                           .case_insensitive(true)
                            .possible_values(&["Chul", "Israel"]))
+                       .arg(Arg::with_name("YomTovObservance")
+                           .long("yomtov-observance")
+                           .help("How many days of Yom Tov are kept, if it isn't as in --location: a traveler from Chutz La'aretz in Israel keeps two days with \"diaspora\", and hears the Israeli parsha")
+                           .takes_value(true)
+                           .required(false)
+                           .case_insensitive(true)
+                           .possible_values(&["israel", "diaspora"]))
+                       .arg(Arg::with_name("Provenance")
+                           .long("provenance")
+                           .help("Adds the rule which listed each event, like \"dechiyah:lo-adu-rosh\" or \"custom:config.toml#12\", to debug calendars merged from many sources. It's the \"source\" of every event in JSON")
+                           .takes_value(false)
+                           .required(false))
                        .arg(Arg::with_name("AmountYears")
                            .long("years")
-                           .help("Generate events for n years")
+                           .help("Generate events for n years. A negative number generates the n years ending with the given year")
                            .takes_value(true)
+                           .allow_hyphen_values(true)
                            .required(false)
                            .default_value("1"))
+                       .arg(Arg::with_name("Until")
+                           .long("until")
+                           .help("Generate events up to and including this year. Can be before the given year")
+                           .takes_value(true)
+                           .required(false)
+                           .conflicts_with("AmountYears"))
+                       .arg(Arg::with_name("Month")
+                           .long("month")
+                           .help("Only list the events in this month of a Hebrew year, such as Kislev or Adar2")
+                           .takes_value(true)
+                           .required(false)
+                           .conflicts_with_all(&["Until", "GregorianMonth"]))
+                       .arg(Arg::with_name("GregorianMonth")
+                           .long("gmonth")
+                           .help("Only list the events in this month (1-12) of a Gregorian year")
+                           .takes_value(true)
+                           .required(false)
+                           .conflicts_with("Until"))
+                       .arg(Arg::with_name("ReadingCycle")
+                           .long("reading-cycle")
+                           .help("Annotate each Shabbos with the verses read in the annual (full kriah) cycle, or also with the verses read in this year of the triennial cycle.")
+                           .takes_value(true)
+                           .required(false)
+                           .possible_values(&["annual", "triennial"]))
                        .arg(Arg::with_name("City")
                            .long("city")
                            .help("Specify city for candle lighting times. Can be a built-in city or a city specified in the config file.")
                            .takes_value(true)
                            .required(false))
+                       .arg(Arg::with_name("Coordinates")
+                           .long("coordinates")
+                           .help("The latitude and longitude to calculate candle lighting times for, like \"40.69,-73.99\" or \"40°41'N 73°59'W\", instead of a city")
+                           .takes_value(true)
+                           .allow_hyphen_values(true)
+                           .required(false)
+                           .conflicts_with_all(&["City", "Address"]))
+                       .arg(Arg::with_name("Address")
+                           .long("address")
+                           .help("An address to calculate candle lighting times for, instead of a city. It's found in the built-in cities and the cities of the config file, or with the geocoder set in the config file")
+                           .takes_value(true)
+                           .required(false)
+                           .conflicts_with("City"))
+                       .arg(Arg::with_name("TimeZone")
+                           .long("time-zone")
+                           .help("The time zone of --coordinates or --address, like \"America/New_York\". Defaults to that of the nearest built-in city")
+                           .takes_value(true)
+                           .required(false))
                        .arg(Arg::with_name("Events")
                        .long("show")
                        .help("What events to list")
@@ -93,65 +846,258 @@ where
                        .multiple(true)
                        .required(false)
                        .use_delimiter(true)
-                       .possible_values(&[
-                           "yom-tov",
-                           "shabbos",
-                           "special-parshas",
-                           "chol",
-                           "minor-holidays",
-                           "omer",
-                           "custom-holidays",
-                           "daf-yomi",
-                           "yerushalmi-yomi",
-                           "rambam-3-chapters",
-                           "rambam-1-chapter",
-                           "israeli-holidays",
-                           "chabad-holidays",
-                           "shabbos-mevarchim"
-                       ])
+                       .alias("events")
+                       .possible_values(EVENTS)
+                       .possible_value("all")
+                       .possible_values(&times.iter().map(String::as_str).collect::<Vec<&str>>())
+                       .possible_values(&TimeOfDay::arg_names().collect::<Vec<&str>>())
                        .default_value("yom-tov"))
+                       .arg(Arg::with_name("Preset")
+                       .long("preset")
+                       .help("List a curated set of events. Can be combined with --show")
+                       .takes_value(true)
+                       .required(false)
+                       .possible_values(&["orthodox-diaspora", "israel", "daily-study"]))
+                       .arg(Arg::with_name("HavdalahOpinion")
+                       .long("havdalah-opinion")
+                       .help("When Shabbos and Yom Tov end, printed instead of the default of 42 minutes after sunset. Passing several prints all of them")
+                       .takes_value(true)
+                       .multiple(true)
+                       .required(false)
+                       .use_delimiter(true)
+                       .possible_values(&["42", "50", "72", "8.5", "rabbeinu-tam"]))
+                       .arg(Arg::with_name("WeekStart")
+                       .long("week-start")
+                       .help("The day of the week the Daf Hashavua starts on")
+                       .takes_value(true)
+                       .required(false)
+                       .possible_values(&["sunday", "shabbos"])
+                       .default_value("sunday"))
+                       .arg(Arg::with_name("PolarFallback")
+                       .long("polar-fallback")
+                       .help("What havdalah by degrees is on nights the sun doesn't go far enough below the horizon. By default it doesn't occur; \"midnight\" uses the time the sun is lowest")
+                       .takes_value(true)
+                       .required(false)
+                       .possible_values(&["none", "midnight"]))
+                       .arg(Arg::with_name("SolarAlgorithm")
+                       .long("solar-algorithm")
+                       .help("The algorithm candle lighting, havdalah and the zmanim are calculated with, to match the tables you compare them to")
+                       .takes_value(true)
+                       .required(false)
+                       .possible_values(&["noaa", "usno"])
+                       .default_value("noaa"))
+                       .arg(Arg::with_name("Category")
+                       .long("category")
+                       .help("Only print events in these categories")
+                       .takes_value(true)
+                       .multiple(true)
+                       .required(false)
+                       .use_delimiter(true)
+                       .possible_values(CATEGORIES))
+                       .arg(Arg::with_name("ExcludeCategory")
+                       .long("exclude-category")
+                       .help("Don't print events in these categories")
+                       .takes_value(true)
+                       .multiple(true)
+                       .required(false)
+                       .use_delimiter(true)
+                       .possible_values(CATEGORIES))
                        .arg(Arg::with_name("Year")
                        .required(true)
-                       .takes_value(true))).get_matches_safe()?, output_type)
+                       .takes_value(true)));
+    // With --print json (or cbor or msgpack), --version prints what this build supports, instead of clap's line.
+    let app = if output_type.is_data() {
+        app.setting(AppSettings::DisableVersion).arg(
+            Arg::with_name("Version")
+                .long("version")
+                .short("V")
+                .help("Prints version information"),
+        )
+    } else {
+        app
+    };
+    let args = with_env_options(&app, args.into_iter().map(Into::into).collect());
+    parse_args(app.get_matches_from_safe(args)?, output_type)
+}
+
+/// Options set in the environment under another name than their own.
+const ENV_ALIASES: &[(&str, &str)] = &[("output", "print")];
+
+/// Adds the options set in the environment which weren't passed, as `HECA_` and the name of the
+/// option, like `HECA_LANGUAGE` for `--language` or `HECA_TIME_ZONE` for `--time-zone`. So the
+/// arguments come first, then the environment, and then the config file. A flag is passed if its
+/// variable is `1`, `true` or `yes`. Variables of options the subcommand doesn't take, or which
+/// conflict with the arguments, are left out; one with a value the option doesn't take fails.
+fn with_env_options(app: &App<'_, '_>, args: Vec<OsString>) -> Vec<OsString> {
+    let mut variables: Vec<(String, String)> = env::vars()
+        .filter_map(|(name, value)| {
+            let option = name.strip_prefix("HECA_")?.to_lowercase().replace('_', "-");
+            Some((option, value))
+        })
+        .collect();
+    variables.sort();
+    let parses = |args: &[OsString]| app.clone().get_matches_from_safe(args);
+    // The arguments' own errors are printed without the environment.
+    if variables.is_empty() || parses(&args).is_err() {
+        return args;
+    }
+
+    let mut args = args;
+    for (option, value) in variables {
+        let option = ENV_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == option)
+            .map_or(option.as_str(), |(_, option)| option);
+        let passed = args.iter().any(|arg| {
+            let arg = arg.to_string_lossy();
+            arg.strip_prefix("--")
+                .and_then(|arg| arg.strip_prefix(option))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+        });
+        if passed || value.is_empty() {
+            continue;
+        }
+        let mut candidates = vec![format!("--{}={}", option, value)];
+        if ["1", "true", "yes"].contains(&value.to_lowercase().as_str()) {
+            candidates.push(format!("--{}", option));
+        }
+        // Options of the program come before the subcommand, and those of the subcommand after it.
+        let found = candidates.iter().find_map(|candidate| {
+            [1, args.len()].iter().find_map(|&index| {
+                let mut with_option = args.clone();
+                with_option.insert(index, candidate.into());
+                match parses(&with_option) {
+                    Ok(_) => Some(Ok(with_option)),
+                    Err(err)
+                        if err.kind == ErrorKind::InvalidValue
+                            || err.kind == ErrorKind::ValueValidation =>
+                    {
+                        Some(Err(with_option))
+                    }
+                    Err(_) => None,
+                }
+            })
+        });
+        match found {
+            Some(Ok(with_option)) => args = with_option,
+            // A value the option doesn't take is kept, so parsing fails with its error.
+            Some(Err(with_option)) => return with_option,
+            None => {}
+        }
+    }
+    args
+}
+
+fn str_to_language(language: &str) -> Language {
+    match language {
+        "en_US" | "en" => Language::English,
+        "he_IL" | "he" => Language::Hebrew,
+        _ => unreachable!(),
+    }
+}
+
+fn get_second_language(
+    language: Language,
+    passed_languages: Option<Vec<&str>>,
+) -> Option<Language> {
+    passed_languages
+        .and_then(|languages| languages.get(1).map(|x| str_to_language(x)))
+        .filter(|second_language| *second_language != language)
 }
 
 fn get_language(config_language: Option<Language>, passed_language: Option<&str>) -> Language {
     if let Some(language) = passed_language {
-        match language {
-            "en_US" => Language::English,
-            "he_IL" => Language::Hebrew,
-            _ => unreachable!(),
-        }
+        str_to_language(language)
     } else if let Some(language) = config_language {
         language
-    } else if let Ok(language) = env::var("LANG") {
-        if language == "he_IL.UTF-8" || language == "he_IL" {
-            Language::Hebrew
-        } else {
-            Language::English
-        }
     } else {
-        Language::English
+        system_language().unwrap_or(Language::English)
     }
 }
 
+fn get_transliteration(
+    config_transliteration: Option<Transliteration>,
+    passed_transliteration: Option<&str>,
+) -> Transliteration {
+    if let Some(transliteration) = passed_transliteration {
+        str_to_transliteration(transliteration).unwrap()
+    } else if let Some(transliteration) = config_transliteration {
+        transliteration
+    } else {
+        Transliteration::Sephardi
+    }
+}
+
+/// Returns the options before the subcommand which `heca batch` passes on to every request, with
+/// their values. `--print` and `--out` are left out, since the batch prints every response itself.
+fn global_options(matches: &ArgMatches<'_>) -> Vec<(String, Option<String>)> {
+    let mut globals = vec![];
+    for (arg, name) in &[
+        ("configfile", "config"),
+        ("Profile", "profile"),
+        ("transliteration", "transliteration"),
+        ("Today", "today"),
+    ] {
+        if let Some(value) = matches.value_of(arg) {
+            globals.push((name.to_string(), Some(value.to_string())));
+        }
+    }
+    if let Some(languages) = matches.values_of("language") {
+        globals.push((
+            "language".into(),
+            Some(languages.collect::<Vec<&str>>().join(",")),
+        ));
+    }
+    for (arg, name) in &[("Ascii", "ascii"), ("DenyWarnings", "deny-warnings")] {
+        if matches.is_present(arg) {
+            globals.push((name.to_string(), None));
+        }
+    }
+    globals
+}
+
 fn parse_args(matches: ArgMatches<'_>, output_type: OutputType) -> Result<MainArgs, AppError> {
-    let config_file = Config::from_location(matches.value_of("configfile"))?;
+    let config_file =
+        Config::from_location(matches.value_of("configfile"), matches.value_of("Profile"))?;
 
     let _ = match matches.value_of("type") {
         Some(x) => match x {
             "regular" => Some(OutputType::Regular),
             "pretty" => Some(OutputType::Pretty),
             "json" => Some(OutputType::JSON),
+            "ics" => Some(OutputType::ICS),
+            "cbor" => Some(OutputType::CBOR),
+            "msgpack" => Some(OutputType::MessagePack),
+            #[cfg(feature = "parquet")]
+            "parquet" => Some(OutputType::Parquet),
             x => return Err(AppError::TypeError(x.into())),
         },
         None => None,
     };
 
     let language = get_language(config_file.language, matches.value_of("language"));
+    let second_language =
+        get_second_language(language, matches.values_of("language").map(|x| x.collect()));
+    let transliteration = get_transliteration(
+        config_file.transliteration,
+        matches.value_of("transliteration"),
+    );
 
-    let command = if let Some(matches) = matches.subcommand_matches("list") {
-        list::parse_options(matches, &config_file, language, &config_file.custom_days)?
+    let warnings = Warnings {
+        output_type,
+        deny: matches.occurrences_of("DenyWarnings") > 0,
+    };
+
+    let command = if matches.occurrences_of("Version") > 0 {
+        Command::Version(VersionArgs)
+    } else if let Some(matches) = matches.subcommand_matches("list") {
+        list::parse_options(
+            matches,
+            &config_file,
+            language,
+            &config_file.custom_days,
+            warnings,
+        )?
     } else if let Some(matches) = matches.subcommand_matches("convert") {
         let datefmt = if let Some(datefmt) = matches.value_of("DateFormat") {
             match datefmt {
@@ -176,15 +1122,127 @@ fn parse_args(matches: ArgMatches<'_>, output_type: OutputType) -> Result<MainAr
                 "fuzzy" => ConfigDateType::Fuzzy,
                 _ => unreachable!(),
             },
+            get_location(matches, &config_file, language)?,
         )?
+    } else if matches.subcommand_matches("limits").is_some() {
+        Command::Limits(LimitsArgs)
+    } else if matches.subcommand_matches("self-test").is_some() {
+        Command::SelfTest(SelfTestArgs)
+    } else if matches.subcommand_matches("verify-anchors").is_some() {
+        Command::VerifyAnchors(VerifyAnchorsArgs)
+    } else if let Some(matches) = matches.subcommand_matches("zmanim") {
+        zmanim::parse_options(matches, &config_file)?
+    } else if let Some(matches) = matches.subcommand_matches("diff-calendars") {
+        Command::DiffCalendars(DiffCalendarsArgs {
+            old: matches.value_of("Old").unwrap().into(),
+            new: matches.value_of("New").unwrap().into(),
+        })
+    } else if let Some(matches) = matches.subcommand_matches("merge") {
+        Command::Merge(MergeArgs {
+            files: matches
+                .values_of("Files")
+                .unwrap()
+                .map(String::from)
+                .collect(),
+            provenance: matches.is_present("Provenance"),
+        })
+    } else if let Some(matches) = matches.subcommand_matches("table") {
+        let (from, to) = get_hebrew_year_range(matches)?;
+        Command::Table(TableArgs { from, to })
+    } else if let Some(matches) = matches.subcommand_matches("year-info") {
+        let year = matches.value_of("Year").unwrap();
+        Command::YearInfo(YearInfoArgs {
+            year: year
+                .parse()
+                .map_err(|_| AppError::YearIsNotANumber(year.into()))?,
+            explain: matches.is_present("Explain"),
+            doubled: if matches.is_present("Doubled") {
+                Some(get_location(matches, &config_file, language)?)
+            } else {
+                None
+            },
+        })
+    } else if let Some(matches) = matches.subcommand_matches("month-info") {
+        let year = matches.value_of("Year").unwrap();
+        let month = matches.value_of("Month").unwrap();
+        Command::MonthInfo(MonthInfoArgs {
+            year: year
+                .parse()
+                .map_err(|_| AppError::YearIsNotANumber(year.into()))?,
+            month: str_to_month(month)
+                .or_else(|| str_to_month(&month.to_lowercase()))
+                .ok_or_else(|| AppError::MonthNotParsed(month.into()))?,
+            explain: matches.is_present("Explain"),
+        })
+    } else if let Some(matches) = matches.subcommand_matches("stats") {
+        let (from, to) = get_hebrew_year_range(matches)?;
+        let event = match matches.value_of("Event") {
+            Some(event) => Some(EventStats {
+                event: event.into(),
+                by: match matches.value_of("By").unwrap_or("weekday") {
+                    "weekday" => StatsBy::Weekday,
+                    x => unreachable!("{}", x),
+                },
+                location: get_location(matches, &config_file, language)?,
+                csv: matches.is_present("Csv"),
+            }),
+            None => None,
+        };
+        Command::Stats(StatsArgs { from, to, event })
+    } else if let Some(matches) = matches.subcommand_matches("slug") {
+        slug::parse_options(matches, &config_file, language)?
+    } else if let Some(matches) = matches.subcommand_matches("digest") {
+        digest::parse_options(matches, &config_file, language, warnings)?
+    } else if let Some(matches) = matches.subcommand_matches("status-bar") {
+        status_bar::parse_options(matches, &config_file, language, warnings)?
+    } else if let Some(matches) = matches.subcommand_matches("catchup") {
+        catchup::parse_options(matches, &config_file, language, warnings)?
+    } else if let Some(matches) = matches.subcommand_matches("plan-siyum") {
+        plan_siyum::parse_options(matches, &config_file, language, warnings)?
+    } else if let Some(matches) = matches.subcommand_matches("bar-mitzvah") {
+        bar_mitzvah::parse_options(matches, &config_file, language)?
+    } else if let Some(matches) = matches.subcommand_matches("check-date") {
+        check_date::parse_options(matches, &config_file, language)?
+    } else if let Some(matches) = matches.subcommand_matches("find") {
+        find::parse_options(matches, &config_file, language)?
+    } else if matches.subcommand_matches("batch").is_some() {
+        if !output_type.is_data() {
+            return Err(AppError::ArgUndefinedError(
+                "batch prints every response on a line of its own, with --print json, cbor or msgpack"
+                    .into(),
+            ));
+        }
+        Command::Batch(BatchArgs {
+            globals: global_options(&matches),
+        })
     } else {
         return Err(AppError::ArgUndefinedError(String::from(matches.usage())));
     };
 
+    let out = matches.value_of_os("out").map(std::path::PathBuf::from);
+    // The locale only says what the terminal can show, not what a file can hold.
+    let ascii = matches.occurrences_of("Ascii") > 0 || (out.is_none() && locale_is_not_utf8());
+    // The English names of events are the transliterations of their Hebrew names, so they're
+    // printed instead of transliterating Hebrew letter by letter.
+    let (language, second_language, transliteration) = if ascii {
+        let transliteration = match transliteration {
+            Transliteration::Hebrew => Transliteration::Sephardi,
+            transliteration => transliteration,
+        };
+        (Language::English, None, transliteration)
+    } else {
+        (language, second_language, transliteration)
+    };
+
     Ok(MainArgs {
         custom_days: None,
         output_type,
         language,
+        second_language,
+        transliteration,
+        out,
+        ascii,
+        today: get_today(&matches)?,
         command,
     })
 }
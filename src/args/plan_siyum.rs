@@ -0,0 +1,88 @@
+use crate::args::digest::today;
+use crate::args::prelude::{
+    get_location, get_today_time_zone, parse_date, parse_gregorian_date, Config,
+};
+use crate::args::types::{AppError, Command, Language, PlanSiyumArgs};
+use crate::prelude::constants::{GEMARAS_SECOND_CYCLE, MASECHTOS};
+use crate::prelude::Warnings;
+use chrono::Weekday;
+use clap::ArgMatches;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+    warnings: Warnings,
+) -> Result<Command, AppError> {
+    let name = matches.value_of("Masechta").unwrap();
+    let masechta = find_masechta(name)
+        .ok_or_else(|| AppError::ArgUndefinedError(format!("Unknown masechta: {}", name)))?;
+    let last_daf = GEMARAS_SECOND_CYCLE[masechta as usize].3;
+    let start_daf = matches.value_of("StartDaf").unwrap();
+    let start_daf = match start_daf.parse() {
+        Ok(daf) if (2..=last_daf).contains(&daf) => daf,
+        _ => {
+            return Err(AppError::ArgUndefinedError(format!(
+                "--start-daf must be from 2 to {}, the last daf of {}, not {}",
+                last_daf, name, start_daf
+            )))
+        }
+    };
+    let from = match matches.value_of("From") {
+        Some(date) => parse_gregorian_date(date)?,
+        None => today(
+            matches,
+            get_today_time_zone(matches, None, warnings)?.as_ref(),
+        )?,
+    };
+    let by = parse_date(matches.value_of("By").unwrap())?;
+    if by < from {
+        return Err(AppError::ArgUndefinedError(format!(
+            "--by ({}) is before the first day of the schedule ({})",
+            by, from
+        )));
+    }
+    let days = matches
+        .values_of("Days")
+        .unwrap()
+        .map(|x| match x {
+            "sun" => Weekday::Sun,
+            "mon" => Weekday::Mon,
+            "tue" => Weekday::Tue,
+            "wed" => Weekday::Wed,
+            "thu" => Weekday::Thu,
+            "fri" => Weekday::Fri,
+            "sat" => Weekday::Sat,
+            x => unreachable!("{}", x),
+        })
+        .collect();
+    Ok(Command::PlanSiyum(PlanSiyumArgs {
+        masechta,
+        start_daf,
+        from,
+        by,
+        days,
+        location: get_location(matches, config, language)?,
+    }))
+}
+
+/// Finds the masechta by any of the names it's printed with, ignoring case, spaces and dots, so
+/// `Kesubos`, `ketubot` and `Ket.` are all Kesubos.
+fn find_masechta(name: &str) -> Option<u8> {
+    let normalize = |name: &str| -> String {
+        name.chars()
+            .filter(|x| x.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let name = normalize(name);
+    GEMARAS_SECOND_CYCLE
+        .iter()
+        .zip(MASECHTOS.iter())
+        .position(|((sephardi, hebrew, _, _), (_, ashkenazi, abbreviated))| {
+            [sephardi, hebrew, ashkenazi, abbreviated]
+                .iter()
+                .any(|x| normalize(x) == name)
+        })
+        .map(|x| x as u8)
+}
@@ -1,14 +1,175 @@
-use crate::args::types::{AppError, CustomHoliday, DayMonth, Language};
-use crate::args::DATE_TOKEN;
+use crate::algorithms::arithmetic::days_in_month;
+use crate::algorithms::candle_lighting::City;
+use crate::args::types::{
+    AnniversaryKind, AppError, CustomDate, CustomHoliday, DayModifier, DayMonth, Language, Origin,
+    PolarFallback, SefirahCustom, SolarAlgorithm, TimeOfDay, Today, Transliteration,
+};
+use crate::args::{DATE_TOKEN, EVENTS};
+use crate::gematria::parse_hebrew_numeral;
+use crate::prelude::constants::{check_gregorian_year, check_hebrew_year};
+use crate::prelude::system::system_time_zone;
+use crate::prelude::{Warning, Warnings};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
+use clap::ArgMatches;
 use heca_lib::prelude::{HebrewMonth, Location};
+use heca_lib::HebrewDate;
 use serde::Deserialize;
-use std::convert::TryInto;
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::ErrorKind;
 use std::num::NonZeroI8;
 use zmanim::prelude::tz::TimeZone;
 
+/// Returns the location passed to `--location`, set in `HECA_LOCATION` (or `LOC`) or in the
+/// config file. Defaults to Israel for Hebrew and Chutz La'aretz otherwise.
+/// Returns the time zone today is in: `--time-zone`, or the city's, or the system's. It warns if
+/// there's none of them, and returns `None` for UTC.
+pub fn get_today_time_zone(
+    matches: &ArgMatches<'_>,
+    city: Option<&City>,
+    warnings: Warnings,
+) -> Result<Option<TimeZone>, AppError> {
+    if let Some(time_zone) = matches.value_of("TimeZone") {
+        return TimeZone::try_from(time_zone)
+            .map(Some)
+            .map_err(|_| AppError::InvalidTimeZone(time_zone.into()));
+    }
+    if let Some(city) = city {
+        return Ok(Some(city.time_zone.clone()));
+    }
+    let time_zone = system_time_zone();
+    if time_zone.is_none() {
+        warnings.warn(Warning::UnknownTimeZone(
+            "Can't find the system's time zone, so today is the date in UTC. Pass --time-zone to set it"
+                .into(),
+        ))?;
+    }
+    Ok(time_zone)
+}
+
+/// Parses `--today` (which `HECA_TODAY` sets): a time like `2025-03-11T20:00`, with or without seconds
+/// and an offset, or a date, which is its midnight.
+pub fn get_today(matches: &ArgMatches<'_>) -> Result<Option<Today>, AppError> {
+    let today = match matches.value_of("Today") {
+        Some(today) => today.to_string(),
+        None => return Ok(None),
+    };
+    let offset = match today.strip_suffix('Z') {
+        Some(utc) => format!("{}+00:00", utc),
+        None => today.clone(),
+    };
+    if let Some(time) = ["%Y-%m-%dT%H:%M:%S%.f%:z", "%Y-%m-%dT%H:%M%:z"]
+        .iter()
+        .find_map(|format| DateTime::parse_from_str(&offset, format).ok())
+    {
+        return Ok(Some(Today::Exact(time.with_timezone(&Utc))));
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&today, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(&today, "%Y-%m-%d")
+                .ok()
+                .map(|x| x.and_hms(0, 0, 0))
+        })
+        .map(|x| Some(Today::Local(x)))
+        .ok_or_else(|| {
+            AppError::ArgUndefinedError(format!(
+                "--today must be a time like 2025-03-11T20:00, not {}",
+                today
+            ))
+        })
+}
+
+pub fn get_location(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+) -> Result<Location, AppError> {
+    Ok(if let Some(location) = matches.value_of("Location") {
+        str_to_location(location)?
+    } else if let Some(location) = env::var_os("LOC") {
+        let location: String = location.into_string().unwrap();
+        str_to_location(&location)?
+    } else if let Some(location) = &config.location {
+        *location
+    } else if language == Language::Hebrew {
+        Location::Israel
+    } else {
+        Location::Chul
+    })
+}
+
+/// Parses `--from` and `--to`, a range of Hebrew years which includes both ends.
+pub fn get_hebrew_year_range(matches: &ArgMatches<'_>) -> Result<(u64, u64), AppError> {
+    let year = |name: &str| -> Result<u64, AppError> {
+        let year = matches.value_of(name).unwrap();
+        year.parse()
+            .map_err(|_| AppError::YearIsNotANumber(year.into()))
+    };
+    let (from, to) = (year("From")?, year("To")?);
+    if to < from {
+        return Err(AppError::InvalidYearRange(format!(
+            "--to {} is before --from {}",
+            to, from
+        )));
+    }
+    Ok((from, to))
+}
+
+/// Parses a Gregorian date written as year, month and day.
+pub fn parse_gregorian_date(date: &str) -> Result<NaiveDate, AppError> {
+    let sp = date.split(&DATE_TOKEN[..]).collect::<Vec<&str>>();
+    if sp.len() != 3 {
+        return Err(AppError::SplitDateError);
+    }
+    let year = sp[0]
+        .parse()
+        .map_err(|_| AppError::CannotParseYear(sp[0].into()))?;
+    let month = sp[1]
+        .parse()
+        .map_err(|_| AppError::CannotParseMonth(sp[1].into()))?;
+    let day = sp[2]
+        .parse()
+        .map_err(|_| AppError::CannotParseDay(sp[2].into()))?;
+    check_gregorian_year(year as i64)?;
+    NaiveDate::from_ymd_opt(year, month, day)
+        .ok_or(AppError::InvalidGregorianDate(year, month, day))
+}
+
+/// Reads a Hebrew date written from the year, like `5786-Nissan-1`, or a Gregorian date.
+pub fn parse_date(date: &str) -> Result<NaiveDate, AppError> {
+    let sp = date.split(&DATE_TOKEN[..]).collect::<Vec<&str>>();
+    if sp.len() != 3 {
+        return Err(AppError::SplitDateError);
+    }
+    if sp[1].parse::<u8>().is_ok() {
+        return parse_gregorian_date(date);
+    }
+    let (day, month, year) = parse_hebrew(&[sp[2], sp[1], sp[0]])?;
+    let night: DateTime<Utc> = HebrewDate::from_ymd(year.unwrap(), month, day)?.into();
+    Ok((night + Duration::days(1)).date().naive_utc())
+}
+
+pub fn get_polar_fallback(matches: &ArgMatches<'_>) -> PolarFallback {
+    match matches.value_of("PolarFallback") {
+        Some("midnight") => PolarFallback::Midnight,
+        Some("none") | None => PolarFallback::None,
+        Some(x) => unreachable!("{}", x),
+    }
+}
+
+pub fn get_solar_algorithm(matches: &ArgMatches<'_>) -> SolarAlgorithm {
+    match matches.value_of("SolarAlgorithm") {
+        Some("usno") => SolarAlgorithm::Usno,
+        Some("noaa") | None => SolarAlgorithm::Noaa,
+        Some(x) => unreachable!("{}", x),
+    }
+}
+
 pub fn str_to_location(location: &str) -> Result<Location, AppError> {
     match location.to_lowercase().as_ref() {
         "chul" => Ok(Location::Chul),
@@ -17,13 +178,44 @@ pub fn str_to_location(location: &str) -> Result<Location, AppError> {
     }
 }
 
+pub fn str_to_sefirah_custom(custom: &str) -> Option<SefirahCustom> {
+    match custom {
+        "first-days" => Some(SefirahCustom::FirstDays),
+        "last-days" => Some(SefirahCustom::LastDays),
+        "whole" => Some(SefirahCustom::Whole),
+        _ => None,
+    }
+}
+
+pub fn str_to_transliteration(transliteration: &str) -> Option<Transliteration> {
+    match transliteration {
+        "sephardi" => Some(Transliteration::Sephardi),
+        "ashkenazi" => Some(Transliteration::Ashkenazi),
+        "abbreviated" => Some(Transliteration::Abbreviated),
+        "hebrew" => Some(Transliteration::Hebrew),
+        _ => None,
+    }
+}
+
 pub struct Config {
     pub custom_days: Vec<CustomHoliday>,
+    /// Duplicate custom holidays, and custom holidays which use the id of a built-in event.
+    pub custom_day_conflicts: Vec<String>,
     pub language: Option<Language>,
+    pub transliteration: Option<Transliteration>,
     pub location: Option<Location>,
     pub exact_days: Option<bool>,
     pub default_city: Option<String>,
     pub cities: Option<Vec<ConfigCity>>,
+    /// The program `--address` runs to find addresses.
+    pub geocoder: Option<String>,
+    /// The days of Sefiras HaOmer `check-date` keeps for weddings.
+    pub sefirah: Option<SefirahCustom>,
+    /// The events `list` shows when `--show` isn't passed.
+    pub show: Option<Vec<String>>,
+    /// How many minutes before sunset candles are lit in a city, by its name, replacing the
+    /// minutes the city has, like 40 in Jerusalem.
+    pub candle_lighting: HashMap<String, u8>,
 }
 
 #[cfg(macos)]
@@ -43,7 +235,13 @@ fn get_config_file(program_name: &str) -> Option<String> {
 }
 
 impl Config {
-    pub fn from_location(pass_value: Option<&str>) -> Result<Self, AppError> {
+    /// Reads the config file passed to `--config`, or else the default one. With a profile (from
+    /// `--profile`), the settings of the profile replace those at the top of the file, and its
+    /// custom holidays are added to theirs.
+    pub fn from_location(
+        pass_value: Option<&str>,
+        profile: Option<&str>,
+    ) -> Result<Self, AppError> {
         let mut config_file = if let Some(v) = pass_value {
             Some(File::open(v)?)
         } else if let Some(config_file) = get_config_file("heca") {
@@ -62,11 +260,25 @@ impl Config {
             None
         };
         let mut custom_days = vec![];
+        let mut custom_day_conflicts = vec![];
         let mut language = None;
+        let mut transliteration = None;
         let mut default_city = None;
         let mut cities = None;
+        let mut geocoder = None;
+        let mut sefirah = None;
         let mut location = None;
         let mut exact_days = None;
+        let mut show = None;
+        let mut candle_lighting = HashMap::new();
+        if config_file.is_none() {
+            if let Some(profile) = profile {
+                return Err(AppError::ConfigError(format!(
+                    "There's no config file to find the profile {} in",
+                    profile
+                )));
+            }
+        }
         if let Some(ref mut file) = config_file {
             let mut f = String::new();
             file.read_to_string(&mut f)?;
@@ -81,19 +293,34 @@ impl Config {
                                 Some(
                                     c.into_iter()
                                         .map(|(date, title, json)| InnerDate {
-                                            date,
+                                            date: Some(date),
+                                            relative_to: None,
+                                            offset: None,
+                                            month: None,
+                                            by_day: None,
                                             title,
                                             json,
                                             if_not_exists: None,
+                                            from_year: None,
+                                            until_year: None,
+                                            origin_year: None,
+                                            anniversary: None,
+                                            modifier: None,
                                         })
                                         .collect(),
                                 )
                             }),
                             language: c.language,
+                            transliteration: None,
                             location: c.location,
                             exact_days: c.exact_days,
                             default_city: c.default_city,
                             cities: c.cities,
+                            geocoder: None,
+                            sefirah: None,
+                            show: None,
+                            candle_lighting: None,
+                            profiles: None,
                         },
                         Err(_) => {
                             return Err(err.into());
@@ -101,12 +328,40 @@ impl Config {
                     }
                 }
             };
+            let config = match profile {
+                Some(profile) => config.with_profile(profile)?,
+                None => config,
+            };
+            if let Some(shown) = config.show {
+                if let Some(event) = shown.iter().find(|event| !is_shown_event(event)) {
+                    return Err(AppError::ConfigError(format!(
+                        "Wrong event {} in show. Must be one of the events of --show",
+                        event
+                    )));
+                }
+                show = Some(shown);
+            }
             if let Some(default_city_config) = config.default_city {
                 default_city = Some(default_city_config);
             }
             if let Some(cities_config) = config.cities {
                 cities = Some(cities_config);
             }
+            if let Some(minutes) = config.candle_lighting {
+                candle_lighting = minutes;
+            }
+            geocoder = config.geocoder;
+            sefirah = config
+                .sefirah
+                .map(|custom| {
+                    str_to_sefirah_custom(&custom).ok_or_else(|| {
+                        AppError::ConfigError(format!(
+                            "Wrong sefirah {}. Must be \"first-days\", \"last-days\" or \"whole\"",
+                            custom
+                        ))
+                    })
+                })
+                .transpose()?;
             if let Some(loc) = config.location {
                 location = Some(str_to_location(loc.as_ref())?);
             }
@@ -123,7 +378,27 @@ impl Config {
                         l
                     ),
                 });
+            transliteration = config
+                .transliteration
+                .map(|transliteration_string| {
+                    str_to_transliteration(&transliteration_string).ok_or_else(|| {
+                        AppError::ConfigError(format!(
+                            "Wrong transliteration {}. Must be \"sephardi\", \"ashkenazi\", \"abbreviated\" or \"hebrew\"",
+                            transliteration_string
+                        ))
+                    })
+                })
+                .transpose()?;
             if let Some(days) = config.days {
+                // Custom holidays are printed by `list --provenance` with the name of the file.
+                let file_name = pass_value
+                    .map(String::from)
+                    .or_else(|| get_config_file("heca"))
+                    .and_then(|path| {
+                        let name = std::path::Path::new(&path).file_name()?;
+                        Some(name.to_string_lossy().to_string())
+                    })
+                    .unwrap_or_default();
                 for e in days {
                     let date = e.date;
                     let printable = e.title;
@@ -147,45 +422,258 @@ impl Config {
                                 .collect()
                         });
                     let if_not_exists = if_not_exists.map_or(Ok(None), |v| v.map(Some))?;
-                    let h_date = date.split(&DATE_TOKEN[..]).collect::<Vec<&str>>();
-
-                    if h_date.len() != 2 {
-                        return Err(AppError::DateSyntaxError(date));
+                    let date = match (date, e.relative_to, e.month, e.by_day) {
+                        (Some(date), None, None, None) => {
+                            let h_date = date.split(&DATE_TOKEN[..]).collect::<Vec<&str>>();
+                            if h_date.len() != 2 {
+                                return Err(AppError::DateSyntaxError(date));
+                            }
+                            let (day, month, _) = parse_hebrew(&h_date)?;
+                            let day =
+                                NonZeroI8::new(day.try_into().map_err(|_| {
+                                    AppError::DayIsNotAValidNumber(format!("{}", day))
+                                })?)
+                                .ok_or_else(|| {
+                                    AppError::DayIsNotAValidNumber(format!("{}", day))
+                                })?;
+                            CustomDate::Hebrew(DayMonth { month, day })
+                        }
+                        (None, Some(event), None, None) if is_built_in_event(&event) => {
+                            CustomDate::Relative {
+                                event,
+                                offset: e.offset.unwrap_or(0),
+                            }
+                        }
+                        (None, Some(event), None, None) => {
+                            return Err(AppError::ConfigError(format!(
+                                "{} is relative to {}, which isn't the id of a built-in event",
+                                json, event
+                            )))
+                        }
+                        (None, None, Some(month), Some(by_day)) => {
+                            let month = str_to_month(&month)
+                                .or_else(|| str_to_month(&month.to_lowercase()))
+                                .ok_or(AppError::MonthNotParsed(month))?;
+                            let (nth, weekday) = parse_by_day(&by_day).ok_or_else(|| {
+                                AppError::ConfigError(format!(
+                                    "Wrong byDay {} in {}. Must be a week and a day, like \"1SU\" for the first Sunday or \"-1SA\" for the last Shabbos",
+                                    by_day, json
+                                ))
+                            })?;
+                            CustomDate::Weekday {
+                                month,
+                                nth,
+                                weekday,
+                            }
+                        }
+                        _ => {
+                            return Err(AppError::ConfigError(format!(
+                                "{} needs either a date, relativeTo, or a month and byDay",
+                                json
+                            )))
+                        }
+                    };
+                    if e.offset.is_some() && !matches!(date, CustomDate::Relative { .. }) {
+                        return Err(AppError::ConfigError(format!(
+                            "{} has an offset but isn't relativeTo an event",
+                            json
+                        )));
+                    }
+                    if let (Some(from_year), Some(until_year)) = (e.from_year, e.until_year) {
+                        if from_year > until_year {
+                            return Err(AppError::ConfigError(format!(
+                                "{} has a fromYear ({}) after its untilYear ({})",
+                                json, from_year, until_year
+                            )));
+                        }
                     }
-                    let (day, month, _) = parse_hebrew(&h_date)?;
+                    let origin = match (e.origin_year, e.anniversary) {
+                        (Some(year), anniversary) => Some(Origin {
+                            year,
+                            kind: match anniversary.as_deref() {
+                                Some("yahrzeit") => AnniversaryKind::Yahrzeit,
+                                Some("birthday") => AnniversaryKind::Birthday,
+                                Some("anniversary") | None => AnniversaryKind::Anniversary,
+                                Some(x) => {
+                                    return Err(AppError::ConfigError(format!(
+                                        "Wrong anniversary {} in {}. Must be \"yahrzeit\", \"birthday\" or \"anniversary\"",
+                                        x, json
+                                    )))
+                                }
+                            },
+                        }),
+                        (None, Some(_)) => {
+                            return Err(AppError::ConfigError(format!(
+                                "{} has an anniversary but no originYear",
+                                json
+                            )))
+                        }
+                        (None, None) => None,
+                    };
+                    let modifier = match e.modifier.as_deref() {
+                        Some("shabbos_before") => Some(DayModifier::ShabbosBefore),
+                        Some("shabbos_after") => Some(DayModifier::ShabbosAfter),
+                        Some(x) => {
+                            return Err(AppError::ConfigError(format!(
+                                "Wrong modifier {} in {}. Must be \"shabbos_before\" or \"shabbos_after\"",
+                                x, json
+                            )))
+                        }
+                        None => None,
+                    };
 
                     custom_days.push(CustomHoliday {
-                        date: DayMonth {
-                            month,
-                            day: NonZeroI8::new(
-                                day.try_into().map_err(|_| {
-                                    AppError::DayIsNotAValidNumber(format!("{}", day))
-                                })?,
-                            )
-                            .ok_or_else(|| AppError::DayIsNotAValidNumber(format!("{}", day)))?,
-                        },
+                        date,
                         printable,
                         json,
                         if_not_exists,
+                        from_year: e.from_year,
+                        until_year: e.until_year,
+                        origin,
+                        count: None,
+                        modifier,
+                        source: None,
+                    });
+                }
+                let lines = custom_day_lines(&f, &custom_days);
+                for (custom_day, line) in custom_days.iter_mut().zip(&lines) {
+                    custom_day.source = Some(match line {
+                        Some(line) => format!("{}#{}", file_name, line),
+                        None => file_name.clone(),
                     });
                 }
+                custom_day_conflicts = find_custom_day_conflicts(&custom_days, &lines);
             }
         }
         Ok(Self {
             default_city,
             language,
+            transliteration,
             custom_days,
+            custom_day_conflicts,
             location,
             exact_days,
             cities,
+            geocoder,
+            sefirah,
+            show,
+            candle_lighting,
+        })
+    }
+}
+
+/// Whether the event can be passed to `--show`, like `shabbos` or `zmanim:candles`.
+fn is_shown_event(event: &str) -> bool {
+    event == "all"
+        || EVENTS.contains(&event)
+        || TimeOfDay::from_arg_name(event.strip_prefix("zmanim:").unwrap_or(event)).is_some()
+}
+
+/// Finds the line of the config file each custom holiday is on. Since the TOML parser doesn't
+/// keep track of where values came from, each custom holiday is assumed to be on the first line
+/// after the previous one containing its id.
+fn custom_day_lines(source: &str, custom_days: &[CustomHoliday]) -> Vec<Option<usize>> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut next_line = 0;
+    custom_days
+        .iter()
+        .map(|custom_day| {
+            let quoted = format!("\"{}\"", custom_day.json);
+            let found = (next_line..lines.len()).find(|x| lines[*x].contains(&quoted));
+            if let Some(found) = found {
+                next_line = found + 1;
+            }
+            found.map(|x| x + 1)
         })
+        .collect()
+}
+
+/// Finds custom holidays with the same id or date as an earlier one, or with the id of a
+/// built-in event, given the lines they're on.
+fn find_custom_day_conflicts(
+    custom_days: &[CustomHoliday],
+    line_numbers: &[Option<usize>],
+) -> Vec<String> {
+    let location = |index: usize| match line_numbers[index] {
+        Some(line) => format!("line {}", line),
+        None => format!("custom holiday #{}", index + 1),
+    };
+
+    let mut conflicts = vec![];
+    for (index, custom_day) in custom_days.iter().enumerate() {
+        if let Some(earlier) = custom_days[..index]
+            .iter()
+            .position(|x| x.json == custom_day.json)
+        {
+            conflicts.push(format!(
+                "{}: {} has the same id as {}",
+                location(index),
+                custom_day.json,
+                location(earlier)
+            ));
+        } else if let Some(earlier) = custom_days[..index].iter().position(|x| {
+            x.date == custom_day.date
+                && x.modifier == custom_day.modifier
+                && years_overlap(x, custom_day)
+        }) {
+            conflicts.push(format!(
+                "{}: {} is on the same date as {} ({})",
+                location(index),
+                custom_day.json,
+                custom_days[earlier].json,
+                location(earlier)
+            ));
+        }
+        if is_built_in_event(&custom_day.json) {
+            conflicts.push(format!(
+                "{}: {} is the id of a built-in event",
+                location(index),
+                custom_day.json
+            ));
+        }
     }
+    conflicts
+}
+
+fn years_overlap(first: &CustomHoliday, second: &CustomHoliday) -> bool {
+    first.from_year.max(second.from_year).unwrap_or(0)
+        <= first
+            .until_year
+            .unwrap_or(u64::MAX)
+            .min(second.until_year.unwrap_or(u64::MAX))
+}
+
+fn is_built_in_event(json: &str) -> bool {
+    use crate::algorithms::agricultural::Agricultural;
+    use crate::algorithms::chabad_holidays::ChabadHoliday;
+    use crate::algorithms::israeli_holidays::IsraeliHoliday;
+    use crate::args::types::{DaveningChange, MinorDays, SpanKind};
+    use heca_lib::prelude::{Chol, Parsha, SpecialParsha, YomTov};
+
+    let json = serde_json::Value::String(json.into());
+    serde_json::from_value::<YomTov>(json.clone()).is_ok()
+        || serde_json::from_value::<Chol>(json.clone()).is_ok()
+        || serde_json::from_value::<Parsha>(json.clone()).is_ok()
+        || serde_json::from_value::<SpecialParsha>(json.clone()).is_ok()
+        || serde_json::from_value::<MinorDays>(json.clone()).is_ok()
+        || serde_json::from_value::<IsraeliHoliday>(json.clone()).is_ok()
+        || serde_json::from_value::<ChabadHoliday>(json.clone()).is_ok()
+        || serde_json::from_value::<Agricultural>(json.clone()).is_ok()
+        || serde_json::from_value::<DaveningChange>(json.clone()).is_ok()
+        || serde_json::from_value::<SpanKind>(json).is_ok()
 }
 
+/// Reads a Hebrew date from its day, month and (optionally) year. The day and year can be written
+/// in numbers or in Hebrew letters, like `ט"ו` and `תשפ"ו`; a year without the thousands is in
+/// the 6th millennium. With a year, fails if the day is past the end of the month that year, like
+/// 30 Cheshvan in a year Cheshvan has 29 days. Without one (in the config file, where `ifNotExists`
+/// says what to do in those years), any day is read.
 pub fn parse_hebrew(sp: &[&str]) -> Result<(NonZeroI8, HebrewMonth, Option<u64>), AppError> {
     let day: i8 = sp[0]
         .parse()
-        .map_err(|_| AppError::DayIsNotAValidNumber(sp[0].to_owned()))?;
+        .ok()
+        .or_else(|| parse_hebrew_numeral(sp[0])?.try_into().ok())
+        .ok_or_else(|| AppError::DayIsNotAValidNumber(sp[0].to_owned()))?;
     let day = if let Some(day) = NonZeroI8::new(
         day.try_into()
             .map_err(|_| AppError::DayIsNotAValidNumber(sp[0].into()))?,
@@ -197,7 +685,12 @@ pub fn parse_hebrew(sp: &[&str]) -> Result<(NonZeroI8, HebrewMonth, Option<u64>)
     let year = if let Some(y) = sp.get(2) {
         Some(
             y.parse()
-                .map_err(|_| AppError::YearIsNotANumber(sp[2].to_owned()))?,
+                .ok()
+                .or_else(|| match parse_hebrew_numeral(y)? {
+                    year if year < 1000 => Some(year as u64 + 5000),
+                    year => Some(year as u64),
+                })
+                .ok_or_else(|| AppError::YearIsNotANumber(sp[2].to_owned()))?,
         )
     } else {
         None
@@ -205,10 +698,44 @@ pub fn parse_hebrew(sp: &[&str]) -> Result<(NonZeroI8, HebrewMonth, Option<u64>)
     let month = str_to_month(sp[1])
         .or_else(|| str_to_month(&(String::from(sp[1]).to_lowercase())))
         .ok_or_else(|| AppError::MonthNotParsed(sp[1].to_owned()))?;
+    if let Some(year) = year {
+        check_hebrew_year(year as i64)?;
+        let days = days_in_month(year, month)?;
+        if day.get() > days {
+            return Err(AppError::DayNotInMonth {
+                year,
+                month,
+                day: day.get(),
+                days,
+            });
+        }
+    }
     Ok((day, month, year))
 }
 
-fn str_to_month(text: &str) -> Option<HebrewMonth> {
+/// Parses the weekday of an RRULE's BYDAY, like `2MO` for the second Monday, into the week and
+/// the day. The week is counted from the end if it's negative.
+fn parse_by_day(by_day: &str) -> Option<(i8, Weekday)> {
+    let split = by_day.len().checked_sub(2)?;
+    let (nth, weekday) = (by_day.get(..split)?, by_day.get(split..)?);
+    let nth: i8 = nth.trim_start_matches('+').parse().ok()?;
+    let weekday = match weekday {
+        "SU" => Weekday::Sun,
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        _ => return None,
+    };
+    if nth == 0 || nth.abs() > 5 {
+        return None;
+    }
+    Some((nth, weekday))
+}
+
+pub fn str_to_month(text: &str) -> Option<HebrewMonth> {
     match text {
         "תשרי" => Some(HebrewMonth::Tishrei),
         "חשוון" => Some(HebrewMonth::Cheshvan),
@@ -273,20 +800,104 @@ pub struct ConfigCity {
 struct ConfigFile {
     days: Option<Vec<InnerDate>>,
     language: Option<String>,
+    transliteration: Option<String>,
     location: Option<String>,
     #[serde(rename = "exact-days")]
     exact_days: Option<bool>,
     #[serde(rename = "default-city")]
     default_city: Option<String>,
     cities: Option<Vec<ConfigCity>>,
+    geocoder: Option<String>,
+    sefirah: Option<String>,
+    show: Option<Vec<String>>,
+    #[serde(rename = "candle-lighting")]
+    candle_lighting: Option<HashMap<String, u8>>,
+    profiles: Option<HashMap<String, ConfigProfile>>,
+}
+
+/// A profile of the config file, like `[profiles.shul]`, which `--profile shul` picks.
+#[derive(Deserialize)]
+struct ConfigProfile {
+    days: Option<Vec<InnerDate>>,
+    language: Option<String>,
+    transliteration: Option<String>,
+    location: Option<String>,
+    #[serde(rename = "exact-days")]
+    exact_days: Option<bool>,
+    #[serde(rename = "default-city")]
+    default_city: Option<String>,
+    geocoder: Option<String>,
+    sefirah: Option<String>,
+    show: Option<Vec<String>>,
+    #[serde(rename = "candle-lighting")]
+    candle_lighting: Option<HashMap<String, u8>>,
 }
+
+impl ConfigFile {
+    /// Replaces the settings with those the profile sets, and adds its custom holidays and
+    /// candle lighting minutes.
+    fn with_profile(mut self, name: &str) -> Result<Self, AppError> {
+        let mut profiles = self.profiles.take().unwrap_or_default();
+        let profile = match profiles.remove(name) {
+            Some(profile) => profile,
+            None => {
+                let mut names: Vec<String> = profiles.into_keys().collect();
+                names.sort();
+                return Err(AppError::ConfigError(if names.is_empty() {
+                    format!(
+                        "There's no profile {}, since the config file has none",
+                        name
+                    )
+                } else {
+                    format!(
+                        "There's no profile {}. The profiles are {}",
+                        name,
+                        names.join(", ")
+                    )
+                }));
+            }
+        };
+        if let Some(days) = profile.days {
+            self.days.get_or_insert_with(Vec::new).extend(days);
+        }
+        self.language = profile.language.or(self.language);
+        self.transliteration = profile.transliteration.or(self.transliteration);
+        self.location = profile.location.or(self.location);
+        self.exact_days = profile.exact_days.or(self.exact_days);
+        self.default_city = profile.default_city.or(self.default_city);
+        self.geocoder = profile.geocoder.or(self.geocoder);
+        self.sefirah = profile.sefirah.or(self.sefirah);
+        self.show = profile.show.or(self.show);
+        if let Some(minutes) = profile.candle_lighting {
+            self.candle_lighting
+                .get_or_insert_with(HashMap::new)
+                .extend(minutes);
+        }
+        Ok(self)
+    }
+}
+
 #[derive(Deserialize)]
 struct InnerDate {
-    date: String,
+    date: Option<String>,
+    #[serde(rename = "relativeTo")]
+    relative_to: Option<String>,
+    offset: Option<i64>,
+    month: Option<String>,
+    #[serde(rename = "byDay")]
+    by_day: Option<String>,
     title: String,
     json: String,
     #[serde(rename = "ifNotExists")]
     if_not_exists: Option<Vec<String>>,
+    #[serde(rename = "fromYear")]
+    from_year: Option<u64>,
+    #[serde(rename = "untilYear")]
+    until_year: Option<u64>,
+    #[serde(rename = "originYear")]
+    origin_year: Option<u64>,
+    anniversary: Option<String>,
+    modifier: Option<String>,
 }
 
 #[derive(Deserialize, PartialEq, Eq, Clone, Copy)]
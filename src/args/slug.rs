@@ -0,0 +1,21 @@
+use crate::args::prelude::{get_location, parse_gregorian_date, Config};
+use crate::args::types::{AppError, Command, Language, SlugArgs, SlugSeries};
+use clap::ArgMatches;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+) -> Result<Command, AppError> {
+    let date = parse_gregorian_date(matches.value_of("Date").unwrap())?;
+    let series = match matches.value_of("Series") {
+        Some("daf-yomi") => SlugSeries::DafYomi,
+        Some("parsha") | None => SlugSeries::Parsha,
+        Some(x) => unreachable!("{}", x),
+    };
+    Ok(Command::Slug(SlugArgs {
+        date,
+        location: get_location(matches, config, language)?,
+        series,
+    }))
+}
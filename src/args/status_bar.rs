@@ -0,0 +1,28 @@
+use crate::args::digest::list_args;
+use crate::args::prelude::{get_today_time_zone, parse_gregorian_date, Config};
+use crate::args::types::{AppError, Command, Language, StatusBarArgs};
+use crate::prelude::Warnings;
+use clap::ArgMatches;
+
+pub fn parse_options(
+    matches: &ArgMatches<'_>,
+    config: &Config,
+    language: Language,
+    warnings: Warnings,
+) -> Result<Command, AppError> {
+    let list = list_args(matches, config, language, warnings)?;
+    let (date, time_zone) = match matches.value_of("Date") {
+        Some(date) => (Some(parse_gregorian_date(date)?), None),
+        None => (
+            None,
+            get_today_time_zone(matches, list.city.as_ref(), warnings)?,
+        ),
+    };
+    Ok(Command::StatusBar(StatusBarArgs {
+        date,
+        time_zone,
+        list,
+        watch: matches.occurrences_of("Watch") > 0,
+        exec: matches.value_of("Exec").map(String::from),
+    }))
+}
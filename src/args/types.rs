@@ -1,43 +1,423 @@
+use crate::algorithms::agricultural::Agricultural;
 use crate::algorithms::candle_lighting::CITIES;
 use crate::algorithms::chabad_holidays::ChabadHoliday;
 use crate::algorithms::israeli_holidays::IsraeliHoliday;
+use crate::algorithms::reading_cycle::{join_parshiyos, split_parsha, TorahPortion};
 
 use std::num::NonZeroI8;
 
 use chrono::prelude::*;
 use heca_lib::prelude::*;
-use heca_lib::HebrewDate;
+use heca_lib::{HebrewDate, HebrewYear};
 use serde::ser::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 pub struct MainArgs {
     pub custom_days: Option<Vec<Name>>,
     pub output_type: OutputType,
     pub language: Language,
+    pub second_language: Option<Language>,
+    pub transliteration: Transliteration,
+    /// The file to write the output to, instead of stdout.
+    pub out: Option<std::path::PathBuf>,
+    /// Print only ASCII, for terminals which can't show UTF-8.
+    pub ascii: bool,
+    /// The time `--today` sets the clock to, if it's given.
+    pub today: Option<Today>,
     pub command: Command,
 }
 
+/// The time `--today` (or `HECA_TODAY`) sets the clock to instead of the system's, so that
+/// commands which print today's day print the same day every time they're run.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Today {
+    /// A time without an offset, like `2025-03-11T20:00`, is the time in the time zone today is
+    /// found in, or in UTC.
+    Local(NaiveDateTime),
+    /// A time with an offset, like `2025-03-11T20:00+02:00`.
+    Exact(DateTime<Utc>),
+}
+
 #[derive(Eq, PartialEq, Clone, Copy)]
 pub enum Language {
     English,
     Hebrew,
 }
 
+impl Language {
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Hebrew => "he",
+        }
+    }
+}
+
+/// How masechta names are written when printing in English.
+#[derive(Eq, PartialEq, Clone, Copy)]
+pub enum Transliteration {
+    Sephardi,
+    Ashkenazi,
+    Abbreviated,
+    Hebrew,
+}
+
 pub enum Command {
     Convert(ConvertArgs),
     List(ListArgs),
+    Limits(LimitsArgs),
+    SelfTest(SelfTestArgs),
+    VerifyAnchors(VerifyAnchorsArgs),
+    Zmanim(ZmanimArgs),
+    Slug(SlugArgs),
+    DiffCalendars(DiffCalendarsArgs),
+    Merge(MergeArgs),
+    Table(TableArgs),
+    Stats(StatsArgs),
+    YearInfo(YearInfoArgs),
+    MonthInfo(MonthInfoArgs),
+    Digest(DigestArgs),
+    StatusBar(StatusBarArgs),
+    Catchup(CatchupArgs),
+    PlanSiyum(PlanSiyumArgs),
+    BarMitzvah(BarMitzvahArgs),
+    CheckDate(CheckDateArgs),
+    Find(FindArgs),
+    Batch(BatchArgs),
+    Version(VersionArgs),
+}
+
+pub struct LimitsArgs;
+
+/// `--version` with `--print json`.
+pub struct VersionArgs;
+
+pub struct SelfTestArgs;
+
+pub struct VerifyAnchorsArgs;
+
+/// The range of Hebrew years `heca table` prints, including both ends.
+pub struct TableArgs {
+    pub from: u64,
+    pub to: u64,
+}
+
+/// The range of Hebrew years `heca stats` counts, including both ends.
+pub struct StatsArgs {
+    pub from: u64,
+    pub to: u64,
+    /// With `--event`, counts the days of the event, named like in `heca find`, instead.
+    pub event: Option<EventStats>,
+}
+
+/// How `heca stats --event` counts the days of an event.
+pub struct EventStats {
+    pub event: String,
+    pub by: StatsBy,
+    pub location: Location,
+    /// Prints the table as CSV.
+    pub csv: bool,
+}
+
+/// What the days of an event are counted by.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum StatsBy {
+    Weekday,
+}
+
+pub struct YearInfoArgs {
+    pub year: u64,
+    /// Explains the dechiyos of Rosh Hashanah and the lengths of Cheshvan and Kislev.
+    pub explain: bool,
+    /// Lists which parshiyos are read together in the location, and why.
+    pub doubled: Option<Location>,
+}
+
+pub struct MonthInfoArgs {
+    pub year: u64,
+    pub month: HebrewMonth,
+    pub explain: bool,
+}
+
+/// A week for `heca digest` to print as an email.
+pub struct DigestArgs {
+    /// The Sunday the week starts on.
+    pub week: NaiveDate,
+    /// The events and daily study of the week, listed like `heca list`.
+    pub list: ListArgs,
+    /// The zmanim printed for every day, if `list` has a city.
+    pub zmanim: Vec<Zman>,
+    /// The `To` and `From` headers of the email.
+    pub to: Option<String>,
+    pub from: Option<String>,
+}
+
+/// A day for `heca status-bar` to print.
+pub struct StatusBarArgs {
+    /// The day to print, or `None` for the day it is now, which starts at sunset if there's a
+    /// city.
+    pub date: Option<NaiveDate>,
+    /// The time zone the day is in, or `None` for UTC.
+    pub time_zone: Option<zmanim::prelude::tz::TimeZone>,
+    /// The events around the day, listed like `heca list`.
+    pub list: ListArgs,
+    /// Whether to keep running, printing the day again whenever it changes.
+    pub watch: bool,
+    /// The program to run with the output on its stdin, instead of printing it.
+    pub exec: Option<String>,
+}
+
+pub struct ZmanimArgs {
+    pub year: i32,
+    pub city: City,
+    pub zmanim: Vec<Zman>,
+    pub table: Option<TableFormat>,
+    pub polar_fallback: PolarFallback,
+    pub solar_algorithm: SolarAlgorithm,
+}
+
+/// The paths of two calendars printed by `heca --print json list`.
+pub struct DiffCalendarsArgs {
+    pub old: String,
+    pub new: String,
+}
+
+/// The paths of calendars in the format of `heca --print json list`, from the lowest priority
+/// to the highest.
+pub struct MergeArgs {
+    pub files: Vec<String>,
+    /// Adds the file and the place in it of every event without a `source`.
+    pub provenance: bool,
+}
+
+/// `heca catchup`: how to get back to the daily study after falling behind.
+pub struct CatchupArgs {
+    pub study: DailyStudy,
+    pub location: Location,
+    /// How many days of the cycle haven't been learned yet.
+    pub behind: u32,
+    /// How many days of the cycle are learned every day until catching up.
+    pub pace: u32,
+    /// The first day of the plan.
+    pub date: NaiveDate,
+}
+
+/// `heca plan-siyum`: a schedule to learn a masechta by a date.
+pub struct PlanSiyumArgs {
+    /// The index of the masechta in `MASECHTOS`.
+    pub masechta: u8,
+    /// The daf to start from.
+    pub start_daf: u8,
+    /// The first day to learn on.
+    pub from: NaiveDate,
+    /// The last day to learn on.
+    pub by: NaiveDate,
+    /// The days of the week to learn on.
+    pub days: Vec<Weekday>,
+    pub location: Location,
+}
+
+/// `heca bar-mitzvah`: what to prepare for the reading of a bar mitzvah.
+pub struct BarMitzvahArgs {
+    /// The bar mitzvah reads on the Shabbos on or after the date.
+    pub date: NaiveDate,
+    pub location: Location,
+}
+
+/// `heca check-date`: whether a date is free of the restrictions of an occasion.
+pub struct CheckDateArgs {
+    pub date: NaiveDate,
+    pub occasion: CheckedOccasion,
+    pub location: Location,
+    pub sefirah: SefirahCustom,
+}
+
+/// `heca find`: the next times an event falls on a day of the week.
+pub struct FindArgs {
+    /// The name of the event, like `erev-pesach`.
+    pub event: String,
+    pub weekday: Option<Weekday>,
+    /// How many times to find.
+    pub limit: usize,
+    /// The Hebrew years searched, including both ends.
+    pub from: u64,
+    pub until: u64,
+    pub location: Location,
+}
+
+/// `heca batch`: a request on every line of stdin, each answered on a line of stdout.
+pub struct BatchArgs {
+    /// The options `heca batch` was run with which come before the subcommand, like `--config`,
+    /// with their values, if they take one. Every request is parsed with them, unless it passes
+    /// its own.
+    pub globals: Vec<(String, Option<String>)>,
+}
+
+/// What `heca check-date` checks a date for.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CheckedOccasion {
+    Wedding,
+}
+
+/// Which days of Sefiras HaOmer weddings aren't held on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum SefirahCustom {
+    /// From Pesach until Lag BaOmer.
+    FirstDays,
+    /// From Rosh Chodesh Iyar until the three days before Shavuos, other than Lag BaOmer.
+    LastDays,
+    /// From Pesach until the three days before Shavuos, other than Lag BaOmer.
+    Whole,
+}
+
+pub struct SlugArgs {
+    pub date: NaiveDate,
+    pub location: Location,
+    pub series: SlugSeries,
+}
+
+/// What `heca slug` names the date after.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SlugSeries {
+    /// The parsha of the week, read on the Shabbos on or after the date.
+    Parsha,
+    DafYomi,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TableFormat {
+    Pretty,
+    Csv,
+}
+
+/// What a zman based on how far the sun is below the horizon is on days the sun doesn't go that
+/// far down, which happens in the summer far from the equator. Selected with `--polar-fallback`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PolarFallback {
+    /// The zman doesn't occur.
+    None,
+    /// The zman is at midnight, when the sun is lowest.
+    Midnight,
+}
+
+/// The algorithm sunrise, sunset and the zmanim are calculated with. Selected with
+/// `--solar-algorithm`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SolarAlgorithm {
+    /// NOAA's solar calculator.
+    Noaa,
+    /// The US Naval Observatory's Almanac for Computers.
+    Usno,
 }
 
+/// A time of the day printed by `heca zmanim`. The times between sunrise and sunset are in
+/// halachic hours (a twelfth of the daylight), according to the Gra.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Zman {
+    /// When the sun is 16.1 degrees below the horizon in the morning.
+    AlosHashachar,
+    /// When the sun is 11.5 degrees below the horizon in the morning.
+    Misheyakir,
+    Sunrise,
+    SofZmanShma,
+    SofZmanTefillah,
+    Chatzos,
+    MinchaGedola,
+    MinchaKetana,
+    PlagHamincha,
+    Sunset,
+    /// When the sun is 8.5 degrees below the horizon in the evening.
+    TzeisHakochavim,
+    /// Four hours into Erev Pesach, when chametz can't be eaten anymore. It's only listed on
+    /// Erev Pesach, so it isn't in `ZMANIM`.
+    SofZmanAchilasChametz,
+    /// Five hours into Erev Pesach, when chametz has to be burnt or nullified.
+    SofZmanBiurChametz,
+}
+
+pub const ZMANIM: &[(&str, Zman)] = &[
+    ("alos", Zman::AlosHashachar),
+    ("misheyakir", Zman::Misheyakir),
+    ("sunrise", Zman::Sunrise),
+    ("sof-zman-shma", Zman::SofZmanShma),
+    ("sof-zman-tefillah", Zman::SofZmanTefillah),
+    ("chatzos", Zman::Chatzos),
+    ("mincha-gedola", Zman::MinchaGedola),
+    ("mincha-ketana", Zman::MinchaKetana),
+    ("plag-hamincha", Zman::PlagHamincha),
+    ("sunset", Zman::Sunset),
+    ("tzeis", Zman::TzeisHakochavim),
+];
+
+impl Zman {
+    /// The name passed to `--zmanim`, which is also the column's name in CSV.
+    pub fn arg_name(self) -> &'static str {
+        ZMANIM.iter().find(|(_, zman)| *zman == self).unwrap().0
+    }
+}
+
+/// A time listed as an event of its own with `list --show zmanim:...`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TimeOfDay {
+    CandleLighting,
+    Havdalah,
+    Zman(Zman),
+}
+
+impl TimeOfDay {
+    /// The names which can be passed after `zmanim:`: `candles`, `havdalah` and the zmanim.
+    pub fn arg_names() -> impl Iterator<Item = &'static str> {
+        ["candles", "havdalah"]
+            .iter()
+            .copied()
+            .chain(ZMANIM.iter().map(|(name, _)| *name))
+    }
+
+    pub fn from_arg_name(name: &str) -> Option<Self> {
+        match name {
+            "candles" => Some(TimeOfDay::CandleLighting),
+            "havdalah" => Some(TimeOfDay::Havdalah),
+            name => ZMANIM
+                .iter()
+                .find(|(x, _)| *x == name)
+                .map(|(_, zman)| TimeOfDay::Zman(*zman)),
+        }
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Eq, PartialEq, Copy, Clone)]
 pub enum OutputType {
     Regular,
     Pretty,
     JSON,
+    ICS,
+    /// The same data as `JSON`, in CBOR.
+    CBOR,
+    /// The same data as `JSON`, in MessagePack.
+    MessagePack,
+    /// An Apache Parquet file of the events of `list`, with the `parquet` feature. Other
+    /// commands print as usual.
+    Parquet,
+}
+
+impl OutputType {
+    /// Whether the output is data for other programs to read (JSON, CBOR or MessagePack), so
+    /// that errors and warnings are printed as JSON too.
+    pub fn is_data(self) -> bool {
+        matches!(
+            self,
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack
+        )
+    }
 }
 
 pub struct ConvertArgs {
     pub date: ConvertType,
     pub language: Language,
+    /// Where the parsha and holidays of the date are looked up.
+    pub location: Location,
 }
 
 #[derive(Debug)]
@@ -49,11 +429,61 @@ pub enum ConvertType {
 pub struct ListArgs {
     pub year: YearType,
     pub location: Location,
+    /// Where Yom Tov is kept, from `--yomtov-observance`, which is `location` unless a traveler
+    /// keeps the Yom Tov of home.
+    pub yom_tov_observance: Location,
     pub events: Vec<Event>,
     pub amnt_years: u64,
     pub no_sort: bool,
     pub exact_days: bool,
+    pub categories: Option<Vec<Category>>,
+    pub excluded_categories: Vec<Category>,
     pub city: Option<City>,
+    pub reading_cycle: Option<ReadingCycle>,
+    pub merge_spans: bool,
+    pub merge_rosh_chodesh: bool,
+    pub strict: bool,
+    pub month: Option<MonthFilter>,
+    pub only_shabbos: bool,
+    pub havdalah: Vec<HavdalahOpinion>,
+    pub polar_fallback: PolarFallback,
+    pub solar_algorithm: SolarAlgorithm,
+    pub icons: bool,
+    /// The fields of every event to print in JSON, like `name.en`, or `None` for all of them.
+    pub fields: Option<Vec<String>>,
+    pub short_names: bool,
+    pub headers: Option<Headers>,
+    pub summary: bool,
+    /// Adds a hash of the events and how they were listed to JSON and iCalendar.
+    pub meta: bool,
+    /// Reports what would be listed instead of listing it.
+    pub check: bool,
+    pub warn_shabbos_conflicts: bool,
+    /// Names the rule which listed each event, like `dechiyah:lo-adu-rosh`.
+    pub provenance: bool,
+    /// Prints the daily study with links to its text instead of the list.
+    pub links: Option<LinkFormat>,
+    /// The reminders added to the events in iCalendar.
+    pub alarms: Vec<Alarm>,
+    /// The files the events of some categories are written to, instead of the output.
+    pub routes: Vec<Route>,
+    /// The state file of `--append-since-last-run`, which keeps the last date printed.
+    pub append_since_last_run: Option<std::path::PathBuf>,
+    pub warnings: Warnings,
+}
+
+/// Restricts `heca list` to a single month of the year.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MonthFilter {
+    Hebrew(HebrewMonth),
+    /// The Gregorian month, from 1 to 12.
+    Gregorian(u32),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ReadingCycle {
+    Annual,
+    Triennial,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -64,22 +494,215 @@ pub enum Event {
     DailyStudy(DailyStudy),
     IsraeliHolidays,
     ChabadHolidays,
+    /// Tefillas Geshem and Tefillas Tal, when the prayers for rain and dew start.
+    DaveningChanges,
+    /// The agricultural dates of Eretz Yisrael, which are only listed in Israel.
+    Agricultural,
     ShabbosMevarchim,
+    /// A time of the day, listed on every day it's on. It needs a city.
+    Zman(TimeOfDay),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CustomHoliday {
     pub printable: String,
     pub json: String,
-    pub date: DayMonth,
+    pub date: CustomDate,
     pub if_not_exists: Option<Vec<DayMonth>>,
+    /// The first Hebrew year this is listed in, if it's not listed in every year.
+    pub from_year: Option<u64>,
+    /// The last Hebrew year this is listed in.
+    pub until_year: Option<u64>,
+    /// For yahrzeits, birthdays and other anniversaries, the year they're counted from.
+    pub origin: Option<Origin>,
+    /// Which anniversary this is, set for every listed day of a custom holiday with an origin.
+    pub count: Option<u64>,
+    /// Moves the day to a Shabbos near its date, like an aufruf before a wedding.
+    pub modifier: Option<DayModifier>,
+    /// Where it's defined: the name of the config file and the line of its id, like
+    /// `config.toml#12`, printed by `list --provenance`.
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomDate {
+    Hebrew(DayMonth),
+    /// A number of days after a built-in event (or before it, if it's negative), which is
+    /// named by its id in JSON, like `Pesach1`. It isn't listed in years without the event.
+    Relative {
+        event: String,
+        offset: i64,
+    },
+    /// The `nth` weekday of a Hebrew month, or the `-nth` from its end if it's negative, like
+    /// `BYDAY=1SU` in an RRULE. It isn't listed in years without it, like a 5th Shabbos.
+    Weekday {
+        month: HebrewMonth,
+        nth: i8,
+        weekday: Weekday,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DayModifier {
+    /// The last Shabbos before the date.
+    ShabbosBefore,
+    /// The first Shabbos after the date.
+    ShabbosAfter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Origin {
+    pub year: u64,
+    pub kind: AnniversaryKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnniversaryKind {
+    Yahrzeit,
+    Birthday,
+    Anniversary,
+}
+
+impl CustomHoliday {
+    pub fn applies_to(&self, year: u64) -> bool {
+        !matches!(self.from_year, Some(from_year) if year < from_year)
+            && !matches!(self.until_year, Some(until_year) if year > until_year)
+            && !matches!(self.origin, Some(origin) if year <= origin.year)
+    }
+
+    /// Returns the night the custom holiday is listed on, given the night its date starts on.
+    pub fn night_of(&self, night: DateTime<Utc>) -> DateTime<Utc> {
+        // Days are counted from Sunday, so Shabbos is 6. A date on Shabbos moves a week.
+        let weekday = (night + chrono::Duration::days(1))
+            .weekday()
+            .num_days_from_sunday() as i64;
+        match self.modifier {
+            None => night,
+            Some(DayModifier::ShabbosBefore) => night - chrono::Duration::days(weekday + 1),
+            Some(DayModifier::ShabbosAfter) => {
+                night + chrono::Duration::days(7 - (weekday + 1) % 7)
+            }
+        }
+    }
+
+    /// Returns the custom holiday as listed in the given year, with its anniversary count.
+    pub fn in_year(&self, year: u64) -> CustomHoliday {
+        let mut custom_holiday = self.clone();
+        custom_holiday.count = self.origin.map(|origin| year - origin.year);
+        custom_holiday
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        let mut p = lock.write(self.printable.as_bytes()).ok()?;
+        if let (Some(origin), Some(count)) = (self.origin, self.count) {
+            let anniversary = match language {
+                Language::English => match origin.kind {
+                    AnniversaryKind::Yahrzeit => format!(" ({} yahrzeit)", ordinal(count)),
+                    AnniversaryKind::Birthday if count == 13 => {
+                        " (bar mitzvah - 13th birthday)".to_string()
+                    }
+                    AnniversaryKind::Birthday => format!(" ({} birthday)", ordinal(count)),
+                    AnniversaryKind::Anniversary => {
+                        format!(" ({} anniversary)", ordinal(count))
+                    }
+                },
+                Language::Hebrew => match origin.kind {
+                    AnniversaryKind::Yahrzeit | AnniversaryKind::Anniversary => {
+                        format!(" (יום השנה ה-{})", count)
+                    }
+                    AnniversaryKind::Birthday if count == 13 => {
+                        " (בר מצווה - יום הולדת 13)".to_string()
+                    }
+                    AnniversaryKind::Birthday => format!(" (יום הולדת {})", count),
+                },
+            };
+            p += lock.write(anniversary.as_bytes()).ok()?;
+        }
+        Some(p)
+    }
+}
+
+/// Returns an English ordinal, such as "1st" or "12th".
+fn ordinal(n: u64) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DailyStudy {
     DafYomi,
+    /// One daf a week, starting on the given day of the week.
+    DafHashavua(WeekStart),
     Rambam(RambamChapters),
     YerushalmiYomi,
+    Chumash,
+}
+
+/// The day a weekly study starts on, selected with `--week-start`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum WeekStart {
+    Sunday,
+    Shabbos,
+}
+
+/// A reminder added to events in iCalendar with `--alarm`, like `candles:-18m`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alarm {
+    pub target: AlarmTarget,
+    pub when: AlarmTime,
+}
+
+/// The events an alarm is added to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AlarmTarget {
+    All,
+    /// The days with candle lighting, reminding of the time of candle lighting.
+    CandleLighting,
+    /// The days with havdalah, reminding of the time of havdalah.
+    Havdalah,
+    Category(Category),
+    /// A daily study, by its id, like `DafYomi`.
+    DailyStudy(&'static str),
+}
+
+/// When an alarm goes off.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AlarmTime {
+    /// Before (if it's negative) or after the start of the event, or the time it reminds of.
+    Offset(chrono::Duration),
+    /// At a time of the day the event is on, or of the day of the time it reminds of.
+    At(NaiveTime),
+}
+
+/// A file the events of a category are written to with `--route`, like `zmanim=>zmanim.ics`.
+#[derive(Clone)]
+pub struct Route {
+    pub category: Category,
+    pub path: std::path::PathBuf,
+    /// The format the file is written in, from its extension.
+    pub output_type: OutputType,
+}
+
+/// How `heca list --links` prints the daily study and its links.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum LinkFormat {
+    Csv,
+    Json,
+}
+
+/// The sections the pretty output is split into with `--headers`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Headers {
+    HebrewYear,
+    GregorianYear,
+    /// The Hebrew month, along with its year.
+    Month,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -105,6 +728,128 @@ pub struct DayVal {
     pub day: chrono::DateTime<Utc>,
     pub name: Name,
     pub candle_lighting: Option<Option<DateTime<FixedOffset>>>,
+    pub torah_portion: Option<TorahPortion>,
+    /// The end of Shabbos or Yom Tov, on the last day of one, if a city was given.
+    pub havdalah: Vec<Havdalah>,
+    /// When the event starts and ends, for events which only last part of the day, like a fast,
+    /// if a city was given.
+    pub time_range: Option<TimeRange>,
+}
+
+impl DayVal {
+    /// The civil date the day is printed with: the date of the night it starts on, or, for
+    /// observances during the day (see `Name::starts_at_night`), the date of the day itself.
+    pub fn civil_date(&self) -> NaiveDate {
+        if self.name.starts_at_night() {
+            self.day.date().naive_utc()
+        } else {
+            (self.day + chrono::Duration::days(1)).date().naive_utc()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Havdalah {
+    pub opinion: HavdalahOpinion,
+    /// `None` if the sun doesn't go far enough below the horizon that night, which happens in
+    /// the summer far from the equator.
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub time: Option<DateTime<FixedOffset>>,
+}
+
+/// When an event which lasts part of a day starts and ends, like a fast from dawn until
+/// nightfall.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeRange {
+    /// `None` if it doesn't occur that day, like dawn in the summer far from the equator.
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub start: Option<DateTime<FixedOffset>>,
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub end: Option<DateTime<FixedOffset>>,
+}
+
+fn serialize_time<S>(time: &Option<DateTime<FixedOffset>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match time {
+        Some(time) => serializer.serialize_str(&time.to_rfc3339()),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_time<'de, D>(deserializer: D) -> Result<Option<DateTime<FixedOffset>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(time) => DateTime::parse_from_rfc3339(&time)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// When Shabbos and Yom Tov end, selected with `--havdalah-opinion`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HavdalahOpinion {
+    Minutes42,
+    Minutes50,
+    Minutes72,
+    /// When the sun is 8.5 degrees below the horizon.
+    Degrees8_5,
+    /// When the sun is 16.1 degrees below the horizon, which is 72 minutes after sunset in
+    /// Jerusalem at the equinox.
+    RabbeinuTam,
+}
+
+impl HavdalahOpinion {
+    /// The fixed amount of minutes after sunset, if this opinion is a fixed time.
+    pub fn after_sunset(self) -> Option<i64> {
+        match self {
+            HavdalahOpinion::Minutes42 => Some(42),
+            HavdalahOpinion::Minutes50 => Some(50),
+            HavdalahOpinion::Minutes72 => Some(72),
+            HavdalahOpinion::Degrees8_5 | HavdalahOpinion::RabbeinuTam => None,
+        }
+    }
+
+    pub fn degrees_below_horizon(self) -> f64 {
+        match self {
+            HavdalahOpinion::Degrees8_5 => 8.5,
+            HavdalahOpinion::RabbeinuTam => 16.1,
+            _ => 0.833,
+        }
+    }
+
+    pub fn pretty_print<W: Write>(self, lock: &mut W, language: Language) -> Option<usize> {
+        let p = match language {
+            Language::English => match self {
+                HavdalahOpinion::Minutes42 => "42 minutes",
+                HavdalahOpinion::Minutes50 => "50 minutes",
+                HavdalahOpinion::Minutes72 => "72 minutes",
+                HavdalahOpinion::Degrees8_5 => "8.5 degrees",
+                HavdalahOpinion::RabbeinuTam => "Rabbeinu Tam",
+            },
+            Language::Hebrew => match self {
+                HavdalahOpinion::Minutes42 => "42 דקות",
+                HavdalahOpinion::Minutes50 => "50 דקות",
+                HavdalahOpinion::Minutes72 => "72 דקות",
+                HavdalahOpinion::Degrees8_5 => "8.5 מעלות",
+                HavdalahOpinion::RabbeinuTam => "רבינו תם",
+            },
+        };
+        lock.write(p.as_bytes()).ok()
+    }
 }
 
 impl Serialize for DayVal {
@@ -126,6 +871,9 @@ impl Serialize for DayVal {
                             None => state.serialize_field("candleLighting", "undefined")?,
                         };
                     }
+                    if !self.havdalah.is_empty() {
+                        state.serialize_field("havdalah", &self.havdalah)?;
+                    }
                 }
                 TorahReading::Chol(chol) => {
                     state.serialize_field("type", "Chol")?;
@@ -140,6 +888,12 @@ impl Serialize for DayVal {
                             None => state.serialize_field("candleLighting", "undefined")?,
                         };
                     }
+                    if !self.havdalah.is_empty() {
+                        state.serialize_field("havdalah", &self.havdalah)?;
+                    }
+                    if let Some(torah_portion) = &self.torah_portion {
+                        state.serialize_field("torahPortion", torah_portion)?;
+                    }
                 }
                 TorahReading::SpecialParsha(special_parsha) => {
                     state.serialize_field("type", "YomTov")?;
@@ -153,6 +907,9 @@ impl Serialize for DayVal {
             Name::CustomHoliday(custom_holiday) => {
                 state.serialize_field("type", "CustomHoliday")?;
                 state.serialize_field("name", &custom_holiday.json)?;
+                if let Some(count) = custom_holiday.count {
+                    state.serialize_field("count", &count)?;
+                }
             }
             Name::IsraeliHoliday(holiday) => {
                 state.serialize_field("type", "IsraeliHoliday")?;
@@ -185,15 +942,24 @@ impl Serialize for DayVal {
                     }
                 }
             }
+            Name::Agricultural(agricultural) => {
+                state.serialize_field("type", "Agricultural")?;
+                state.serialize_field("name", &format!("{:?}", agricultural))?;
+            }
+            Name::DaveningChange(change) => {
+                state.serialize_field("type", "DaveningChange")?;
+                state.serialize_field("name", change)?;
+            }
             Name::DailyStudy(daily_study) => {
                 match daily_study {
                     DailyStudyOutput::Daf(daf) => {
-                        let mut m = HashMap::new();
-                        m.insert("masechta", daf.masechta_json.to_string());
-                        m.insert("daf", daf.daf.to_string());
                         state.serialize_field("type", "DafYomi")?;
                         state.serialize_field("topic", &daf)?;
                     }
+                    DailyStudyOutput::DafHashavua(daf) => {
+                        state.serialize_field("type", "DafHashavua")?;
+                        state.serialize_field("topic", &daf)?;
+                    }
                     DailyStudyOutput::RambamThreeChapters(halacha) => {
                         state.serialize_field("type", "Rambam3Chapters")?;
                         let v = vec![&halacha.ch1, &halacha.ch2, &halacha.ch3];
@@ -207,24 +973,635 @@ impl Serialize for DayVal {
                         state.serialize_field("type", "Yerushalmi")?;
                         state.serialize_field("topic", &yerushalmi_yomi)?;
                     }
+                    DailyStudyOutput::Chumash(chumash) => {
+                        state.serialize_field("type", "Chumash")?;
+                        state.serialize_field("topic", &chumash)?;
+                    }
                 };
             }
-            Name::ShabbosMevarchim(shabbos_mevarchim) => {
-                state.serialize_field("type", "ShabbosMevarchim")?;
-                state.serialize_field("month", &shabbos_mevarchim.hebrew_month)?;
-                state.serialize_field("molad", &shabbos_mevarchim)?;
+            Name::ShabbosMevarchim(shabbos_mevarchim) => {
+                state.serialize_field("type", "ShabbosMevarchim")?;
+                state.serialize_field("month", &shabbos_mevarchim.hebrew_month)?;
+                state.serialize_field("molad", &shabbos_mevarchim)?;
+            }
+            Name::Span(span) => {
+                state.serialize_field("type", "Span")?;
+                state.serialize_field("name", &span.kind)?;
+                if span.kind == SpanKind::RoshChodesh {
+                    state.serialize_field("month", &span.month())?;
+                }
+                state.serialize_field("end", &span.end)?;
+                if !span.nights.is_empty() {
+                    state.serialize_field("nights", &span.nights)?;
+                }
+            }
+            Name::Shabbos(shabbos) => {
+                state.serialize_field("type", "ShabbosSummary")?;
+                match shabbos.reading {
+                    TorahReading::YomTov(yt) => state.serialize_field("name", &yt)?,
+                    TorahReading::Chol(chol) => state.serialize_field("name", &chol)?,
+                    TorahReading::Shabbos(parsha) => state.serialize_field("name", &parsha)?,
+                    TorahReading::SpecialParsha(special_parsha) => {
+                        state.serialize_field("name", &special_parsha)?
+                    }
+                };
+                state.serialize_field("special", &shabbos.special)?;
+                state.serialize_field("events", &shabbos.events)?;
+                if let Some(Some(t)) = self.candle_lighting {
+                    state.serialize_field("candleLighting", &t.to_rfc3339())?;
+                }
+                if !self.havdalah.is_empty() {
+                    state.serialize_field("havdalah", &self.havdalah)?;
+                }
+                if let Some(torah_portion) = &self.torah_portion {
+                    state.serialize_field("torahPortion", torah_portion)?;
+                }
+            }
+            Name::StudySession(session) => {
+                state.serialize_field("type", "StudySession")?;
+                let units: Vec<DayVal> = session
+                    .units
+                    .iter()
+                    .map(|x| DayVal {
+                        day: self.day,
+                        name: Name::DailyStudy(x.clone()),
+                        candle_lighting: None,
+                        torah_portion: None,
+                        havdalah: vec![],
+                        time_range: None,
+                    })
+                    .collect();
+                state.serialize_field("units", &units)?;
+            }
+            Name::Zman(zman) => {
+                state.serialize_field("type", "Zman")?;
+                state.serialize_field("name", &zman.json_name())?;
+                state.serialize_field("time", &zman.time.map(|x| x.to_rfc3339()))?;
+                if let Some(opinion) = zman.opinion {
+                    state.serialize_field("opinion", &opinion)?;
+                }
+            }
+        };
+        state.serialize_field("category", &self.name.category())?;
+        if let Some(time_range) = &self.time_range {
+            state.serialize_field("timeRange", time_range)?;
+        }
+        state.end()
+    }
+}
+
+/// A day as printed by `--print json`, before its name is put back together. Names printed in
+/// two languages with `--second-language` can't be read back.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayValJson {
+    day: DateTime<Utc>,
+    #[serde(rename = "type")]
+    kind: String,
+    name: Option<String>,
+    topic: Option<serde_json::Value>,
+    count: Option<u64>,
+    month: Option<HebrewMonth>,
+    end: Option<DateTime<Utc>>,
+    #[serde(default)]
+    special: Vec<SpecialShabbos>,
+    #[serde(default)]
+    events: Vec<DayVal>,
+    #[serde(default)]
+    nights: Vec<DayVal>,
+    #[serde(default)]
+    units: Vec<DayVal>,
+    candle_lighting: Option<String>,
+    #[serde(default)]
+    havdalah: Vec<Havdalah>,
+    torah_portion: Option<TorahPortionJson>,
+    time: Option<String>,
+    opinion: Option<HavdalahOpinion>,
+    time_range: Option<TimeRange>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TorahPortionJson {
+    triennial_year: Option<u8>,
+}
+
+#[derive(Deserialize)]
+struct MasechtaJson {
+    masechta: String,
+    daf: u8,
+}
+
+#[derive(Deserialize)]
+struct RambamJson {
+    halacha: String,
+    chapter: u8,
+}
+
+#[derive(Deserialize)]
+struct ChumashJson {
+    parshiyos: Vec<String>,
+    aliyah: u8,
+}
+
+/// Reads a name printed by serializing a unit variant, such as a `YomTov` or a `Parsha`.
+fn from_name<T: serde::de::DeserializeOwned>(name: &str) -> Option<T> {
+    use serde::de::IntoDeserializer;
+    T::deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(name)).ok()
+}
+
+impl DayValJson {
+    fn name_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, String> {
+        let name = self
+            .name
+            .as_deref()
+            .ok_or_else(|| format!("{} is missing its name", self.kind))?;
+        from_name(name).ok_or_else(|| format!("Unknown {}: {}", self.kind, name))
+    }
+
+    fn topic_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, String> {
+        let topic = self
+            .topic
+            .clone()
+            .ok_or_else(|| format!("{} is missing its topic", self.kind))?;
+        serde_json::from_value(topic).map_err(|e| format!("{}: {}", self.kind, e))
+    }
+
+    fn hebrew_date(&self) -> Result<HebrewDate, String> {
+        HebrewDate::try_from(self.day).map_err(|e| format!("{}: {}", self.day, e))
+    }
+
+    fn get_name(&self) -> Result<Name, String> {
+        Ok(match self.kind.as_str() {
+            // Special parshiyos are printed with the type of a Yom Tov.
+            "YomTov" => Name::TorahReading(
+                self.name_as()
+                    .map(TorahReading::YomTov)
+                    .or_else(|_| self.name_as().map(TorahReading::SpecialParsha))?,
+            ),
+            "Chol" => Name::TorahReading(TorahReading::Chol(self.name_as()?)),
+            "Shabbos" => Name::TorahReading(TorahReading::Shabbos(self.name_as()?)),
+            "MinorDays" => Name::MinorDays(self.name_as()?),
+            "CustomHoliday" => {
+                // Only the id is printed, so it's used as the printable name too.
+                let json: String = self.name_as()?;
+                let date = self.hebrew_date()?;
+                Name::CustomHoliday(Box::new(CustomHoliday {
+                    printable: json.clone(),
+                    json,
+                    date: CustomDate::Hebrew(DayMonth {
+                        month: date.month(),
+                        day: date.day(),
+                    }),
+                    if_not_exists: None,
+                    from_year: None,
+                    until_year: None,
+                    origin: None,
+                    count: self.count,
+                    modifier: None,
+                    source: None,
+                }))
+            }
+            "IsraeliHoliday" => Name::IsraeliHoliday(self.name_as()?),
+            "ChabadHoliday" => Name::ChabadHoliday(self.name_as()?),
+            "Agricultural" => Name::Agricultural(self.name_as()?),
+            "DaveningChange" => Name::DaveningChange(self.name_as()?),
+            "DafYomi" => Name::DailyStudy(DailyStudyOutput::Daf(Daf::from_json(self.topic_as()?)?)),
+            "DafHashavua" => Name::DailyStudy(DailyStudyOutput::DafHashavua(Daf::from_json(
+                self.topic_as()?,
+            )?)),
+            "Rambam3Chapters" => {
+                let chapters: Vec<RambamJson> = self.topic_as()?;
+                let mut chapters = chapters.into_iter().map(RambamChapter::from_json);
+                match (
+                    chapters.next(),
+                    chapters.next(),
+                    chapters.next(),
+                    chapters.next(),
+                ) {
+                    (Some(ch1), Some(ch2), Some(ch3), None) => Name::DailyStudy(
+                        DailyStudyOutput::RambamThreeChapters(RambamThreeChapter {
+                            ch1: ch1?,
+                            ch2: ch2?,
+                            ch3: ch3?,
+                        }),
+                    ),
+                    _ => return Err("Rambam3Chapters must have three chapters".into()),
+                }
+            }
+            "Rambam1Chapter" => Name::DailyStudy(DailyStudyOutput::RambamOneChapters(
+                RambamChapter::from_json(self.topic_as()?)?,
+            )),
+            "Yerushalmi" => Name::DailyStudy(DailyStudyOutput::YerushalmiYomi(
+                YerushalmiYomi::from_json(self.topic_as()?)?,
+            )),
+            "Chumash" => Name::DailyStudy(DailyStudyOutput::Chumash(Chumash::from_json(
+                self.topic_as()?,
+            )?)),
+            "ShabbosMevarchim" => {
+                let month = self
+                    .month
+                    .ok_or_else(|| "ShabbosMevarchim is missing its month".to_string())?;
+                let year =
+                    HebrewYear::new(self.hebrew_date()?.year()).map_err(|e| e.to_string())?;
+                Name::ShabbosMevarchim(
+                    ShabbosMevarchim::new(&year, month)
+                        .ok_or_else(|| format!("There is no {:?} in {}", month, year.year()))?,
+                )
+            }
+            "Span" => Name::Span(Span {
+                kind: self.name_as()?,
+                end: self
+                    .end
+                    .ok_or_else(|| "Span is missing its end".to_string())?,
+                nights: self.nights.clone(),
+            }),
+            "ShabbosSummary" => Name::Shabbos(ShabbosSummary {
+                reading: self
+                    .name_as()
+                    .map(TorahReading::Shabbos)
+                    .or_else(|_| self.name_as().map(TorahReading::YomTov))
+                    .or_else(|_| self.name_as().map(TorahReading::SpecialParsha))
+                    .or_else(|_| self.name_as().map(TorahReading::Chol))?,
+                special: self.special.clone(),
+                events: self.events.clone(),
+            }),
+            "Zman" => Name::Zman(ZmanTime {
+                time_of_day: match self.name.as_deref() {
+                    Some("CandleLighting") => TimeOfDay::CandleLighting,
+                    Some("Havdalah") => TimeOfDay::Havdalah,
+                    _ => TimeOfDay::Zman(self.name_as()?),
+                },
+                opinion: self.opinion,
+                time: self
+                    .time
+                    .as_deref()
+                    .map(DateTime::parse_from_rfc3339)
+                    .transpose()
+                    .map_err(|e| e.to_string())?,
+            }),
+            "StudySession" => Name::StudySession(StudySession {
+                units: self
+                    .units
+                    .iter()
+                    .map(|x| match &x.name {
+                        Name::DailyStudy(unit) => Ok(unit.clone()),
+                        _ => Err("StudySession can only have daily study".to_string()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            }),
+            kind => return Err(format!("Unknown type of event: {}", kind)),
+        })
+    }
+
+    fn into_day_val(self) -> Result<DayVal, String> {
+        let name = self.get_name()?;
+        let candle_lighting = match self.candle_lighting.as_deref() {
+            None => None,
+            Some("undefined") => Some(None),
+            Some(time) => Some(Some(
+                DateTime::parse_from_rfc3339(time).map_err(|e| e.to_string())?,
+            )),
+        };
+        let torah_portion = match (&self.torah_portion, &name) {
+            (None, _) => None,
+            (
+                Some(torah_portion),
+                Name::TorahReading(TorahReading::Shabbos(parsha))
+                | Name::Shabbos(ShabbosSummary {
+                    reading: TorahReading::Shabbos(parsha),
+                    ..
+                }),
+            ) => Some(TorahPortion::with_triennial_year(
+                *parsha,
+                torah_portion.triennial_year,
+            )),
+            (Some(_), _) => return Err(format!("{} has a Torah portion but no parsha", self.kind)),
+        };
+        Ok(DayVal {
+            day: self.day,
+            name,
+            candle_lighting,
+            torah_portion,
+            havdalah: self.havdalah,
+            time_range: self.time_range,
+        })
+    }
+}
+
+/// Reads back a day printed with `--print json`. Everything printed is kept, but custom holidays
+/// only print their id, which is read back as their name too.
+impl<'de> Deserialize<'de> for DayVal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        DayValJson::deserialize(deserializer)?
+            .into_day_val()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A time of the day listed as an event of its own, on the civil date it's on.
+#[derive(Debug, Clone)]
+pub struct ZmanTime {
+    pub time_of_day: TimeOfDay,
+    /// The opinion of a havdalah time, if several were asked for with `--havdalah-opinion`.
+    pub opinion: Option<HavdalahOpinion>,
+    /// `None` if it doesn't occur that day, which happens far from the equator.
+    pub time: Option<DateTime<FixedOffset>>,
+}
+
+impl ZmanTime {
+    /// The name printed in JSON, which is the name of the zman, `CandleLighting` or `Havdalah`.
+    fn json_name(&self) -> String {
+        match self.time_of_day {
+            TimeOfDay::Zman(zman) => format!("{:?}", zman),
+            time_of_day => format!("{:?}", time_of_day),
+        }
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        self.print(lock, language, false)
+    }
+
+    /// Prints the zman with its abbreviated name, for `list --short-names`.
+    pub fn short_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        self.print(lock, language, true)
+    }
+
+    fn print<W: Write>(&self, lock: &mut W, language: Language, short: bool) -> Option<usize> {
+        let name = match (self.time_of_day, language) {
+            (TimeOfDay::CandleLighting, Language::English) => "Candle lighting",
+            (TimeOfDay::CandleLighting, Language::Hebrew) => "הדלקת נרות",
+            (TimeOfDay::Havdalah, Language::English) => "Havdalah",
+            (TimeOfDay::Havdalah, Language::Hebrew) => "הבדלה",
+            (TimeOfDay::Zman(zman), language) => short
+                .then(|| crate::prelude::print::short_zman_name(zman, language))
+                .flatten()
+                .unwrap_or_else(|| crate::prelude::print::zman_name(zman, language)),
+        };
+        let mut p = lock.write(name.as_bytes()).ok()?;
+        p += lock.write(b" ").ok()?;
+        p += match (self.time, language) {
+            (Some(time), _) => lock
+                .write(format!("{}:{:02}", time.hour(), time.minute()).as_bytes())
+                .ok()?,
+            (None, Language::English) => lock.write(b"does not occur").ok()?,
+            (None, Language::Hebrew) => lock.write("לא חל".as_bytes()).ok()?,
+        };
+        if let Some(opinion) = self.opinion {
+            p += lock.write(b" (").ok()?;
+            p += opinion.pretty_print(lock, language)?;
+            p += lock.write(b")").ok()?;
+        }
+        Some(p)
+    }
+}
+
+/// A multi-day observance printed as a single event, used by `--merge-spans`.
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub kind: SpanKind,
+    /// The night the observance is over, so that consecutive days can be merged by
+    /// comparing this with the next day.
+    pub end: DateTime<Utc>,
+    /// The nights of Chanukah, as they're listed without `--merge-spans`, printed in JSON so
+    /// the detail of every night isn't lost. Empty for the other observances.
+    pub nights: Vec<DayVal>,
+}
+
+impl Span {
+    /// The month a merged Rosh Chodesh is of, which is the month its second day is in.
+    pub fn month(&self) -> HebrewMonth {
+        HebrewDate::try_from(self.end - chrono::Duration::days(1))
+            .unwrap()
+            .month()
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        self.print(lock, language, false)
+    }
+
+    /// Prints the span with the abbreviated name of its month, for `list --short-names`.
+    pub fn short_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        self.print(lock, language, true)
+    }
+
+    fn print<W: Write>(&self, lock: &mut W, language: Language, short: bool) -> Option<usize> {
+        let mut p = self.kind.pretty_print(lock, language)?;
+        if self.kind == SpanKind::RoshChodesh {
+            let name = short
+                .then(|| crate::prelude::print::short_month_name(self.month(), language))
+                .flatten();
+            let month = match language {
+                Language::English => format!(
+                    " {} (2 days)",
+                    name.unwrap_or_else(|| crate::prelude::print::hebrew_month_english(
+                        self.month()
+                    ))
+                ),
+                Language::Hebrew => format!(
+                    " {} (ב' ימים)",
+                    name.unwrap_or_else(|| crate::prelude::print::hebrew_month_hebrew(
+                        self.month()
+                    ))
+                ),
+            };
+            p += lock.write(month.as_bytes()).ok()?;
+        }
+        Some(p)
+    }
+}
+
+/// Everything on a single Shabbos, printed as one event by `--only-shabbos`.
+#[derive(Debug, Clone)]
+pub struct ShabbosSummary {
+    /// The parsha, or the Yom Tov reading if Shabbos is Yom Tov.
+    pub reading: TorahReading,
+    pub special: Vec<SpecialShabbos>,
+    /// The other events on Shabbos, such as a special parsha, Rosh Chodesh or Chanukah.
+    pub events: Vec<DayVal>,
+}
+
+impl ShabbosSummary {
+    pub fn pretty_print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+        transliteration: Transliteration,
+    ) -> Option<usize> {
+        self.print(lock, language, transliteration, false)
+    }
+
+    /// Prints everything on Shabbos with their abbreviated names, for `list --short-names`.
+    pub fn short_print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+        transliteration: Transliteration,
+    ) -> Option<usize> {
+        self.print(lock, language, transliteration, true)
+    }
+
+    fn print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+        transliteration: Transliteration,
+        short: bool,
+    ) -> Option<usize> {
+        let reading = short
+            .then(|| crate::prelude::print::short_torah_reading(self.reading, language))
+            .flatten()
+            .unwrap_or_else(|| crate::prelude::print::torah_reading(self.reading, language));
+        let mut written = lock.write(reading.as_bytes()).ok()?;
+        for special in &self.special {
+            let name = short
+                .then(|| crate::prelude::print::short_special_shabbos_name(*special, language))
+                .flatten()
+                .unwrap_or_else(|| crate::prelude::print::special_shabbos_name(*special, language));
+            written += lock.write(b"; ").ok()?;
+            written += lock.write(name.as_bytes()).ok()?;
+        }
+        for event in &self.events {
+            written += lock.write(b"; ").ok()?;
+            written += if short {
+                event.name.short_print(lock, language, transliteration)?
+            } else {
+                event.name.pretty_print(lock, language, transliteration)?
+            };
+        }
+        Some(written)
+    }
+}
+
+/// What to learn on one day of a plan, like `heca catchup` makes.
+#[derive(Debug, Clone)]
+pub struct StudySession {
+    pub units: Vec<DailyStudyOutput>,
+}
+
+impl StudySession {
+    /// Prints the units separated by commas. Dapim one after the other in the same masechta are
+    /// printed as a range, like "Kesubos 30-32".
+    pub fn pretty_print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+        transliteration: Transliteration,
+    ) -> Option<usize> {
+        let mut p = 0;
+        let mut units = self.units.iter().peekable();
+        while let Some(unit) = units.next() {
+            if p > 0 {
+                p += lock.write(b", ").ok()?;
+            }
+            p += Name::DailyStudy(unit.clone()).pretty_print(lock, language, transliteration)?;
+            if let DailyStudyOutput::Daf(first) = unit {
+                let mut last = first;
+                while let Some(DailyStudyOutput::Daf(next)) = units.peek() {
+                    if next.masechta != last.masechta || next.daf != last.daf + 1 {
+                        break;
+                    }
+                    last = next;
+                    units.next();
+                }
+                if last.daf != first.daf {
+                    p += lock.write(format!("-{}", last.daf()).as_bytes()).ok()?;
+                }
             }
+        }
+        Some(p)
+    }
+}
+
+/// A Shabbos named after its haftarah or its place in the year.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SpecialShabbos {
+    Shuva,
+    Shira,
+    HaGadol,
+    Chazon,
+    Nachamu,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SpanKind {
+    Pesach,
+    Sukkos,
+    Chanukah,
+    Omer,
+    /// The two days of Rosh Chodesh, merged by `--merge-rosh-chodesh` rather than `--merge-spans`.
+    RoshChodesh,
+}
+
+impl SpanKind {
+    /// Returns the observance a single day belongs to, if it is one that can be merged.
+    pub fn from_name(name: &Name) -> Option<SpanKind> {
+        match name {
+            Name::TorahReading(TorahReading::YomTov(yom_tov)) => match yom_tov {
+                YomTov::Pesach1
+                | YomTov::Pesach2
+                | YomTov::Pesach3
+                | YomTov::Pesach4
+                | YomTov::Pesach5
+                | YomTov::Pesach6
+                | YomTov::Pesach7
+                | YomTov::Pesach8 => Some(SpanKind::Pesach),
+                YomTov::Sukkos1
+                | YomTov::Sukkos2
+                | YomTov::Sukkos3
+                | YomTov::Sukkos4
+                | YomTov::Sukkos5
+                | YomTov::Sukkos6
+                | YomTov::Sukkos7 => Some(SpanKind::Sukkos),
+                _ => None,
+            },
+            Name::TorahReading(TorahReading::Chol(
+                Chol::Chanukah1
+                | Chol::Chanukah2
+                | Chol::Chanukah3
+                | Chol::Chanukah4
+                | Chol::Chanukah5
+                | Chol::Chanukah6
+                | Chol::Chanukah7
+                | Chol::Chanukah8,
+            )) => Some(SpanKind::Chanukah),
+            Name::MinorDays(day) if day.is_omer() => Some(SpanKind::Omer),
+            _ => None,
+        }
+    }
+
+    pub fn pretty_print<W: Write>(self, lock: &mut W, language: Language) -> Option<usize> {
+        let p = match language {
+            Language::English => match self {
+                SpanKind::Pesach => "Pesach",
+                SpanKind::Sukkos => "Sukkos",
+                SpanKind::Chanukah => "Chanukah",
+                SpanKind::Omer => "Sefiras HaOmer",
+                SpanKind::RoshChodesh => "Rosh Chodesh",
+            },
+            Language::Hebrew => match self {
+                SpanKind::Pesach => "חג הפסח",
+                SpanKind::Sukkos => "חג הסוכות",
+                SpanKind::Chanukah => "חנוכה",
+                SpanKind::Omer => "ספירת העומר",
+                SpanKind::RoshChodesh => "ראש חודש",
+            },
         };
-        state.end()
+        lock.write(p.as_bytes()).ok()
     }
 }
 
 #[derive(Debug, Clone)]
 pub enum DailyStudyOutput {
     Daf(Daf),
+    DafHashavua(Daf),
     RambamThreeChapters(RambamThreeChapter),
     RambamOneChapters(RambamChapter),
     YerushalmiYomi(YerushalmiYomi),
+    Chumash(Chumash),
 }
 
 #[derive(Debug, Clone)]
@@ -245,11 +1622,11 @@ impl RambamThreeChapter {
         Self { ch1, ch2, ch3 }
     }
 
-    pub fn pretty_print(
-        &self,
-        lock: &mut BufWriter<StdoutLock<'_>>,
-        language: Language,
-    ) -> Option<usize> {
+    pub fn chapters(&self) -> [&RambamChapter; 3] {
+        [&self.ch1, &self.ch2, &self.ch3]
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
         let mut sum = self.ch1.pretty_print(lock, language)?;
         sum += lock.write(b" - ").ok()?;
         sum += self.ch3.pretty_print(lock, language)?;
@@ -259,9 +1636,8 @@ impl RambamThreeChapter {
 
 #[derive(Debug, Clone)]
 pub struct RambamChapter {
-    halacha_english: &'static str,
-    halacha_json: &'static str,
-    halacha_hebrew: &'static str,
+    /// The index of the halacha in `RAMBAM`.
+    halacha: u8,
     chapter: u8,
 }
 
@@ -272,25 +1648,29 @@ impl Serialize for RambamChapter {
     {
         use crate::types::*;
         let mut state = serializer.serialize_struct("Day", 2)?;
-        state.serialize_field("halacha", &self.halacha_json)?;
+        state.serialize_field("halacha", RAMBAM[self.halacha as usize].2)?;
         state.serialize_field("chapter", &self.chapter)?;
         state.end()
     }
 }
 
 impl RambamChapter {
+    fn from_json(json: RambamJson) -> Result<Self, String> {
+        RAMBAM
+            .iter()
+            .position(|x| x.2 == json.halacha && (1..=x.3).contains(&json.chapter))
+            .map(|halacha| Self {
+                halacha: halacha as u8,
+                chapter: json.chapter,
+            })
+            .ok_or_else(|| format!("Unknown chapter: {} {}", json.halacha, json.chapter))
+    }
+
     pub fn from_days(day: u16) -> Self {
         let mut day = day;
         let mut index = 0;
-        let mut halacha_english;
-        let mut halacha_json;
-        let mut halacha_hebrew;
 
         let chapter = loop {
-            halacha_english = RAMBAM[index].0;
-            halacha_hebrew = RAMBAM[index].1;
-            halacha_json = RAMBAM[index].2;
-
             if day < (RAMBAM[index].3 as u16) {
                 break day as u8 + 1;
             } else {
@@ -299,22 +1679,26 @@ impl RambamChapter {
             }
         };
         Self {
-            halacha_english,
-            halacha_json,
-            halacha_hebrew,
+            halacha: index as u8,
             chapter,
         }
     }
 
-    pub fn pretty_print(
-        &self,
-        lock: &mut BufWriter<StdoutLock<'_>>,
-        language: Language,
-    ) -> Option<usize> {
+    /// The English name of the halacha the chapter is in, which is Sefaria's.
+    pub fn halacha_name(&self) -> &'static str {
+        RAMBAM[self.halacha as usize].0
+    }
+
+    pub fn chapter(&self) -> u8 {
+        self.chapter
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        let halacha = &RAMBAM[self.halacha as usize];
         let mut p = if language == Language::English {
-            lock.write(self.halacha_english.as_bytes()).ok()?
+            lock.write(halacha.0.as_bytes()).ok()?
         } else {
-            lock.write(self.halacha_hebrew.as_bytes()).ok()?
+            lock.write(halacha.1.as_bytes()).ok()?
         };
         p += lock.write(b" ").ok()?;
         let mut daf_arr = [b'\0'; 3];
@@ -326,25 +1710,28 @@ impl RambamChapter {
 
 #[derive(Debug, Clone)]
 pub struct YerushalmiYomi {
-    masechta_english: &'static str,
-    masechta_json: &'static str,
-    masechta_hebrew: &'static str,
+    /// The index of the masechta in `YERUSHALMI`.
+    masechta: u8,
     daf: u8,
 }
 
 impl YerushalmiYomi {
+    fn from_json(json: MasechtaJson) -> Result<Self, String> {
+        YERUSHALMI
+            .iter()
+            .position(|x| x.2 == json.masechta && (1..=x.3).contains(&json.daf))
+            .map(|masechta| Self {
+                masechta: masechta as u8,
+                daf: json.daf - 1,
+            })
+            .ok_or_else(|| format!("Unknown daf: {} {}", json.masechta, json.daf))
+    }
+
     pub fn from_days(day: u16) -> Self {
         let mut day = day;
         let mut index = 0;
-        let mut masechta_english;
-        let mut masechta_json;
-        let mut masechta_hebrew;
 
         let daf = loop {
-            masechta_english = YERUSHALMI[index].0;
-            masechta_hebrew = YERUSHALMI[index].1;
-            masechta_json = YERUSHALMI[index].2;
-
             if day < (YERUSHALMI[index].3 as u16) {
                 break day as u8;
             } else {
@@ -353,21 +1740,22 @@ impl YerushalmiYomi {
             }
         };
         Self {
-            masechta_english,
-            masechta_json,
-            masechta_hebrew,
+            masechta: index as u8,
             daf,
         }
     }
-    pub fn pretty_print(
-        &self,
-        lock: &mut BufWriter<StdoutLock<'_>>,
-        language: Language,
-    ) -> Option<usize> {
+
+    /// The English name of the masechta, like `Jerusalem Talmud Berakhot`.
+    pub fn masechta_name(&self) -> &'static str {
+        YERUSHALMI[self.masechta as usize].0
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        let masechta = &YERUSHALMI[self.masechta as usize];
         let mut p = if language == Language::English {
-            lock.write(self.masechta_english.as_bytes()).ok()?
+            lock.write(masechta.0.as_bytes()).ok()?
         } else {
-            lock.write(self.masechta_hebrew.as_bytes()).ok()?
+            lock.write(masechta.1.as_bytes()).ok()?
         };
         p += lock.write(b" ").ok()?;
         let mut daf_arr = [b'\0'; 3];
@@ -384,17 +1772,113 @@ impl Serialize for YerushalmiYomi {
     {
         use crate::types::*;
         let mut state = serializer.serialize_struct("Day", 2)?;
-        state.serialize_field("masechta", &self.masechta_json)?;
+        state.serialize_field("masechta", YERUSHALMI[self.masechta as usize].2)?;
         state.serialize_field("daf", &(self.daf + 1))?;
         state.end()
     }
 }
 
+/// The aliyah of the coming Shabbos' parsha learned on a given day, starting with Rishon on Sunday.
+///
+/// If the coming Shabbos is Yom Tov, the parsha of the next Shabbos that has one is learned, except
+/// for the days between Haazinu and Simchas Torah, when Vezos Haberachah is learned.
+#[derive(Debug, Clone)]
+pub struct Chumash {
+    parsha: Option<Parsha>,
+    aliyah: u8,
+}
+
+impl Chumash {
+    pub fn new(parsha: Option<Parsha>, aliyah: u8) -> Self {
+        Self { parsha, aliyah }
+    }
+
+    /// The parsha, or `None` for Vezos Haberachah.
+    pub fn parsha(&self) -> Option<Parsha> {
+        self.parsha
+    }
+
+    pub fn aliyah(&self) -> u8 {
+        self.aliyah
+    }
+
+    fn from_json(json: ChumashJson) -> Result<Self, String> {
+        let parsha =
+            if json.parshiyos == ["VezosHaberachah"] {
+                None
+            } else {
+                let parshiyos = json
+                    .parshiyos
+                    .iter()
+                    .map(|x| from_name(x).ok_or_else(|| format!("Unknown parsha: {}", x)))
+                    .collect::<Result<Vec<Parsha>, String>>()?;
+                Some(join_parshiyos(&parshiyos).ok_or_else(|| {
+                    format!("{} aren't read together", json.parshiyos.join(" and "))
+                })?)
+            };
+        if !(1..=7).contains(&json.aliyah) {
+            return Err(format!("Unknown aliyah: {}", json.aliyah));
+        }
+        Ok(Self::new(parsha, json.aliyah))
+    }
+
+    pub fn pretty_print<W: Write>(&self, lock: &mut W, language: Language) -> Option<usize> {
+        let mut p = match language {
+            Language::English => lock.write(b"Chumash: ").ok()?,
+            Language::Hebrew => lock.write("חומש: ".as_bytes()).ok()?,
+        };
+        p += match (self.parsha, language) {
+            (Some(parsha), language) => lock
+                .write(
+                    crate::prelude::print::torah_reading(TorahReading::Shabbos(parsha), language)
+                        .as_bytes(),
+                )
+                .ok()?,
+            (None, Language::English) => lock.write(b"Vezos Haberachah").ok()?,
+            (None, Language::Hebrew) => lock.write("וזאת הברכה".as_bytes()).ok()?,
+        };
+        p += lock.write(b" - ").ok()?;
+        p += lock
+            .write(aliyah_name(self.aliyah, language).as_bytes())
+            .ok()?;
+        Some(p)
+    }
+}
+
+/// The name of an aliyah, counted from 1 for Rishon.
+pub fn aliyah_name(aliyah: u8, language: Language) -> &'static str {
+    match language {
+        Language::English => [
+            "Rishon", "Sheni", "Shlishi", "Revi'i", "Chamishi", "Shishi", "Shevi'i",
+        ][aliyah as usize - 1],
+        Language::Hebrew => {
+            ["ראשון", "שני", "שלישי", "רביעי", "חמישי", "שישי", "שביעי"][aliyah as usize - 1]
+        }
+    }
+}
+
+impl Serialize for Chumash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Chumash", 2)?;
+        match self.parsha {
+            Some(parsha) => state.serialize_field("parshiyos", &split_parsha(parsha))?,
+            None => state.serialize_field("parshiyos", &["VezosHaberachah"])?,
+        };
+        state.serialize_field("aliyah", &self.aliyah)?;
+        state.end()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Daf {
-    masechta_english: &'static str,
-    masechta_json: &'static str,
-    masechta_hebrew: &'static str,
+    /// The masechtos of the cycle the daf is in, since some have a different amount of dapim
+    /// in each cycle.
+    gemaras: &'static Gemaras,
+    /// The index of the masechta in `gemaras` and in `MASECHTOS`.
+    masechta: u8,
     daf: u8,
 }
 
@@ -405,28 +1889,36 @@ impl Serialize for Daf {
     {
         use crate::types::*;
         let mut state = serializer.serialize_struct("day", 2)?;
-        state.serialize_field("masechta", &self.masechta_json)?;
+        state.serialize_field("masechta", self.gemaras[self.masechta as usize].2)?;
         state.serialize_field("daf", &(self.daf + 2))?;
         state.end()
     }
 }
 
 impl Daf {
-    pub fn from_days(
-        day: u16,
-        gemaras: &[(&'static str, &'static str, &'static str, u8); 37],
-    ) -> Self {
+    fn from_json(json: MasechtaJson) -> Result<Self, String> {
+        // Masechtos have the same place in the list in every cycle, but some have a different
+        // amount of dapim.
+        DAF_YOMI_ERAS
+            .iter()
+            .find_map(|(_, gemaras)| {
+                let index = gemaras
+                    .iter()
+                    .position(|x| x.2 == json.masechta && (2..=x.3).contains(&json.daf))?;
+                Some(Self {
+                    gemaras,
+                    masechta: index as u8,
+                    daf: json.daf - 2,
+                })
+            })
+            .ok_or_else(|| format!("Unknown daf: {} {}", json.masechta, json.daf))
+    }
+
+    pub fn from_days(day: u16, gemaras: &'static Gemaras) -> Self {
         let mut day = day;
         let mut index = 0;
-        let mut masechta_english;
-        let mut masechta_json;
-        let mut masechta_hebrew;
 
         let daf = loop {
-            masechta_english = gemaras[index].0;
-            masechta_hebrew = gemaras[index].1;
-            masechta_json = gemaras[index].2;
-
             if day < (gemaras[index].3 as u16 - 1) {
                 break day as u8;
             } else {
@@ -435,23 +1927,52 @@ impl Daf {
             }
         };
         Self {
-            masechta_english,
-            masechta_json,
-            masechta_hebrew,
+            gemaras,
+            masechta: index as u8,
             daf,
         }
     }
 
-    pub fn pretty_print(
+    /// The daf of the masechta, in the tables of the current Daf Yomi cycle.
+    pub fn new(masechta: u8, daf: u8) -> Self {
+        let gemaras = &GEMARAS_SECOND_CYCLE;
+        let before: u16 = gemaras[..masechta as usize]
+            .iter()
+            .map(|x| x.3 as u16 - 1)
+            .sum();
+        Self::from_days(before + daf as u16 - 2, gemaras)
+    }
+
+    pub fn masechta_ashkenazi(&self) -> &'static str {
+        MASECHTOS[self.masechta as usize].1
+    }
+
+    pub fn masechta_name(
+        &self,
+        language: Language,
+        transliteration: Transliteration,
+    ) -> &'static str {
+        let index = self.masechta as usize;
+        match (language, transliteration) {
+            (Language::Hebrew, _) | (_, Transliteration::Hebrew) => self.gemaras[index].1,
+            (Language::English, Transliteration::Sephardi) => self.gemaras[index].0,
+            (Language::English, Transliteration::Ashkenazi) => MASECHTOS[index].1,
+            (Language::English, Transliteration::Abbreviated) => MASECHTOS[index].2,
+        }
+    }
+
+    pub fn daf(&self) -> u8 {
+        self.daf + 2
+    }
+
+    pub fn pretty_print<W: Write>(
         &self,
-        lock: &mut BufWriter<StdoutLock<'_>>,
+        lock: &mut W,
         language: Language,
+        transliteration: Transliteration,
     ) -> Option<usize> {
-        let mut p = if language == Language::English {
-            lock.write(self.masechta_english.as_bytes()).ok()?
-        } else {
-            lock.write(self.masechta_hebrew.as_bytes()).ok()?
-        };
+        let masechta = self.masechta_name(language, transliteration);
+        let mut p = lock.write(masechta.as_bytes()).ok()?;
         p += lock.write(b" ").ok()?;
         let mut daf_arr = [b'\0'; 3];
         let count_y = itoa::write(&mut daf_arr[..], self.daf + 2).unwrap();
@@ -464,14 +1985,229 @@ impl Daf {
 pub enum Name {
     TorahReading(TorahReading),
     MinorDays(MinorDays),
-    CustomHoliday(CustomHoliday),
+    /// Boxed, since it's much bigger than the rest, and every day is as big as the biggest.
+    CustomHoliday(Box<CustomHoliday>),
     DailyStudy(DailyStudyOutput),
     IsraeliHoliday(IsraeliHoliday),
     ChabadHoliday(ChabadHoliday),
+    Agricultural(Agricultural),
+    DaveningChange(DaveningChange),
     ShabbosMevarchim(ShabbosMevarchim),
+    Span(Span),
+    Shabbos(ShabbosSummary),
+    Zman(ZmanTime),
+    StudySession(StudySession),
+}
+
+impl Name {
+    /// Whether the observance starts on the night of the Hebrew date. Minor fasts, the days
+    /// before a Yom Tov and daily study are observed during the day, so they don't.
+    pub fn starts_at_night(&self) -> bool {
+        if let Name::Agricultural(agricultural) = self {
+            return agricultural.starts_at_night();
+        }
+        !matches!(
+            self,
+            Name::TorahReading(TorahReading::Chol(
+                Chol::TzomGedalia | Chol::TenTeves | Chol::TaanisEsther | Chol::SeventeenTammuz
+            )) | Name::MinorDays(
+                MinorDays::TaanisBechoros
+                    | MinorDays::BiurChametz
+                    | MinorDays::ErevRoshHashanah
+                    | MinorDays::ErevYomKippur
+                    | MinorDays::ErevSukkos
+                    | MinorDays::ErevPesach
+                    | MinorDays::ErevShavuos
+            ) | Name::DailyStudy(_)
+                | Name::StudySession(_)
+                | Name::DaveningChange(_)
+                | Name::Zman(_)
+        )
+    }
+
+    /// Which event this is, from its type and name as they're printed in JSON. Daily study is
+    /// only identified by its type, so that correcting a topic doesn't make it a new event.
+    pub fn id(&self) -> String {
+        match self {
+            Name::TorahReading(TorahReading::YomTov(yom_tov)) => format!("YomTov {:?}", yom_tov),
+            Name::TorahReading(TorahReading::SpecialParsha(special_parsha)) => {
+                format!("YomTov {:?}", special_parsha)
+            }
+            Name::TorahReading(TorahReading::Chol(chol)) => format!("Chol {:?}", chol),
+            Name::TorahReading(TorahReading::Shabbos(parsha)) => format!("Shabbos {:?}", parsha),
+            Name::MinorDays(day) => format!("MinorDays {:?}", day),
+            Name::CustomHoliday(custom_holiday) => {
+                format!("CustomHoliday {}", custom_holiday.json)
+            }
+            Name::IsraeliHoliday(holiday) => format!("IsraeliHoliday {:?}", holiday),
+            Name::ChabadHoliday(holiday) => format!("ChabadHoliday {:?}", holiday),
+            Name::Agricultural(agricultural) => format!("Agricultural {:?}", agricultural),
+            Name::DaveningChange(change) => format!("DaveningChange {:?}", change),
+            Name::DailyStudy(daily_study) => match daily_study {
+                DailyStudyOutput::Daf(_) => "DafYomi",
+                DailyStudyOutput::DafHashavua(_) => "DafHashavua",
+                DailyStudyOutput::RambamThreeChapters(_) => "Rambam3Chapters",
+                DailyStudyOutput::RambamOneChapters(_) => "Rambam1Chapter",
+                DailyStudyOutput::YerushalmiYomi(_) => "Yerushalmi",
+                DailyStudyOutput::Chumash(_) => "Chumash",
+            }
+            .into(),
+            Name::ShabbosMevarchim(shabbos_mevarchim) => {
+                format!("ShabbosMevarchim {:?}", shabbos_mevarchim.hebrew_month)
+            }
+            Name::Span(span) => format!("Span {:?}", span.kind),
+            Name::Shabbos(_) => "ShabbosSummary".into(),
+            Name::StudySession(_) => "StudySession".into(),
+            Name::Zman(zman) => match zman.opinion {
+                Some(opinion) => format!("Zman {} {:?}", zman.json_name(), opinion),
+                None => format!("Zman {}", zman.json_name()),
+            },
+        }
+    }
+
+    pub fn category(&self) -> Category {
+        match self {
+            Name::TorahReading(TorahReading::YomTov(_)) => Category::YomTov,
+            Name::TorahReading(TorahReading::Chol(chol)) => match chol {
+                Chol::TzomGedalia
+                | Chol::TenTeves
+                | Chol::TaanisEsther
+                | Chol::SeventeenTammuz
+                | Chol::NineAv => Category::Fast,
+                Chol::Chanukah1
+                | Chol::Chanukah2
+                | Chol::Chanukah3
+                | Chol::Chanukah4
+                | Chol::Chanukah5
+                | Chol::Chanukah6
+                | Chol::Chanukah7
+                | Chol::Chanukah8
+                | Chol::Purim
+                | Chol::ShushanPurim => Category::Chol,
+                _ => Category::RoshChodesh,
+            },
+            Name::TorahReading(TorahReading::Shabbos(_)) => Category::Parsha,
+            Name::TorahReading(TorahReading::SpecialParsha(_)) => Category::SpecialParsha,
+            Name::MinorDays(MinorDays::TaanisBechoros) => Category::Fast,
+            Name::MinorDays(_) => Category::Minor,
+            Name::CustomHoliday(_) => Category::Custom,
+            Name::DailyStudy(_) | Name::StudySession(_) => Category::DailyStudy,
+            Name::Agricultural(_) => Category::Agricultural,
+            Name::DaveningChange(_) => Category::DaveningChanges,
+            Name::IsraeliHoliday(_) | Name::ChabadHoliday(_) | Name::ShabbosMevarchim(_) => {
+                Category::Minor
+            }
+            Name::Span(span) => match span.kind {
+                SpanKind::Pesach | SpanKind::Sukkos => Category::YomTov,
+                SpanKind::Chanukah => Category::Chol,
+                SpanKind::RoshChodesh => Category::RoshChodesh,
+                SpanKind::Omer => Category::Minor,
+            },
+            Name::Shabbos(_) => Category::Parsha,
+            Name::Zman(_) => Category::Zman,
+        }
+    }
+
+    /// The emoji printed before the name with `--icons`, if its category has one.
+    pub fn icon(&self) -> Option<&'static str> {
+        match self.category() {
+            Category::YomTov => Some("🎉"),
+            Category::DailyStudy => Some("📖"),
+            _ => None,
+        }
+    }
+
+    /// Prints the name abbreviated, for `list --short-names`. Names without an abbreviation in
+    /// the language are printed in full.
+    pub fn short_print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+        transliteration: Transliteration,
+    ) -> Option<usize> {
+        let short = match self {
+            Name::TorahReading(name) => crate::prelude::print::short_torah_reading(*name, language),
+            Name::MinorDays(day) => {
+                crate::prelude::print::short_minor_holidays(day.clone(), language)
+            }
+            Name::DailyStudy(DailyStudyOutput::Daf(d)) if language == Language::English => {
+                return d.pretty_print(lock, language, Transliteration::Abbreviated);
+            }
+            Name::DaveningChange(change) => {
+                crate::prelude::print::short_davening_change_name(*change, language)
+            }
+            Name::Zman(zman) => return zman.short_print(lock, language),
+            Name::Span(span) => return span.short_print(lock, language),
+            Name::Shabbos(shabbos) => return shabbos.short_print(lock, language, transliteration),
+            _ => None,
+        };
+        match short {
+            Some(short) => lock.write(short.as_bytes()).ok(),
+            None => self.pretty_print(lock, language, transliteration),
+        }
+    }
+
+    pub fn pretty_print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+        transliteration: Transliteration,
+    ) -> Option<usize> {
+        match self {
+            Name::TorahReading(name) => lock
+                .write(crate::prelude::print::torah_reading(*name, language).as_bytes())
+                .ok(),
+            Name::MinorDays(day) => lock
+                .write(crate::prelude::print::minor_holidays(day.clone(), language).as_bytes())
+                .ok(),
+            Name::CustomHoliday(custom_holiday) => custom_holiday.pretty_print(lock, language),
+            Name::DailyStudy(daily_study) => match daily_study {
+                DailyStudyOutput::Daf(d) => d.pretty_print(lock, language, transliteration),
+                DailyStudyOutput::DafHashavua(d) => {
+                    let p = match language {
+                        Language::English => lock.write(b"Daf Hashavua: ").ok()?,
+                        Language::Hebrew => lock.write("דף השבוע: ".as_bytes()).ok()?,
+                    };
+                    Some(p + d.pretty_print(lock, language, transliteration)?)
+                }
+                DailyStudyOutput::RambamThreeChapters(three_chapter) => {
+                    three_chapter.pretty_print(lock, language)
+                }
+                DailyStudyOutput::RambamOneChapters(one_chapter) => {
+                    one_chapter.pretty_print(lock, language)
+                }
+                DailyStudyOutput::YerushalmiYomi(yerushalmi_yomi) => {
+                    yerushalmi_yomi.pretty_print(lock, language)
+                }
+                DailyStudyOutput::Chumash(chumash) => chumash.pretty_print(lock, language),
+            },
+            Name::IsraeliHoliday(israeli_holidays) => israeli_holidays.pretty_print(lock, language),
+            Name::ChabadHoliday(chabad_holidays) => chabad_holidays.pretty_print(lock, language),
+            Name::Agricultural(agricultural) => agricultural.pretty_print(lock, language),
+            Name::DaveningChange(change) => lock
+                .write(crate::prelude::print::davening_change_name(*change, language).as_bytes())
+                .ok(),
+            Name::ShabbosMevarchim(shabbos_mevarchim) => {
+                shabbos_mevarchim.pretty_print(lock, language)
+            }
+            Name::Span(span) => span.pretty_print(lock, language),
+            Name::Shabbos(shabbos) => shabbos.pretty_print(lock, language, transliteration),
+            Name::Zman(zman) => zman.pretty_print(lock, language),
+            Name::StudySession(session) => session.pretty_print(lock, language, transliteration),
+        }
+    }
+}
+
+/// A change to the daily prayers, said in Musaf of the day it's on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DaveningChange {
+    /// On Shmini Atzeres, from when Mashiv HaRuach is said.
+    TefillasGeshem,
+    /// On the first day of Pesach, from when Mashiv HaRuach isn't said anymore.
+    TefillasTal,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MinorDays {
     Omer1,
     Omer2,
@@ -535,16 +2271,98 @@ pub enum MinorDays {
     ShushanPurimKattan,
     ShabbosHaGadol,
     TaanisBechoros,
+    /// The night chametz is searched for, the night before Erev Pesach or Thursday night.
+    BedikasChametz,
+    /// The day chametz is burnt, Erev Pesach or Friday.
+    BiurChametz,
     ShabbosChazon,
     ShabbosNachamu,
     LeilSlichos,
     ShabbosShuva,
+    AvosUbanim,
+}
+
+impl MinorDays {
+    pub fn is_omer(&self) -> bool {
+        matches!(
+            self,
+            MinorDays::Omer1
+                | MinorDays::Omer2
+                | MinorDays::Omer3
+                | MinorDays::Omer4
+                | MinorDays::Omer5
+                | MinorDays::Omer6
+                | MinorDays::Omer7
+                | MinorDays::Omer8
+                | MinorDays::Omer9
+                | MinorDays::Omer10
+                | MinorDays::Omer11
+                | MinorDays::Omer12
+                | MinorDays::Omer13
+                | MinorDays::Omer14
+                | MinorDays::Omer15
+                | MinorDays::Omer16
+                | MinorDays::Omer17
+                | MinorDays::Omer18
+                | MinorDays::Omer19
+                | MinorDays::Omer20
+                | MinorDays::Omer21
+                | MinorDays::Omer22
+                | MinorDays::Omer23
+                | MinorDays::Omer24
+                | MinorDays::Omer25
+                | MinorDays::Omer26
+                | MinorDays::Omer27
+                | MinorDays::Omer28
+                | MinorDays::Omer29
+                | MinorDays::Omer30
+                | MinorDays::Omer31
+                | MinorDays::Omer32
+                | MinorDays::Omer33
+                | MinorDays::Omer34
+                | MinorDays::Omer35
+                | MinorDays::Omer36
+                | MinorDays::Omer37
+                | MinorDays::Omer38
+                | MinorDays::Omer39
+                | MinorDays::Omer40
+                | MinorDays::Omer41
+                | MinorDays::Omer42
+                | MinorDays::Omer43
+                | MinorDays::Omer44
+                | MinorDays::Omer45
+                | MinorDays::Omer46
+                | MinorDays::Omer47
+                | MinorDays::Omer48
+                | MinorDays::Omer49
+        )
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
 pub enum MinorHoliday {
     Omer,
     Minor,
+    AvosUbanim,
+}
+
+/// A coarse grouping of every event, emitted as `category` in JSON output and used by
+/// `--category`/`--exclude-category`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    YomTov,
+    Chol,
+    Fast,
+    RoshChodesh,
+    Parsha,
+    SpecialParsha,
+    Minor,
+    Custom,
+    DailyStudy,
+    Agricultural,
+    DaveningChanges,
+    Zman,
 }
 
 type Month = u32;
@@ -560,6 +2378,15 @@ pub enum AppError {
     ArgUndefinedError(String),
     DayIsNotAValidNumber(String),
     YearIsNotANumber(String),
+    InvalidYearRange(String),
+    MissingCustomHoliday(String),
+    SelfTestFailed(String),
+    AnchorMismatch(String),
+    YearOutOfRange {
+        min: i64,
+        max: i64,
+        got: i64,
+    },
     MonthNotParsed(String),
     CannotParseMonth(String),
     CannotParseDay(String),
@@ -568,8 +2395,25 @@ pub enum AppError {
     SplitDateError,
     ConfigError(String),
     ReadError(String),
+    WriteError(String),
     TypeError(String),
     CityNotFound(String),
+    CannotParseCoordinates(String),
+    InvalidTimeZone(String),
+    AddressNotFound(String),
+    UnknownEvent {
+        event: String,
+        from: u64,
+        until: u64,
+    },
+    DayNotInMonth {
+        year: u64,
+        month: HebrewMonth,
+        day: i8,
+        days: i8,
+    },
+    BadRequest(String),
+    ConflictingArgs(String),
 }
 
 use clap::ErrorKind;
@@ -579,7 +2423,32 @@ impl Serialize for AppError {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("AppError", 2)?;
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        self.serialize_fields(&mut state)?;
+        state.end()
+    }
+}
+
+/// An error with its message in a language, which is printed to stderr with `--print json`.
+pub struct LocalizedError<'a>(pub &'a AppError, pub Language);
+
+impl Serialize for LocalizedError<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let LocalizedError(error, language) = self;
+        let mut state = serializer.serialize_struct("AppError", 4)?;
+        error.serialize_fields(&mut state)?;
+        state.serialize_field("message", &error.message(*language))?;
+        state.end()
+    }
+}
+
+impl AppError {
+    /// Writes the `type` of the error, its `error` (the detail, like the date which isn't valid)
+    /// and its `code`.
+    fn serialize_fields<S: SerializeStruct>(&self, state: &mut S) -> Result<(), S::Error> {
         match self {
             AppError::DateSyntaxError(err) => {
                 state.serialize_field("type", "DateSyntaxError")?;
@@ -593,6 +2462,10 @@ impl Serialize for AppError {
                 state.serialize_field("type", "ReadError")?;
                 state.serialize_field("error", err)?;
             }
+            AppError::WriteError(err) => {
+                state.serialize_field("type", "WriteError")?;
+                state.serialize_field("error", err)?;
+            }
             AppError::SplitDateError => {
                 state.serialize_field("type", "SplitDateError")?;
             }
@@ -624,6 +2497,29 @@ impl Serialize for AppError {
                 state.serialize_field("type", "YearIsNotANumber")?;
                 state.serialize_field("error", year)?;
             }
+            AppError::InvalidYearRange(err) => {
+                state.serialize_field("type", "InvalidYearRange")?;
+                state.serialize_field("error", err)?;
+            }
+            AppError::MissingCustomHoliday(err) => {
+                state.serialize_field("type", "MissingCustomHoliday")?;
+                state.serialize_field("error", err)?;
+            }
+            AppError::SelfTestFailed(err) => {
+                state.serialize_field("type", "SelfTestFailed")?;
+                state.serialize_field("error", err)?;
+            }
+            AppError::AnchorMismatch(err) => {
+                state.serialize_field("type", "AnchorMismatch")?;
+                state.serialize_field("error", err)?;
+            }
+            AppError::YearOutOfRange { min, max, got } => {
+                state.serialize_field("type", "YearOutOfRange")?;
+                state.serialize_field("error", &self.to_string())?;
+                state.serialize_field("min", min)?;
+                state.serialize_field("max", max)?;
+                state.serialize_field("got", got)?;
+            }
             AppError::DayIsNotAValidNumber(day) => {
                 state.serialize_field("type", "DayIsNotAValidNumber")?;
                 state.serialize_field("error", day)?;
@@ -682,17 +2578,56 @@ impl Serialize for AppError {
                 state.serialize_field("type", "CityNotFoundError")?;
                 state.serialize_field("error", e)?;
             }
+            AppError::CannotParseCoordinates(e) => {
+                state.serialize_field("type", "CannotParseCoordinates")?;
+                state.serialize_field("error", e)?;
+            }
+            AppError::InvalidTimeZone(e) => {
+                state.serialize_field("type", "InvalidTimeZone")?;
+                state.serialize_field("error", e)?;
+            }
+            AppError::AddressNotFound(e) => {
+                state.serialize_field("type", "AddressNotFound")?;
+                state.serialize_field("error", e)?;
+            }
+            AppError::UnknownEvent { event, .. } => {
+                state.serialize_field("type", "UnknownEvent")?;
+                state.serialize_field("error", event)?;
+            }
+            AppError::DayNotInMonth {
+                year,
+                month,
+                day,
+                days,
+            } => {
+                state.serialize_field("type", "DayNotInMonth")?;
+                state.serialize_field("error", &self.to_string())?;
+                state.serialize_field("year", year)?;
+                state.serialize_field("month", month)?;
+                state.serialize_field("day", day)?;
+                state.serialize_field("days", days)?;
+            }
+            AppError::BadRequest(e) => {
+                state.serialize_field("type", "BadRequest")?;
+                state.serialize_field("error", e)?;
+            }
+            AppError::ConflictingArgs(e) => {
+                state.serialize_field("type", "ConflictingArgs")?;
+                state.serialize_field("error", e)?;
+            }
         };
-        state.end()
+        state.serialize_field("code", self.code())
     }
 }
 
 use crate::algorithms::candle_lighting::City;
 use crate::algorithms::shabbos_mevarchim::ShabbosMevarchim;
-use crate::prelude::constants::{RAMBAM, YERUSHALMI};
-use std::collections::HashMap;
+use crate::prelude::constants::{
+    Gemaras, DAF_YOMI_ERAS, GEMARAS_SECOND_CYCLE, MASECHTOS, RAMBAM, YERUSHALMI,
+};
+use crate::prelude::{hebrew_month_english, hebrew_month_hebrew, Warnings};
 use std::fmt;
-use std::io::{BufWriter, StdoutLock, Write};
+use std::io::Write;
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -704,10 +2639,11 @@ impl fmt::Display for AppError {
             ),
             AppError::TypeError(err) => write!(
                 f,
-                r#"Cannot understand output format: {}. Options are ["regular", "pretty", "json"]"#,
+                r#"Cannot understand output format: {}. Options are ["regular", "pretty", "json", "ics", "cbor", "msgpack"]"#,
                 err
             ),
             AppError::ReadError(err) => write!(f, "Read error: {}", err),
+            AppError::WriteError(err) => write!(f, "Write error: {}", err),
             AppError::SplitDateError => write!(
                 f,
                 "Cannot split the date. Deliminators are: '-', '/', '_', '\\', '.', ',', '=']"
@@ -725,6 +2661,15 @@ impl fmt::Display for AppError {
             AppError::YearIsNotANumber(year) => {
                 write!(f, "{} is not a valid year as it's not a number", year)
             }
+            AppError::InvalidYearRange(err) => write!(f, "Invalid range of years: {}", err),
+            AppError::MissingCustomHoliday(err) => write!(f, "{}", err),
+            AppError::SelfTestFailed(err) => write!(f, "Self-test failed for {}", err),
+            AppError::AnchorMismatch(err) => write!(f, "{}", err),
+            AppError::YearOutOfRange { min, max, got } => write!(
+                f,
+                "Year {} is out of range. Supported years are {} to {} (see `heca limits`)",
+                got, min, max
+            ),
             AppError::DayIsNotAValidNumber(day) => write!(
                 f,
                 "{} is not a valid day as it's not a number larger than 0",
@@ -751,6 +2696,189 @@ impl fmt::Display for AppError {
                     e, list_of_city_names
                 )
             }
+            AppError::CannotParseCoordinates(e) => write!(
+                f,
+                "Cannot parse coordinates {}. Pass the latitude and the longitude, like \"40.69,-73.99\" or \"40°41'N 73°59'W\"",
+                e
+            ),
+            AppError::InvalidTimeZone(e) => write!(
+                f,
+                "{} is not a time zone. Pass a name from the tz database, like \"America/New_York\"",
+                e
+            ),
+            AppError::AddressNotFound(e) => write!(
+                f,
+                "Could not find address {}. Pass --coordinates instead, or set a geocoder in the config file",
+                e
+            ),
+            AppError::UnknownEvent { event, from, until } => write!(
+                f,
+                "There's no event called {} from {} until {}",
+                event, from, until
+            ),
+            AppError::DayNotInMonth {
+                year,
+                month,
+                day,
+                days,
+            } => write!(
+                f,
+                "{} {} has {} days, so there's no {} {} {}",
+                hebrew_month_english(*month),
+                year,
+                days,
+                day,
+                hebrew_month_english(*month),
+                year
+            ),
+            AppError::BadRequest(e) => write!(f, "Cannot understand the request: {}", e),
+            AppError::ConflictingArgs(e) => write!(f, "Conflicting arguments: {}", e),
+        }
+    }
+}
+
+impl AppError {
+    /// The code of the error, like `E208` for an invalid Gregorian date, which doesn't change
+    /// between versions or languages, so programs can tell errors apart without reading the
+    /// message. Arguments are E1xx, dates and years E2xx, events E3xx, files E4xx, places E5xx
+    /// and checks E6xx.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::ArgError(_) => "E100",
+            AppError::ArgUndefinedError(_) => "E101",
+            AppError::TypeError(_) => "E102",
+            AppError::LocationError(_) => "E103",
+            AppError::BadRequest(_) => "E104",
+            AppError::ConflictingArgs(_) => "E105",
+            AppError::DateSyntaxError(_) => "E200",
+            AppError::SplitDateError => "E201",
+            AppError::MonthNotParsed(_) => "E202",
+            AppError::CannotParseMonth(_) => "E203",
+            AppError::CannotParseDay(_) => "E204",
+            AppError::CannotParseYear(_) => "E205",
+            AppError::DayIsNotAValidNumber(_) => "E206",
+            AppError::YearIsNotANumber(_) => "E207",
+            AppError::InvalidGregorianDate(..) => "E208",
+            AppError::ConversionError(_) => "E209",
+            AppError::InvalidYearRange(_) => "E210",
+            AppError::YearOutOfRange { .. } => "E211",
+            AppError::DayNotInMonth { .. } => "E212",
+            AppError::UnknownEvent { .. } => "E300",
+            AppError::MissingCustomHoliday(_) => "E301",
+            AppError::ConfigError(_) => "E400",
+            AppError::ReadError(_) => "E401",
+            AppError::WriteError(_) => "E402",
+            AppError::CityNotFound(_) => "E500",
+            AppError::CannotParseCoordinates(_) => "E501",
+            AppError::InvalidTimeZone(_) => "E502",
+            AppError::AddressNotFound(_) => "E503",
+            AppError::SelfTestFailed(_) => "E600",
+            AppError::AnchorMismatch(_) => "E601",
+        }
+    }
+
+    /// The message of the error in `language`. Details which come from elsewhere, like the
+    /// messages of clap and of the config file's parser, are left in English.
+    pub fn message(&self, language: Language) -> String {
+        if language == Language::English {
+            return self.to_string();
+        }
+        match self {
+            AppError::ArgError(err) => err.to_string(),
+            AppError::ArgUndefinedError(err) => format!("שגיאה בארגומנטים: {}", err),
+            AppError::TypeError(err) => format!(
+                "סוג הפלט {} לא מוכר. האפשרויות הן regular, pretty, json, ics, cbor ו-msgpack",
+                err
+            ),
+            AppError::LocationError(location) => {
+                format!("{} אינו מיקום. המיקום צריך להיות Chul או Israel", location)
+            }
+            AppError::DateSyntaxError(date) => format!(
+                "אי אפשר לקרוא את התאריך {} בקובץ ההגדרות. התאריך צריך להיות בצורה YYYY/MM/DD|Name",
+                date
+            ),
+            AppError::SplitDateError => {
+                "אי אפשר לחלק את התאריך. המפרידים הם '-', '/', '_', '\\', '.', ',' ו-'='".into()
+            }
+            AppError::MonthNotParsed(month) => format!("{} אינו חודש", month),
+            AppError::CannotParseMonth(month) => format!("אי אפשר לקרוא את החודש {}", month),
+            AppError::CannotParseDay(day) => format!("אי אפשר לקרוא את היום {}", day),
+            AppError::CannotParseYear(year) => format!("אי אפשר לקרוא את השנה {}", year),
+            AppError::DayIsNotAValidNumber(day) => {
+                format!("{} אינו יום תקין, כי הוא אינו מספר גדול מ-0", day)
+            }
+            AppError::YearIsNotANumber(year) => {
+                format!("{} אינה שנה תקינה, כי היא אינה מספר", year)
+            }
+            AppError::InvalidGregorianDate(year, month, day) => format!(
+                "{}/{}/{} (שנה/חודש/יום) אינו תאריך לועזי תקין",
+                year, month, day
+            ),
+            AppError::ConversionError(err) => match err {
+                ConversionError::IsNotLeapYear => {
+                    "אין אדר א' ואדר ב' בשנה שאינה מעוברת".into()
+                }
+                ConversionError::TooManyDaysInMonth(days) => {
+                    format!("אין יום כזה בחודש. בחודש יש רק {} ימים", days)
+                }
+                ConversionError::IsLeapYear => {
+                    "בשנה מעוברת אין אדר סתם. יש לבחור אדר א' או אדר ב'".into()
+                }
+                ConversionError::YearTooSmall => {
+                    "אי אפשר לחשב את הלוח לשנים שלפני 3764 לבריאת העולם".into()
+                }
+            },
+            AppError::InvalidYearRange(err) => format!("טווח השנים אינו תקין: {}", err),
+            AppError::YearOutOfRange { min, max, got } => format!(
+                "השנה {} מחוץ לטווח. השנים הנתמכות הן {} עד {} (ראו `heca limits`)",
+                got, min, max
+            ),
+            AppError::UnknownEvent { event, from, until } => {
+                format!("אין אירוע בשם {} מ-{} עד {}", event, from, until)
+            }
+            AppError::DayNotInMonth {
+                year,
+                month,
+                day,
+                days,
+            } => format!(
+                "ב{} {} יש {} ימים, ולכן אין יום {} ב{} {}",
+                hebrew_month_hebrew(*month),
+                year,
+                days,
+                day,
+                hebrew_month_hebrew(*month),
+                year
+            ),
+            AppError::MissingCustomHoliday(err) => format!("חג מותאם אישית חסר: {}", err),
+            AppError::ConfigError(err) => format!("שגיאה בקובץ ההגדרות: {}", err),
+            AppError::ReadError(err) => format!("שגיאה בקריאה: {}", err),
+            AppError::WriteError(err) => format!("שגיאה בכתיבה: {}", err),
+            AppError::CityNotFound(city) => {
+                let mut cities: Vec<_> = CITIES.iter().map(|x| &*x.name).collect();
+                cities.sort_unstable();
+                format!(
+                    "העיר {} לא נמצאה. הערים האפשריות הן:\n{}",
+                    city,
+                    cities.join("\n")
+                )
+            }
+            AppError::CannotParseCoordinates(coordinates) => format!(
+                "אי אפשר לקרוא את הקואורדינטות {}. יש לתת קו רוחב וקו אורך, כמו \"40.69,-73.99\" או \"40°41'N 73°59'W\"",
+                coordinates
+            ),
+            AppError::InvalidTimeZone(time_zone) => format!(
+                "{} אינו אזור זמן. יש לתת שם ממסד הנתונים tz, כמו \"Asia/Jerusalem\"",
+                time_zone
+            ),
+            AppError::AddressNotFound(address) => format!(
+                "הכתובת {} לא נמצאה. אפשר לתת --coordinates במקום, או להגדיר geocoder בקובץ ההגדרות",
+                address
+            ),
+            AppError::SelfTestFailed(err) => format!("הבדיקה העצמית נכשלה ב-{}", err),
+            AppError::AnchorMismatch(err) => format!("שגיאה בנקודות העוגן: {}", err),
+            AppError::BadRequest(err) => format!("אי אפשר להבין את הבקשה: {}", err),
+            AppError::ConflictingArgs(err) => format!("ארגומנטים סותרים: {}", err),
         }
     }
 }
@@ -0,0 +1,41 @@
+use crate::args::location::get_city;
+use crate::args::prelude::{get_polar_fallback, get_solar_algorithm, Config};
+use crate::args::types::{AppError, Command, TableFormat, ZmanimArgs, ZMANIM};
+use clap::ArgMatches;
+
+pub fn parse_options(matches: &ArgMatches<'_>, config: &Config) -> Result<Command, AppError> {
+    let year = matches.value_of("Year").unwrap();
+    let year = year
+        .parse()
+        .map_err(|_| AppError::YearIsNotANumber(year.into()))?;
+    let city = get_city(matches, config)?.ok_or_else(|| {
+        AppError::ArgUndefinedError(
+            "zmanim needs a city. Pass --city, --coordinates or --address, or set HECA_CITY or default-city in the \
+             config file"
+                .into(),
+        )
+    })?;
+    let zmanim = match matches.values_of("Zmanim") {
+        Some(zmanim) => zmanim
+            .map(|name| ZMANIM.iter().find(|(x, _)| *x == name).unwrap().1)
+            .collect(),
+        None => ZMANIM.iter().map(|(_, zman)| *zman).collect(),
+    };
+    let table = if matches.occurrences_of("Table") > 0 {
+        match matches.value_of("Table") {
+            Some("csv") => Some(TableFormat::Csv),
+            Some("pretty") | None => Some(TableFormat::Pretty),
+            Some(x) => unreachable!("{}", x),
+        }
+    } else {
+        None
+    };
+    Ok(Command::Zmanim(ZmanimArgs {
+        year,
+        city,
+        zmanim,
+        table,
+        polar_fallback: get_polar_fallback(matches),
+        solar_algorithm: get_solar_algorithm(matches),
+    }))
+}
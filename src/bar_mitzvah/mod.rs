@@ -0,0 +1,423 @@
+use crate::algorithms::leining::{self, Verses};
+use crate::algorithms::reading_cycle::split_parsha;
+use crate::args::types::{
+    aliyah_name, AppError, BarMitzvahArgs, DayVal, Language, MainArgs, Name, OutputType,
+    PolarFallback, SolarAlgorithm, SpecialShabbos,
+};
+use crate::list::providers::TorahReadings;
+use crate::list::{get_events_between, write_date};
+use crate::prelude::print::{special_shabbos_name, torah_reading};
+use crate::prelude::{
+    events_on, night_of, print_output, write_data, write_error, CountingWriter, Printable, Runnable,
+};
+use crate::year_info::civil_date;
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::*;
+use heca_lib::HebrewDate;
+use serde::{Serialize, Serializer};
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// Why the maftir or the haftarah isn't the parsha's.
+#[derive(Debug, Clone, Copy)]
+enum Occasion {
+    /// A special parsha, Rosh Chodesh or Chanukah.
+    Reading(TorahReading),
+    Shabbos(SpecialShabbos),
+    /// The day after Shabbos is Rosh Chodesh.
+    MacharChodesh,
+}
+
+impl Occasion {
+    fn name(self, language: Language) -> &'static str {
+        match (self, language) {
+            (Occasion::Reading(reading), language) => torah_reading(reading, language),
+            (Occasion::Shabbos(special), language) => special_shabbos_name(special, language),
+            (Occasion::MacharChodesh, Language::English) => "Machar Chodesh",
+            (Occasion::MacharChodesh, Language::Hebrew) => "מחר חודש",
+        }
+    }
+}
+
+impl Serialize for Occasion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Occasion::Reading(TorahReading::SpecialParsha(special)) => {
+                special.serialize(serializer)
+            }
+            Occasion::Reading(TorahReading::Chol(chol)) => chol.serialize(serializer),
+            Occasion::Reading(_) => unreachable!(),
+            Occasion::Shabbos(special) => special.serialize(serializer),
+            Occasion::MacharChodesh => serializer.serialize_str("MacharChodesh"),
+        }
+    }
+}
+
+/// The reading of the parsha a bar mitzvah prepares.
+struct Leining {
+    parsha: Parsha,
+    aliyos: Option<Vec<Verses>>,
+    maftir: Verses,
+    maftir_occasion: Option<Occasion>,
+    haftarah: Vec<Verses>,
+    haftarah_occasion: Option<Occasion>,
+    /// The last Shabbos before the bar mitzvah on which each of the parshiyos was read.
+    practice: Vec<(Parsha, NaiveDate)>,
+}
+
+/// The output of `heca bar-mitzvah`.
+struct BarMitzvah {
+    shabbos: NaiveDate,
+    reading: TorahReading,
+    /// There's none if Shabbos is Yom Tov or Chol Hamoed, which don't have a parsha.
+    leining: Option<Leining>,
+}
+
+impl BarMitzvahArgs {
+    fn torah_readings(&self, events: Vec<TorahReadingType>) -> TorahReadings<'static> {
+        TorahReadings {
+            events,
+            location: self.location,
+            yom_tov_observance: self.location,
+            city: None,
+            reading_cycle: None,
+            havdalah: vec![],
+            polar_fallback: PolarFallback::None,
+            solar_algorithm: SolarAlgorithm::Noaa,
+        }
+    }
+
+    fn bar_mitzvah(&self) -> Result<BarMitzvah, AppError> {
+        let shabbos =
+            self.date + Duration::days(6 - i64::from(self.date.weekday().num_days_from_sunday()));
+        let (events, _) = events_on(
+            &self.torah_readings(vec![
+                TorahReadingType::Shabbos,
+                TorahReadingType::YomTov,
+                TorahReadingType::Chol,
+                TorahReadingType::SpecialParsha,
+            ]),
+            shabbos,
+        );
+        let readings: Vec<TorahReading> = events
+            .iter()
+            .filter_map(|x| match x.name {
+                Name::TorahReading(reading) => Some(reading),
+                _ => None,
+            })
+            .collect();
+        let parsha = readings.iter().find_map(|x| match x {
+            TorahReading::Shabbos(parsha) => Some(*parsha),
+            _ => None,
+        });
+        let parsha = match parsha {
+            Some(parsha) => parsha,
+            None => {
+                return Ok(BarMitzvah {
+                    shabbos,
+                    reading: *readings
+                        .iter()
+                        .find(|x| matches!(x, TorahReading::YomTov(_)))
+                        .unwrap(),
+                    leining: None,
+                })
+            }
+        };
+        let special = readings.iter().find_map(|x| match x {
+            TorahReading::SpecialParsha(special) => Some(*special),
+            _ => None,
+        });
+        // Rosh Chodesh or Chanukah, which have a maftir. Chanukah is read rather than Rosh
+        // Chodesh Teves.
+        let chol = readings
+            .iter()
+            .filter_map(|x| match x {
+                TorahReading::Chol(chol) if leining::chol_maftir(*chol).is_some() => Some(*chol),
+                _ => None,
+            })
+            .min_by_key(|x| !is_chanukah(*x));
+        let (maftir, maftir_occasion) = match (special, chol) {
+            (Some(special), _) => (
+                leining::special_parsha_maftir(special),
+                Some(Occasion::Reading(TorahReading::SpecialParsha(special))),
+            ),
+            (None, Some(chol)) => (
+                leining::chol_maftir(chol).unwrap(),
+                Some(Occasion::Reading(TorahReading::Chol(chol))),
+            ),
+            (None, None) => (leining::maftir(parsha), None),
+        };
+        let (haftarah, haftarah_occasion) = haftarah(shabbos, parsha, special, chol)?;
+        Ok(BarMitzvah {
+            shabbos,
+            reading: TorahReading::Shabbos(parsha),
+            leining: Some(Leining {
+                parsha,
+                aliyos: leining::aliyos(parsha),
+                maftir,
+                maftir_occasion,
+                haftarah,
+                haftarah_occasion,
+                practice: self.practice(shabbos, parsha)?,
+            }),
+        })
+    }
+
+    /// Finds the last time before the bar mitzvah each of the parshiyos was read, whether on its
+    /// own or together with the other. A parsha is read about once a year, so a year and a
+    /// bit, for years with a leap month, is searched.
+    fn practice(
+        &self,
+        shabbos: NaiveDate,
+        parsha: Parsha,
+    ) -> Result<Vec<(Parsha, NaiveDate)>, AppError> {
+        let (from, until) = (night_of(shabbos - Duration::weeks(60)), night_of(shabbos));
+        let events = get_events_between(
+            HebrewDate::try_from(from)?.year(),
+            HebrewDate::try_from(until)?.year() + 1,
+            from,
+            until,
+            &[&self.torah_readings(vec![TorahReadingType::Shabbos])],
+        )?;
+        Ok(split_parsha(parsha)
+            .into_iter()
+            .filter_map(|parsha| {
+                events
+                    .iter()
+                    .rev()
+                    .find(|x| match x.name {
+                        Name::TorahReading(TorahReading::Shabbos(read)) => {
+                            split_parsha(read).contains(&parsha)
+                        }
+                        _ => false,
+                    })
+                    .map(|x: &DayVal| (parsha, (x.day + Duration::days(1)).date().naive_utc()))
+            })
+            .collect())
+    }
+}
+
+fn is_chanukah(chol: Chol) -> bool {
+    matches!(
+        chol,
+        Chol::Chanukah1
+            | Chol::Chanukah2
+            | Chol::Chanukah3
+            | Chol::Chanukah4
+            | Chol::Chanukah5
+            | Chol::Chanukah6
+            | Chol::Chanukah7
+            | Chol::Chanukah8
+    )
+}
+
+/// The haftarah read on the Shabbos. A special parsha comes first, then Chanukah, Shabbos Shuva
+/// and Shabbos HaGadol. The haftaros of affliction and consolation, from the Three Weeks until
+/// Rosh Hashanah, are read even on Rosh Chodesh or before it.
+fn haftarah(
+    shabbos: NaiveDate,
+    parsha: Parsha,
+    special: Option<SpecialParsha>,
+    chol: Option<Chol>,
+) -> Result<(Vec<Verses>, Option<Occasion>), AppError> {
+    let date = HebrewDate::try_from(night_of(shabbos))?;
+    let tomorrow = HebrewDate::try_from(night_of(shabbos + Duration::days(1)))?;
+    let day = date.day().get();
+    if let Some(special) = special {
+        return Ok((
+            leining::special_parsha_haftarah(special),
+            Some(Occasion::Reading(TorahReading::SpecialParsha(special))),
+        ));
+    }
+    if let Some(chol) = chol.filter(|x| is_chanukah(*x)) {
+        return Ok((
+            leining::chanukah_haftarah(chol == Chol::Chanukah8),
+            Some(Occasion::Reading(TorahReading::Chol(chol))),
+        ));
+    }
+    if date.month() == HebrewMonth::Tishrei && (3..=9).contains(&day) {
+        return Ok((
+            leining::shabbos_shuva_haftarah(),
+            Some(Occasion::Shabbos(SpecialShabbos::Shuva)),
+        ));
+    }
+    if date.month() == HebrewMonth::Nissan && (8..=14).contains(&day) {
+        return Ok((
+            leining::shabbos_hagadol_haftarah(),
+            Some(Occasion::Shabbos(SpecialShabbos::HaGadol)),
+        ));
+    }
+    // Shabbos Chazon is the last Shabbos before Tisha B'Av, or Tisha B'Av itself.
+    let tisha_bav = civil_date(date.year(), HebrewMonth::Av, 9)?;
+    let chazon = tisha_bav
+        - Duration::days(i64::from(
+            (tisha_bav.weekday().num_days_from_sunday() + 1) % 7,
+        ));
+    let weeks_before = (chazon - shabbos).num_days() / 7;
+    if (1..=2).contains(&weeks_before) {
+        return Ok((leining::three_weeks_haftarah(weeks_before), None));
+    }
+    let consolation = matches!(date.month(), HebrewMonth::Av | HebrewMonth::Elul);
+    if let Some(chol) = chol.filter(|_| !consolation) {
+        return Ok((
+            leining::rosh_chodesh_haftarah(),
+            Some(Occasion::Reading(TorahReading::Chol(chol))),
+        ));
+    }
+    if !consolation
+        && tomorrow.month() != HebrewMonth::Tishrei
+        && [1, 30].contains(&tomorrow.day().get())
+    {
+        return Ok((
+            leining::machar_chodesh_haftarah(),
+            Some(Occasion::MacharChodesh),
+        ));
+    }
+    Ok((leining::haftarah(parsha), None))
+}
+
+/// Writes a range of verses, followed by how many there are if it's in the Torah, like "Exodus
+/// 30:11-30:16 (6 verses)".
+fn write_verses<W: Write>(
+    lock: &mut W,
+    verses: &Verses,
+    language: Language,
+) -> std::io::Result<()> {
+    write!(lock, "{} {}", verses.sefer.name(language), verses.range())?;
+    if let Some(count) = verses.count() {
+        match language {
+            Language::English => write!(lock, " ({} verses)", count)?,
+            Language::Hebrew => write!(lock, " ({} פסוקים)", count)?,
+        }
+    }
+    Ok(())
+}
+
+impl BarMitzvah {
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let language = args.language;
+        match language {
+            Language::English => lock.write_all(b"Shabbos ")?,
+            Language::Hebrew => lock.write_all("שבת ".as_bytes())?,
+        };
+        write_date(lock, Utc.from_utc_date(&self.shabbos).and_hms(0, 0, 0))?;
+        writeln!(lock, ": {}", torah_reading(self.reading, language))?;
+        let leining = match &self.leining {
+            Some(leining) => leining,
+            None => {
+                return match language {
+                    Language::English => writeln!(lock, "There's no parsha on this Shabbos"),
+                    Language::Hebrew => writeln!(lock, "אין פרשה בשבת זו"),
+                }
+            }
+        };
+        match language {
+            Language::English => lock.write_all(b"Torah reading: ")?,
+            Language::Hebrew => lock.write_all("קריאת התורה: ".as_bytes())?,
+        };
+        write_verses(lock, &leining::reading(leining.parsha), language)?;
+        lock.write_all(b"\n")?;
+        for (index, aliyah) in leining.aliyos.iter().flatten().enumerate() {
+            write!(lock, "{}: ", aliyah_name(index as u8 + 1, language))?;
+            write_verses(lock, aliyah, language)?;
+            lock.write_all(b"\n")?;
+        }
+        match language {
+            Language::English => lock.write_all(b"Maftir: ")?,
+            Language::Hebrew => lock.write_all("מפטיר: ".as_bytes())?,
+        };
+        write_verses(lock, &leining.maftir, language)?;
+        if let Some(occasion) = leining.maftir_occasion {
+            write!(lock, " - {}", occasion.name(language))?;
+        }
+        match language {
+            Language::English => lock.write_all(b"\nHaftarah: ")?,
+            Language::Hebrew => lock.write_all("\nהפטרה: ".as_bytes())?,
+        };
+        for (index, verses) in leining.haftarah.iter().enumerate() {
+            if index != 0 {
+                lock.write_all(b", ")?;
+            }
+            write!(lock, "{} {}", verses.sefer.name(language), verses.range())?;
+        }
+        if let Some(occasion) = leining.haftarah_occasion {
+            write!(lock, " - {}", occasion.name(language))?;
+        }
+        lock.write_all(b"\n")?;
+        if leining.practice.is_empty() {
+            return Ok(());
+        }
+        match language {
+            Language::English => lock.write_all(b"\nLast read: ")?,
+            Language::Hebrew => lock.write_all("\nנקראה לאחרונה: ".as_bytes())?,
+        };
+        for (index, (parsha, date)) in leining.practice.iter().enumerate() {
+            if index != 0 {
+                lock.write_all(b", ")?;
+            }
+            lock.write_all(torah_reading(TorahReading::Shabbos(*parsha), language).as_bytes())?;
+            match language {
+                Language::English => lock.write_all(b" on ")?,
+                Language::Hebrew => lock.write_all(" ב-".as_bytes())?,
+            };
+            write_date(lock, Utc.from_utc_date(date).and_hms(0, 0, 0))?;
+        }
+        lock.write_all(b"\n")
+    }
+
+    fn json(&self) -> serde_json::Value {
+        let verses =
+            |verses: &Verses| serde_json::json!({"verses": verses, "count": verses.count()});
+        let leining = self.leining.as_ref().map(|leining| {
+            serde_json::json!({
+                "torah": verses(&leining::reading(leining.parsha)),
+                "aliyos": leining.aliyos.as_ref().map(|x| x.iter().map(verses).collect::<Vec<_>>()),
+                "maftir": verses(&leining.maftir),
+                "maftirOccasion": leining.maftir_occasion,
+                "haftarah": leining.haftarah,
+                "haftarahOccasion": leining.haftarah_occasion,
+                "practice": leining.practice.iter().map(|(parsha, date)| serde_json::json!({
+                    "parsha": parsha,
+                    "date": date,
+                })).collect::<Vec<_>>(),
+            })
+        });
+        let mut json = serde_json::json!({
+            "shabbos": self.shabbos,
+            "leining": leining,
+        });
+        match self.reading {
+            TorahReading::Shabbos(parsha) => json["parsha"] = serde_json::json!(parsha),
+            TorahReading::YomTov(yom_tov) => json["yomTov"] = serde_json::json!(yom_tov),
+            _ => unreachable!(),
+        }
+        json
+    }
+}
+
+impl Printable for BarMitzvah {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, &self.json())
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for BarMitzvahArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        print_output(&self.bar_mitzvah()?, args)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::args::build_args;
+use crate::args::types::{AppError, BatchArgs, Command, LocalizedError, MainArgs};
+use crate::prelude::{output, write_data, write_error, Runnable};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{stdin, BufRead, Write};
+
+/// The commands a request can run.
+const COMMANDS: &[&str] = &["list"];
+
+/// The options of a request which come before the subcommand, and replace those `heca batch` was
+/// run with.
+const GLOBALS: &[&str] = &[
+    "config",
+    "profile",
+    "language",
+    "transliteration",
+    "today",
+    "ascii",
+    "deny-warnings",
+];
+
+/// Returns the value of an option, or `None` if it's a flag: `true` passes the flag, and `false`
+/// and `null` leave the option out. Lists are joined with commas, like `--show yom-tov,shabbos`.
+fn option_value(key: &str, value: &Value) -> Result<Option<Option<String>>, AppError> {
+    let scalar = |value: &Value| match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        _ => Err(AppError::BadRequest(format!(
+            "{} must be a string, a number or a list of them",
+            key
+        ))),
+    };
+    Ok(match value {
+        Value::Bool(true) => Some(None),
+        Value::Bool(false) | Value::Null => None,
+        Value::Array(values) => Some(Some(
+            values
+                .iter()
+                .map(scalar)
+                .collect::<Result<Vec<String>, AppError>>()?
+                .join(","),
+        )),
+        value => Some(Some(scalar(value)?)),
+    })
+}
+
+/// Turns a request, like `{"cmd":"list","year":5785,"years":2}`, into the arguments heca would be
+/// run with for it, like `heca list 5785 --years=2`. `year` is the year `list` takes, and every
+/// other key is an option.
+fn request_args(line: &str, globals: &[(String, Option<String>)]) -> Result<Vec<String>, AppError> {
+    let request: Value =
+        serde_json::from_str(line).map_err(|e| AppError::BadRequest(e.to_string()))?;
+    let request = request
+        .as_object()
+        .ok_or_else(|| AppError::BadRequest("the request must be an object".into()))?;
+    let cmd = match request.get("cmd") {
+        Some(Value::String(cmd)) if COMMANDS.contains(&cmd.as_str()) => cmd,
+        Some(cmd) => {
+            return Err(AppError::BadRequest(format!(
+                "{} isn't a command. The commands are {}",
+                cmd,
+                COMMANDS.join(", ")
+            )))
+        }
+        None => return Err(AppError::BadRequest("the request has no cmd".into())),
+    };
+
+    let mut globals = globals.to_vec();
+    let mut options = vec![];
+    for (key, value) in request {
+        if key == "cmd" {
+            continue;
+        }
+        if key == "year" {
+            match value {
+                Value::Number(year) => options.insert(0, year.to_string()),
+                _ => return Err(AppError::BadRequest("year must be a number".into())),
+            }
+            continue;
+        }
+        let value = option_value(key, value)?;
+        if GLOBALS.contains(&key.as_str()) {
+            globals.retain(|(name, _)| name != key);
+            if let Some(value) = value {
+                globals.push((key.clone(), value));
+            }
+        } else if let Some(value) = value {
+            options.push(match value {
+                Some(value) => format!("--{}={}", key, value),
+                None => format!("--{}", key),
+            });
+        }
+    }
+
+    let mut args = vec!["heca".to_string()];
+    for (name, value) in globals {
+        args.push(match value {
+            Some(value) => format!("--{}={}", name, value),
+            None => format!("--{}", name),
+        });
+    }
+    args.push(cmd.clone());
+    args.extend(options);
+    Ok(args)
+}
+
+/// The response to a request which failed.
+#[derive(Serialize)]
+struct ErrorResponse<'a> {
+    error: LocalizedError<'a>,
+}
+
+/// Answers a request by printing into `out`.
+fn answer(
+    line: &str,
+    batch: &BatchArgs,
+    args: &MainArgs,
+    out: &mut dyn Write,
+) -> Result<(), AppError> {
+    let request = build_args(request_args(line, &batch.globals)?, args.output_type)?;
+    match request.command {
+        Command::List(ref sub_args) => sub_args.print_into(&request, out),
+        _ => unreachable!(),
+    }
+}
+
+impl Runnable for BatchArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let mut out = output(args)?;
+        for line in stdin().lock().lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // A request which fails is answered with its error, so the next ones still are.
+            let mut response = vec![];
+            if let Err(err) = answer(&line, self, args, &mut response) {
+                response.clear();
+                write_data(
+                    &mut response,
+                    args.output_type,
+                    &ErrorResponse {
+                        error: LocalizedError(&err, args.language),
+                    },
+                )
+                .map_err(write_error)?;
+            }
+            out.write_all(&response).map_err(write_error)?;
+            out.flush().map_err(write_error)?;
+        }
+        Ok(())
+    }
+}
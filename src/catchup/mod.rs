@@ -0,0 +1,168 @@
+use crate::args::types::{
+    AppError, CatchupArgs, DailyStudyOutput, DayVal, Language, MainArgs, Name, OutputType,
+    StudySession,
+};
+use crate::list::providers::DailyStudies;
+use crate::list::{get_events_between, ics, write_date, Return};
+use crate::prelude::{
+    night_of, print_output, write_data, write_error, CountingWriter, Printable, Runnable,
+};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::HebrewDate;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// The plan printed by `heca catchup`: what to learn every day, and the day it's done.
+struct Catchup {
+    plan: Return,
+    /// The day the plan catches up with the cycle.
+    rejoin: NaiveDate,
+    /// What the cycle is up to on that day.
+    rejoin_with: Option<DailyStudyOutput>,
+}
+
+impl CatchupArgs {
+    /// How many days it takes to catch up. Every day, `pace` days of the cycle are learned while
+    /// the cycle goes on by one, so the plan gains `pace - 1` days on it.
+    fn days(&self) -> u32 {
+        (self.behind + self.pace - 2) / (self.pace - 1)
+    }
+
+    /// What the cycle learns on every day from `first` until `last`, inclusive.
+    fn cycle(
+        &self,
+        first: NaiveDate,
+        last: NaiveDate,
+    ) -> Result<HashMap<DateTime<Utc>, DailyStudyOutput>, AppError> {
+        let (from, until) = (night_of(first), night_of(last) + Duration::days(1));
+        let provider = DailyStudies {
+            events: vec![self.study.clone()],
+            location: self.location,
+        };
+        let events = get_events_between(
+            HebrewDate::try_from(from)?.year(),
+            HebrewDate::try_from(until)?.year() + 1,
+            from,
+            until,
+            &[&provider],
+        )?;
+        Ok(events
+            .into_iter()
+            .filter_map(|x| match x.name {
+                Name::DailyStudy(study) => Some((x.day, study)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    fn plan(&self) -> Result<Catchup, AppError> {
+        let days = self.days();
+        let rejoin = self.date + Duration::days(i64::from(days.max(1)) - 1);
+        let cycle = self.cycle(self.date - Duration::days(self.behind.into()), rejoin)?;
+        let (behind, pace) = (i64::from(self.behind), i64::from(self.pace));
+        let list = (0..i64::from(days))
+            .filter_map(|day| {
+                // The days of the cycle learned on the day, counted from the start of the plan.
+                // The last day stops at the day the cycle is up to.
+                let units: Vec<DailyStudyOutput> = (day * pace - behind
+                    ..((day + 1) * pace - behind).min(day + 1))
+                    .filter_map(|x| cycle.get(&night_of(self.date + Duration::days(x))))
+                    .cloned()
+                    .collect();
+                if units.is_empty() {
+                    return None;
+                }
+                Some(DayVal {
+                    day: night_of(self.date + Duration::days(day)),
+                    name: Name::StudySession(StudySession { units }),
+                    candle_lighting: None,
+                    torah_portion: None,
+                    havdalah: vec![],
+                    time_range: None,
+                })
+            })
+            .collect();
+        Ok(Catchup {
+            plan: Return {
+                list,
+                icons: false,
+                location: ics::location_id(self.location, None),
+                time_zone: None,
+                fields: None,
+                short_names: false,
+                shabbos_conflicts: None,
+                headers: None,
+                summary: None,
+                meta: false,
+                alarms: vec![],
+                candle_lighting_minutes: None,
+                provenance: None,
+            },
+            rejoin,
+            rejoin_with: cycle.get(&night_of(rejoin)).cloned(),
+        })
+    }
+}
+
+impl Catchup {
+    /// Writes the plan, followed by the day it catches up, like "Back on schedule on 2025/1/10,
+    /// with Kesubos 45".
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        self.plan.pretty_print(args, lock)?;
+        if !self.plan.list.is_empty() {
+            lock.write_all(b"\n")?;
+        }
+        match args.language {
+            Language::English => lock.write_all(b"Back on schedule on ")?,
+            Language::Hebrew => lock.write_all("חוזרים ללוח ב-".as_bytes())?,
+        };
+        write_date(lock, Utc.from_utc_date(&self.rejoin).and_hms(0, 0, 0))?;
+        if let Some(unit) = &self.rejoin_with {
+            match args.language {
+                Language::English => lock.write_all(b", with ")?,
+                Language::Hebrew => lock.write_all(", עם ".as_bytes())?,
+            };
+            Name::DailyStudy(unit.clone())
+                .pretty_print(lock, args.language, args.transliteration)
+                .unwrap();
+        }
+        lock.write_all(b"\n")
+    }
+}
+
+impl Printable for Catchup {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        match args.output_type {
+            // The calendar formats only have the events.
+            OutputType::ICS | OutputType::Parquet => self.plan.print(args, out),
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let mut lock = CountingWriter::new(out);
+                write_data(
+                    &mut lock,
+                    args.output_type,
+                    &serde_json::json!({
+                        "rejoin": self.rejoin,
+                        "days": self.plan.list.len(),
+                        "events": self.plan.json_list(args),
+                    }),
+                )
+                .map_err(write_error)?;
+                Ok(lock.count)
+            }
+            OutputType::Pretty | OutputType::Regular => {
+                let mut lock = CountingWriter::new(out);
+                self.pretty_print(args, &mut lock).map_err(write_error)?;
+                Ok(lock.count)
+            }
+        }
+    }
+}
+
+impl Runnable for CatchupArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        print_output(&self.plan()?, args)?;
+        Ok(())
+    }
+}
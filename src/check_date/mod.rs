@@ -0,0 +1,207 @@
+use crate::algorithms::restrictions::{self, Restriction};
+use crate::args::types::{
+    AppError, CheckDateArgs, CheckedOccasion, Language, MainArgs, Name, OutputType, PolarFallback,
+    SefirahCustom, SolarAlgorithm,
+};
+use crate::list::providers::TorahReadings;
+use crate::list::{get_events_between, write_date};
+use crate::prelude::{
+    night_of, print_output, write_data, write_error, CountingWriter, Printable, Runnable,
+};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::*;
+use heca_lib::HebrewDate;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// How many days before and after the date the nearest dates without restrictions are looked
+/// for. The longest restrictions, Pesach followed by Sefiras HaOmer, are about two months long.
+const SEARCH_DAYS: i64 = 120;
+
+/// The output of `heca check-date`.
+struct DateCheck {
+    date: NaiveDate,
+    occasion: CheckedOccasion,
+    sefirah: SefirahCustom,
+    restrictions: Vec<Restriction>,
+    /// The nearest dates before and after the date without restrictions, if it has any.
+    nearest: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl CheckDateArgs {
+    /// The Yom Tov and fast readings of the days around the date, by the civil date of their day.
+    fn readings(&self) -> Result<HashMap<NaiveDate, Vec<TorahReading>>, AppError> {
+        let from = night_of(self.date - Duration::days(SEARCH_DAYS));
+        let until = night_of(self.date + Duration::days(SEARCH_DAYS + 1));
+        let provider = TorahReadings {
+            events: vec![TorahReadingType::YomTov, TorahReadingType::Chol],
+            location: self.location,
+            yom_tov_observance: self.location,
+            city: None,
+            reading_cycle: None,
+            havdalah: vec![],
+            polar_fallback: PolarFallback::None,
+            solar_algorithm: SolarAlgorithm::Noaa,
+        };
+        let events = get_events_between(
+            HebrewDate::try_from(from)?.year(),
+            HebrewDate::try_from(until)?.year() + 1,
+            from,
+            until,
+            &[&provider],
+        )?;
+        let mut readings: HashMap<NaiveDate, Vec<TorahReading>> = HashMap::new();
+        for event in events {
+            if let Name::TorahReading(reading) = event.name {
+                readings
+                    .entry((event.day + Duration::days(1)).date().naive_utc())
+                    .or_default()
+                    .push(reading);
+            }
+        }
+        Ok(readings)
+    }
+
+    /// Why the occasion can't be held on the day of the date.
+    fn restrictions(
+        &self,
+        date: NaiveDate,
+        readings: &HashMap<NaiveDate, Vec<TorahReading>>,
+    ) -> Result<Vec<Restriction>, AppError> {
+        match self.occasion {
+            CheckedOccasion::Wedding => {
+                let mut found = vec![];
+                if date.weekday() == Weekday::Sat {
+                    found.push(Restriction::Shabbos);
+                }
+                found.extend(
+                    readings
+                        .get(&date)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|x| restrictions::reading(*x, self.location)),
+                );
+                found.extend(restrictions::periods(
+                    HebrewDate::try_from(night_of(date))?,
+                    self.sefirah,
+                ));
+                found.dedup();
+                Ok(found)
+            }
+        }
+    }
+
+    /// The first date without restrictions going from the date by `step` days at a time.
+    fn nearest(
+        &self,
+        step: i64,
+        readings: &HashMap<NaiveDate, Vec<TorahReading>>,
+    ) -> Result<NaiveDate, AppError> {
+        for days in 1..=SEARCH_DAYS {
+            let date = self.date + Duration::days(days * step);
+            if self.restrictions(date, readings)?.is_empty() {
+                return Ok(date);
+            }
+        }
+        unreachable!("No date without restrictions near {}", self.date)
+    }
+
+    fn check(&self) -> Result<DateCheck, AppError> {
+        let readings = self.readings()?;
+        let restrictions = self.restrictions(self.date, &readings)?;
+        let nearest = if restrictions.is_empty() {
+            None
+        } else {
+            Some((self.nearest(-1, &readings)?, self.nearest(1, &readings)?))
+        };
+        Ok(DateCheck {
+            date: self.date,
+            occasion: self.occasion,
+            sefirah: self.sefirah,
+            restrictions,
+            nearest,
+        })
+    }
+}
+
+impl DateCheck {
+    fn occasion_name(&self, language: Language) -> &'static str {
+        match (self.occasion, language) {
+            (CheckedOccasion::Wedding, Language::English) => "a wedding",
+            (CheckedOccasion::Wedding, Language::Hebrew) => "חתונה",
+        }
+    }
+
+    /// Writes the restrictions, like "Restrictions on a wedding on 2026/7/14: the Three Weeks",
+    /// followed by the nearest dates without them.
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let occasion = self.occasion_name(args.language);
+        match (self.restrictions.is_empty(), args.language) {
+            (true, Language::English) => write!(lock, "No restrictions on {} on ", occasion)?,
+            (true, Language::Hebrew) => write!(lock, "אין הגבלות על {} ב-", occasion)?,
+            (false, Language::English) => write!(lock, "Restrictions on {} on ", occasion)?,
+            (false, Language::Hebrew) => write!(lock, "הגבלות על {} ב-", occasion)?,
+        };
+        write_date(lock, Utc.from_utc_date(&self.date).and_hms(0, 0, 0))?;
+        if let Some((before, after)) = self.nearest {
+            let names: Vec<&str> = self
+                .restrictions
+                .iter()
+                .map(|x| x.name(args.language))
+                .collect();
+            writeln!(lock, ": {}", names.join(", "))?;
+            match args.language {
+                Language::English => lock.write_all(b"Nearest dates without them: ")?,
+                Language::Hebrew => lock.write_all("התאריכים הקרובים בלי הגבלות: ".as_bytes())?,
+            };
+            write_date(lock, Utc.from_utc_date(&before).and_hms(0, 0, 0))?;
+            match args.language {
+                Language::English => lock.write_all(b" and ")?,
+                Language::Hebrew => lock.write_all(" ו-".as_bytes())?,
+            };
+            write_date(lock, Utc.from_utc_date(&after).and_hms(0, 0, 0))?;
+        }
+        lock.write_all(b"\n")
+    }
+}
+
+impl Printable for DateCheck {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let occasion = match self.occasion {
+                    CheckedOccasion::Wedding => "wedding",
+                };
+                write_data(
+                    &mut lock,
+                    args.output_type,
+                    &serde_json::json!({
+                        "date": self.date,
+                        "for": occasion,
+                        "sefirah": self.sefirah,
+                        "restrictions": self.restrictions,
+                        "nearest": self.nearest.map(|(before, after)| serde_json::json!({
+                            "before": before,
+                            "after": after,
+                        })),
+                    }),
+                )
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for CheckDateArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        print_output(&self.check()?, args)?;
+        Ok(())
+    }
+}
@@ -1,18 +1,87 @@
-use crate::args::types::{AppError, ConvertArgs, ConvertType, Language, MainArgs, OutputType};
+use crate::algorithms::arithmetic::days_in_year;
+use crate::args::types::{
+    AppError, ConvertArgs, ConvertType, DayVal, Language, MainArgs, OutputType, PolarFallback,
+    SolarAlgorithm,
+};
+use crate::list::providers::{weekly_reading, MinorHolidays, TorahReadings};
 use crate::prelude::print;
-use crate::Runnable;
+use crate::prelude::{
+    events_on, print_output, write_data, write_error, CountingWriter, Printable, Runnable,
+};
 use chrono::prelude::*;
 use chrono::Duration;
 use either::Either;
-use heca_lib::HebrewDate;
-use serde::ser::SerializeSeq;
+use heca_lib::prelude::{Location, MonthSchedule, TorahReadingType};
+use heca_lib::{HebrewDate, HebrewYear};
 use serde::{Serialize, Serializer};
 use std::convert::TryInto;
+use std::io::Write;
 
 #[derive(Debug)]
 pub struct Return {
     pub day: Either<[chrono::DateTime<Utc>; 2], [HebrewDate; 2]>,
     pub orig_day: Either<HebrewDate, chrono::DateTime<Utc>>,
+    pub context: Context,
+}
+
+/// What the JSON output tells about the Hebrew date during the day: the date converted from,
+/// or the date before sunset of the Gregorian date converted from.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Context {
+    pub day_of_week: String,
+    pub year_type: MonthSchedule,
+    pub leap_year: bool,
+    pub year_length: i64,
+    /// The reading of the Shabbos on or after the date.
+    pub parsha: DayVal,
+    /// The holidays, fasts, Rosh Chodesh and minor days on the date. Empty if it isn't one.
+    pub holidays: Vec<DayVal>,
+}
+
+impl Context {
+    /// `date` is the Hebrew date, and `civil` the Gregorian date of its day.
+    fn new(date: HebrewDate, civil: NaiveDate, location: Location) -> Result<Context, AppError> {
+        let year = HebrewYear::new(date.year())?;
+        let holidays = TorahReadings {
+            events: vec![TorahReadingType::YomTov, TorahReadingType::Chol],
+            location,
+            yom_tov_observance: location,
+            city: None,
+            reading_cycle: None,
+            havdalah: vec![],
+            polar_fallback: PolarFallback::None,
+            solar_algorithm: SolarAlgorithm::Noaa,
+        };
+        let (mut events, _) = events_on(&holidays, civil);
+        let minor_holidays = MinorHolidays {
+            city: None,
+            polar_fallback: PolarFallback::None,
+            solar_algorithm: SolarAlgorithm::Noaa,
+        };
+        events.extend(events_on(&minor_holidays, civil).0);
+        Ok(Context {
+            day_of_week: civil.format("%A").to_string(),
+            year_type: year.year_type(),
+            leap_year: year.is_leap_year(),
+            year_length: days_in_year(date.year())?,
+            parsha: weekly_reading(civil, location).0,
+            holidays: events,
+        })
+    }
+}
+
+/// The date converted from is the `input`. A Gregorian date is converted to the Hebrew dates
+/// before and after sunset, and a Hebrew date to the sunsets it's between.
+#[derive(Serialize)]
+struct Json<'a> {
+    input: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hebrew: Option<&'a [HebrewDate; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gregorian: Option<&'a [DateTime<Utc>; 2]>,
+    #[serde(flatten)]
+    context: &'a Context,
 }
 
 impl Serialize for Return {
@@ -20,30 +89,31 @@ impl Serialize for Return {
     where
         S: Serializer,
     {
-        match self.day {
-            Either::Left(val) => serialize_array(val, serializer),
-            Either::Right(val) => serialize_array(val, serializer),
-        }
-    }
-}
-
-fn serialize_array<S, A>(cv: [A; 2], serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-    A: Serialize,
-{
-    let mut seq = serializer.serialize_seq(Some(2))?;
-    for e in &cv {
-        seq.serialize_element(e)?;
+        let json = match (&self.orig_day, &self.day) {
+            (Either::Right(date), Either::Right(hebrew)) => Json {
+                input: date.format("%Y-%m-%d").to_string().into(),
+                hebrew: Some(hebrew),
+                gregorian: None,
+                context: &self.context,
+            },
+            (Either::Left(date), Either::Left(gregorian)) => Json {
+                input: serde_json::to_value(date).unwrap(),
+                hebrew: None,
+                gregorian: Some(gregorian),
+                context: &self.context,
+            },
+            _ => unreachable!("The conversion is from the other calendar"),
+        };
+        json.serialize(serializer)
     }
-    seq.end()
 }
 
 impl Return {
-    fn pretty_print(&self, args: &MainArgs) -> Result<(), AppError> {
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
         match args.language {
             Language::English => match self.orig_day {
-                Either::Right(r) => println!(
+                Either::Right(r) => writeln!(
+                    lock,
                     "{}: From {} {} {} to {} {} {}.",
                     r.format("%A %B %-d %Y"),
                     self.day.right().unwrap()[0].day(),
@@ -53,7 +123,8 @@ impl Return {
                     print::hebrew_month_english(self.day.right().unwrap()[1].month()),
                     self.day.right().unwrap()[1].year(),
                 ),
-                Either::Left(l) => println!(
+                Either::Left(l) => writeln!(
+                    lock,
                     "{} {} {} -> From sunset {} to sunset {}.",
                     l.day(),
                     print::hebrew_month_english(l.month()),
@@ -63,7 +134,8 @@ impl Return {
                 ),
             },
             Language::Hebrew => match self.orig_day {
-                Either::Right(r) => println!(
+                Either::Right(r) => writeln!(
+                    lock,
                     "{}: {} {} {} - {} {} {}.",
                     r.format("%A %B %-d %Y"),
                     self.day.right().unwrap()[0].day(),
@@ -73,7 +145,8 @@ impl Return {
                     print::hebrew_month_hebrew(self.day.right().unwrap()[1].month()),
                     self.day.right().unwrap()[1].year(),
                 ),
-                Either::Left(l) => println!(
+                Either::Left(l) => writeln!(
+                    lock,
                     "{} {} {}: {} - {}.",
                     l.day(),
                     print::hebrew_month_hebrew(l.month()),
@@ -82,47 +155,49 @@ impl Return {
                     self.day.left().unwrap()[1].format("%A %B %-d %Y"),
                 ),
             },
-        };
-        Ok(())
-    }
-    fn json_print(&self) -> Result<(), AppError> {
-        match &self.day {
-            Either::Right(r) => println!("{}", serde_json::to_string(&r).unwrap()),
-            Either::Left(r) => println!("{}", serde_json::to_string(&r).unwrap()),
-        };
-        Ok(())
+        }
     }
 }
 
-impl Return {
-    fn print(&self, args: &MainArgs) -> Result<(), AppError> {
+impl Printable for Return {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
         match args.output_type {
-            OutputType::JSON => self.json_print(),
-            OutputType::Pretty | OutputType::Regular => self.pretty_print(args),
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
         }
+        .map_err(write_error)?;
+        Ok(lock.count)
     }
 }
 
 impl Runnable for ConvertArgs {
     fn run(&self, args: &MainArgs) -> Result<(), AppError> {
         let ret = match self.date {
-            ConvertType::Gregorian(date) => Return {
-                orig_day: Either::Right(date.and_hms(0, 0, 1)),
-                day: Either::Right([
-                    date.and_hms(0, 0, 1).try_into()?,
-                    date.and_hms(23, 0, 1).try_into()?,
-                ]),
-            },
-            ConvertType::Hebrew(date) => Return {
-                orig_day: Either::Left(date),
-                day: Either::Left({
-                    let first_day: DateTime<Utc> = date.into();
-                    [first_day, first_day + Duration::days(1)]
-                }),
-            },
+            ConvertType::Gregorian(date) => {
+                let day: HebrewDate = date.and_hms(0, 0, 1).try_into()?;
+                Return {
+                    orig_day: Either::Right(date.and_hms(0, 0, 1)),
+                    day: Either::Right([day, date.and_hms(23, 0, 1).try_into()?]),
+                    context: Context::new(day, date.naive_utc(), self.location)?,
+                }
+            }
+            ConvertType::Hebrew(date) => {
+                let first_day: DateTime<Utc> = date.into();
+                let day = first_day + Duration::days(1);
+                Return {
+                    orig_day: Either::Left(date),
+                    day: Either::Left([first_day, day]),
+                    context: Context::new(date, day.naive_utc().date(), self.location)?,
+                }
+            }
         };
 
-        ret.print(args)?;
+        print_output(&ret, args)?;
         Ok(())
     }
 }
@@ -0,0 +1,166 @@
+use crate::args::types::{AppError, DiffCalendarsArgs, MainArgs, OutputType};
+use crate::json_calendar::{format_day, load, Event, DETAILS};
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use chrono::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// An event on a different day is only moved if it's this close to where it was. Farther away,
+/// it's most likely a different year's event, and is listed as removed and added.
+const MAX_MOVE_DAYS: i64 = 30;
+
+#[derive(Serialize)]
+struct Move {
+    from: Value,
+    to: Value,
+    event: Event,
+}
+
+#[derive(Serialize)]
+struct Change {
+    old: Event,
+    new: Event,
+}
+
+#[derive(Default, Serialize)]
+struct Diff {
+    added: Vec<Event>,
+    removed: Vec<Event>,
+    moved: Vec<Move>,
+    changed: Vec<Change>,
+}
+
+fn diff(old: Vec<Event>, new: Vec<Event>) -> Diff {
+    let mut events: BTreeMap<String, (Vec<Event>, Vec<Event>)> = BTreeMap::new();
+    for event in old {
+        events.entry(event.identity()).or_default().0.push(event);
+    }
+    for event in new {
+        events.entry(event.identity()).or_default().1.push(event);
+    }
+    let mut diff = Diff::default();
+    for (_, (mut old, mut new)) in events {
+        old.sort_by_key(|x| x.day);
+        new.sort_by_key(|x| x.day);
+        // Events on the same day in both calendars are the same event.
+        let mut old_left = vec![];
+        let mut new_left = vec![];
+        let mut new = new.into_iter().peekable();
+        for old_event in old {
+            while new.peek().is_some_and(|x| x.day < old_event.day) {
+                new_left.push(new.next().unwrap());
+            }
+            match new.peek() {
+                Some(new_event) if new_event.day == old_event.day => {
+                    let new_event = new.next().unwrap();
+                    if new_event.details() != old_event.details() {
+                        diff.changed.push(Change {
+                            old: old_event,
+                            new: new_event,
+                        });
+                    }
+                }
+                _ => old_left.push(old_event),
+            }
+        }
+        new_left.extend(new);
+        // The rest are paired up in order.
+        let mut old_left = old_left.into_iter();
+        let mut new_left = new_left.into_iter();
+        loop {
+            match (old_left.next(), new_left.next()) {
+                (Some(old_event), Some(new_event))
+                    if (new_event.day - old_event.day).num_days().abs() <= MAX_MOVE_DAYS =>
+                {
+                    diff.moved.push(Move {
+                        from: old_event.value["day"].clone(),
+                        to: new_event.value["day"].clone(),
+                        event: new_event,
+                    })
+                }
+                (Some(old_event), Some(new_event)) => {
+                    diff.removed.push(old_event);
+                    diff.added.push(new_event);
+                }
+                (Some(old_event), None) => diff.removed.push(old_event),
+                (None, Some(new_event)) => diff.added.push(new_event),
+                (None, None) => break,
+            }
+        }
+    }
+    diff.added.sort_by_key(|x| x.day);
+    diff.removed.sort_by_key(|x| x.day);
+    diff.moved.sort_by_key(|x| x.event.day);
+    diff.changed.sort_by_key(|x| x.new.day);
+    diff
+}
+
+impl Diff {
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let label = |event: &Event| event.label(args.language, args.transliteration);
+        for event in &self.added {
+            writeln!(lock, "+ {}: {}", format_day(event.day), label(event))?;
+        }
+        for event in &self.removed {
+            writeln!(lock, "- {}: {}", format_day(event.day), label(event))?;
+        }
+        for moved in &self.moved {
+            let from = DateTime::parse_from_rfc3339(moved.from.as_str().unwrap()).unwrap();
+            writeln!(
+                lock,
+                "> {} -> {}: {}",
+                format_day(from),
+                format_day(moved.event.day),
+                label(&moved.event)
+            )?;
+        }
+        for change in &self.changed {
+            let fields: Vec<&str> = DETAILS
+                .iter()
+                .filter(|x| change.old.value.get(**x) != change.new.value.get(**x))
+                .copied()
+                .collect();
+            writeln!(
+                lock,
+                "* {}: {} ({})",
+                format_day(change.new.day),
+                label(&change.new),
+                fields.join(", ")
+            )?;
+        }
+        writeln!(
+            lock,
+            "{} added, {} removed, {} moved, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.moved.len(),
+            self.changed.len()
+        )
+    }
+}
+
+impl Printable for Diff {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for DiffCalendarsArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let diff = diff(load(&self.old)?, load(&self.new)?);
+        print_output(&diff, args)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use crate::args::types::{AppError, Category, DayVal, DigestArgs, Language, MainArgs, OutputType};
+use crate::list::providers::weekly_reading;
+use crate::list::Return;
+use crate::prelude::{
+    print_output, write_data, write_error, write_table, CountingWriter, Printable, Runnable,
+};
+use crate::zmanim_table::{get_day, Luach};
+use chrono::prelude::*;
+use chrono::Duration;
+use serde::Serialize;
+use std::io::Write;
+
+/// The parsha, events, zmanim and daily study of a week, printed as an email with a plain text
+/// and an HTML version, so that a cron job can pipe it into sendmail.
+struct Digest<'a> {
+    args: &'a DigestArgs,
+    /// The reading of the week's Shabbos.
+    reading: DayVal,
+    events: Return,
+    study: Return,
+    zmanim: Option<Luach<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Json<'a> {
+    week: String,
+    reading: &'a DayVal,
+    events: &'a [DayVal],
+    study: &'a [DayVal],
+    zmanim: Option<&'a Luach<'a>>,
+}
+
+impl Digest<'_> {
+    fn subject(&self, args: &MainArgs) -> String {
+        let date = |date: NaiveDate| format!("{}/{}/{}", date.year(), date.month(), date.day());
+        let (first, last) = (self.args.week, self.args.week + Duration::days(6));
+        match args.language {
+            Language::English => format!(
+                "Week of {}, {} to {}",
+                self.reading_name(args),
+                date(first),
+                date(last)
+            ),
+            Language::Hebrew => format!(
+                "שבוע {}, {} עד {}",
+                self.reading_name(args),
+                date(first),
+                date(last)
+            ),
+        }
+    }
+
+    fn reading_name(&self, args: &MainArgs) -> String {
+        let mut name = vec![];
+        self.reading
+            .name
+            .pretty_print(&mut name, args.language, args.transliteration)
+            .unwrap();
+        String::from_utf8(name).unwrap()
+    }
+
+    /// Returns the headings and lines of every section which isn't empty. The zmanim are
+    /// returned separately, as a table.
+    fn sections(&self, args: &MainArgs) -> Vec<(String, Vec<String>)> {
+        let (shabbos, events, study) = match args.language {
+            Language::English => ("Shabbos", "Events", "Daily study"),
+            Language::Hebrew => ("שבת", "אירועים", "לימוד יומי"),
+        };
+        let lines = |list: &Return| {
+            let mut printed = vec![];
+            list.pretty_print(args, &mut printed).unwrap();
+            String::from_utf8(printed)
+                .unwrap()
+                .lines()
+                .map(String::from)
+                .collect::<Vec<String>>()
+        };
+        let mut sections = vec![(shabbos.into(), vec![self.reading_name(args)])];
+        for (heading, list) in &[(events, &self.events), (study, &self.study)] {
+            if !list.list.is_empty() {
+                sections.push((heading.to_string(), lines(list)));
+            }
+        }
+        sections
+    }
+
+    fn zmanim_heading(&self, args: &MainArgs) -> Option<String> {
+        let city = self.args.list.city.as_ref()?;
+        Some(match args.language {
+            Language::English => format!("Zmanim in {}", city.name),
+            Language::Hebrew => format!("זמנים ב{}", city.name),
+        })
+    }
+
+    fn text_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        writeln!(lock, "{}", self.subject(args))?;
+        for (heading, lines) in self.sections(args) {
+            writeln!(lock, "\n{}", heading)?;
+            for line in lines {
+                writeln!(lock, "{}", line)?;
+            }
+        }
+        if let (Some(heading), Some(zmanim)) = (self.zmanim_heading(args), &self.zmanim) {
+            writeln!(lock, "\n{}", heading)?;
+            write_table(lock, &zmanim.rows(args.language))?;
+        }
+        Ok(())
+    }
+
+    fn html_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let (lang, dir) = match args.language {
+            Language::English => ("en", "ltr"),
+            Language::Hebrew => ("he", "rtl"),
+        };
+        let subject = escape(&self.subject(args));
+        writeln!(lock, "<!DOCTYPE html>")?;
+        writeln!(lock, "<html lang=\"{}\" dir=\"{}\">", lang, dir)?;
+        writeln!(
+            lock,
+            "<head><meta charset=\"utf-8\"><title>{}</title></head>",
+            subject
+        )?;
+        writeln!(lock, "<body>")?;
+        writeln!(lock, "<h1>{}</h1>", subject)?;
+        for (heading, lines) in self.sections(args) {
+            writeln!(lock, "<h2>{}</h2>", escape(&heading))?;
+            writeln!(lock, "<ul>")?;
+            for line in lines {
+                writeln!(lock, "<li>{}</li>", escape(&line))?;
+            }
+            writeln!(lock, "</ul>")?;
+        }
+        if let (Some(heading), Some(zmanim)) = (self.zmanim_heading(args), &self.zmanim) {
+            writeln!(lock, "<h2>{}</h2>", escape(&heading))?;
+            writeln!(lock, "<table>")?;
+            for (index, row) in zmanim.rows(args.language).iter().enumerate() {
+                let cell = if index == 0 { "th" } else { "td" };
+                write!(lock, "<tr>")?;
+                for value in row {
+                    write!(lock, "<{}>{}</{}>", cell, escape(value), cell)?;
+                }
+                writeln!(lock, "</tr>")?;
+            }
+            writeln!(lock, "</table>")?;
+        }
+        writeln!(lock, "</body>")?;
+        writeln!(lock, "</html>")
+    }
+
+    /// Prints the digest as a MIME message, with the plain text and the HTML as alternatives.
+    fn email_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let boundary = format!("heca-digest-{}", self.args.week.format("%Y-%m-%d"));
+        if let Some(to) = &self.args.to {
+            writeln!(lock, "To: {}", to)?;
+        }
+        if let Some(from) = &self.args.from {
+            writeln!(lock, "From: {}", from)?;
+        }
+        writeln!(lock, "Subject: {}", encode_header(&self.subject(args)))?;
+        writeln!(lock, "MIME-Version: 1.0")?;
+        writeln!(
+            lock,
+            "Content-Type: multipart/alternative; boundary=\"{}\"",
+            boundary
+        )?;
+        for (content_type, html) in &[("text/plain", false), ("text/html", true)] {
+            writeln!(lock, "\n--{}", boundary)?;
+            writeln!(lock, "Content-Type: {}; charset=utf-8", content_type)?;
+            writeln!(lock, "Content-Transfer-Encoding: 8bit\n")?;
+            if *html {
+                self.html_print(args, lock)?;
+            } else {
+                self.text_print(args, lock)?;
+            }
+        }
+        writeln!(lock, "\n--{}--", boundary)
+    }
+}
+
+/// Escapes the text for HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Returns the header as it is if it's ASCII, or else as MIME encoded words (RFC 2047), each
+/// on a line of its own.
+fn encode_header(header: &str) -> String {
+    if header.is_ascii() {
+        return header.into();
+    }
+    let mut words = vec![];
+    let mut word = String::new();
+    for c in header.chars() {
+        // Encoded words are at most 75 characters long, which fits 45 bytes in base64.
+        if word.len() + c.len_utf8() > 45 {
+            words.push(format!("=?UTF-8?B?{}?=", base64(word.as_bytes())));
+            word.clear();
+        }
+        word.push(c);
+    }
+    words.push(format!("=?UTF-8?B?{}?=", base64(word.as_bytes())));
+    words.join("\n ")
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0, |value, (index, byte)| {
+            value | (*byte as u32) << (16 - index * 8)
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(ALPHABET[(value >> (18 - index * 6) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+impl Printable for Digest<'_> {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let json = Json {
+                    week: self.args.week.format("%Y-%m-%d").to_string(),
+                    reading: &self.reading,
+                    events: &self.events.list,
+                    study: &self.study.list,
+                    zmanim: self.zmanim.as_ref(),
+                };
+                write_data(&mut lock, args.output_type, &json)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.email_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for DigestArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let (reading, _) = weekly_reading(self.week, self.list.location);
+        let mut events = self
+            .list
+            .list_between(self.week, self.week + Duration::days(7))?;
+        let study = Return {
+            list: events
+                .list
+                .iter()
+                .filter(|x| x.name.category() == Category::DailyStudy)
+                .cloned()
+                .collect(),
+            icons: false,
+            location: events.location.clone(),
+            time_zone: None,
+            fields: None,
+            short_names: false,
+            shabbos_conflicts: None,
+            headers: None,
+            summary: None,
+            meta: false,
+            alarms: vec![],
+            candle_lighting_minutes: None,
+            provenance: None,
+        };
+        events
+            .list
+            .retain(|x| x.name.category() != Category::DailyStudy);
+        let zmanim = self.list.city.as_ref().map(|city| Luach {
+            zmanim: &self.zmanim,
+            days: (0..7)
+                .map(|day| {
+                    get_day(
+                        self.list.solar_algorithm.sun(),
+                        city,
+                        self.week + Duration::days(day),
+                        &self.zmanim,
+                        self.list.polar_fallback,
+                    )
+                })
+                .collect(),
+            table: None,
+        });
+        let digest = Digest {
+            args: self,
+            reading,
+            events,
+            study,
+            zmanim,
+        };
+        print_output(&digest, args)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use crate::args::types::{
+    AppError, Event, FindArgs, Language, MainArgs, MinorHoliday, Name, OutputType, Transliteration,
+};
+use crate::gematria::hebrew_numeral;
+use crate::list::{EventFilter, HebrewYearExt};
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::{Location, TorahReadingType};
+use heca_lib::HebrewYear;
+use std::io::Write;
+
+/// The events which can be found, each listed on its own so that once the event is found, only
+/// the kind of events it's in is listed in the years after it.
+fn searched_events() -> Vec<Event> {
+    vec![
+        Event::TorahReadingType(TorahReadingType::YomTov),
+        Event::TorahReadingType(TorahReadingType::Chol),
+        Event::TorahReadingType(TorahReadingType::Shabbos),
+        Event::TorahReadingType(TorahReadingType::SpecialParsha),
+        Event::MinorHoliday(MinorHoliday::Minor),
+        Event::MinorHoliday(MinorHoliday::Omer),
+        Event::IsraeliHolidays,
+        Event::ChabadHolidays,
+    ]
+}
+
+/// Lowercases the name and leaves out everything but letters and digits, so that `erev-pesach`,
+/// `Erev Pesach` and `ErevPesach` are the same name.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+pub(crate) fn printed_name(
+    name: &Name,
+    language: Language,
+    transliteration: Transliteration,
+) -> String {
+    let mut buf = vec![];
+    name.pretty_print(&mut buf, language, transliteration)
+        .unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Whether the event is called `query`, in English, in Hebrew or by its JSON name.
+fn is_named(name: &Name, query: &str) -> bool {
+    let id = name.id();
+    let json_name = id.split_once(' ').map_or(id.as_str(), |(_, name)| name);
+    normalize(json_name) == query
+        || [
+            (Language::English, Transliteration::Ashkenazi),
+            (Language::English, Transliteration::Sephardi),
+            (Language::Hebrew, Transliteration::Hebrew),
+        ]
+        .iter()
+        .any(|&(language, transliteration)| {
+            normalize(&printed_name(name, language, transliteration)) == query
+        })
+}
+
+/// Returns the kinds of events with an event called `query`, from the first year from `from`
+/// until `until` it's in, so that the years after it only list them. Fails if there's none.
+pub(crate) fn event_filters(
+    query: &str,
+    from: u64,
+    until: u64,
+    location: Location,
+) -> Result<Vec<EventFilter>, AppError> {
+    let name = normalize(query);
+    for year in from..=until {
+        let hebrew_year = HebrewYear::new(year)?;
+        let mut filters = vec![];
+        for filter in searched_events()
+            .into_iter()
+            .map(|x| EventFilter::new(vec![x]))
+        {
+            if hebrew_year
+                .iter_events(location, &filter)?
+                .any(|x| is_named(&x.name, &name))
+            {
+                filters.push(filter);
+            }
+        }
+        if !filters.is_empty() {
+            return Ok(filters);
+        }
+    }
+    Err(AppError::UnknownEvent {
+        event: query.into(),
+        from,
+        until,
+    })
+}
+
+/// The days of the year the event called `query` is on, with the Gregorian date of the day (not
+/// of the night before it), in order.
+pub(crate) fn days_of_event(
+    year: u64,
+    query: &str,
+    filters: &[EventFilter],
+    location: Location,
+) -> Result<Vec<(NaiveDate, Name)>, AppError> {
+    let query = normalize(query);
+    let hebrew_year = HebrewYear::new(year)?;
+    let mut days: Vec<(NaiveDate, Name)> = filters
+        .iter()
+        .map(|filter| hebrew_year.iter_events(location, filter))
+        .collect::<Result<Vec<_>, AppError>>()?
+        .into_iter()
+        .flatten()
+        .filter(|x| is_named(&x.name, &query))
+        .map(|x| ((x.day + Duration::days(1)).date().naive_utc(), x.name))
+        .collect();
+    days.sort_by_key(|(date, _)| *date);
+    Ok(days)
+}
+
+/// A day the event is on.
+struct Found {
+    year: u64,
+    /// The Gregorian date of the day, not of the night before it.
+    date: NaiveDate,
+    name: Name,
+}
+
+/// The output of `heca find`.
+struct Findings {
+    from: u64,
+    until: u64,
+    found: Vec<Found>,
+}
+
+impl FindArgs {
+    /// Lists the years one at a time, and stops at the last year or once `limit` days are found.
+    fn find(&self) -> Result<Findings, AppError> {
+        let filters = event_filters(&self.event, self.from, self.until, self.location)?;
+        let mut found = vec![];
+        for year in self.from..=self.until {
+            found.extend(
+                days_of_event(year, &self.event, &filters, self.location)?
+                    .into_iter()
+                    .filter(|(date, _)| self.weekday.is_none_or(|x| date.weekday() == x))
+                    .map(|(date, name)| Found { year, date, name }),
+            );
+            if found.len() >= self.limit {
+                found.truncate(self.limit);
+                break;
+            }
+        }
+        Ok(Findings {
+            from: self.from,
+            until: self.until,
+            found,
+        })
+    }
+}
+
+impl Findings {
+    /// Writes a line for each day, like "5801: Erev Pesach, Saturday 2041-04-13".
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let date = |date: NaiveDate| match args.language {
+            Language::English => date.format("%A %Y-%m-%d").to_string(),
+            Language::Hebrew => format!(
+                "יום {} {}",
+                hebrew_numeral(date.weekday().number_from_sunday()),
+                date.format("%Y-%m-%d")
+            ),
+        };
+        if self.found.is_empty() {
+            match args.language {
+                Language::English => {
+                    writeln!(lock, "Not found from {} until {}", self.from, self.until)?
+                }
+                Language::Hebrew => writeln!(lock, "לא נמצא מ-{} עד {}", self.from, self.until)?,
+            }
+        }
+        for found in &self.found {
+            writeln!(
+                lock,
+                "{}: {}, {}",
+                found.year,
+                printed_name(&found.name, args.language, args.transliteration),
+                date(found.date)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Printable for Findings {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let found: Vec<serde_json::Value> = self
+                    .found
+                    .iter()
+                    .map(|x| {
+                        serde_json::json!({
+                            "year": x.year,
+                            "date": x.date,
+                            "name": printed_name(&x.name, args.language, args.transliteration),
+                        })
+                    })
+                    .collect();
+                write_data(
+                    &mut lock,
+                    args.output_type,
+                    &serde_json::json!({
+                        "from": self.from,
+                        "until": self.until,
+                        "found": found,
+                    }),
+                )
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for FindArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        print_output(&self.find()?, args)?;
+        Ok(())
+    }
+}
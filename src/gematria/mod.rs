@@ -0,0 +1,190 @@
+//! Gematria: writing numbers in Hebrew letters, like `ט"ו` or `ה'תשפ"ו`, and reading them back.
+//! `build.rs` includes this file too, to number the days in the Hebrew locale files.
+
+const HEBREW_LETTERS: &[(u32, char)] = &[
+    (400, 'ת'),
+    (300, 'ש'),
+    (200, 'ר'),
+    (100, 'ק'),
+    (90, 'צ'),
+    (80, 'פ'),
+    (70, 'ע'),
+    (60, 'ס'),
+    (50, 'נ'),
+    (40, 'מ'),
+    (30, 'ל'),
+    (20, 'כ'),
+    (10, 'י'),
+    (9, 'ט'),
+    (8, 'ח'),
+    (7, 'ז'),
+    (6, 'ו'),
+    (5, 'ה'),
+    (4, 'ד'),
+    (3, 'ג'),
+    (2, 'ב'),
+    (1, 'א'),
+];
+
+/// The value of a Hebrew letter, including the final forms, which are worth the same as the
+/// letter.
+fn letter_value(letter: char) -> Option<u32> {
+    let letter = match letter {
+        'ך' => 'כ',
+        'ם' => 'מ',
+        'ן' => 'נ',
+        'ף' => 'פ',
+        'ץ' => 'צ',
+        x => x,
+    };
+    HEBREW_LETTERS
+        .iter()
+        .find(|(_, x)| *x == letter)
+        .map(|(value, _)| *value)
+}
+
+/// Writes a number below 1000 in letters, without a geresh or gershayim.
+fn letters(number: u32) -> Vec<char> {
+    let mut letters = vec![];
+    let mut rest = number;
+    for (value, letter) in HEBREW_LETTERS {
+        while rest >= *value {
+            letters.push(*letter);
+            rest -= value;
+        }
+    }
+    if let [.., tens @ 'י', ones @ ('ה' | 'ו')] = letters.as_mut_slice() {
+        *tens = 'ט';
+        *ones = if *ones == 'ה' { 'ו' } else { 'ז' };
+    }
+    letters
+}
+
+/// Writes a number in Hebrew letters, such as `ג'` or `ל"ג`: a single letter is followed by a
+/// geresh, and the last letter of a longer number is preceded by gershayim. 15 and 16 are written
+/// `ט"ו` and `ט"ז`, so they don't spell the Name. The thousands are written as a letter with a
+/// geresh before the rest, like `ה'תשפ"ו` for 5786. The number must be between 1 and 9999.
+pub fn hebrew_numeral(number: u32) -> String {
+    assert!(
+        (1..10000).contains(&number),
+        "{} can't be written in Hebrew letters",
+        number
+    );
+    let mut numeral = String::new();
+    if number >= 1000 {
+        numeral.extend(letters(number / 1000));
+        numeral.push('\'');
+    }
+    let mut letters = letters(number % 1000);
+    if let Some(last) = letters.pop() {
+        numeral.extend(letters.iter());
+        if letters.is_empty() {
+            numeral.push(last);
+            numeral.push('\'');
+        } else {
+            numeral.push('"');
+            numeral.push(last);
+        }
+    }
+    numeral
+}
+
+/// Reads a number written in Hebrew letters, like `ט"ו`, `תשפ"ו` or `ה'תשפ"ו`. The geresh and
+/// gershayim can be written as apostrophes and quotes (`'` and `"`) or as the Hebrew marks (`׳`
+/// and `״`), or left out. A single letter with a geresh is read as a number below 10, so `ה'`
+/// is 5 and not 5000. Returns `None` if it isn't a number: if it has other characters, or
+/// letters out of order (other than `טו` and `טז`).
+pub fn parse_hebrew_numeral(numeral: &str) -> Option<u32> {
+    let chars: Vec<char> = numeral.trim().chars().collect();
+    let mut thousands = 0;
+    let mut values: Vec<u32> = vec![];
+    for (index, c) in chars.iter().enumerate() {
+        match c {
+            // A geresh after the thousands is followed by the rest of the number. After the only
+            // letter, it's the end of the number.
+            '\'' | '׳' if index + 1 < chars.len() => {
+                if values.is_empty() || thousands > 0 {
+                    return None;
+                }
+                thousands = in_order(&values)?;
+                values.clear();
+            }
+            '\'' | '׳' | '"' | '״' => {}
+            c => values.push(letter_value(*c)?),
+        }
+    }
+    let rest = if values.is_empty() {
+        0
+    } else {
+        in_order(&values)?
+    };
+    Some(thousands * 1000 + rest).filter(|x| *x > 0)
+}
+
+/// Adds up the letters of a number, if they're in order: from the largest to the smallest, with
+/// ת repeated for 800 and more, and 15 and 16 written as `טו` and `טז`.
+fn in_order(values: &[u32]) -> Option<u32> {
+    let ordered = values.windows(2).enumerate().all(|(index, pair)| {
+        let last = index + 2 == values.len();
+        pair[0] > pair[1]
+            || (pair[0] == 400 && pair[1] == 400)
+            || (last && pair == [9, 6])
+            || (last && pair == [9, 7])
+    });
+    if ordered {
+        Some(values.iter().sum())
+    } else {
+        None
+    }
+}
+
+/// The gematria of a word or a phrase: the sum of its letters, like 376 for `שלום`. Characters
+/// which aren't Hebrew letters are skipped.
+pub fn value(text: &str) -> u32 {
+    text.chars().filter_map(letter_value).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hebrew_numerals() {
+        assert_eq!(hebrew_numeral(1), "א'");
+        assert_eq!(hebrew_numeral(10), "י'");
+        assert_eq!(hebrew_numeral(15), "ט\"ו");
+        assert_eq!(hebrew_numeral(16), "ט\"ז");
+        assert_eq!(hebrew_numeral(33), "ל\"ג");
+        assert_eq!(hebrew_numeral(49), "מ\"ט");
+        assert_eq!(hebrew_numeral(115), "קט\"ו");
+        assert_eq!(hebrew_numeral(785), "תשפ\"ה");
+        assert_eq!(hebrew_numeral(5786), "ה'תשפ\"ו");
+        assert_eq!(hebrew_numeral(5000), "ה'");
+        assert_eq!(hebrew_numeral(5001), "ה'א'");
+    }
+
+    #[test]
+    fn parsed_numerals() {
+        // A letter with a geresh is a number below 10, not thousands.
+        for number in (1..10000).filter(|x| x % 1000 != 0) {
+            assert_eq!(parse_hebrew_numeral(&hebrew_numeral(number)), Some(number));
+        }
+        assert_eq!(parse_hebrew_numeral("תשפ״ו"), Some(786));
+        assert_eq!(parse_hebrew_numeral("ה׳תשפ״ו"), Some(5786));
+        assert_eq!(parse_hebrew_numeral("טו"), Some(15));
+        assert_eq!(parse_hebrew_numeral("תתקצט"), Some(999));
+        assert_eq!(parse_hebrew_numeral("ך"), Some(20));
+        assert_eq!(parse_hebrew_numeral("אב"), None);
+        assert_eq!(parse_hebrew_numeral("יי"), None);
+        assert_eq!(parse_hebrew_numeral("15"), None);
+        assert_eq!(parse_hebrew_numeral(""), None);
+        assert_eq!(parse_hebrew_numeral("'"), None);
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(value("שלום"), 376);
+        assert_eq!(value("חי"), 18);
+        assert_eq!(value("ברוך הבא!"), 228 + 8);
+    }
+}
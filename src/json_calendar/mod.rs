@@ -0,0 +1,97 @@
+use crate::args::types::{AppError, DayVal, Language, Transliteration};
+use chrono::prelude::*;
+use chrono::Duration;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// The fields which say when or how an event is observed, rather than which event it is. An
+/// event whose details differ between the calendars is changed, not removed and added.
+pub const DETAILS: &[&str] = &[
+    "candleLighting",
+    "candleLightingMinutes",
+    "havdalah",
+    "torahPortion",
+    "timeRange",
+    "source",
+];
+
+/// An event from a calendar printed with `--print=json`. Only its `day` is parsed; the rest is
+/// kept as-is.
+#[derive(Clone, Serialize)]
+#[serde(transparent)]
+pub struct Event {
+    #[serde(skip)]
+    pub day: DateTime<FixedOffset>,
+    pub value: Map<String, Value>,
+}
+
+/// Reads a calendar printed with `--print=json`.
+pub fn load(path: &str) -> Result<Vec<Event>, AppError> {
+    let read_error = |err: String| AppError::ReadError(format!("{}: {}", path, err));
+    let file = std::fs::read_to_string(path).map_err(|err| read_error(err.to_string()))?;
+    let values: Vec<Map<String, Value>> =
+        serde_json::from_str(&file).map_err(|err| read_error(err.to_string()))?;
+    values
+        .into_iter()
+        .map(|value| {
+            let day = value
+                .get("day")
+                .and_then(Value::as_str)
+                .and_then(|day| DateTime::parse_from_rfc3339(day).ok())
+                .ok_or_else(|| read_error("every event needs a day".into()))?;
+            Ok(Event { day, value })
+        })
+        .collect()
+}
+
+impl Event {
+    /// What the event is, without when it is or its details.
+    pub fn identity(&self) -> String {
+        let mut identity = self.value.clone();
+        identity.remove("day");
+        // The UID is made of the day and the location, so it doesn't say which event this is.
+        identity.remove("uid");
+        for detail in DETAILS {
+            identity.remove(*detail);
+        }
+        serde_json::to_string(&identity).unwrap()
+    }
+
+    pub fn details(&self) -> Vec<Option<&Value>> {
+        DETAILS.iter().map(|x| self.value.get(*x)).collect()
+    }
+
+    /// The title of the event, as `list` prints it in the language. Events which heca can't
+    /// read back, like those from other calendars, are described by their type, name and topic.
+    pub fn label(&self, language: Language, transliteration: Transliteration) -> String {
+        if let Ok(day) = serde_json::from_value::<DayVal>(Value::Object(self.value.clone())) {
+            let mut title = vec![];
+            if day
+                .name
+                .pretty_print(&mut title, language, transliteration)
+                .is_some()
+            {
+                return String::from_utf8(title).unwrap();
+            }
+        }
+        let mut label = vec![];
+        for field in &["type", "name", "topic"] {
+            match self.value.get(*field) {
+                Some(Value::String(x)) => label.push(x.clone()),
+                Some(Value::Object(x)) => label.extend(x.values().map(|x| match x {
+                    Value::String(x) => x.clone(),
+                    x => x.to_string(),
+                })),
+                Some(Value::Null) | None => {}
+                Some(x) => label.push(x.to_string()),
+            }
+        }
+        label.join(" ")
+    }
+}
+
+/// The civil date of an event. Its `day` is the night it starts on, so that's the next day.
+pub fn format_day(day: DateTime<FixedOffset>) -> String {
+    let day = day + Duration::days(1);
+    format!("{}/{}/{}", day.year(), day.month(), day.day())
+}
@@ -0,0 +1,35 @@
+//! The library behind the `heca` command line tool. Besides the tool itself, it exposes the event
+//! providers `heca list` is built from (see [`EventProvider`] and [`list::get_events`]), so other
+//! crates can list their own events alongside them. [`list::HebrewYearExt`] lists a single
+//! year's events a month at a time, for searches which stop at the first match.
+
+pub mod algorithms;
+pub mod anchors;
+pub mod args;
+mod bar_mitzvah;
+mod batch;
+mod catchup;
+mod check_date;
+mod convert;
+mod diff;
+mod digest;
+mod find;
+pub mod gematria;
+mod json_calendar;
+mod limits;
+pub mod list;
+mod merge;
+pub mod month_info;
+mod plan_siyum;
+pub mod prelude;
+mod self_test;
+mod slug;
+mod stats;
+mod status_bar;
+mod version;
+pub mod year_info;
+mod year_table;
+mod zmanim_table;
+
+use crate::args::types;
+pub use crate::prelude::{EventProvider, Runnable};
@@ -0,0 +1,87 @@
+use crate::args::types::{AppError, Language, LimitsArgs, MainArgs, OutputType};
+use crate::prelude::constants::{
+    MAX_GREGORIAN_YEAR, MAX_HEBREW_YEAR, MIN_GREGORIAN_YEAR, MIN_HEBREW_YEAR,
+};
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use serde::Serialize;
+use std::io::Write;
+
+/// The years heca can list and convert.
+#[derive(Serialize)]
+pub(crate) struct Limits {
+    hebrew: Range,
+    gregorian: Range,
+}
+
+#[derive(Serialize)]
+struct Range {
+    min: u64,
+    max: u64,
+}
+
+impl Runnable for LimitsArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        print_output(&Limits::new(), args)?;
+        Ok(())
+    }
+}
+
+impl Limits {
+    pub(crate) fn new() -> Self {
+        Limits {
+            hebrew: Range {
+                min: MIN_HEBREW_YEAR,
+                max: MAX_HEBREW_YEAR,
+            },
+            gregorian: Range {
+                min: MIN_GREGORIAN_YEAR,
+                max: MAX_GREGORIAN_YEAR,
+            },
+        }
+    }
+
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        match args.language {
+            Language::English => {
+                writeln!(
+                    lock,
+                    "Hebrew years: {} to {}",
+                    self.hebrew.min, self.hebrew.max
+                )?;
+                writeln!(
+                    lock,
+                    "Gregorian years: {} to {}",
+                    self.gregorian.min, self.gregorian.max
+                )
+            }
+            Language::Hebrew => {
+                writeln!(
+                    lock,
+                    "שנים עבריות: {} עד {}",
+                    self.hebrew.min, self.hebrew.max
+                )?;
+                writeln!(
+                    lock,
+                    "שנים לועזיות: {} עד {}",
+                    self.gregorian.min, self.gregorian.max
+                )
+            }
+        }
+    }
+}
+
+impl Printable for Limits {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
@@ -0,0 +1,44 @@
+//! `--append-since-last-run`, which keeps the last date printed in a state file, so a cron job
+//! which runs `heca list` again only prints the days it didn't print yet.
+
+use crate::args::types::{AppError, DayVal};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What's kept in the state file between runs.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct State {
+    /// The civil date of the last day printed.
+    last_date: NaiveDate,
+}
+
+/// Returns the last date printed by the runs before, or `None` on the first run, when the state
+/// file doesn't exist yet.
+pub(crate) fn last_date(path: &Path) -> Result<Option<NaiveDate>, AppError> {
+    let read_error = |err: String| AppError::ReadError(format!("{}: {}", path.display(), err));
+    let state = match std::fs::read_to_string(path) {
+        Ok(state) => state,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(read_error(err.to_string())),
+    };
+    let state: State = serde_json::from_str(&state).map_err(|e| read_error(e.to_string()))?;
+    Ok(Some(state.last_date))
+}
+
+/// Records the last date of the days printed. Nothing is recorded if no days were printed, so
+/// the date of the runs before is kept. The state is written to a file next to it first and
+/// then moved over it, so a run which is stopped halfway doesn't leave it half written.
+pub(crate) fn record(path: &Path, printed: &[DayVal]) -> Result<(), AppError> {
+    let last_date = match printed.iter().map(DayVal::civil_date).max() {
+        Some(last_date) => last_date,
+        None => return Ok(()),
+    };
+    let write_error =
+        |err: std::io::Error| AppError::WriteError(format!("{}: {}", path.display(), err));
+    let temporary = path.with_extension("tmp");
+    let state = serde_json::to_string(&State { last_date }).unwrap();
+    std::fs::write(&temporary, state + "\n").map_err(write_error)?;
+    std::fs::rename(&temporary, path).map_err(write_error)
+}
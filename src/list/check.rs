@@ -0,0 +1,127 @@
+use super::summary::Summary;
+use super::{hebrew_year, write_date, Return};
+use crate::args::types::{AppError, Event, Language, ListArgs, MainArgs, OutputType};
+use crate::prelude::{write_data, write_error, CountingWriter, Printable};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::*;
+use serde::Serialize;
+use std::io::Write;
+
+/// What `heca list` would print, reported by `--check` instead of the events: the inputs it
+/// read, the years and dates it covers and how many events it has.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Check {
+    location: Location,
+    city: Option<CheckedCity>,
+    custom_days: usize,
+    first_hebrew_year: u64,
+    last_hebrew_year: u64,
+    #[serde(flatten)]
+    summary: Summary,
+}
+
+#[derive(Serialize)]
+struct CheckedCity {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+impl Check {
+    /// Takes the list made with `summary` set, which is always the case with `--check`.
+    pub(crate) fn new(list_args: &ListArgs, result: Return) -> Self {
+        let summary = result.summary.unwrap();
+        let night_before =
+            |date: NaiveDate| Utc.from_utc_date(&date).and_hms(18, 0, 0) - Duration::days(1);
+        Check {
+            location: list_args.location,
+            city: list_args.city.as_ref().map(|city| CheckedCity {
+                name: city.name.to_string(),
+                latitude: city.latitude,
+                longitude: city.longitude,
+            }),
+            custom_days: list_args
+                .events
+                .iter()
+                .filter(|x| matches!(x, Event::CustomHoliday(_)))
+                .count(),
+            first_hebrew_year: hebrew_year(night_before(summary.from)),
+            last_hebrew_year: hebrew_year(night_before(summary.until)),
+            summary,
+        }
+    }
+
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let location = match (self.location, args.language) {
+            (Location::Israel, Language::English) => "Israel",
+            (Location::Chul, Language::English) => "Chutz La'aretz",
+            (Location::Israel, Language::Hebrew) => "ארץ ישראל",
+            (Location::Chul, Language::Hebrew) => "חוץ לארץ",
+        };
+        let (location_label, city_label, custom_label, years_label, dates_label, events_label) =
+            match args.language {
+                Language::English => (
+                    "Location",
+                    "City",
+                    "Custom days",
+                    "Hebrew years",
+                    "Dates",
+                    "Events",
+                ),
+                Language::Hebrew => (
+                    "מקום",
+                    "עיר",
+                    "ימים מותאמים",
+                    "שנים עבריות",
+                    "תאריכים",
+                    "אירועים",
+                ),
+            };
+        let to = match args.language {
+            Language::English => " to ",
+            Language::Hebrew => " עד ",
+        };
+        writeln!(lock, "{}: {}", location_label, location)?;
+        if let Some(city) = &self.city {
+            writeln!(
+                lock,
+                "{}: {} ({}, {})",
+                city_label, city.name, city.latitude, city.longitude
+            )?;
+        }
+        writeln!(lock, "{}: {}", custom_label, self.custom_days)?;
+        writeln!(
+            lock,
+            "{}: {}{}{}",
+            years_label, self.first_hebrew_year, to, self.last_hebrew_year
+        )?;
+        write!(lock, "{}: ", dates_label)?;
+        write_date(lock, Utc.from_utc_date(&self.summary.from).and_hms(0, 0, 0))?;
+        lock.write_all(to.as_bytes())?;
+        write_date(
+            lock,
+            Utc.from_utc_date(&self.summary.until).and_hms(0, 0, 0),
+        )?;
+        write!(lock, "\n{}: ", events_label)?;
+        self.summary.write_counts(lock, args.language)?;
+        lock.write_all(b"\n")
+    }
+}
+
+impl Printable for Check {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
@@ -0,0 +1,127 @@
+use super::providers::is_yom_tov_day;
+use super::write_date;
+use crate::args::types::{DayVal, Language, MainArgs, Name, Transliteration};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::*;
+use heca_lib::{HebrewDate, HebrewYear};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::Write;
+
+/// A custom day which lands on Shabbos or Yom Tov, such as a siyum or a community dinner which
+/// has to be moved that year.
+#[derive(Debug, Clone)]
+pub struct ShabbosConflict {
+    pub day: DayVal,
+    pub shabbos: bool,
+    pub yom_tov: Option<YomTov>,
+}
+
+/// Returns the custom days in the list which land on Shabbos or on a day of Yom Tov where work
+/// is forbidden, in the order they're listed.
+pub fn shabbos_conflicts(list: &[DayVal], location: Location) -> Vec<ShabbosConflict> {
+    let mut yom_tov_nights: HashMap<u64, HashMap<DateTime<Utc>, YomTov>> = HashMap::new();
+    list.iter()
+        .filter(|x| matches!(x.name, Name::CustomHoliday(_)))
+        .filter_map(|x| {
+            let year = HebrewDate::try_from(x.day).unwrap().year();
+            let nights = yom_tov_nights.entry(year).or_insert_with(|| {
+                HebrewYear::new(year)
+                    .unwrap()
+                    .get_holidays(location, &[TorahReadingType::YomTov])
+                    .into_iter()
+                    .filter_map(|x| match x.name() {
+                        TorahReading::YomTov(yt) if is_yom_tov_day(yt, location) => {
+                            Some((x.day().into(), yt))
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            });
+            // The day is stored as the night it starts on, so Shabbos starts on Friday night.
+            let shabbos = (x.day + Duration::days(1)).weekday() == Weekday::Sat;
+            let yom_tov = nights.get(&x.day).copied();
+            if shabbos || yom_tov.is_some() {
+                Some(ShabbosConflict {
+                    day: x.clone(),
+                    shabbos,
+                    yom_tov,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+impl ShabbosConflict {
+    /// Writes the civil date of the day, its name and what it lands on, like "2025/3/8: Siyum
+    /// lands on Shabbos".
+    fn pretty_print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+        transliteration: Transliteration,
+    ) -> std::io::Result<()> {
+        write_date(lock, self.day.day + Duration::days(1))?;
+        lock.write_all(b": ")?;
+        self.day
+            .name
+            .pretty_print(lock, language, transliteration)
+            .unwrap();
+        match language {
+            Language::English => lock.write_all(b" lands on ")?,
+            Language::Hebrew => lock.write_all(" חל ב".as_bytes())?,
+        };
+        if self.shabbos {
+            match language {
+                Language::English => lock.write_all(b"Shabbos")?,
+                Language::Hebrew => lock.write_all("שבת".as_bytes())?,
+            };
+        }
+        if let Some(yom_tov) = self.yom_tov {
+            if self.shabbos {
+                match language {
+                    Language::English => lock.write_all(b" and ")?,
+                    Language::Hebrew => lock.write_all(" ו".as_bytes())?,
+                };
+            }
+            Name::TorahReading(TorahReading::YomTov(yom_tov))
+                .pretty_print(lock, language, transliteration)
+                .unwrap();
+        }
+        Ok(())
+    }
+
+    /// The conflict as it's reported in a warning.
+    pub fn message(&self) -> String {
+        let mut message = vec![];
+        self.pretty_print(&mut message, Language::English, Transliteration::Ashkenazi)
+            .unwrap();
+        String::from_utf8(message).unwrap()
+    }
+}
+
+/// Writes the conflicts as a section after the list, or a line saying there aren't any.
+pub(super) fn print_section<W: Write>(
+    conflicts: &[ShabbosConflict],
+    args: &MainArgs,
+    lock: &mut W,
+) -> std::io::Result<()> {
+    match args.language {
+        Language::English => lock.write_all(b"\nCustom days on Shabbos or Yom Tov:\n")?,
+        Language::Hebrew => lock.write_all("\nימים מותאמים בשבת או ביום טוב:\n".as_bytes())?,
+    };
+    if conflicts.is_empty() {
+        match args.language {
+            Language::English => lock.write_all(b"None\n")?,
+            Language::Hebrew => lock.write_all("אין\n".as_bytes())?,
+        };
+    }
+    for conflict in conflicts {
+        conflict.pretty_print(lock, args.language, args.transliteration)?;
+        lock.write_all(b"\n")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,485 @@
+use super::{Return, CANDLE_LIGHTING_ICON, HAVDALAH_ICON};
+use crate::algorithms::candle_lighting::City;
+use crate::algorithms::solar::tz;
+use crate::args::types::{
+    Alarm, AlarmTarget, AlarmTime, DayVal, Language, MainArgs, Name, ZmanTime,
+};
+use crate::prelude::hebrew_month_english;
+use crate::prelude::system::now;
+use crate::slug::{push_slug, slugify};
+use chrono::prelude::*;
+use chrono::Duration;
+use chrono::TimeZone as _;
+use chrono_tz::OffsetComponents;
+use heca_lib::prelude::Location;
+use heca_lib::HebrewDate;
+use std::convert::TryFrom;
+use std::io::{Result, Write};
+use zmanim::prelude::tz::TimeZone;
+
+/// Prints the list as an iCalendar (RFC 5545) file of all-day events. A day which
+/// starts on the night of the 2nd is shown on the 3rd, the way calendars show it. With `icons`,
+/// the summary starts with the emoji of the event, candle lighting and havdalah.
+///
+/// The times listed with `--show zmanim:...` are events at that time in the city's time zone,
+/// which is written out as a VTIMEZONE, so that a calendar in another time zone still shows
+/// candle lighting in New York at the time it is in New York. Events with a time range, like
+/// fasts, last from their start until their end in that time zone.
+///
+/// Events get a VALARM for each of the `alarms` which applies to them. With `meta`, the hash of
+/// the events and the arguments are the X-WR-HASH and X-WR-PARAMETERS of the calendar.
+pub fn print<W: Write>(result: &Return, args: &MainArgs, lock: &mut W) -> Result<()> {
+    let Return {
+        list,
+        icons,
+        location,
+        alarms,
+        ..
+    } = result;
+    let dtstamp = begin_calendar(lock, now(args.today, None))?;
+    if result.meta {
+        let meta = result.metadata();
+        write_line(lock, &format!("X-WR-HASH:{}", meta.hash))?;
+        write_line(
+            lock,
+            &format!("X-WR-PARAMETERS:{}", escape(&meta.arguments.join(" "))),
+        )?;
+    }
+    let times: Vec<DateTime<FixedOffset>> = list.iter().filter_map(time).collect();
+    let tzid = match (&result.time_zone, times.first(), times.last()) {
+        (Some(time_zone), Some(first), Some(last)) => {
+            let tzid = tz(time_zone).name().to_owned();
+            let (from, until) = times.iter().fold((*first, *last), |(from, until), x| {
+                (from.min(*x), until.max(*x))
+            });
+            write_time_zone(
+                lock,
+                &tzid,
+                time_zone,
+                (
+                    from.naive_utc().date().pred(),
+                    until.naive_utc().date().succ(),
+                ),
+            )?;
+            Some(tzid)
+        }
+        _ => None,
+    };
+    for d in list {
+        let triggers: Vec<String> = alarms
+            .iter()
+            .filter_map(|alarm| trigger(d, tzid.as_ref().and(time(d)), alarm))
+            .collect();
+        if let (Some(tzid), Some(time)) = (&tzid, time(d)) {
+            write_timed_event(
+                lock,
+                &dtstamp,
+                &uid(d, location),
+                (
+                    tzid,
+                    time.naive_local(),
+                    end_time(d).map(|x| x.naive_local()),
+                ),
+                (
+                    &summary(d, *icons, args),
+                    &description(d, result.candle_lighting_minutes, args),
+                ),
+                &triggers,
+            )?;
+            continue;
+        }
+        let start = d.day + Duration::days(1);
+        let end = match &d.name {
+            Name::Span(span) => span.end + Duration::days(1),
+            _ => start + Duration::days(1),
+        };
+        write_all_day_event(
+            lock,
+            &dtstamp,
+            &uid(d, location),
+            (start.naive_utc().date(), end.naive_utc().date()),
+            (
+                &summary(d, *icons, args),
+                &description(d, result.candle_lighting_minutes, args),
+            ),
+            &triggers,
+        )?;
+    }
+    end_calendar(lock)
+}
+
+/// The location events are listed for, as it's written in their UIDs: `israel` or `chul`,
+/// followed by the city if candle lighting times are listed.
+pub fn location_id(location: Location, city: Option<&City>) -> String {
+    let location = match location {
+        Location::Israel => "israel",
+        Location::Chul => "chul",
+    };
+    match city {
+        Some(city) => format!("{}-{}", location, slugify(&city.name)),
+        None => location.into(),
+    }
+}
+
+/// A UID made of the event, the Hebrew date it starts on and the location, like
+/// `yomtov-pesach1-5785-nissan-15-chul@heca`. It's the same every time the calendar is printed,
+/// so that importing an updated calendar updates its events instead of adding them again.
+pub fn uid(d: &DayVal, location: &str) -> String {
+    use std::fmt::Write;
+    let date = HebrewDate::try_from(d.day).unwrap();
+    let mut uid = String::with_capacity(64);
+    push_slug(&mut uid, &d.name.id());
+    write!(uid, "-{}-", date.year()).unwrap();
+    push_slug(&mut uid, hebrew_month_english(date.month()));
+    write!(uid, "-{}-{}@heca", date.day(), location).unwrap();
+    uid
+}
+
+/// Writes the start of the calendar, and returns the time stamp of its events, which is `now`.
+pub fn begin_calendar<W: Write>(lock: &mut W, now: DateTime<Utc>) -> Result<String> {
+    write_line(lock, "BEGIN:VCALENDAR")?;
+    write_line(lock, "VERSION:2.0")?;
+    write_line(
+        lock,
+        concat!("PRODID:-//heca//heca ", env!("CARGO_PKG_VERSION"), "//EN"),
+    )?;
+    write_line(lock, "CALSCALE:GREGORIAN")?;
+    Ok(now.format("%Y%m%dT%H%M%SZ").to_string())
+}
+
+pub fn end_calendar<W: Write>(lock: &mut W) -> Result<()> {
+    write_line(lock, "END:VCALENDAR")?;
+    lock.flush()
+}
+
+/// Writes an all-day event from the start date until (but not including) the end date. The
+/// description is left out if it's empty.
+pub fn write_event<W: Write>(
+    lock: &mut W,
+    dtstamp: &str,
+    uid: &str,
+    dates: (NaiveDate, NaiveDate),
+    summary: &str,
+    description: &str,
+) -> Result<()> {
+    write_all_day_event(lock, dtstamp, uid, dates, (summary, description), &[])
+}
+
+/// Writes an all-day event with an alarm for each of the triggers.
+fn write_all_day_event<W: Write>(
+    lock: &mut W,
+    dtstamp: &str,
+    uid: &str,
+    (start, end): (NaiveDate, NaiveDate),
+    (summary, description): (&str, &str),
+    triggers: &[String],
+) -> Result<()> {
+    write_line(lock, "BEGIN:VEVENT")?;
+    write_line(lock, &format!("UID:{}", uid))?;
+    write_line(lock, &format!("DTSTAMP:{}", dtstamp))?;
+    write_line(
+        lock,
+        &format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")),
+    )?;
+    write_line(lock, &format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")))?;
+    write_line(lock, &format!("SUMMARY:{}", escape(summary)))?;
+    if !description.is_empty() {
+        write_line(lock, &format!("DESCRIPTION:{}", escape(description)))?;
+    }
+    write_alarms(lock, summary, triggers)?;
+    write_line(lock, "END:VEVENT")
+}
+
+/// Writes an event at a time in the time zone, which lasts until `end` if it has one, and
+/// otherwise has no duration.
+fn write_timed_event<W: Write>(
+    lock: &mut W,
+    dtstamp: &str,
+    uid: &str,
+    (tzid, start, end): (&str, NaiveDateTime, Option<NaiveDateTime>),
+    (summary, description): (&str, &str),
+    triggers: &[String],
+) -> Result<()> {
+    write_line(lock, "BEGIN:VEVENT")?;
+    write_line(lock, &format!("UID:{}", uid))?;
+    write_line(lock, &format!("DTSTAMP:{}", dtstamp))?;
+    write_line(
+        lock,
+        &format!("DTSTART;TZID={}:{}", tzid, start.format("%Y%m%dT%H%M%S")),
+    )?;
+    if let Some(end) = end {
+        write_line(
+            lock,
+            &format!("DTEND;TZID={}:{}", tzid, end.format("%Y%m%dT%H%M%S")),
+        )?;
+    }
+    write_line(lock, &format!("SUMMARY:{}", escape(summary)))?;
+    if !description.is_empty() {
+        write_line(lock, &format!("DESCRIPTION:{}", escape(description)))?;
+    }
+    write_alarms(lock, summary, triggers)?;
+    write_line(lock, "END:VEVENT")
+}
+
+/// Writes an alarm which shows the summary of the event for each of the triggers.
+fn write_alarms<W: Write>(lock: &mut W, summary: &str, triggers: &[String]) -> Result<()> {
+    for trigger in triggers {
+        write_line(lock, "BEGIN:VALARM")?;
+        write_line(lock, "ACTION:DISPLAY")?;
+        write_line(lock, &format!("DESCRIPTION:{}", escape(summary)))?;
+        write_line(lock, &format!("TRIGGER{}", trigger))?;
+        write_line(lock, "END:VALARM")?;
+    }
+    Ok(())
+}
+
+/// The TRIGGER of the alarm, as it's written after `TRIGGER`, if the alarm applies to the
+/// event. Alarms on candle lighting and havdalah go off relative to their time. Others go off
+/// relative to the start of the event: its time if it's a time written in the time zone, and
+/// otherwise midnight of its first day, so a time of the day is an offset from midnight.
+fn trigger(d: &DayVal, time: Option<DateTime<FixedOffset>>, alarm: &Alarm) -> Option<String> {
+    let relative_to = |time: DateTime<FixedOffset>| {
+        let time = match alarm.when {
+            AlarmTime::Offset(offset) => time + offset,
+            AlarmTime::At(at) => chrono::TimeZone::from_local_datetime(
+                time.offset(),
+                &time.naive_local().date().and_time(at),
+            )
+            .unwrap(),
+        };
+        format!(
+            ";VALUE=DATE-TIME:{}",
+            time.with_timezone(&Utc).format("%Y%m%dT%H%M%SZ")
+        )
+    };
+    let applies = match alarm.target {
+        AlarmTarget::CandleLighting => return d.candle_lighting.flatten().map(relative_to),
+        AlarmTarget::Havdalah => return d.havdalah.iter().find_map(|x| x.time).map(relative_to),
+        AlarmTarget::All => true,
+        AlarmTarget::Category(category) => d.name.category() == category,
+        AlarmTarget::DailyStudy(id) => matches!(d.name, Name::DailyStudy(_)) && d.name.id() == id,
+    };
+    if !applies {
+        return None;
+    }
+    Some(match (time, alarm.when) {
+        (Some(time), AlarmTime::At(_)) => relative_to(time),
+        (_, AlarmTime::Offset(offset)) => format!(":{}", duration(offset)),
+        (None, AlarmTime::At(at)) => format!(":{}", duration(at - NaiveTime::from_hms(0, 0, 0))),
+    })
+}
+
+/// A duration (RFC 5545, section 3.3.6), like `-PT18M` or `P1DT2H`, to the minute.
+fn duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().abs();
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    let mut written = String::from(if duration < Duration::zero() {
+        "-P"
+    } else {
+        "P"
+    });
+    if days > 0 {
+        written.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 || days == 0 {
+        written.push('T');
+        if hours > 0 {
+            written.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 || hours == 0 {
+            written.push_str(&format!("{}M", minutes));
+        }
+    }
+    written
+}
+
+/// The time of a zman, candle lighting or havdalah listed as an event of its own, if it occurs,
+/// or the start of an event with a time range, if it starts and ends that day.
+fn time(d: &DayVal) -> Option<DateTime<FixedOffset>> {
+    match &d.name {
+        Name::Zman(ZmanTime { time, .. }) => *time,
+        _ => end_time(d).and(d.time_range?.start),
+    }
+}
+
+/// The end of an event with a time range, if it starts and ends that day.
+fn end_time(d: &DayVal) -> Option<DateTime<FixedOffset>> {
+    let time_range = d.time_range?;
+    time_range.start.and(time_range.end)
+}
+
+/// Writes a VTIMEZONE with the offset of the time zone at the start of `from`, and every time it
+/// changes until `until` (RFC 5545, section 3.6.5), as the tz database has them. Each offset is standard
+/// time or daylight saving time by whether daylight saving time is in effect then.
+fn write_time_zone<W: Write>(
+    lock: &mut W,
+    tzid: &str,
+    time_zone: &TimeZone,
+    (from, until): (NaiveDate, NaiveDate),
+) -> Result<()> {
+    let from = Utc.from_utc_datetime(&from.and_hms(0, 0, 0));
+    let until = Utc.from_utc_datetime(&until.and_hms(0, 0, 0));
+    let tz = tz(time_zone);
+    let offset = |instant: DateTime<Utc>| tz.offset_from_utc_datetime(&instant.naive_utc());
+    // When every change happens, and the offsets before and after it. The offset is checked
+    // every day, and a change is narrowed down to the second it happens at.
+    let first = offset(from);
+    let mut changes = vec![(from, first, first)];
+    let mut before = from;
+    while before < until {
+        let after = before + Duration::days(1);
+        if offset(after).fix() != offset(before).fix() {
+            let (mut low, mut high) = (before, after);
+            while high - low > Duration::seconds(1) {
+                let middle = low + (high - low) / 2;
+                if offset(middle).fix() == offset(low).fix() {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+            changes.push((high, offset(low), offset(high)));
+        }
+        before = after;
+    }
+    write_line(lock, "BEGIN:VTIMEZONE")?;
+    write_line(lock, &format!("TZID:{}", tzid))?;
+    for (start, from, to) in changes {
+        let kind = if to.dst_offset() == Duration::zero() {
+            "STANDARD"
+        } else {
+            "DAYLIGHT"
+        };
+        write_line(lock, &format!("BEGIN:{}", kind))?;
+        // The start is in the local time before the change.
+        write_line(
+            lock,
+            &format!(
+                "DTSTART:{}",
+                (start.naive_utc() + from.fix()).format("%Y%m%dT%H%M%S")
+            ),
+        )?;
+        write_line(lock, &format!("TZOFFSETFROM:{}", utc_offset(from.fix())))?;
+        write_line(lock, &format!("TZOFFSETTO:{}", utc_offset(to.fix())))?;
+        write_line(lock, &format!("END:{}", kind))?;
+    }
+    write_line(lock, "END:VTIMEZONE")
+}
+
+/// Formats an offset like `-0500` (RFC 5545, section 3.3.14).
+fn utc_offset(offset: FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+}
+
+fn summary(d: &DayVal, icons: bool, args: &MainArgs) -> String {
+    let mut summary = vec![];
+    if icons {
+        let icons: Vec<&str> = d
+            .name
+            .icon()
+            .into_iter()
+            .chain(d.candle_lighting.map(|_| CANDLE_LIGHTING_ICON))
+            .chain(Some(HAVDALAH_ICON).filter(|_| !d.havdalah.is_empty()))
+            .collect();
+        if !icons.is_empty() {
+            write!(summary, "{} ", icons.join("")).unwrap();
+        }
+    }
+    d.name
+        .pretty_print(&mut summary, args.language, args.transliteration)
+        .unwrap();
+    if let Some(second_language) = args.second_language {
+        summary.extend_from_slice(b" | ");
+        d.name
+            .pretty_print(&mut summary, second_language, args.transliteration)
+            .unwrap();
+    }
+    String::from_utf8(summary).unwrap()
+}
+
+/// The times of candle lighting and havdalah, with how many minutes before sunset candles are
+/// lit, like `Candle lighting 17:40 (18 minutes before sunset)`.
+fn description(d: &DayVal, candle_lighting_minutes: Option<u8>, args: &MainArgs) -> String {
+    let mut description = String::new();
+    if let Some(Some(candle_lighting_time)) = d.candle_lighting {
+        match args.language {
+            Language::English => description.push_str("Candle lighting "),
+            Language::Hebrew => description.push_str("הדלקת נרות "),
+        };
+        description.push_str(&candle_lighting_time.format("%H:%M").to_string());
+        if let Some(minutes) = candle_lighting_minutes {
+            description.push_str(&match args.language {
+                Language::English => format!(" ({} minutes before sunset)", minutes),
+                Language::Hebrew => format!(" ({} דקות לפני השקיעה)", minutes),
+            });
+        }
+    }
+    for havdalah in &d.havdalah {
+        if !description.is_empty() {
+            description.push('\n');
+        }
+        match args.language {
+            Language::English => description.push_str("Havdalah "),
+            Language::Hebrew => description.push_str("הבדלה "),
+        };
+        match havdalah.time {
+            Some(time) => description.push_str(&time.format("%H:%M").to_string()),
+            None => match args.language {
+                Language::English => description.push_str("does not occur"),
+                Language::Hebrew => description.push_str("לא חל"),
+            },
+        }
+        if d.havdalah.len() > 1 {
+            let mut opinion = vec![];
+            havdalah.opinion.pretty_print(&mut opinion, args.language);
+            description.push_str(&format!(" ({})", String::from_utf8(opinion).unwrap()));
+        }
+    }
+    if let Some(torah_portion) = &d.torah_portion {
+        let mut verses = vec![];
+        torah_portion
+            .pretty_print(&mut verses, args.language)
+            .unwrap();
+        let verses = String::from_utf8(verses).unwrap();
+        let verses = verses.trim().trim_start_matches('(').trim_end_matches(')');
+        if !description.is_empty() {
+            description.push('\n');
+        }
+        description.push_str(verses);
+    }
+    description
+}
+
+/// Escapes a TEXT value (RFC 5545, section 3.3.11).
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes a content line, folding it so that no line is longer than 75 octets
+/// (RFC 5545, section 3.1). Lines are only folded on character boundaries.
+fn write_line<W: Write>(lock: &mut W, line: &str) -> Result<()> {
+    let mut written = 0;
+    for c in line.chars() {
+        if written + c.len_utf8() > 75 {
+            lock.write_all(b"\r\n ")?;
+            written = 1;
+        }
+        let mut buf = [0; 4];
+        lock.write_all(c.encode_utf8(&mut buf).as_bytes())?;
+        written += c.len_utf8();
+    }
+    lock.write_all(b"\r\n")
+}
@@ -0,0 +1,193 @@
+use super::Return;
+use crate::algorithms::leining::{self, Sefer, Verses};
+use crate::algorithms::reading_cycle::split_parsha;
+use crate::args::types::{
+    AppError, Daf, DailyStudyOutput, Language, LinkFormat, MainArgs, Name, OutputType,
+    RambamChapter, Transliteration,
+};
+use crate::prelude::{write_data, write_error, CountingWriter, Printable};
+use std::io::Write;
+
+const SEFARIA: &str = "https://www.sefaria.org/";
+
+/// The daily study of a list with links to its text, printed by `--links` instead of the list.
+pub(crate) struct Links {
+    list: Return,
+    format: LinkFormat,
+}
+
+/// The Sefaria reference of a daf of the Bavli, like `Bava_Kamma.2`, which is both of its
+/// amudim. Meilah is followed by Kinnim, Tamid and Middos in the Daf Yomi, and Kinnim and Middos
+/// only have a Mishnah. Shekalim is learned from the Yerushalmi, so it links to the masechta
+/// like the Yerushalmi Yomi does.
+fn daf_ref(daf: &Daf) -> String {
+    let masechta = daf
+        .masechta_name(Language::English, Transliteration::Sephardi)
+        .replace(' ', "_");
+    match (masechta.as_str(), daf.daf()) {
+        ("Shekalim", _) => yerushalmi_ref("Jerusalem Talmud Shekalim"),
+        ("Meilah", 23..=24) => String::from("Mishnah_Kinnim"),
+        ("Meilah", 25) => String::from("Tamid.25b"),
+        ("Meilah", 26..=33) => format!("Tamid.{}", daf.daf()),
+        ("Meilah", 34..=37) => String::from("Mishnah_Middot"),
+        (masechta, daf) => format!("{}.{}", masechta, daf),
+    }
+}
+
+/// The Sefaria reference of a masechta of the Yerushalmi, like `Jerusalem_Talmud_Sheviit`.
+/// Sefaria divides the Yerushalmi into chapters and halachos, not into the dapim of the Vilna
+/// edition it's learned by, so a daf can't be linked to on its own.
+fn yerushalmi_ref(masechta: &str) -> String {
+    masechta.replace('\'', "").replace(' ', "_")
+}
+
+/// The parts of the Rambam's introduction, which Sefaria divides into mitzvos instead of the
+/// chapters they're learned in, so they link to the whole part.
+const RAMBAM_INTRODUCTION: &[&str] = &[
+    "Transmission of the Oral Law",
+    "Positive Mitzvot",
+    "Negative Mitzvot",
+    "Overview of Mishneh Torah Contents",
+];
+
+/// The Sefaria references of chapters of the Rambam, like `Mishneh_Torah%2C_Repentance.1-3`.
+/// Chapters learned on the same day can be in two halachos, so they're a reference for each.
+fn rambam_refs(chapters: &[&RambamChapter]) -> Vec<String> {
+    let mut refs: Vec<(&str, u8, u8)> = vec![];
+    for chapter in chapters {
+        match refs.last_mut() {
+            Some((halacha, _, last)) if *halacha == chapter.halacha_name() => {
+                *last = chapter.chapter()
+            }
+            _ => refs.push((chapter.halacha_name(), chapter.chapter(), chapter.chapter())),
+        }
+    }
+    refs.into_iter()
+        .map(|(halacha, first, last)| {
+            let book = format!("Mishneh_Torah%2C_{}", halacha.replace(' ', "_"));
+            if RAMBAM_INTRODUCTION.contains(&halacha) {
+                book
+            } else if first == last {
+                format!("{}.{}", book, first)
+            } else {
+                format!("{}.{}-{}", book, first, last)
+            }
+        })
+        .collect()
+}
+
+/// The Sefaria reference of verses of the Torah, like `Exodus.30.11-16`.
+fn verses_ref(verses: &Verses) -> String {
+    let book = match verses.sefer {
+        Sefer::Torah(book) => book.english(),
+        Sefer::Navi(navi) => navi.english(),
+    }
+    .replace(' ', "_");
+    if verses.from.0 == verses.to.0 {
+        format!(
+            "{}.{}.{}-{}",
+            book, verses.from.0, verses.from.1, verses.to.1
+        )
+    } else {
+        format!(
+            "{}.{}.{}-{}.{}",
+            book, verses.from.0, verses.from.1, verses.to.0, verses.to.1
+        )
+    }
+}
+
+/// Returns links to the text of the daily study on Sefaria. The Chumash of a doubled parsha is
+/// the aliyah of both of its parshiyos, so it has a link to each, like three chapters of the
+/// Rambam in two halachos.
+pub(crate) fn sefaria_links(study: &DailyStudyOutput) -> Vec<String> {
+    let refs = match study {
+        DailyStudyOutput::Daf(daf) | DailyStudyOutput::DafHashavua(daf) => vec![daf_ref(daf)],
+        DailyStudyOutput::Chumash(chumash) => {
+            let aliyah = chumash.aliyah() as usize - 1;
+            match chumash.parsha() {
+                Some(parsha) => split_parsha(parsha)
+                    .iter()
+                    .map(|&x| verses_ref(&leining::aliyos(x).unwrap()[aliyah]))
+                    .collect(),
+                None => vec![verses_ref(&leining::vezos_haberachah_aliyos()[aliyah])],
+            }
+        }
+        DailyStudyOutput::RambamThreeChapters(chapters) => rambam_refs(&chapters.chapters()),
+        DailyStudyOutput::RambamOneChapters(chapter) => rambam_refs(&[chapter]),
+        DailyStudyOutput::YerushalmiYomi(yerushalmi) => {
+            vec![yerushalmi_ref(yerushalmi.masechta_name())]
+        }
+    };
+    refs.into_iter()
+        .map(|x| format!("{}{}", SEFARIA, x))
+        .collect()
+}
+
+/// Quotes a field of CSV if it needs to be.
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl Links {
+    pub(crate) fn new(mut list: Return, format: LinkFormat) -> Self {
+        list.list.retain(|x| matches!(x.name, Name::DailyStudy(_)));
+        Self { list, format }
+    }
+
+    fn studies(&self) -> impl Iterator<Item = &DailyStudyOutput> {
+        self.list.list.iter().map(|x| match &x.name {
+            Name::DailyStudy(study) => study,
+            _ => unreachable!(),
+        })
+    }
+
+    fn csv_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        writeln!(lock, "date,name,link")?;
+        for (day, study) in self.list.list.iter().zip(self.studies()) {
+            let mut name = vec![];
+            day.name
+                .pretty_print(&mut name, args.language, args.transliteration)
+                .unwrap();
+            writeln!(
+                lock,
+                "{},{},{}",
+                day.civil_date().format("%Y-%m-%d"),
+                csv_field(&String::from_utf8_lossy(&name)),
+                sefaria_links(study).join(" ")
+            )?;
+        }
+        Ok(())
+    }
+
+    fn json_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let list: Vec<serde_json::Value> = self
+            .list
+            .json_list(args)
+            .into_iter()
+            .zip(self.studies())
+            .map(|(mut value, study)| {
+                if let Some(object) = value.as_object_mut() {
+                    object.insert("links".into(), sefaria_links(study).into());
+                }
+                value
+            })
+            .collect();
+        write_data(lock, OutputType::JSON, &list)
+    }
+}
+
+impl Printable for Links {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match self.format {
+            LinkFormat::Csv => self.csv_print(args, &mut lock),
+            LinkFormat::Json => self.json_print(args, &mut lock),
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
@@ -1,143 +1,651 @@
-use crate::algorithms::{chabad_holidays, israeli_holidays, shabbos_mevarchim};
+mod append;
+mod check;
+pub mod conflicts;
+pub mod ics;
+mod links;
+// Without the feature, the writer is only compiled for its tests.
+#[cfg(any(feature = "parquet", test))]
+#[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+mod parquet;
+pub mod provenance;
+pub mod providers;
+mod shabbos;
+pub mod summary;
 
-use crate::algorithms::candle_lighting::City;
 use crate::args::types::{
-    AppError, CustomHoliday, Daf, DailyStudy, DailyStudyOutput, DayVal, Event, Language, ListArgs,
-    MainArgs, MinorHoliday, Name, OutputType, RambamChapter, RambamChapters, RambamThreeChapter,
+    Alarm, AppError, Category, Chumash, Command, CustomDate, CustomHoliday, Daf, DailyStudy,
+    DailyStudyOutput, DayVal, Event, Headers, Language, ListArgs, MainArgs, MinorHoliday,
+    MonthFilter, Name, OutputType, PolarFallback, RambamChapter, RambamChapters,
+    RambamThreeChapter, Route, SolarAlgorithm, Span, SpanKind, TimeOfDay, VersionArgs, WeekStart,
     YearType, YerushalmiYomi,
 };
-use crate::prelude::constants::{get_minor_holidays, GEMARAS_FIRST_CYCLE, GEMARAS_SECOND_CYCLE};
-use crate::prelude::get_omer::get_omer;
-use crate::prelude::print;
-use crate::Runnable;
+use crate::prelude::constants::{
+    check_gregorian_year, check_hebrew_year, DAF_YOMI_ERAS, GEMARAS_SECOND_CYCLE,
+};
+use crate::prelude::{
+    output, print, print_output, sha256::sha256, write_data, write_error, CountingWriter,
+    EventProvider, Printable, Runnable, Warning, Warnings,
+};
+use crate::year_info::YearInfo;
 use chrono::prelude::*;
 use chrono::Duration;
+use conflicts::ShabbosConflict;
 use heca_lib::prelude::Chol::NineAv;
 use heca_lib::prelude::*;
 use heca_lib::{HebrewDate, HebrewYear};
+use provenance::Provenance;
+use providers::{
+    Agricultural, AvosUbanim, ChabadHolidays, CustomHolidays, DailyStudies, DaveningChanges,
+    IsraeliHolidays, MinorHolidays, Omer, ShabbosMevarchim, TorahReadings, Zmanim,
+};
 use rayon::prelude::*;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
-use std::io::stdout;
-use std::io::BufWriter;
 use std::io::Write;
+use summary::Summary;
 
 #[derive(Debug, Serialize)]
 #[serde(transparent)]
 pub struct Return {
-    list: Vec<DayVal>,
+    pub list: Vec<DayVal>,
+    /// Whether to print emoji before the events, candle lighting and havdalah.
+    #[serde(skip)]
+    pub icons: bool,
+    /// The location the events were listed for, as it's written in their UIDs.
+    #[serde(skip)]
+    pub location: String,
+    /// The time zone of the city, which the times listed as events are in in iCalendar.
+    #[serde(skip)]
+    pub time_zone: Option<zmanim::prelude::tz::TimeZone>,
+    /// The fields of every event to print in JSON, CBOR and MessagePack, like `name.en`, or
+    /// `None` for all of them.
+    #[serde(skip)]
+    pub fields: Option<Vec<String>>,
+    /// Whether to print the abbreviated names of the events.
+    #[serde(skip)]
+    pub short_names: bool,
+    /// The custom days which land on Shabbos or Yom Tov, printed after the list, if
+    /// `--warn-shabbos-conflicts` was passed.
+    #[serde(skip)]
+    pub shabbos_conflicts: Option<Vec<ShabbosConflict>>,
+    /// The sections to split the pretty output into, with `--headers`.
+    #[serde(skip)]
+    pub headers: Option<Headers>,
+    /// The counts of the events and the dates they cover, printed after the list, with
+    /// `--summary`.
+    #[serde(skip)]
+    pub summary: Option<Summary>,
+    /// Whether to add a hash of the events and how they were listed, with `--meta`.
+    #[serde(skip)]
+    pub meta: bool,
+    /// The reminders added to the events in iCalendar.
+    #[serde(skip)]
+    pub alarms: Vec<Alarm>,
+    /// How many minutes before sunset the city lights candles, recorded with every candle
+    /// lighting time.
+    #[serde(skip)]
+    pub candle_lighting_minutes: Option<u8>,
+    /// Names the rule which listed each event, with `--provenance`.
+    #[serde(skip)]
+    pub provenance: Option<Provenance>,
+}
+
+/// A day as it's printed in JSON, along with its UID, the minutes before sunset its candle
+/// lighting time is, and the rule which listed it.
+#[derive(Serialize)]
+struct JsonDay<'a> {
+    #[serde(flatten)]
+    day: &'a DayVal,
+    #[serde(
+        rename = "candleLightingMinutes",
+        skip_serializing_if = "Option::is_none"
+    )]
+    candle_lighting_minutes: Option<u8>,
+    uid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+}
+
+impl<'a> JsonDay<'a> {
+    fn new(day: &'a DayVal, list: &Return) -> Self {
+        JsonDay {
+            day,
+            candle_lighting_minutes: list
+                .candle_lighting_minutes
+                .filter(|_| matches!(day.candle_lighting, Some(Some(_)))),
+            uid: ics::uid(day, &list.location),
+            source: list.provenance.map(|x| x.source(day)),
+        }
+    }
+}
+
+/// Every day of a list with its UID, serialized as a single sequence for CBOR and MessagePack.
+struct DataList<'a>(&'a Return);
+
+impl Serialize for DataList<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.list.iter().map(|d| JsonDay::new(d, self.0)))
+    }
+}
+
+/// What `--meta` adds to the output, so a feed which didn't change can be skipped.
+#[derive(Serialize)]
+pub struct Meta {
+    /// The SHA-256 hash of the events, with their UIDs, as JSON. It doesn't depend on the format
+    /// they're printed in, or on the time they're printed at.
+    pub hash: String,
+    /// The version of heca.
+    pub version: &'static str,
+    /// The arguments heca was run with.
+    pub arguments: Vec<String>,
 }
 
+pub const CANDLE_LIGHTING_ICON: &str = "🕯";
+pub const HAVDALAH_ICON: &str = "🍷";
+
 impl Return {
-    fn pretty_print(&self, args: &MainArgs) -> Result<(), AppError> {
-        let stdout = stdout();
-        let mut lock = BufWriter::with_capacity(1024 * 1024, stdout.lock());
-        self.list.iter().for_each(|d| {
-            let ret = d.day;
-            let year = ret.year();
-            let month = ret.month();
-            let day = ret.day();
-            let name = d.name.clone();
-
-            let mut year_arr = [b'\0'; 16];
-            let mut month_arr = [b'\0'; 2];
-            let mut day_arr = [b'\0'; 2];
-            let count_y = itoa::write(&mut year_arr[..], year).unwrap();
-            let count_m = itoa::write(&mut month_arr[..], month).unwrap();
-            let count_d = itoa::write(&mut day_arr[..], day).unwrap();
-            match args.language {
-                Language::English => lock.write(b"Night of ").unwrap(),
-                Language::Hebrew => lock.write("ליל ".as_bytes()).unwrap(),
-            };
-            lock.write(&year_arr[..count_y as usize]).unwrap();
-            lock.write(b"/").unwrap();
-            lock.write(&month_arr[..count_m as usize]).unwrap();
-            lock.write(b"/").unwrap();
-            lock.write(&day_arr[..count_d as usize]).unwrap();
-            lock.write(b": ").unwrap();
-            match name {
-                Name::TorahReading(name) => {
-                    let mut res = lock
-                        .write(print::torah_reading(name, args.language).as_bytes())
-                        .unwrap();
-                    if let Some(l) = d.candle_lighting {
-                        res += match args.language {
-                            Language::English => lock.write(b". Candle lighting").unwrap(),
-                            Language::Hebrew => lock.write(". הדלקת נרות".as_bytes()).unwrap(),
-                        };
-                        if let Some(candle_lighting_time) = l {
-                            let mut hour_arr = [b'\0'; 2];
-                            let mut minute_arr = [b'\0'; 2];
-                            res += lock.write(b" ").unwrap();
-                            itoa::write(&mut hour_arr[..], candle_lighting_time.hour()).unwrap();
-                            let minute_write =
-                                itoa::write(&mut minute_arr[..], candle_lighting_time.minute())
-                                    .unwrap();
-                            res += lock.write(&hour_arr).unwrap();
-                            res += lock.write(b":").unwrap();
-                            if minute_write == 1 {
-                                res += lock.write(b"0").unwrap();
-                            }
-                            res += lock.write(&minute_arr).unwrap();
-                        };
+    pub(crate) fn pretty_print<W: Write>(
+        &self,
+        args: &MainArgs,
+        lock: &mut W,
+    ) -> std::io::Result<()> {
+        let mut section = None;
+        for d in &self.list {
+            if let Some(headers) = self.headers {
+                let key = section_key(d, headers);
+                if section != Some(key) {
+                    if section.is_some() {
+                        lock.write_all(b"\n")?;
                     }
-                    Some(res)
+                    // The months of a Hebrew year are under the header of their year.
+                    if headers == Headers::Month && section.map(|x| x.0) != Some(key.0) {
+                        writeln!(lock, "{}", year_header(key.0 as u64, args.language))?;
+                    }
+                    writeln!(lock, "{}", header(d, headers, args.language))?;
+                    section = Some(key);
                 }
-                Name::MinorDays(day) => lock
-                    .write(print::minor_holidays(day, args.language).as_bytes())
-                    .ok(),
-                Name::CustomHoliday(custom_holiday) => {
-                    lock.write(custom_holiday.printable.as_bytes()).ok()
+            }
+            if d.name.starts_at_night() {
+                match args.language {
+                    Language::English => lock.write_all(b"Night of ")?,
+                    Language::Hebrew => lock.write_all("ליל ".as_bytes())?,
+                };
+                write_date(lock, d.day)?;
+            } else {
+                // The day is stored as the night it starts on, so the civil date is the next day.
+                write_date(lock, d.day + Duration::days(1))?;
+            }
+            if let Name::Span(span) = &d.name {
+                match args.language {
+                    Language::English => lock.write_all(b" until night of ")?,
+                    Language::Hebrew => lock.write_all(" עד ליל ".as_bytes())?,
+                };
+                write_date(lock, span.end)?;
+            }
+            lock.write_all(b": ")?;
+            if let Some(icon) = d.name.icon().filter(|_| self.icons) {
+                write!(lock, "{} ", icon)?;
+            }
+            for (index, language) in std::iter::once(args.language)
+                .chain(args.second_language)
+                .enumerate()
+            {
+                if index > 0 {
+                    lock.write_all(b" | ")?;
                 }
-                Name::DailyStudy(daily_study) => match daily_study {
-                    DailyStudyOutput::Daf(d) => d.pretty_print(&mut lock, args.language),
-                    DailyStudyOutput::RambamThreeChapters(three_chapter) => {
-                        three_chapter.pretty_print(&mut lock, args.language)
-                    }
-                    DailyStudyOutput::RambamOneChapters(one_chapter) => {
-                        one_chapter.pretty_print(&mut lock, args.language)
+                if self.short_names {
+                    d.name.short_print(lock, language, args.transliteration)
+                } else {
+                    d.name.pretty_print(lock, language, args.transliteration)
+                }
+                .unwrap();
+            }
+            if let Some(l) = d.candle_lighting {
+                lock.write_all(b". ")?;
+                if self.icons {
+                    write!(lock, "{} ", CANDLE_LIGHTING_ICON)?;
+                }
+                match args.language {
+                    Language::English => lock.write_all(b"Candle lighting")?,
+                    Language::Hebrew => lock.write_all("הדלקת נרות".as_bytes())?,
+                };
+                if let Some(candle_lighting_time) = l {
+                    lock.write_all(b" ")?;
+                    write_time(lock, candle_lighting_time)?;
+                };
+            }
+            if !d.havdalah.is_empty() {
+                lock.write_all(b". ")?;
+                if self.icons {
+                    write!(lock, "{} ", HAVDALAH_ICON)?;
+                }
+                match args.language {
+                    Language::English => lock.write_all(b"Havdalah")?,
+                    Language::Hebrew => lock.write_all("הבדלה".as_bytes())?,
+                };
+                for (index, havdalah) in d.havdalah.iter().enumerate() {
+                    lock.write_all(if index == 0 { b" " } else { b", " })?;
+                    match havdalah.time {
+                        Some(time) => write_time(lock, time)?,
+                        None => match args.language {
+                            Language::English => lock.write_all(b"does not occur")?,
+                            Language::Hebrew => lock.write_all("לא חל".as_bytes())?,
+                        },
                     }
-                    DailyStudyOutput::YerushalmiYomi(yerushalmi_yomi) => {
-                        yerushalmi_yomi.pretty_print(&mut lock, args.language)
+                    // A single opinion is the one that was asked for, so it isn't named.
+                    if d.havdalah.len() > 1 {
+                        lock.write_all(b" (")?;
+                        havdalah.opinion.pretty_print(lock, args.language).unwrap();
+                        lock.write_all(b")")?;
                     }
-                },
-                Name::IsraeliHoliday(israeli_holidays) => {
-                    israeli_holidays.pretty_print(&mut lock, args.language)
                 }
-                Name::ChabadHoliday(chabad_holidays) => {
-                    chabad_holidays.pretty_print(&mut lock, args.language)
-                }
-                Name::ShabbosMevarchim(shabbos_mevarchim) => {
-                    shabbos_mevarchim.pretty_print(&mut lock, args.language)
+            }
+            if let Some(time_range) = d.time_range {
+                let (starts, ends) = match args.language {
+                    Language::English => (". Starts ", ", ends "),
+                    Language::Hebrew => (". מתחיל ", ", מסתיים "),
+                };
+                for (text, time) in [(starts, time_range.start), (ends, time_range.end)] {
+                    lock.write_all(text.as_bytes())?;
+                    match time {
+                        Some(time) => write_time(lock, time)?,
+                        None => match args.language {
+                            Language::English => lock.write_all(b"does not occur")?,
+                            Language::Hebrew => lock.write_all("לא חל".as_bytes())?,
+                        },
+                    }
                 }
-            };
-            lock.write(b"\n").unwrap();
-        });
+            }
+            if let Some(torah_portion) = &d.torah_portion {
+                torah_portion.pretty_print(lock, args.language).unwrap();
+            }
+            if let Some(provenance) = &self.provenance {
+                write!(lock, " [{}]", provenance.source(d))?;
+            }
+            lock.write_all(b"\n")?;
+        }
+        if let Some(conflicts) = &self.shabbos_conflicts {
+            conflicts::print_section(conflicts, args, lock)?;
+        }
+        if let Some(summary) = &self.summary {
+            summary.pretty_print(lock, args.language)?;
+        }
         Ok(())
     }
-    fn json_print(&self) -> Result<(), AppError> {
-        println!("{}", serde_json::to_string(&self).unwrap());
+    /// Returns the day as it's printed in JSON, with its name in both languages if two were
+    /// asked for.
+    fn json_value(&self, d: &DayVal, args: &MainArgs) -> serde_json::Value {
+        let mut value = serde_json::to_value(JsonDay::new(d, self)).unwrap();
+        if let Some(second_language) = args.second_language {
+            let mut name = serde_json::Map::new();
+            for language in &[args.language, second_language] {
+                let mut printed = vec![];
+                d.name
+                    .pretty_print(&mut printed, *language, args.transliteration)
+                    .unwrap();
+                name.insert(
+                    language.code().into(),
+                    String::from_utf8(printed).unwrap().into(),
+                );
+            }
+            value["name"] = name.into();
+        }
+        value
+    }
+
+    /// Returns every day as it's printed in JSON, with only the fields of `--fields`.
+    pub(crate) fn json_list(&self, args: &MainArgs) -> Vec<serde_json::Value> {
+        self.list
+            .iter()
+            .map(|d| {
+                let value = self.json_value(d, args);
+                match &self.fields {
+                    Some(fields) => select_fields(&value, fields),
+                    None => value,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the hash of the events and how they were listed.
+    pub fn metadata(&self) -> Meta {
+        let events = serde_json::to_vec(&DataList(self)).unwrap();
+        Meta {
+            hash: format!("sha256:{}", sha256(&events)),
+            version: env!("CARGO_PKG_VERSION"),
+            arguments: std::env::args().skip(1).collect(),
+        }
+    }
+
+    fn data_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        if args.second_language.is_some()
+            || self.fields.is_some()
+            || self.summary.is_some()
+            || self.meta
+        {
+            let list = self.json_list(args);
+            let mut meta = match &self.summary {
+                Some(summary) => serde_json::to_value(summary).unwrap(),
+                None => serde_json::Value::Object(serde_json::Map::new()),
+            };
+            if self.meta {
+                if let serde_json::Value::Object(fields) =
+                    serde_json::to_value(self.metadata()).unwrap()
+                {
+                    meta.as_object_mut().unwrap().extend(fields);
+                }
+            }
+            if self.summary.is_some() || self.meta {
+                write_data(
+                    lock,
+                    args.output_type,
+                    &serde_json::json!({ "events": list, "meta": meta }),
+                )?;
+            } else {
+                write_data(lock, args.output_type, &list)?;
+            }
+        } else if args.output_type != OutputType::JSON {
+            write_data(lock, args.output_type, &DataList(self))?;
+        } else {
+            // Each day is serialized into the same buffer and written out, instead of building
+            // the whole list in memory first.
+            let mut buf = Vec::with_capacity(256);
+            lock.write_all(b"[")?;
+            for (index, d) in self.list.iter().enumerate() {
+                buf.clear();
+                if index > 0 {
+                    buf.push(b',');
+                }
+                let day = JsonDay::new(d, self);
+                serde_json::to_writer(&mut buf, &day)?;
+                lock.write_all(&buf)?;
+            }
+            lock.write_all(b"]\n")?;
+        }
         Ok(())
     }
 }
 
 impl Return {
-    fn print(&self, args: &MainArgs) -> Result<(), AppError> {
+    /// Returns the events of the category, to write to the file of a `--route`. The summary and
+    /// the Shabbos conflicts are only printed with the rest of the list.
+    fn routed(&self, category: Category) -> Return {
+        Return {
+            list: self
+                .list
+                .iter()
+                .filter(|x| x.name.category() == category)
+                .cloned()
+                .collect(),
+            icons: self.icons,
+            location: self.location.clone(),
+            time_zone: self.time_zone.clone(),
+            fields: self.fields.clone(),
+            short_names: self.short_names,
+            shabbos_conflicts: None,
+            headers: self.headers,
+            summary: None,
+            meta: self.meta,
+            alarms: self.alarms.clone(),
+            candle_lighting_minutes: self.candle_lighting_minutes,
+            provenance: self.provenance,
+        }
+    }
+}
+
+/// The arguments to print the events of a route with: the format of its file, and its file
+/// instead of `--out`.
+fn route_args(args: &MainArgs, route: &Route) -> MainArgs {
+    MainArgs {
+        custom_days: None,
+        output_type: route.output_type,
+        language: args.language,
+        second_language: args.second_language,
+        transliteration: args.transliteration,
+        out: Some(route.path.clone()),
+        ascii: args.ascii,
+        today: args.today,
+        // Only the list is printed with these.
+        command: Command::Version(VersionArgs),
+    }
+}
+
+impl Printable for Return {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
         match args.output_type {
-            OutputType::JSON => self.json_print(),
-            OutputType::Pretty | OutputType::Regular => self.pretty_print(args),
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                self.data_print(args, &mut lock)
+            }
+            OutputType::ICS => ics::print(self, args, &mut lock),
+            #[cfg(feature = "parquet")]
+            OutputType::Parquet => parquet::print(&self.list, &self.location, args, &mut lock),
+            #[cfg(not(feature = "parquet"))]
+            OutputType::Parquet => unreachable!("--print parquet needs the parquet feature"),
+            OutputType::Pretty | OutputType::Regular => self.pretty_print(args, &mut lock),
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+/// Returns only the fields of the value at the paths, like `name.en`, keeping the objects they're
+/// in. Paths which aren't in the value are left out.
+fn select_fields(value: &serde_json::Value, fields: &[String]) -> serde_json::Value {
+    let mut selected = serde_json::Value::Object(serde_json::Map::new());
+    for field in fields {
+        let path: Vec<&str> = field.split('.').collect();
+        let found = path
+            .iter()
+            .try_fold(value, |value, key| value.as_object()?.get(*key));
+        if let Some(found) = found {
+            let (last, parents) = path.split_last().unwrap();
+            let mut object = &mut selected;
+            for key in parents {
+                object = object
+                    .as_object_mut()
+                    .unwrap()
+                    .entry(*key)
+                    .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            }
+            object
+                .as_object_mut()
+                .unwrap()
+                .insert(last.to_string(), found.clone());
         }
     }
+    selected
+}
+
+/// Returns the header of the section the day is in: its Hebrew or Gregorian year, or its Hebrew
+/// month and year, like "Kislev 5785".
+/// The year, or the year and month, of the section of `--headers` the day is in.
+fn section_key(d: &DayVal, headers: Headers) -> (i64, u8) {
+    match headers {
+        Headers::HebrewYear => (hebrew_year(d.day) as i64, 0),
+        Headers::GregorianYear => (d.civil_date().year() as i64, 0),
+        Headers::Month => {
+            let date = HebrewDate::try_from(d.day).unwrap();
+            (date.year() as i64, date.month() as u8)
+        }
+    }
+}
+
+fn header(d: &DayVal, headers: Headers, language: Language) -> String {
+    match headers {
+        Headers::HebrewYear => year_header(hebrew_year(d.day), language),
+        Headers::GregorianYear => d.civil_date().year().to_string(),
+        Headers::Month => {
+            let date = HebrewDate::try_from(d.day).unwrap();
+            let month = match language {
+                Language::English => print::hebrew_month_english(date.month()),
+                Language::Hebrew => print::hebrew_month_hebrew(date.month()),
+            };
+            format!("{} {}", month, date.year())
+        }
+    }
+}
+
+/// The Hebrew year with its keviyah and length, like a luach prints at the top of the year.
+fn year_header(year: u64, language: Language) -> String {
+    match YearInfo::new(year) {
+        Ok(info) => format!("{} ({})", year, info.description(language)),
+        Err(_) => year.to_string(),
+    }
+}
+
+pub(crate) fn write_date<W: Write>(lock: &mut W, date: DateTime<Utc>) -> std::io::Result<()> {
+    let mut year_arr = [b'\0'; 16];
+    let mut month_arr = [b'\0'; 2];
+    let mut day_arr = [b'\0'; 2];
+    let count_y = itoa::write(&mut year_arr[..], date.year())?;
+    let count_m = itoa::write(&mut month_arr[..], date.month())?;
+    let count_d = itoa::write(&mut day_arr[..], date.day())?;
+    lock.write_all(&year_arr[..count_y as usize])?;
+    lock.write_all(b"/")?;
+    lock.write_all(&month_arr[..count_m as usize])?;
+    lock.write_all(b"/")?;
+    lock.write_all(&day_arr[..count_d as usize])
+}
+
+fn write_time<W: Write>(lock: &mut W, time: DateTime<FixedOffset>) -> std::io::Result<()> {
+    let mut hour_arr = [b'\0'; 2];
+    let mut minute_arr = [b'\0'; 2];
+    let count_h = itoa::write(&mut hour_arr[..], time.hour())?;
+    let count_m = itoa::write(&mut minute_arr[..], time.minute())?;
+    lock.write_all(&hour_arr[..count_h])?;
+    lock.write_all(b":")?;
+    if count_m == 1 {
+        lock.write_all(b"0")?;
+    }
+    lock.write_all(&minute_arr[..count_m])
+}
+
+/// Replaces the consecutive days of every multi-day observance with a single `Name::Span`
+/// starting on the first day. Days that aren't part of an observance are left as-is.
+fn merge_spans(list: Vec<DayVal>) -> Vec<DayVal> {
+    let mut merged: Vec<DayVal> = Vec::with_capacity(list.len());
+    let mut open_spans: HashMap<SpanKind, usize> = HashMap::new();
+    for day_val in list {
+        let kind = match SpanKind::from_name(&day_val.name) {
+            Some(kind) => kind,
+            None => {
+                merged.push(day_val);
+                continue;
+            }
+        };
+        if let Some(&index) = open_spans.get(&kind) {
+            if let Name::Span(span) = &mut merged[index].name {
+                if span.end == day_val.day {
+                    span.end = day_val.day + Duration::days(1);
+                    if kind == SpanKind::Chanukah {
+                        span.nights.push(day_val);
+                    }
+                    continue;
+                }
+            }
+        }
+        open_spans.insert(kind, merged.len());
+        let day = day_val.day;
+        let nights = if kind == SpanKind::Chanukah {
+            vec![day_val]
+        } else {
+            vec![]
+        };
+        merged.push(DayVal {
+            day,
+            name: Name::Span(Span {
+                kind,
+                end: day + Duration::days(1),
+                nights,
+            }),
+            candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
+        });
+    }
+    merged
+}
+
+/// Replaces the two days of every two-day Rosh Chodesh with a single `Name::Span` starting on the
+/// first day. A Rosh Chodesh of one day is left as-is.
+fn merge_rosh_chodesh(list: Vec<DayVal>) -> Vec<DayVal> {
+    let mut merged: Vec<DayVal> = Vec::with_capacity(list.len());
+    let mut first_day: Option<usize> = None;
+    for day_val in list {
+        match rosh_chodesh_day(&day_val.name) {
+            Some(1) => first_day = Some(merged.len()),
+            Some(2) => {
+                if let Some(index) = first_day.take() {
+                    if merged[index].day + Duration::days(1) == day_val.day {
+                        merged[index] = DayVal {
+                            day: merged[index].day,
+                            name: Name::Span(Span {
+                                kind: SpanKind::RoshChodesh,
+                                end: day_val.day + Duration::days(1),
+                                nights: vec![],
+                            }),
+                            candle_lighting: None,
+                            torah_portion: None,
+                            havdalah: vec![],
+                            time_range: None,
+                        };
+                        continue;
+                    }
+                }
+            }
+            _ => {}
+        }
+        merged.push(day_val);
+    }
+    merged
+}
+
+/// Returns which day of a two-day Rosh Chodesh the name is, if it's one.
+fn rosh_chodesh_day(name: &Name) -> Option<u8> {
+    match name {
+        Name::TorahReading(TorahReading::Chol(chol)) => match chol {
+            Chol::RoshChodeshCheshvan1
+            | Chol::RoshChodeshKislev1
+            | Chol::RoshChodeshTeves1
+            | Chol::RoshChodeshAdar1
+            | Chol::RoshChodeshAdarRishon1
+            | Chol::RoshChodeshAdarSheni1
+            | Chol::RoshChodeshIyar1
+            | Chol::RoshChodeshTammuz1
+            | Chol::RoshChodeshElul1 => Some(1),
+            Chol::RoshChodeshCheshvan2
+            | Chol::RoshChodeshKislev2
+            | Chol::RoshChodeshTeves2
+            | Chol::RoshChodeshAdar2
+            | Chol::RoshChodeshAdarRishon2
+            | Chol::RoshChodeshAdarSheni2
+            | Chol::RoshChodeshIyar2
+            | Chol::RoshChodeshTammuz2
+            | Chol::RoshChodeshElul2 => Some(2),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 type DailyStudyEvents = Vec<DailyStudy>;
 
-trait GetDayVal {
-    fn get_day_val(&self, start_year: u64, last_year: u64) -> Vec<DayVal>;
+pub(crate) trait GetDayVal {
+    fn get_day_val(&self, start_year: u64, last_year: u64, location: Location) -> Vec<DayVal>;
+    /// Lists the days from the night of `first_day` until the night of `last_day`, inclusive.
+    fn get_day_val_between(
+        &self,
+        first_day: DateTime<Utc>,
+        last_day: DateTime<Utc>,
+        location: Location,
+    ) -> Vec<DayVal>;
 }
 
 impl GetDayVal for DailyStudyEvents {
-    fn get_day_val(&self, start_year: u64, last_year: u64) -> Vec<DayVal> {
+    fn get_day_val(&self, start_year: u64, last_year: u64, location: Location) -> Vec<DayVal> {
         use std::num::NonZeroI8;
         if self.is_empty() {
             return vec![];
@@ -152,159 +660,388 @@ impl GetDayVal for DailyStudyEvents {
                 .unwrap()
                 .try_into()
                 .unwrap();
-        let mut return_val = Vec::new();
-        let mut i = first_day;
-        while i <= last_day {
-            for event in self.iter() {
-                match event {
-                    DailyStudy::DafYomi => {
-                        let first_day_of_second_cycle = Utc.ymd(1975, 6, 23).and_hms(18, 0, 0);
-                        if i >= first_day_of_second_cycle {
-                            let diff = i - first_day_of_second_cycle;
-                            let d = DayVal {
-                                day: i,
-                                name: Name::DailyStudy(DailyStudyOutput::Daf(Daf::from_days(
-                                    (diff.num_days() % 2711).try_into().unwrap(),
-                                    &GEMARAS_SECOND_CYCLE,
-                                ))),
-                                candle_lighting: None,
-                            };
-                            return_val.push(d);
-                        } else {
-                            let first_day_of_first_cycle = Utc.ymd(1923, 9, 10).and_hms(18, 0, 0);
-                            if i >= first_day_of_first_cycle {
-                                let diff = i - first_day_of_first_cycle;
+        self.get_day_val_between(first_day, last_day, location)
+    }
+
+    /// Each Hebrew year is generated in parallel.
+    fn get_day_val_between(
+        &self,
+        first_day: DateTime<Utc>,
+        last_day: DateTime<Utc>,
+        location: Location,
+    ) -> Vec<DayVal> {
+        use std::num::NonZeroI8;
+        if self.is_empty() || first_day > last_day {
+            return vec![];
+        }
+        let start_year = HebrewDate::try_from(first_day).unwrap().year();
+        let last_year = HebrewDate::try_from(last_day).unwrap().year();
+        if start_year == last_year {
+            return get_daily_studies(self, first_day, last_day, location);
+        }
+        let years: Vec<Vec<DayVal>> = (start_year..=last_year)
+            .into_par_iter()
+            .map(|year| {
+                let year_first_day: DateTime<Utc> =
+                    HebrewDate::from_ymd(year, HebrewMonth::Tishrei, NonZeroI8::new(1).unwrap())
+                        .unwrap()
+                        .into();
+                let year_last_day: DateTime<Utc> =
+                    HebrewDate::from_ymd(year, HebrewMonth::Elul, NonZeroI8::new(29).unwrap())
+                        .unwrap()
+                        .into();
+                get_daily_studies(
+                    self,
+                    first_day.max(year_first_day),
+                    last_day.min(year_last_day),
+                    location,
+                )
+            })
+            .collect();
+        years.into_iter().flatten().collect()
+    }
+}
+
+/// Lists the daily studies from the night of `first_day` until the night of `last_day`,
+/// inclusive, one day after the other.
+fn get_daily_studies(
+    events: &[DailyStudy],
+    first_day: DateTime<Utc>,
+    last_day: DateTime<Utc>,
+    location: Location,
+) -> Vec<DayVal> {
+    use std::num::NonZeroI8;
+    let start_year = HebrewDate::try_from(first_day).unwrap().year();
+    let last_year = HebrewDate::try_from(last_day).unwrap().year();
+    let parshiyos: Vec<(DateTime<Utc>, Parsha)> = if events.contains(&DailyStudy::Chumash) {
+        (start_year..=last_year + 1)
+            .flat_map(|year| {
+                HebrewYear::new(year)
+                    .unwrap()
+                    .get_holidays(location, &[TorahReadingType::Shabbos])
+            })
+            .filter_map(|x| match x.name() {
+                TorahReading::Shabbos(parsha) => Some((x.day().into(), parsha)),
+                _ => None,
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    let mut return_val = Vec::new();
+    let mut i = first_day;
+    while i <= last_day {
+        for event in events.iter() {
+            match event {
+                DailyStudy::DafYomi => {
+                    if let Some(daf) = daf_yomi(i) {
+                        let d = DayVal {
+                            day: i,
+                            name: Name::DailyStudy(DailyStudyOutput::Daf(daf)),
+                            candle_lighting: None,
+                            torah_portion: None,
+                            havdalah: vec![],
+                            time_range: None,
+                        };
+                        return_val.push(d);
+                    }
+                }
+                DailyStudy::DafHashavua(week_start) => {
+                    // Counted from Berachos 2 in the week the 14th Daf Yomi cycle started.
+                    let first_week = match week_start {
+                        WeekStart::Sunday => Utc.ymd(2020, 1, 4).and_hms(18, 0, 0),
+                        WeekStart::Shabbos => Utc.ymd(2020, 1, 3).and_hms(18, 0, 0),
+                    };
+                    let diff = i - first_week;
+                    if i >= first_week && diff.num_days() % 7 == 0 {
+                        let d = DayVal {
+                            day: i,
+                            name: Name::DailyStudy(DailyStudyOutput::DafHashavua(Daf::from_days(
+                                (diff.num_days() / 7 % 2711).try_into().unwrap(),
+                                &GEMARAS_SECOND_CYCLE,
+                            ))),
+                            candle_lighting: None,
+                            torah_portion: None,
+                            havdalah: vec![],
+                            time_range: None,
+                        };
+                        return_val.push(d);
+                    }
+                }
+                DailyStudy::Rambam(chapters) => {
+                    let first_day = Utc.ymd(1984, 4, 27).and_hms(18, 0, 0);
+                    let diff: Duration = i - first_day;
+                    if i >= first_day {
+                        match chapters {
+                            RambamChapters::One => {
                                 let d = DayVal {
-                                    day: i,
                                     candle_lighting: None,
-                                    name: Name::DailyStudy(DailyStudyOutput::Daf(Daf::from_days(
-                                        (diff.num_days() % 2702).try_into().unwrap(),
-                                        &GEMARAS_FIRST_CYCLE,
-                                    ))),
+                                    torah_portion: None,
+                                    havdalah: vec![],
+                                    day: i,
+                                    name: Name::DailyStudy(DailyStudyOutput::RambamOneChapters(
+                                        RambamChapter::from_days(
+                                            (diff.num_days() % 1017).try_into().unwrap(),
+                                        ),
+                                    )),
+                                    time_range: None,
                                 };
                                 return_val.push(d);
                             }
-                        }
-                    }
-                    DailyStudy::Rambam(chapters) => {
-                        let first_day = Utc.ymd(1984, 4, 27).and_hms(18, 0, 0);
-                        let diff: Duration = i - first_day;
-                        if i >= first_day {
-                            match chapters {
-                                RambamChapters::One => {
-                                    let d = DayVal {
-                                        candle_lighting: None,
-                                        day: i,
-                                        name: Name::DailyStudy(
-                                            DailyStudyOutput::RambamOneChapters(
-                                                RambamChapter::from_days(
-                                                    (diff.num_days() % 1017).try_into().unwrap(),
-                                                ),
-                                            ),
-                                        ),
-                                    };
-                                    return_val.push(d);
-                                }
-                                RambamChapters::Three => {
-                                    let d = DayVal {
-                                        candle_lighting: None,
-                                        day: i,
-                                        name: Name::DailyStudy(
-                                            DailyStudyOutput::RambamThreeChapters(
-                                                RambamThreeChapter::from_days(
-                                                    (diff.num_days() % (1017 / 3))
-                                                        .try_into()
-                                                        .unwrap(),
-                                                ),
-                                            ),
+                            RambamChapters::Three => {
+                                let d = DayVal {
+                                    candle_lighting: None,
+                                    torah_portion: None,
+                                    havdalah: vec![],
+                                    day: i,
+                                    name: Name::DailyStudy(DailyStudyOutput::RambamThreeChapters(
+                                        RambamThreeChapter::from_days(
+                                            (diff.num_days() % (1017 / 3)).try_into().unwrap(),
                                         ),
-                                    };
-                                    return_val.push(d);
-                                }
+                                    )),
+                                    time_range: None,
+                                };
+                                return_val.push(d);
                             }
                         }
                     }
-                    DailyStudy::YerushalmiYomi => {
-                        let first_day_of_yerushalmi_yomi = Utc.ymd(1980, 2, 1).and_hms(18, 0, 0);
-                        if i >= first_day_of_yerushalmi_yomi {
-                            let cur_hebrew_day: HebrewDate = i.try_into().unwrap();
-                            let first_hebrew_day_of_yerushalmi_yomi: HebrewDate =
-                                first_day_of_yerushalmi_yomi.try_into().unwrap();
-                            let amnt_years =
-                                cur_hebrew_day.year() - first_hebrew_day_of_yerushalmi_yomi.year();
-
-                            let diff_days = i - first_day_of_yerushalmi_yomi;
-
-                            let this_years_tisha_beav = HebrewYear::new(cur_hebrew_day.year())
-                                .unwrap()
-                                .get_holidays(Location::Chul, &[TorahReadingType::Chol])
-                                .into_iter()
-                                .find(|x| x.name() == TorahReading::Chol(NineAv))
-                                .unwrap()
-                                .day();
-                            if !(cur_hebrew_day.month() == HebrewMonth::Tishrei
-                                && cur_hebrew_day.day() == NonZeroI8::new(10).unwrap())
-                                && !(cur_hebrew_day == this_years_tisha_beav)
+                }
+                DailyStudy::YerushalmiYomi => {
+                    let first_day_of_yerushalmi_yomi = Utc.ymd(1980, 2, 1).and_hms(18, 0, 0);
+                    if i >= first_day_of_yerushalmi_yomi {
+                        let cur_hebrew_day: HebrewDate = i.try_into().unwrap();
+                        let first_hebrew_day_of_yerushalmi_yomi: HebrewDate =
+                            first_day_of_yerushalmi_yomi.try_into().unwrap();
+                        let amnt_years =
+                            cur_hebrew_day.year() - first_hebrew_day_of_yerushalmi_yomi.year();
+
+                        let diff_days = i - first_day_of_yerushalmi_yomi;
+
+                        let this_years_tisha_beav = HebrewYear::new(cur_hebrew_day.year())
+                            .unwrap()
+                            .get_holidays(Location::Chul, &[TorahReadingType::Chol])
+                            .into_iter()
+                            .find(|x| x.name() == TorahReading::Chol(NineAv))
+                            .unwrap()
+                            .day();
+                        if !(cur_hebrew_day.month() == HebrewMonth::Tishrei
+                            && cur_hebrew_day.day() == NonZeroI8::new(10).unwrap())
+                            && !(cur_hebrew_day == this_years_tisha_beav)
+                        {
+                            let amnt_yom_kippur_this_year = if cur_hebrew_day.month()
+                                == HebrewMonth::Tishrei
+                                && cur_hebrew_day.day() < NonZeroI8::new(10).unwrap()
                             {
-                                let amnt_yom_kippur_this_year = if cur_hebrew_day.month()
-                                    == HebrewMonth::Tishrei
-                                    && cur_hebrew_day.day() < NonZeroI8::new(10).unwrap()
-                                {
+                                0
+                            } else {
+                                1
+                            };
+                            let amnt_tisha_beav_this_year =
+                                if cur_hebrew_day < this_years_tisha_beav {
                                     0
                                 } else {
                                     1
                                 };
-                                let amnt_tisha_beav_this_year =
-                                    if cur_hebrew_day < this_years_tisha_beav {
-                                        0
-                                    } else {
-                                        1
-                                    };
-                                let amnt_yom_kippur = if amnt_years == 0 {
-                                    0
-                                } else if amnt_years == 1 {
-                                    amnt_yom_kippur_this_year
-                                } else {
-                                    amnt_years - 1 + amnt_yom_kippur_this_year
-                                };
-                                let amnt_tisha_beav = if amnt_years == 0 {
-                                    amnt_tisha_beav_this_year
-                                } else if amnt_years == 1 {
-                                    amnt_tisha_beav_this_year + 1
-                                } else {
-                                    amnt_years + amnt_tisha_beav_this_year
+                            let amnt_yom_kippur = if amnt_years == 0 {
+                                0
+                            } else if amnt_years == 1 {
+                                amnt_yom_kippur_this_year
+                            } else {
+                                amnt_years - 1 + amnt_yom_kippur_this_year
+                            };
+                            let amnt_tisha_beav = if amnt_years == 0 {
+                                amnt_tisha_beav_this_year
+                            } else if amnt_years == 1 {
+                                amnt_tisha_beav_this_year + 1
+                            } else {
+                                amnt_years + amnt_tisha_beav_this_year
+                            };
+                            if diff_days.num_days() > 0 {
+                                let d = DayVal {
+                                    day: i,
+                                    name: Name::DailyStudy(DailyStudyOutput::YerushalmiYomi(
+                                        YerushalmiYomi::from_days(
+                                            ((diff_days.num_days() as u64
+                                                - amnt_tisha_beav
+                                                - amnt_yom_kippur)
+                                                % (1563 - 5 - 4))
+                                                .try_into()
+                                                .unwrap(),
+                                        ),
+                                    )),
+                                    candle_lighting: None,
+                                    torah_portion: None,
+                                    havdalah: vec![],
+                                    time_range: None,
                                 };
-                                if diff_days.num_days() > 0 {
-                                    let d = DayVal {
-                                        day: i,
-                                        name: Name::DailyStudy(DailyStudyOutput::YerushalmiYomi(
-                                            YerushalmiYomi::from_days(
-                                                ((diff_days.num_days() as u64
-                                                    - amnt_tisha_beav
-                                                    - amnt_yom_kippur)
-                                                    % (1563 - 5 - 4))
-                                                    .try_into()
-                                                    .unwrap(),
-                                            ),
-                                        )),
-                                        candle_lighting: None,
-                                    };
-                                    return_val.push(d);
-                                }
+                                return_val.push(d);
                             }
                         }
                     }
-                };
-            }
-            if i.weekday() == Weekday::Sun {}
-            i = i + Duration::days(1);
+                }
+                DailyStudy::Chumash => {
+                    let next_shabbos = parshiyos.partition_point(|(day, _)| *day < i);
+                    if let Some((_, parsha)) = parshiyos.get(next_shabbos) {
+                        let aliyah = (i + Duration::days(1)).weekday().number_from_sunday();
+                        let cur_hebrew_day: HebrewDate = i.try_into().unwrap();
+                        let parsha = if *parsha == Parsha::Bereishis
+                            && cur_hebrew_day.month() == HebrewMonth::Tishrei
+                            && cur_hebrew_day < simchas_torah(cur_hebrew_day.year(), location)
+                        {
+                            None
+                        } else {
+                            Some(*parsha)
+                        };
+                        let d = DayVal {
+                            day: i,
+                            name: Name::DailyStudy(DailyStudyOutput::Chumash(Chumash::new(
+                                parsha,
+                                aliyah as u8,
+                            ))),
+                            candle_lighting: None,
+                            torah_portion: None,
+                            havdalah: vec![],
+                            time_range: None,
+                        };
+                        return_val.push(d);
+                    }
+                }
+            };
         }
-        return_val
+        if i.weekday() == Weekday::Sun {}
+        i = i + Duration::days(1);
     }
+    return_val
+}
+
+/// Returns the Daf Yomi learned on the day starting on the night, if the Daf Yomi had started.
+fn daf_yomi(night: DateTime<Utc>) -> Option<Daf> {
+    let (first_night, gemaras) = DAF_YOMI_ERAS
+        .iter()
+        .map(|((year, month, day), gemaras)| {
+            let first_night = Utc.ymd(*year, *month, *day).and_hms(18, 0, 0) - Duration::days(1);
+            (first_night, gemaras)
+        })
+        .rev()
+        .find(|(first_night, _)| *first_night <= night)?;
+    let cycle: i64 = gemaras.iter().map(|x| x.3 as i64 - 1).sum();
+    Some(Daf::from_days(
+        ((night - first_night).num_days() % cycle)
+            .try_into()
+            .unwrap(),
+        gemaras,
+    ))
+}
+
+/// Vezos Haberachah is read on Simchas Torah, which is the 22nd of Tishrei in Israel and the 23rd
+/// in Chutz La'aretz.
+fn simchas_torah(year: u64, location: Location) -> HebrewDate {
+    use std::num::NonZeroI8;
+    let day = match location {
+        Location::Israel => 22,
+        Location::Chul => 23,
+    };
+    HebrewDate::from_ymd(year, HebrewMonth::Tishrei, NonZeroI8::new(day).unwrap()).unwrap()
 }
 
 impl Runnable for ListArgs {
     fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let mut out = output(args)?;
+        self.print_into(args, &mut out)?;
+        out.flush().map_err(write_error)
+    }
+}
+
+impl ListArgs {
+    /// Lists the events and prints them into `out`, except for the routes of `--route`, which are
+    /// printed to their own files. `heca batch` prints every request into the same writer.
+    pub(crate) fn print_into(&self, args: &MainArgs, out: &mut dyn Write) -> Result<(), AppError> {
+        let mut result = self.list()?;
+        let printed = match &self.append_since_last_run {
+            Some(path) => {
+                if let Some(last_date) = append::last_date(path)? {
+                    result.list.retain(|x| x.civil_date() > last_date);
+                }
+                Some((path, result.list.clone()))
+            }
+            None => None,
+        };
+        if self.check {
+            check::Check::new(self, result).print(args, out)?;
+        } else if let Some(format) = self.links {
+            links::Links::new(result, format).print(args, out)?;
+        } else {
+            for route in &self.routes {
+                print_output(&result.routed(route.category), &route_args(args, route))?;
+            }
+            result
+                .list
+                .retain(|x| self.routes.iter().all(|r| r.category != x.name.category()));
+            result.print(args, out)?;
+        }
+        if let Some((path, printed)) = printed {
+            append::record(path, &printed)?;
+        }
+        Ok(())
+    }
+
+    /// Lists the events, filtered, sorted and merged as requested, without printing them.
+    pub fn list(&self) -> Result<Return, AppError> {
+        // Gregorian years and months are filtered by the civil date the events are printed with.
+        let (first_year, last_year, window, dates) = match (&self.year, self.month) {
+            (YearType::Hebrew(year), None) => {
+                let year = *year;
+                check_hebrew_year(year as i64)?;
+                check_hebrew_year((year + self.amnt_years - 1) as i64)?;
+                HebrewYear::new(year)?;
+                HebrewYear::new(year + self.amnt_years)?;
+                (year, year + self.amnt_years, None, None)
+            }
+            (YearType::Hebrew(_), Some(month)) => {
+                let (from, until) = month_window(&self.year, month)?;
+                let last_year = hebrew_year(until - Duration::days(1));
+                (hebrew_year(from), last_year + 1, Some((from, until)), None)
+            }
+            (YearType::Gregorian(year), month) => {
+                let (from, until) = match month {
+                    Some(month) => month_window(&self.year, month)?,
+                    None => {
+                        let year = *year;
+                        check_gregorian_year(year as i64)?;
+                        check_gregorian_year((year + self.amnt_years - 1) as i64)?;
+                        let from = Utc.ymd(year as i32, 1, 1).and_hms(0, 0, 0);
+                        let until = Utc
+                            .ymd((year + self.amnt_years) as i32, 1, 1)
+                            .and_hms(0, 0, 0);
+                        (from, until)
+                    }
+                };
+                let (first_year, last_year, window, dates) = civil_window(from, until);
+                (first_year, last_year, Some(window), Some(dates))
+            }
+        };
+        self.list_in(first_year, last_year, window, dates)
+    }
+
+    /// Lists the events printed with a civil date from `from` until (but not including)
+    /// `until`, whatever the year and month of the arguments are.
+    pub fn list_between(&self, from: NaiveDate, until: NaiveDate) -> Result<Return, AppError> {
+        check_gregorian_year(from.year() as i64)?;
+        check_gregorian_year(until.year() as i64)?;
+        let (first_year, last_year, window, dates) = civil_window(
+            Utc.from_utc_date(&from).and_hms(0, 0, 0),
+            Utc.from_utc_date(&until).and_hms(0, 0, 0),
+        );
+        self.list_in(first_year, last_year, Some(window), Some(dates))
+    }
+
+    fn list_in(
+        &self,
+        first_year: u64,
+        last_year: u64,
+        window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+        dates: Option<(NaiveDate, NaiveDate)>,
+    ) -> Result<Return, AppError> {
         let main_events = self
             .events
             .iter()
@@ -339,227 +1076,555 @@ impl Runnable for ListArgs {
                 }
             })
             .collect::<DailyStudyEvents>();
-        let result: Result<Return, AppError> = match self.year {
-            YearType::Hebrew(year) => {
-                HebrewYear::new(year)?;
-                HebrewYear::new(year + self.amnt_years)?;
-                let mut part1 = get_list(
-                    year,
-                    year + self.amnt_years,
-                    self.location,
-                    &self.events,
-                    &main_events,
-                    &custom_events,
-                    self.exact_days,
-                    &self.city,
-                )?;
-                part1.extend(daily_study_events.get_day_val(year, year + self.amnt_years - 1));
-                Ok(Return { list: part1 })
+        let mut list = get_list(
+            first_year,
+            last_year,
+            window,
+            self,
+            &main_events,
+            &custom_events,
+            &daily_study_events,
+        )?;
+        if let Some((from, until)) = dates {
+            list.retain(|x| x.civil_date() >= from && x.civil_date() < until);
+        }
+        let mut result1 = Return {
+            list,
+            icons: self.icons,
+            location: ics::location_id(self.location, self.city.as_ref()),
+            time_zone: self.city.as_ref().map(|x| x.time_zone.clone()),
+            fields: self.fields.clone(),
+            short_names: self.short_names,
+            shabbos_conflicts: None,
+            headers: self.headers,
+            summary: None,
+            meta: self.meta,
+            alarms: self.alarms.clone(),
+            candle_lighting_minutes: self.city.as_ref().map(|x| x.candlelighting_to_sunset),
+            provenance: if self.provenance {
+                Some(Provenance {
+                    location: self.location,
+                    yom_tov_observance: self.yom_tov_observance,
+                })
+            } else {
+                None
+            },
+        };
+        if self.warn_shabbos_conflicts {
+            let conflicts = conflicts::shabbos_conflicts(&result1.list, self.yom_tov_observance);
+            // The data formats are only the list, so the conflicts are reported as warnings.
+            if !matches!(
+                self.warnings.output_type,
+                OutputType::Pretty | OutputType::Regular
+            ) {
+                for conflict in &conflicts {
+                    self.warnings
+                        .warn(Warning::ShabbosConflict(conflict.message()))?;
+                }
+            } else {
+                result1.shabbos_conflicts = Some(conflicts);
             }
+        }
+        if let Some(categories) = &self.categories {
+            result1
+                .list
+                .retain(|x| categories.contains(&x.name.category()));
+        }
+        if !self.excluded_categories.is_empty() {
+            result1
+                .list
+                .retain(|x| !self.excluded_categories.contains(&x.name.category()));
+        }
+        if self.only_shabbos {
+            result1.list = shabbos::only_shabbos(result1.list);
+        }
+        if !self.no_sort {
+            result1.list.par_sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        }
+        if self.merge_spans {
+            result1.list = merge_spans(result1.list);
+        }
+        if self.merge_rosh_chodesh {
+            result1.list = merge_rosh_chodesh(result1.list);
+        }
+        if self.summary || self.check {
+            let (from, until) = covered_dates(first_year, last_year, window, dates);
+            result1.summary = Some(Summary::new(&result1.list, from, until));
+        }
+        Ok(result1)
+    }
+}
 
-            YearType::Gregorian(year) => {
-                let orig_jan_1 = Utc.ymd(year as i32 - 1, 12, 31).and_hms(18, 0, 0);
-                let last_jan_1 = Utc
-                    .ymd((year + self.amnt_years + 1) as i32, 1, 1)
-                    .and_hms(18, 0, 0);
-                let that_year = HebrewDate::try_from(orig_jan_1).unwrap().year();
-                let last_year = HebrewDate::try_from(last_jan_1).unwrap().year();
-                let mut part1 = get_list(
-                    that_year,
-                    last_year,
-                    self.location,
-                    &self.events,
-                    &main_events,
-                    &custom_events,
-                    self.exact_days,
-                    &self.city,
-                )?;
-                part1.extend(daily_study_events.get_day_val(that_year, last_year));
-                let mut part2: Vec<DayVal> = Vec::with_capacity((self.amnt_years as usize) * 100);
-                part1
-                    .into_iter()
-                    .filter(|x| x.day > Utc.ymd(year as i32, 1, 1).and_hms(0, 0, 0))
-                    .filter(|x| {
-                        x.day
-                            < Utc
-                                .ymd((year + self.amnt_years) as i32, 1, 1)
-                                .and_hms(0, 0, 0)
-                    })
-                    .for_each(|x| part2.push(x));
+/// The nights from the first until (but not including) the second.
+type Nights = (DateTime<Utc>, DateTime<Utc>);
 
-                Ok(Return { list: part2 })
-            }
+/// Returns the Hebrew years and the nights to generate to list the events printed with a civil
+/// date from `from` until `until`, and those dates. Observances during the day start on the night
+/// before their date, so the nights are generated from a day earlier, and only the Hebrew months
+/// the dates span are generated.
+fn civil_window(
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> (u64, u64, Nights, (NaiveDate, NaiveDate)) {
+    let first_night = from - Duration::days(1);
+    let last_year = hebrew_year(until - Duration::days(1));
+    (
+        hebrew_year(first_night),
+        last_year + 1,
+        (first_night, until),
+        (from.naive_utc().date(), until.naive_utc().date()),
+    )
+}
+
+/// Returns the first and last days, inclusive, of the Hebrew years, the nights or the civil dates
+/// the events were listed in.
+fn covered_dates(
+    first_year: u64,
+    last_year: u64,
+    window: Option<Nights>,
+    dates: Option<(NaiveDate, NaiveDate)>,
+) -> (NaiveDate, NaiveDate) {
+    use std::num::NonZeroI8;
+    if let Some((from, until)) = dates {
+        return (from, until.pred());
+    }
+    let (from, until) = window.unwrap_or_else(|| {
+        let rosh_hashanah = |year| -> DateTime<Utc> {
+            HebrewDate::from_ymd(year, HebrewMonth::Tishrei, NonZeroI8::new(1).unwrap())
+                .unwrap()
+                .into()
         };
-        let mut result1 = result?;
-        if !self.no_sort {
-            result1.list.par_sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        (rosh_hashanah(first_year), rosh_hashanah(last_year))
+    });
+    // The last night is the start of the next day, so its date is the last day.
+    (
+        (from + Duration::days(1)).naive_utc().date(),
+        until.naive_utc().date(),
+    )
+}
+
+fn hebrew_year(night: DateTime<Utc>) -> u64 {
+    HebrewDate::try_from(night).unwrap().year()
+}
+
+/// Returns the nights from the start of the Hebrew month until the start of the next month, or
+/// the first day of the Gregorian month and of the next month.
+fn month_window(
+    year: &YearType,
+    month: MonthFilter,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), AppError> {
+    use std::num::NonZeroI8;
+    match (year, month) {
+        (YearType::Hebrew(year), MonthFilter::Hebrew(month)) => {
+            let year = *year;
+            check_hebrew_year(year as i64)?;
+            let from: DateTime<Utc> =
+                HebrewDate::from_ymd(year, month, NonZeroI8::new(1).unwrap())?.into();
+            Ok((from, next_month(from)))
         }
-        result1.print(args)?;
-        Ok(())
+        (YearType::Gregorian(year), MonthFilter::Gregorian(month)) => {
+            let year = *year;
+            check_gregorian_year(year as i64)?;
+            let from = Utc.ymd(year as i32, month, 1).and_hms(0, 0, 0);
+            let until = if month == 12 {
+                Utc.ymd(year as i32 + 1, 1, 1).and_hms(0, 0, 0)
+            } else {
+                Utc.ymd(year as i32, month + 1, 1).and_hms(0, 0, 0)
+            };
+            Ok((from, until))
+        }
+        _ => Err(AppError::ArgUndefinedError(
+            "A Hebrew month needs a Hebrew year, and a Gregorian month a Gregorian year".into(),
+        )),
     }
 }
 
 fn get_list(
     year: u64,
     last_year: u64,
-    location: Location,
+    window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    list_args: &ListArgs,
+    main_events: &[TorahReadingType],
+    custom_events: &[CustomHoliday],
+    daily_study_events: &[DailyStudy],
+) -> Result<Vec<DayVal>, AppError> {
+    let providers = event_providers(
+        &list_args.events,
+        list_args.exact_days,
+        TorahReadings {
+            events: main_events.to_vec(),
+            location: list_args.location,
+            yom_tov_observance: list_args.yom_tov_observance,
+            city: list_args.city.as_ref(),
+            reading_cycle: list_args.reading_cycle,
+            havdalah: list_args.havdalah.clone(),
+            polar_fallback: list_args.polar_fallback,
+            solar_algorithm: list_args.solar_algorithm,
+        },
+        daily_study_events,
+    );
+    let custom_holidays = CustomHolidays::new(custom_events, list_args.location);
+    let mut provider_refs = providers
+        .iter()
+        .map(|provider| provider.as_ref())
+        .collect::<Vec<&dyn EventProvider>>();
+    provider_refs.push(&custom_holidays);
+
+    let list = match window {
+        Some((from, until)) => get_events_between(year, last_year, from, until, &provider_refs)?,
+        None => get_events(year, last_year, &provider_refs)?,
+    };
+    check_missing_custom_events(
+        custom_events,
+        &custom_holidays.missing_years(),
+        list_args.strict,
+        list_args.warnings,
+    )?;
+    Ok(list)
+}
+
+/// Returns the providers of the events other than custom holidays, in the order they're listed.
+fn event_providers<'a>(
     events: &[Event],
-    main_events: &Vec<TorahReadingType>,
-    custom_events: &Vec<CustomHoliday>,
     exact_days: bool,
-    city: &Option<City>,
-) -> Result<Vec<DayVal>, AppError> {
-    let amnt_years = last_year - year;
-    let mut part1: Vec<Vec<DayVal>> = Vec::with_capacity(amnt_years as usize);
-    HebrewYear::new(year)?;
-    HebrewYear::new(year + amnt_years)?;
+    torah_readings: TorahReadings<'a>,
+    daily_study_events: &[DailyStudy],
+) -> Vec<Box<dyn EventProvider + 'a>> {
+    let location = torah_readings.location;
+    let city = torah_readings.city;
+    let polar_fallback = torah_readings.polar_fallback;
+    let solar_algorithm = torah_readings.solar_algorithm;
+    let times: Vec<TimeOfDay> = events
+        .iter()
+        .filter_map(|x| match x {
+            Event::Zman(time) => Some(*time),
+            _ => None,
+        })
+        .collect();
+    // Times need a city, which is checked when the arguments are parsed.
+    let zmanim = match torah_readings.city {
+        Some(city) if !times.is_empty() => Some(Zmanim {
+            times,
+            location: torah_readings.yom_tov_observance,
+            city,
+            havdalah: torah_readings.havdalah.clone(),
+            polar_fallback: torah_readings.polar_fallback,
+            solar_algorithm: torah_readings.solar_algorithm,
+        }),
+        _ => None,
+    };
+    let mut providers: Vec<Box<dyn EventProvider + 'a>> = vec![Box::new(torah_readings)];
+    if let Some(zmanim) = zmanim {
+        providers.push(Box::new(zmanim));
+    }
+    if events.contains(&Event::MinorHoliday(MinorHoliday::Omer)) {
+        providers.push(Box::new(Omer));
+    }
+    if events.contains(&Event::IsraeliHolidays) {
+        providers.push(Box::new(IsraeliHolidays { exact_days }));
+    }
+    if events.contains(&Event::ChabadHolidays) {
+        providers.push(Box::new(ChabadHolidays));
+    }
+    if events.contains(&Event::DaveningChanges) {
+        providers.push(Box::new(DaveningChanges));
+    }
+    // Outside Israel they're only asked for with --show all, so they're left out.
+    if events.contains(&Event::Agricultural) && location == Location::Israel {
+        providers.push(Box::new(Agricultural));
+    }
+    if events.contains(&Event::ShabbosMevarchim) {
+        providers.push(Box::new(ShabbosMevarchim));
+    }
+    if events.contains(&Event::MinorHoliday(MinorHoliday::Minor)) {
+        providers.push(Box::new(MinorHolidays {
+            city,
+            polar_fallback,
+            solar_algorithm,
+        }));
+    }
+    if events.contains(&Event::MinorHoliday(MinorHoliday::AvosUbanim)) {
+        providers.push(Box::new(AvosUbanim { location }));
+    }
+    if !daily_study_events.is_empty() {
+        providers.push(Box::new(DailyStudies {
+            events: daily_study_events.to_vec(),
+            location,
+        }));
+    }
+    providers
+}
 
-    (0 as u32..(amnt_years as u32))
-        .into_par_iter()
-        .map(|x| {
-            let mut ret: Vec<DayVal> = Vec::with_capacity(200);
-            let year = HebrewYear::new(x as u64 + year).unwrap();
-
-            ret.extend(
-                year.get_holidays(location, &main_events)
-                    .into_iter()
-                    .map(|x| {
-                        let day: DateTime<Utc> = x.day().into();
-                        let mut light_on_time = false;
-                        let mut is_shabbos = false;
-                        if let TorahReading::Shabbos(_) = x.name() {
-                            light_on_time = true;
-                            is_shabbos = true;
-                        }
-                        if day.weekday() == Weekday::Fri {
-                            light_on_time = true;
-                            is_shabbos = true;
-                        }
-                        let mut is_yom_tov = false;
-                        if let TorahReading::YomTov(yt) = x.name() {
-                            match yt {
-                                heca_lib::prelude::YomTov::RoshHashanah2 => {
-                                    is_yom_tov = true;
-                                }
-
-                                heca_lib::prelude::YomTov::RoshHashanah1
-                                | heca_lib::prelude::YomTov::YomKippur
-                                | heca_lib::prelude::YomTov::Sukkos1
-                                | heca_lib::prelude::YomTov::ShminiAtzeres
-                                | heca_lib::prelude::YomTov::Pesach1
-                                | heca_lib::prelude::YomTov::Pesach7
-                                | heca_lib::prelude::YomTov::Shavuos1 => {
-                                    is_yom_tov = true;
-                                    if day.weekday() == Weekday::Sat {
-                                        light_on_time = false;
-                                    } else {
-                                        light_on_time = true;
-                                    }
-                                }
-
-                                yt => match location {
-                                    Location::Chul => {
-                                        if yt == heca_lib::prelude::YomTov::Sukkos2
-                                            || yt == heca_lib::prelude::YomTov::SimchasTorah
-                                            || yt == heca_lib::prelude::YomTov::Pesach2
-                                            || yt == heca_lib::prelude::YomTov::Pesach8
-                                            || yt == heca_lib::prelude::YomTov::Shavuos2
-                                        {
-                                            is_yom_tov = true;
-                                        } else {
-                                            is_yom_tov = false;
-                                        }
-                                    }
-                                    Location::Israel => {
-                                        is_yom_tov = false;
-                                    }
-                                },
-                            };
-                        };
-                        if is_shabbos || is_yom_tov {
-                            let candle_lighting = if let Some(city) = city {
-                                use zmanim::prelude::Zmanim;
-                                let date: NaiveDate = day.date().naive_local();
-                                if light_on_time {
-                                    if let Some(time) = zmanim::get(
-                                        &Zmanim::Sunset,
-                                        city.latitude,
-                                        city.longitude,
-                                        date,
-                                        &city.time_zone,
-                                    ) {
-                                        Some(
-                                            time - Duration::minutes(
-                                                city.candlelighting_to_sunset as i64 - 1,
-                                            ),
-                                        )
-                                    } else {
-                                        None
-                                    }
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            };
-                            DayVal {
-                                day,
-                                name: Name::TorahReading(x.name()),
-                                candle_lighting: Some(candle_lighting),
-                            }
-                        } else {
-                            DayVal {
-                                day,
-                                name: Name::TorahReading(x.name()),
-                                candle_lighting: None,
-                            }
-                        }
-                    }),
-            );
+/// The events [`HebrewYearExt::iter_events`] lists, chosen like `heca list --show`. Zmanim,
+/// candle lighting and havdalah need a city, so they can't be listed.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub events: Vec<Event>,
+    /// Lists the modern Israeli holidays on their dates, even if they're moved to avoid Shabbos.
+    pub exact_days: bool,
+}
 
-            if events.contains(&Event::MinorHoliday(MinorHoliday::Omer)) {
-                ret.extend_from_slice(&get_omer(&year));
-            }
-            if events.contains(&Event::IsraeliHolidays) {
-                ret.extend_from_slice(&israeli_holidays::get(&year, exact_days));
-            }
-            if events.contains(&Event::ChabadHolidays) {
-                ret.extend_from_slice(&chabad_holidays::get(&year));
-            }
-            if events.contains(&Event::ShabbosMevarchim) {
-                ret.extend_from_slice(&shabbos_mevarchim::get(&year));
+impl EventFilter {
+    pub fn new(events: Vec<Event>) -> Self {
+        Self {
+            events,
+            exact_days: false,
+        }
+    }
+}
+
+/// Lists a year's events as they're needed, so a search over the years (such as
+/// `(5785..).flat_map(...).find(...)`) stops at the first month with a match.
+pub trait HebrewYearExt {
+    /// Returns the events in the year, sorted by their night. Fails if the filter has zmanim.
+    fn iter_events(
+        &self,
+        location: Location,
+        filter: &EventFilter,
+    ) -> Result<Box<dyn Iterator<Item = DayVal>>, AppError>;
+}
+
+impl HebrewYearExt for HebrewYear {
+    fn iter_events(
+        &self,
+        location: Location,
+        filter: &EventFilter,
+    ) -> Result<Box<dyn Iterator<Item = DayVal>>, AppError> {
+        let mut main_events = vec![];
+        let mut custom_events = vec![];
+        let mut daily_study_events = vec![];
+        for event in &filter.events {
+            match event {
+                Event::TorahReadingType(x) => main_events.push(*x),
+                Event::CustomHoliday(x) => custom_events.push(x.clone()),
+                Event::DailyStudy(x) => daily_study_events.push(x.clone()),
+                Event::Zman(_) => {
+                    return Err(AppError::ArgUndefinedError(
+                        "zmanim need a city, so they can't be listed by iter_events".into(),
+                    ))
+                }
+                // Listed by their own providers.
+                Event::MinorHoliday(_)
+                | Event::IsraeliHolidays
+                | Event::ChabadHolidays
+                | Event::DaveningChanges
+                | Event::Agricultural
+                | Event::ShabbosMevarchim => {}
             }
-            if events.contains(&Event::MinorHoliday(MinorHoliday::Minor)) {
-                ret.extend(get_minor_holidays(&year));
+        }
+        let torah_readings = TorahReadings {
+            events: main_events,
+            location,
+            yom_tov_observance: location,
+            city: None,
+            reading_cycle: None,
+            havdalah: vec![],
+            polar_fallback: PolarFallback::None,
+            solar_algorithm: SolarAlgorithm::Noaa,
+        };
+        let providers = event_providers(
+            &filter.events,
+            filter.exact_days,
+            torah_readings,
+            &daily_study_events,
+        );
+        let year = *self;
+        let months = months(self);
+        let last_month = months.len() - 1;
+        // The events which aren't on every day are few, so they're listed for the whole year
+        // when the first month is needed, and handed out a month at a time. Daily study is only
+        // listed for the month.
+        let mut yearly: Option<std::vec::IntoIter<DayVal>> = None;
+        Ok(Box::new(months.into_iter().enumerate().flat_map(
+            move |(index, (from, until))| {
+                let yearly = yearly
+                    .get_or_insert_with(|| {
+                        let mut events: Vec<DayVal> = providers
+                            .iter()
+                            .filter(|provider| !provider.is_daily())
+                            .flat_map(|provider| provider.get(&year))
+                            .chain(CustomHolidays::new(&custom_events, location).get(&year))
+                            .collect();
+                        events.sort_by_key(|x| x.day);
+                        events.into_iter()
+                    })
+                    .by_ref();
+                let mut events: Vec<DayVal> = if index == last_month {
+                    yearly.collect()
+                } else {
+                    let mut events = vec![];
+                    while yearly.as_slice().first().is_some_and(|x| x.day < until) {
+                        events.extend(yearly.next());
+                    }
+                    events
+                };
+                events.extend(
+                    providers
+                        .iter()
+                        .filter(|provider| provider.is_daily())
+                        .flat_map(|provider| provider.get_between(&year, from, until)),
+                );
+                events.sort_by_key(|x| x.day);
+                events
+            },
+        )))
+    }
+}
+
+/// Lists the events of every provider in the Hebrew years from `year` until (but not
+/// including) `last_year`, in the order of the years and of the providers within each year.
+/// Each provider is generated in parallel in each year, and providers which list every day in
+/// each month, so that a single year with zmanim or daily study still uses every core.
+pub fn get_events(
+    year: u64,
+    last_year: u64,
+    providers: &[&dyn EventProvider],
+) -> Result<Vec<DayVal>, AppError> {
+    generate(year, last_year, providers, None)
+}
+
+/// Like `get_events`, but only lists the events whose night is from `from` until (but not
+/// including) `until`, letting providers skip the rest of the year.
+pub fn get_events_between(
+    year: u64,
+    last_year: u64,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    providers: &[&dyn EventProvider],
+) -> Result<Vec<DayVal>, AppError> {
+    generate(year, last_year, providers, Some((from, until)))
+}
+
+fn generate(
+    year: u64,
+    last_year: u64,
+    providers: &[&dyn EventProvider],
+    window: Option<(DateTime<Utc>, DateTime<Utc>)>,
+) -> Result<Vec<DayVal>, AppError> {
+    HebrewYear::new(year)?;
+    HebrewYear::new(last_year)?;
+    let years = (year..last_year)
+        .map(|x| HebrewYear::new(x).unwrap())
+        .collect::<Vec<HebrewYear>>();
+
+    // Each part of the list is a provider in a year, or in a month of it, and the window it's
+    // generated in.
+    type Part<'a> = (
+        &'a HebrewYear,
+        &'a dyn EventProvider,
+        Option<(DateTime<Utc>, DateTime<Utc>)>,
+    );
+    let mut parts: Vec<Part> = vec![];
+    for year in &years {
+        for provider in providers {
+            if !provider.is_daily() {
+                parts.push((year, *provider, window));
+                continue;
             }
-            custom_events.iter().for_each(|x| {
-                if let Ok(day) = year.get_hebrew_date(x.date.month, x.date.day) {
-                    let d = DayVal {
-                        name: Name::CustomHoliday(x.clone()),
-                        day: day.try_into().unwrap(),
-                        candle_lighting: None,
-                    };
-                    ret.push(d);
-                } else if let Some(not_exists) = &x.if_not_exists {
-                    not_exists.iter().for_each(|day_month| {
-                        if let Ok(day) = year.get_hebrew_date(day_month.month, day_month.day) {
-                            let d = DayVal {
-                                name: Name::CustomHoliday(x.clone()),
-                                day: day.into(),
-                                candle_lighting: None,
-                            };
-                            ret.push(d);
-                        }
-                    });
+            for (from, until) in months(year) {
+                let (from, until) = match window {
+                    Some((window_from, window_until)) => {
+                        (from.max(window_from), until.min(window_until))
+                    }
+                    None => (from, until),
+                };
+                if from < until {
+                    parts.push((year, *provider, Some((from, until))));
                 }
-            });
+            }
+        }
+    }
 
-            ret
+    // The parts are put back in the order they were pushed in, whichever is generated first, so
+    // the list is the same on every run even when it isn't sorted (see `--no-sort`).
+    let mut events: Vec<Vec<DayVal>> = Vec::with_capacity(parts.len());
+    parts
+        .into_par_iter()
+        .map(|(year, provider, window)| match window {
+            Some((from, until)) => provider.get_between(year, from, until),
+            None => provider.get(year),
         })
-        .collect_into_vec(&mut part1);
-    let mut part2: Vec<DayVal> = Vec::with_capacity(amnt_years as usize);
-    part1.into_iter().flatten().for_each(|a| {
-        part2.push(a);
-    });
-    Ok(part2)
+        .collect_into_vec(&mut events);
+    // The parts are moved into a list of the right size, instead of growing it as they come.
+    let mut list = Vec::with_capacity(events.iter().map(Vec::len).sum());
+    events.into_iter().for_each(|days| list.extend(days));
+    Ok(list)
+}
+
+/// Returns the nights each month of the year starts on, and the nights the next month starts on.
+/// The first month starts the night before Rosh Hashanah, since a year's candle lighting for Rosh
+/// Hashanah is on it.
+fn months(year: &HebrewYear) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    use std::num::NonZeroI8;
+    let mut from: DateTime<Utc> = year
+        .get_hebrew_date(HebrewMonth::Tishrei, NonZeroI8::new(1).unwrap())
+        .unwrap()
+        .into();
+    let last_night: DateTime<Utc> = year
+        .get_hebrew_date(HebrewMonth::Elul, NonZeroI8::new(29).unwrap())
+        .unwrap()
+        .into();
+    let mut months = Vec::with_capacity(13);
+    while from <= last_night {
+        let until = next_month(from);
+        let start = if months.is_empty() {
+            from - Duration::days(1)
+        } else {
+            from
+        };
+        months.push((start, until));
+        from = until;
+    }
+    months
+}
+
+/// Returns the night the month after the one starting on `from` starts on.
+fn next_month(from: DateTime<Utc>) -> DateTime<Utc> {
+    // Hebrew months have 29 or 30 days.
+    let until = from + Duration::days(29);
+    if HebrewDate::try_from(until).unwrap().month() == HebrewDate::try_from(from).unwrap().month() {
+        until + Duration::days(1)
+    } else {
+        until
+    }
+}
+
+/// Custom holidays on a day which doesn't exist in every year (such as the 30th of Cheshvan, or
+/// in Adar 2) without a fallback in `ifNotExists` are skipped in those years. Warn about them,
+/// or fail if `--strict` was passed.
+fn check_missing_custom_events(
+    custom_events: &[CustomHoliday],
+    missing_years: &[Vec<u64>],
+    strict: bool,
+    warnings: Warnings,
+) -> Result<(), AppError> {
+    for (custom_event, years) in custom_events.iter().zip(missing_years) {
+        if years.is_empty() {
+            continue;
+        }
+        let years = years
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        let message = match &custom_event.date {
+            CustomDate::Hebrew(_) => format!(
+                "{} ({}) was skipped in {}, since its date doesn't exist in those years. Add an ifNotExists to the config file to list it on another date",
+                custom_event.printable, custom_event.json, years
+            ),
+            CustomDate::Weekday { month, .. } => format!(
+                "{} ({}) was skipped in {}, since {:?} doesn't have that day in those years",
+                custom_event.printable, custom_event.json, years, month
+            ),
+            CustomDate::Relative { event, .. } => format!(
+                "{} ({}) was skipped in {}, since {} isn't in those years",
+                custom_event.printable, custom_event.json, years, event
+            ),
+        };
+        if strict {
+            return Err(AppError::MissingCustomHoliday(message));
+        }
+        warnings.warn(Warning::SkippedCustomHoliday(message))?;
+    }
+    Ok(())
 }
@@ -0,0 +1,483 @@
+//! Prints the list as an Apache Parquet file, so that long ranges of events can be loaded into
+//! pandas, polars or a database without parsing JSON. The file has a single row group, with one
+//! uncompressed, plainly encoded page for every column, which every Parquet reader can read. Its
+//! metadata is written in Thrift's compact protocol, as the format requires.
+
+use crate::args::types::{DayVal, MainArgs};
+use crate::list::ics::uid;
+use chrono::prelude::*;
+use std::convert::TryFrom;
+use std::io::{Result, Write};
+
+const MAGIC: &[u8] = b"PAR1";
+
+// Parquet's physical types, converted types, repetitions and encodings.
+const INT32: i32 = 1;
+const INT64: i32 = 2;
+const BYTE_ARRAY: i32 = 6;
+const UTF8: i32 = 0;
+const DATE: i32 = 6;
+const TIMESTAMP_MILLIS: i32 = 9;
+const REQUIRED: i32 = 0;
+const OPTIONAL: i32 = 1;
+const PLAIN: i32 = 0;
+const RLE: i32 = 3;
+
+enum Values {
+    /// Days since 1970-01-01.
+    Date(Vec<i32>),
+    Text(Vec<String>),
+    /// Milliseconds since 1970-01-01T00:00:00Z, or `None` if there is no time.
+    Timestamp(Vec<Option<i64>>),
+}
+
+struct Column {
+    name: &'static str,
+    values: Values,
+}
+
+impl Column {
+    fn physical_type(&self) -> i32 {
+        match self.values {
+            Values::Date(_) => INT32,
+            Values::Text(_) => BYTE_ARRAY,
+            Values::Timestamp(_) => INT64,
+        }
+    }
+
+    fn converted_type(&self) -> i32 {
+        match self.values {
+            Values::Date(_) => DATE,
+            Values::Text(_) => UTF8,
+            Values::Timestamp(_) => TIMESTAMP_MILLIS,
+        }
+    }
+
+    fn repetition(&self) -> i32 {
+        match self.values {
+            Values::Timestamp(_) => OPTIONAL,
+            _ => REQUIRED,
+        }
+    }
+
+    /// Returns the data of the column's page: the definition levels of an optional column,
+    /// followed by its values.
+    fn page(&self) -> Vec<u8> {
+        let mut page = vec![];
+        match &self.values {
+            Values::Date(values) => {
+                for value in values {
+                    page.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            Values::Text(values) => {
+                for value in values {
+                    page.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                    page.extend_from_slice(value.as_bytes());
+                }
+            }
+            Values::Timestamp(values) => {
+                // A single bit-packed run, with one bit for every value saying if it's there.
+                let mut levels = vec![];
+                push_varint(&mut levels, (values.len() as u64).div_ceil(8) << 1 | 1);
+                for chunk in values.chunks(8) {
+                    levels.push(
+                        chunk
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, value)| value.is_some())
+                            .fold(0, |byte, (index, _)| byte | 1 << index),
+                    );
+                }
+                page.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+                page.extend_from_slice(&levels);
+                for value in values.iter().flatten() {
+                    page.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        page
+    }
+}
+
+/// Where a column's page was written, for the file's metadata.
+struct ColumnChunk {
+    offset: i64,
+    size: i64,
+}
+
+/// Prints the list with a row for every event. Times are in UTC, as Parquet timestamps are.
+pub fn print<W: Write>(
+    list: &[DayVal],
+    location: &str,
+    args: &MainArgs,
+    lock: &mut W,
+) -> Result<()> {
+    let days = |date: NaiveDate| (date - NaiveDate::from_ymd(1970, 1, 1)).num_days() as i32;
+    let title = |d: &DayVal| {
+        let mut title = vec![];
+        d.name
+            .pretty_print(&mut title, args.language, args.transliteration)
+            .unwrap();
+        String::from_utf8(title).unwrap()
+    };
+    let columns = [
+        Column {
+            name: "night",
+            values: Values::Date(
+                list.iter()
+                    .map(|d| days(d.day.naive_utc().date()))
+                    .collect(),
+            ),
+        },
+        Column {
+            name: "date",
+            values: Values::Date(list.iter().map(|d| days(d.civil_date())).collect()),
+        },
+        Column {
+            name: "event",
+            values: Values::Text(list.iter().map(|d| d.name.id()).collect()),
+        },
+        Column {
+            name: "title",
+            values: Values::Text(list.iter().map(title).collect()),
+        },
+        Column {
+            name: "category",
+            values: Values::Text(
+                list.iter()
+                    .map(|d| {
+                        let category = serde_json::to_value(d.name.category()).unwrap();
+                        category.as_str().unwrap().into()
+                    })
+                    .collect(),
+            ),
+        },
+        Column {
+            name: "candle_lighting",
+            values: Values::Timestamp(
+                list.iter()
+                    .map(|d| d.candle_lighting.flatten().map(|x| x.timestamp_millis()))
+                    .collect(),
+            ),
+        },
+        Column {
+            name: "havdalah",
+            values: Values::Timestamp(
+                list.iter()
+                    .map(|d| {
+                        d.havdalah
+                            .first()
+                            .and_then(|x| x.time)
+                            .map(|x| x.timestamp_millis())
+                    })
+                    .collect(),
+            ),
+        },
+        Column {
+            name: "uid",
+            values: Values::Text(list.iter().map(|d| uid(d, location)).collect()),
+        },
+    ];
+    write(&columns, list.len(), lock)
+}
+
+/// Writes the columns, which all have `rows` values, as a Parquet file.
+fn write<W: Write>(columns: &[Column], rows: usize, lock: &mut W) -> Result<()> {
+    let row_count = to_i32(rows, "The number of rows")?;
+    lock.write_all(MAGIC)?;
+    let mut offset = MAGIC.len() as i64;
+    let mut chunks = Vec::with_capacity(columns.len());
+    for column in columns {
+        let page = column.page();
+        let page_size = to_i32(page.len(), &format!("The {} column", column.name))?;
+        let mut header = Thrift::default();
+        header.i32(1, 0); // A data page.
+        header.i32(2, page_size);
+        header.i32(3, page_size);
+        header.begin_struct(5);
+        header.i32(1, row_count);
+        header.i32(2, PLAIN);
+        header.i32(3, RLE);
+        header.i32(4, RLE);
+        header.end_struct();
+        header.end_struct();
+        lock.write_all(&header.buf)?;
+        lock.write_all(&page)?;
+        let size = (header.buf.len() + page.len()) as i64;
+        chunks.push(ColumnChunk { offset, size });
+        offset += size;
+    }
+
+    let footer = metadata(columns, &chunks, row_count.into());
+    lock.write_all(&footer)?;
+    lock.write_all(&(footer.len() as u32).to_le_bytes())?;
+    lock.write_all(MAGIC)
+}
+
+/// Parquet stores the sizes of pages and the number of rows as 32 bit numbers, so a list too long
+/// for them can't be written.
+fn to_i32(value: usize, what: &str) -> Result<i32> {
+    i32::try_from(value).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} is too big for a Parquet file: {}", what, value),
+        )
+    })
+}
+
+/// Returns the file's metadata: its schema, and where every column is.
+fn metadata(columns: &[Column], chunks: &[ColumnChunk], rows: i64) -> Vec<u8> {
+    let mut thrift = Thrift::default();
+    thrift.i32(1, 1);
+    thrift.begin_list(2, STRUCT, columns.len() + 1);
+    thrift.begin_element();
+    thrift.binary(4, b"schema");
+    thrift.i32(5, columns.len() as i32);
+    thrift.end_struct();
+    for column in columns {
+        thrift.begin_element();
+        thrift.i32(1, column.physical_type());
+        thrift.i32(3, column.repetition());
+        thrift.binary(4, column.name.as_bytes());
+        thrift.i32(6, column.converted_type());
+        thrift.end_struct();
+    }
+    thrift.i64(3, rows);
+    thrift.begin_list(4, STRUCT, 1);
+    thrift.begin_element();
+    thrift.begin_list(1, STRUCT, columns.len());
+    for (column, chunk) in columns.iter().zip(chunks) {
+        thrift.begin_element();
+        thrift.i64(2, chunk.offset);
+        thrift.begin_struct(3);
+        thrift.i32(1, column.physical_type());
+        thrift.begin_list(2, I32, 2);
+        thrift.list_i32(PLAIN);
+        thrift.list_i32(RLE);
+        thrift.begin_list(3, BINARY, 1);
+        thrift.list_binary(column.name.as_bytes());
+        thrift.i32(4, 0); // Uncompressed.
+        thrift.i64(5, rows);
+        thrift.i64(6, chunk.size);
+        thrift.i64(7, chunk.size);
+        thrift.i64(9, chunk.offset);
+        thrift.end_struct();
+        thrift.end_struct();
+    }
+    thrift.i64(2, chunks.iter().map(|x| x.size).sum());
+    thrift.i64(3, rows);
+    thrift.end_struct();
+    thrift.binary(
+        6,
+        concat!("heca version ", env!("CARGO_PKG_VERSION")).as_bytes(),
+    );
+    thrift.end_struct();
+    thrift.buf
+}
+
+// The types of Thrift's compact protocol.
+const I32: u8 = 5;
+const I64: u8 = 6;
+const BINARY: u8 = 8;
+const LIST: u8 = 9;
+const STRUCT: u8 = 12;
+
+/// Writes structs in Thrift's compact protocol, which stores every field's id as the difference
+/// from the field before it in the same struct.
+struct Thrift {
+    buf: Vec<u8>,
+    /// The id of the last field written in each struct being written.
+    last_ids: Vec<i16>,
+}
+
+impl Default for Thrift {
+    fn default() -> Self {
+        Thrift {
+            buf: vec![],
+            last_ids: vec![0],
+        }
+    }
+}
+
+impl Thrift {
+    fn field(&mut self, id: i16, kind: u8) {
+        let last_id = self.last_ids.last_mut().unwrap();
+        let delta = id - *last_id;
+        if delta > 0 && delta <= 15 {
+            self.buf.push((delta as u8) << 4 | kind);
+        } else {
+            self.buf.push(kind);
+            push_varint(&mut self.buf, zigzag(id as i64));
+        }
+        *last_id = id;
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field(id, I32);
+        push_varint(&mut self.buf, zigzag(value as i64));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field(id, I64);
+        push_varint(&mut self.buf, zigzag(value));
+    }
+
+    fn binary(&mut self, id: i16, value: &[u8]) {
+        self.field(id, BINARY);
+        self.list_binary(value);
+    }
+
+    fn begin_struct(&mut self, id: i16) {
+        self.field(id, STRUCT);
+        self.last_ids.push(0);
+    }
+
+    fn end_struct(&mut self) {
+        self.buf.push(0);
+        self.last_ids.pop();
+    }
+
+    fn begin_list(&mut self, id: i16, kind: u8, len: usize) {
+        self.field(id, LIST);
+        if len < 15 {
+            self.buf.push((len as u8) << 4 | kind);
+        } else {
+            self.buf.push(0xf0 | kind);
+            push_varint(&mut self.buf, len as u64);
+        }
+    }
+
+    /// Starts a struct in a list, which has no field header.
+    fn begin_element(&mut self) {
+        self.last_ids.push(0);
+    }
+
+    fn list_i32(&mut self, value: i32) {
+        push_varint(&mut self.buf, zigzag(value as i64));
+    }
+
+    fn list_binary(&mut self, value: &[u8]) {
+        push_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn push_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write, Column, Values};
+    use ::parquet::basic::{ConvertedType, Repetition, Type};
+    use ::parquet::file::reader::{FileReader, SerializedFileReader};
+    use ::parquet::record::Field;
+    use std::fs::{self, File};
+
+    #[test]
+    fn read_back() {
+        let columns = [
+            Column {
+                name: "date",
+                values: Values::Date((0..20).map(|x| 20_000 + x).collect()),
+            },
+            Column {
+                name: "title",
+                values: Values::Text((0..20).map(|x| format!("שבת {}", x)).collect()),
+            },
+            Column {
+                name: "havdalah",
+                values: Values::Timestamp(
+                    (0..20)
+                        .map(|x| Some(1_700_000_000_000 + x).filter(|_| x % 3 == 0))
+                        .collect(),
+                ),
+            },
+        ];
+        let path = std::env::temp_dir().join(format!("heca-{}.parquet", std::process::id()));
+        write(&columns, 20, &mut File::create(&path).unwrap()).unwrap();
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 20);
+        let schema: Vec<_> = metadata
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|x| {
+                (
+                    x.name().to_owned(),
+                    x.physical_type(),
+                    x.converted_type(),
+                    x.self_type().get_basic_info().repetition(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            schema,
+            [
+                (
+                    "date".into(),
+                    Type::INT32,
+                    ConvertedType::DATE,
+                    Repetition::REQUIRED
+                ),
+                (
+                    "title".into(),
+                    Type::BYTE_ARRAY,
+                    ConvertedType::UTF8,
+                    Repetition::REQUIRED
+                ),
+                (
+                    "havdalah".into(),
+                    Type::INT64,
+                    ConvertedType::TIMESTAMP_MILLIS,
+                    Repetition::OPTIONAL
+                ),
+            ]
+        );
+
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .get_column_iter()
+                    .map(|(_, field)| field.clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(rows.len(), 20);
+        assert_eq!(
+            rows[3],
+            [
+                Field::Date(20_003),
+                Field::Str("שבת 3".into()),
+                Field::TimestampMillis(1_700_000_000_003),
+            ]
+        );
+        assert_eq!(rows[4][2], Field::Null);
+        assert_eq!(rows.iter().filter(|row| row[2] == Field::Null).count(), 13);
+    }
+
+    #[test]
+    fn too_many_rows() {
+        let rows = i32::MAX as usize + 1;
+        let err = write(&[], rows, &mut vec![]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(
+            err.to_string(),
+            "The number of rows is too big for a Parquet file: 2147483648"
+        );
+    }
+}
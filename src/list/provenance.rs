@@ -0,0 +1,145 @@
+//! The rule which listed each event, printed with `--provenance` to debug calendars merged from
+//! many sources.
+//!
+//! Most events are named by the event of `--show` which lists them, like `israeli-holidays`.
+//! Some are named by the rule which put them on their day: Rosh Hashanah by the dechiyos which
+//! postponed it from the molad, doubled parshiyos by why they're doubled, and fasts by whether
+//! they were moved off Shabbos. Custom holidays are named by where they are in the config file.
+
+use crate::args::types::{DailyStudyOutput, DayVal, MinorDays, Name, SpanKind, TimeOfDay};
+use crate::list::ics::location_id;
+use crate::year_info::doubled::{doubling_reason, PAIRS};
+use crate::year_info::explain::RoshHashanah;
+use crate::year_info::{civil_date, YearInfo};
+use chrono::prelude::*;
+use heca_lib::prelude::{Chol, HebrewMonth, Location, Parsha, TorahReading, YomTov};
+use heca_lib::HebrewDate;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Where the events were listed for, which decides the rules of the parsha and of Yom Tov.
+#[derive(Clone, Copy)]
+pub struct Provenance {
+    pub location: Location,
+    pub yom_tov_observance: Location,
+}
+
+impl fmt::Debug for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Provenance")
+            .field("location", &location_id(self.location, None))
+            .field(
+                "yom_tov_observance",
+                &location_id(self.yom_tov_observance, None),
+            )
+            .finish()
+    }
+}
+
+impl Provenance {
+    /// The rule which listed the day, like `dechiyah:lo-adu-rosh` or `custom:config.toml#12`.
+    pub fn source(&self, d: &DayVal) -> String {
+        match &d.name {
+            Name::TorahReading(TorahReading::YomTov(YomTov::RoshHashanah1))
+            | Name::TorahReading(TorahReading::YomTov(YomTov::RoshHashanah2)) => {
+                rosh_hashanah_source(d.day)
+            }
+            Name::TorahReading(TorahReading::YomTov(_)) => {
+                format!("yom-tov:{}", location_id(self.yom_tov_observance, None))
+            }
+            Name::TorahReading(TorahReading::Shabbos(parsha)) => self.parsha_source(*parsha, d.day),
+            Name::TorahReading(TorahReading::SpecialParsha(_)) => "special-parshas".into(),
+            Name::TorahReading(TorahReading::Chol(chol)) => chol_source(*chol, d.day),
+            Name::MinorDays(MinorDays::AvosUbanim) => "avos-ubanim".into(),
+            Name::MinorDays(day) if day.is_omer() => "omer".into(),
+            Name::MinorDays(_) => "minor-holidays".into(),
+            Name::CustomHoliday(custom_holiday) => match &custom_holiday.source {
+                Some(source) => format!("custom:{}", source),
+                None => format!("custom:{}", custom_holiday.json),
+            },
+            Name::DailyStudy(daily_study) => match daily_study {
+                DailyStudyOutput::Daf(_) => "daf-yomi",
+                DailyStudyOutput::DafHashavua(_) => "daf-hashavua",
+                DailyStudyOutput::RambamThreeChapters(_) => "rambam-3-chapters",
+                DailyStudyOutput::RambamOneChapters(_) => "rambam-1-chapter",
+                DailyStudyOutput::YerushalmiYomi(_) => "yerushalmi-yomi",
+                DailyStudyOutput::Chumash(_) => "chumash",
+            }
+            .into(),
+            Name::IsraeliHoliday(_) => "israeli-holidays".into(),
+            Name::ChabadHoliday(_) => "chabad-holidays".into(),
+            Name::Agricultural(_) => "agricultural".into(),
+            Name::DaveningChange(_) => "davening-changes".into(),
+            Name::ShabbosMevarchim(_) => "shabbos-mevarchim".into(),
+            Name::Span(span) if span.kind == SpanKind::RoshChodesh => "merge-rosh-chodesh".into(),
+            Name::Span(_) => "merge-spans".into(),
+            Name::Shabbos(_) => "only-shabbos".into(),
+            Name::Zman(zman) => format!(
+                "zmanim:{}",
+                match zman.time_of_day {
+                    TimeOfDay::CandleLighting => "candles",
+                    TimeOfDay::Havdalah => "havdalah",
+                    TimeOfDay::Zman(zman) => zman.arg_name(),
+                }
+            ),
+            Name::StudySession(_) => "study-plan".into(),
+        }
+    }
+
+    /// A doubled parsha is named by why it's doubled that year, like `doubling:regular-year`.
+    fn parsha_source(&self, parsha: Parsha, night: DateTime<Utc>) -> String {
+        if !PAIRS.contains(&parsha) {
+            return format!("shabbos:{}", location_id(self.location, None));
+        }
+        let year = HebrewDate::try_from(night).unwrap().year();
+        let reason = YearInfo::new(year).and_then(|info| {
+            let next_rosh_hashanah = civil_date(year + 1, HebrewMonth::Tishrei, 1)?.weekday();
+            Ok(doubling_reason(
+                parsha,
+                &info,
+                next_rosh_hashanah,
+                self.location,
+            ))
+        });
+        match reason {
+            Ok(reason) => format!("doubling:{}", reason.id()),
+            Err(_) => format!("shabbos:{}", location_id(self.location, None)),
+        }
+    }
+}
+
+/// Rosh Hashanah is named by the dechiyos which postponed it, like `dechiyah:lo-adu-rosh`, or
+/// `molad` if it's on the day of the molad.
+fn rosh_hashanah_source(night: DateTime<Utc>) -> String {
+    let year = HebrewDate::try_from(night).unwrap().year();
+    match RoshHashanah::new(year) {
+        Ok(rosh_hashanah) if !rosh_hashanah.dechiyos.is_empty() => format!(
+            "dechiyah:{}",
+            rosh_hashanah
+                .dechiyos
+                .iter()
+                .map(|x| x.id())
+                .collect::<Vec<&str>>()
+                .join("+")
+        ),
+        _ => "molad".into(),
+    }
+}
+
+/// A fast which was moved off Shabbos is `fast:postponed` or `fast:advanced`.
+fn chol_source(chol: Chol, night: DateTime<Utc>) -> String {
+    let day_of_month = match chol {
+        Chol::TzomGedalia => 3,
+        Chol::TenTeves => 10,
+        Chol::TaanisEsther => 13,
+        Chol::SeventeenTammuz => 17,
+        Chol::NineAv => 9,
+        _ => return "chol".into(),
+    };
+    let date = HebrewDate::try_from(night).unwrap();
+    match date.day().get().cmp(&day_of_month) {
+        std::cmp::Ordering::Greater => "fast:postponed".into(),
+        std::cmp::Ordering::Less => "fast:advanced".into(),
+        std::cmp::Ordering::Equal => "chol".into(),
+    }
+}
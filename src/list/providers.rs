@@ -0,0 +1,743 @@
+use super::GetDayVal;
+use crate::algorithms::candle_lighting::City;
+use crate::algorithms::reading_cycle::TorahPortion;
+use crate::algorithms::{
+    agricultural, chabad_holidays, havdalah, israeli_holidays, shabbos_mevarchim,
+};
+use crate::args::types::{
+    CustomDate, CustomHoliday, DailyStudy, DaveningChange, DayVal, HavdalahOpinion, MinorDays,
+    Name, PolarFallback, ReadingCycle, SolarAlgorithm, TimeOfDay, TimeRange, Zman, ZmanTime,
+};
+use crate::prelude::constants::get_minor_holidays;
+use crate::prelude::get_omer::get_omer;
+use crate::prelude::{events_on, EventProvider};
+use crate::zmanim_table::get_zman;
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::*;
+use heca_lib::HebrewYear;
+use std::num::NonZeroI8;
+use std::sync::Mutex;
+
+/// Returns the reading of the Shabbos on or after the civil date, and the Hebrew year it's in.
+/// If that Shabbos is Yom Tov or Chol Hamoed, its reading is returned instead of a parsha.
+pub fn weekly_reading(date: NaiveDate, location: Location) -> (DayVal, HebrewYear) {
+    let shabbos = date + Duration::days(6 - date.weekday().num_days_from_sunday() as i64);
+    let provider = TorahReadings {
+        events: vec![
+            TorahReadingType::Shabbos,
+            TorahReadingType::YomTov,
+            TorahReadingType::Chol,
+        ],
+        location,
+        yom_tov_observance: location,
+        city: None,
+        reading_cycle: None,
+        havdalah: vec![],
+        polar_fallback: PolarFallback::None,
+        solar_algorithm: SolarAlgorithm::Noaa,
+    };
+    let (events, year) = events_on(&provider, shabbos);
+    let priority = |x: &DayVal| match x.name {
+        Name::TorahReading(TorahReading::Shabbos(_)) => 0,
+        Name::TorahReading(TorahReading::YomTov(_)) => 1,
+        _ => 2,
+    };
+    // Every Shabbos has a reading.
+    (events.into_iter().min_by_key(priority).unwrap(), year)
+}
+
+/// Shabbos, Yom Tov and the other days with a Torah reading, with candle lighting and havdalah
+/// times if a city was given.
+pub struct TorahReadings<'a> {
+    pub events: Vec<TorahReadingType>,
+    /// Where the Torah is read, which decides the parsha of every Shabbos.
+    pub location: Location,
+    /// Where Yom Tov is kept, which decides how many days it is. It's `location`, except for
+    /// travelers, who keep the Yom Tov of home.
+    pub yom_tov_observance: Location,
+    pub city: Option<&'a City>,
+    pub reading_cycle: Option<ReadingCycle>,
+    pub havdalah: Vec<HavdalahOpinion>,
+    pub polar_fallback: PolarFallback,
+    pub solar_algorithm: SolarAlgorithm,
+}
+
+impl EventProvider for TorahReadings<'_> {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        let yom_tov_nights: Vec<DateTime<Utc>> = if self.city.is_some() {
+            year.get_holidays(self.yom_tov_observance, &[TorahReadingType::YomTov])
+                .into_iter()
+                .filter(|x| match x.name() {
+                    TorahReading::YomTov(yt) => is_yom_tov_day(yt, self.yom_tov_observance),
+                    _ => false,
+                })
+                .map(|x| x.day().into())
+                .collect()
+        } else {
+            vec![]
+        };
+        let readings = if self.yom_tov_observance == self.location {
+            year.get_holidays(self.location, &self.events)
+        } else {
+            // Yom Tov is listed as it's kept, and the other readings as they're read.
+            let (yom_tov, others): (Vec<TorahReadingType>, Vec<TorahReadingType>) = self
+                .events
+                .iter()
+                .partition(|x| **x == TorahReadingType::YomTov);
+            let mut readings = year.get_holidays(self.location, &others);
+            if !yom_tov.is_empty() {
+                readings.extend(year.get_holidays(self.yom_tov_observance, &yom_tov));
+                readings.sort_by_key(|x| x.day());
+            }
+            readings
+        };
+        readings
+            .into_iter()
+            .map(|x| {
+                let day: DateTime<Utc> = x.day().into();
+                let mut light_on_time = false;
+                let mut is_shabbos = false;
+                let mut torah_portion = None;
+                if let TorahReading::Shabbos(parsha) = x.name() {
+                    light_on_time = true;
+                    is_shabbos = true;
+                    torah_portion = self
+                        .reading_cycle
+                        .map(|cycle| TorahPortion::new(parsha, x.day(), cycle));
+                }
+                if day.weekday() == Weekday::Fri {
+                    light_on_time = true;
+                    is_shabbos = true;
+                }
+                let mut is_yom_tov = false;
+                if let TorahReading::YomTov(yt) = x.name() {
+                    is_yom_tov = is_yom_tov_day(yt, self.yom_tov_observance);
+                    if let heca_lib::prelude::YomTov::RoshHashanah1
+                    | heca_lib::prelude::YomTov::YomKippur
+                    | heca_lib::prelude::YomTov::Sukkos1
+                    | heca_lib::prelude::YomTov::ShminiAtzeres
+                    | heca_lib::prelude::YomTov::Pesach1
+                    | heca_lib::prelude::YomTov::Pesach7
+                    | heca_lib::prelude::YomTov::Shavuos1 = yt
+                    {
+                        light_on_time = day.weekday() != Weekday::Sat;
+                    }
+                };
+                if is_shabbos || is_yom_tov {
+                    let candle_lighting = if let Some(city) = self.city {
+                        let date: NaiveDate = day.date().naive_local();
+                        if light_on_time {
+                            self.solar_algorithm.sun().sunset(city, date).map(|time| {
+                                time - Duration::minutes(city.candlelighting_to_sunset as i64 - 1)
+                            })
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    };
+                    // Havdalah is made on the last day of Shabbos and Yom Tov, when the next
+                    // night is neither.
+                    let next_night = day + Duration::days(1);
+                    let havdalah = match self.city {
+                        Some(city)
+                            if next_night.weekday() != Weekday::Fri
+                                && !yom_tov_nights.contains(&next_night) =>
+                        {
+                            havdalah::get(
+                                self.solar_algorithm.sun(),
+                                city,
+                                next_night.date().naive_local(),
+                                &self.havdalah,
+                                self.polar_fallback,
+                            )
+                        }
+                        _ => vec![],
+                    };
+                    DayVal {
+                        day,
+                        name: Name::TorahReading(x.name()),
+                        candle_lighting: Some(candle_lighting),
+                        torah_portion,
+                        havdalah,
+                        time_range: self.fast(x.name(), day),
+                    }
+                } else {
+                    DayVal {
+                        day,
+                        name: Name::TorahReading(x.name()),
+                        candle_lighting: None,
+                        torah_portion: None,
+                        havdalah: vec![],
+                        time_range: self.fast(x.name(), day),
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+impl TorahReadings<'_> {
+    /// When the reading's fast starts and ends, if it's a fast and there's a city.
+    fn fast(&self, reading: TorahReading, night: DateTime<Utc>) -> Option<TimeRange> {
+        fast_time_range(
+            &Name::TorahReading(reading),
+            night,
+            self.city?,
+            self.solar_algorithm,
+            self.polar_fallback,
+        )
+    }
+}
+
+/// When a fast starts and ends in the city: at dawn, or at sunset the night before on Yom
+/// Kippur and Tisha B'Av, until nightfall. `None` if the day isn't a fast.
+pub(crate) fn fast_time_range(
+    name: &Name,
+    night: DateTime<Utc>,
+    city: &City,
+    solar_algorithm: SolarAlgorithm,
+    polar_fallback: PolarFallback,
+) -> Option<TimeRange> {
+    let date = (night + Duration::days(1)).naive_utc().date();
+    let (start, start_date) = match name {
+        Name::TorahReading(TorahReading::Chol(
+            Chol::TzomGedalia | Chol::TenTeves | Chol::TaanisEsther | Chol::SeventeenTammuz,
+        ))
+        | Name::MinorDays(MinorDays::TaanisBechoros) => (Zman::AlosHashachar, date),
+        Name::TorahReading(TorahReading::Chol(Chol::NineAv))
+        | Name::TorahReading(TorahReading::YomTov(YomTov::YomKippur)) => {
+            (Zman::Sunset, date.pred())
+        }
+        _ => return None,
+    };
+    let zman = |zman, date| get_zman(solar_algorithm.sun(), zman, city, date, polar_fallback);
+    Some(TimeRange {
+        start: zman(start, start_date),
+        end: zman(Zman::TzeisHakochavim, date),
+    })
+}
+
+/// Whether work is forbidden on the day, so that it has candle lighting and havdalah.
+pub(crate) fn is_yom_tov_day(yt: heca_lib::prelude::YomTov, location: Location) -> bool {
+    use heca_lib::prelude::YomTov::*;
+    match yt {
+        RoshHashanah1 | RoshHashanah2 | YomKippur | Sukkos1 | ShminiAtzeres | Pesach1 | Pesach7
+        | Shavuos1 => true,
+        Sukkos2 | SimchasTorah | Pesach2 | Pesach8 | Shavuos2 => location == Location::Chul,
+        _ => false,
+    }
+}
+
+pub struct Omer;
+
+impl EventProvider for Omer {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        get_omer(year).to_vec()
+    }
+}
+
+/// The minor holidays, with the times chametz can be eaten and burnt until on Erev Pesach if
+/// there's a city.
+pub struct MinorHolidays<'a> {
+    pub city: Option<&'a City>,
+    pub polar_fallback: PolarFallback,
+    pub solar_algorithm: SolarAlgorithm,
+}
+
+/// Every Motzei Shabbos of the winter Avos Ubanim program, from the first Shabbos after Sukkos
+/// until the last Motzei Shabbos before Pesach.
+pub struct AvosUbanim {
+    pub location: Location,
+}
+
+impl EventProvider for AvosUbanim {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        let yom_tov = year.get_holidays(self.location, &[TorahReadingType::YomTov]);
+        let night_of = |yom_tov_days: &[YomTov]| -> DateTime<Utc> {
+            yom_tov
+                .iter()
+                .filter(
+                    |x| matches!(x.name(), TorahReading::YomTov(yt) if yom_tov_days.contains(&yt)),
+                )
+                .map(|x| x.day().into())
+                .max()
+                .unwrap()
+        };
+        // Outside of Israel, Sukkos ends with Simchas Torah.
+        let sukkos_ends = night_of(&[YomTov::ShminiAtzeres, YomTov::SimchasTorah]);
+        let pesach = night_of(&[YomTov::Pesach1]);
+        // The night after the first Shabbos after Sukkos.
+        let mut night = sukkos_ends + Duration::days(2);
+        while night.weekday() != Weekday::Sat {
+            night += Duration::days(1);
+        }
+        let mut days = vec![];
+        while night < pesach {
+            days.push(DayVal {
+                day: night,
+                name: Name::MinorDays(MinorDays::AvosUbanim),
+                candle_lighting: None,
+                torah_portion: None,
+                havdalah: vec![],
+                time_range: None,
+            });
+            night += Duration::days(7);
+        }
+        days
+    }
+}
+
+impl EventProvider for MinorHolidays<'_> {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        let mut days = get_minor_holidays(year);
+        if let Some(city) = self.city {
+            for day in &mut days {
+                day.time_range = fast_time_range(
+                    &day.name,
+                    day.day,
+                    city,
+                    self.solar_algorithm,
+                    self.polar_fallback,
+                );
+            }
+            let erev_pesach: DateTime<Utc> = year
+                .get_hebrew_date(HebrewMonth::Nissan, NonZeroI8::new(14).unwrap())
+                .unwrap()
+                .into();
+            let date = (erev_pesach + Duration::days(1)).naive_utc().date();
+            for zman in &[Zman::SofZmanAchilasChametz, Zman::SofZmanBiurChametz] {
+                days.push(DayVal {
+                    day: erev_pesach,
+                    name: Name::Zman(ZmanTime {
+                        time_of_day: TimeOfDay::Zman(*zman),
+                        opinion: None,
+                        time: get_zman(
+                            self.solar_algorithm.sun(),
+                            *zman,
+                            city,
+                            date,
+                            self.polar_fallback,
+                        ),
+                    }),
+                    candle_lighting: None,
+                    torah_portion: None,
+                    havdalah: vec![],
+                    time_range: None,
+                });
+            }
+        }
+        days
+    }
+}
+
+pub struct IsraeliHolidays {
+    pub exact_days: bool,
+}
+
+impl EventProvider for IsraeliHolidays {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        israeli_holidays::get(year, self.exact_days)
+    }
+}
+
+pub struct ChabadHolidays;
+
+impl EventProvider for ChabadHolidays {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        chabad_holidays::get(year)
+    }
+}
+
+pub struct Agricultural;
+
+impl EventProvider for Agricultural {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        agricultural::get(year)
+    }
+}
+
+/// Tefillas Geshem on Shmini Atzeres and Tefillas Tal on the first day of Pesach.
+pub struct DaveningChanges;
+
+impl EventProvider for DaveningChanges {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        [
+            (HebrewMonth::Tishrei, 22, DaveningChange::TefillasGeshem),
+            (HebrewMonth::Nissan, 15, DaveningChange::TefillasTal),
+        ]
+        .iter()
+        .map(|(month, day, change)| DayVal {
+            day: year
+                .get_hebrew_date(*month, NonZeroI8::new(*day).unwrap())
+                .unwrap()
+                .into(),
+            name: Name::DaveningChange(*change),
+            candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
+        })
+        .collect()
+    }
+}
+
+pub struct ShabbosMevarchim;
+
+impl EventProvider for ShabbosMevarchim {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        shabbos_mevarchim::get(year)
+    }
+}
+
+pub struct DailyStudies {
+    pub events: Vec<DailyStudy>,
+    pub location: Location,
+}
+
+impl EventProvider for DailyStudies {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        self.events
+            .get_day_val(year.year(), year.year(), self.location)
+    }
+
+    fn get_between(
+        &self,
+        year: &HebrewYear,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<DayVal> {
+        let year_first_day: DateTime<Utc> = year
+            .get_hebrew_date(HebrewMonth::Tishrei, NonZeroI8::new(1).unwrap())
+            .unwrap()
+            .into();
+        let year_last_day: DateTime<Utc> = year
+            .get_hebrew_date(HebrewMonth::Elul, NonZeroI8::new(29).unwrap())
+            .unwrap()
+            .into();
+        if until <= year_first_day {
+            return vec![];
+        }
+        // The first and last nights of the year inside the window.
+        let first_day = if from <= year_first_day {
+            year_first_day
+        } else {
+            year_first_day
+                + Duration::days((from - year_first_day - Duration::seconds(1)).num_days() + 1)
+        };
+        let last_day = year_last_day.min(
+            year_first_day
+                + Duration::days((until - year_first_day - Duration::seconds(1)).num_days()),
+        );
+        self.events
+            .get_day_val_between(first_day, last_day, self.location)
+    }
+
+    fn is_daily(&self) -> bool {
+        true
+    }
+}
+
+/// Candle lighting, havdalah and the zmanim, each listed as an event of its own on the civil
+/// date it's on. Candles lit from an existing flame after nightfall, on the second night of Yom
+/// Tov, aren't listed.
+pub struct Zmanim<'a> {
+    pub times: Vec<TimeOfDay>,
+    /// Where Yom Tov is kept, which decides the nights candles are lit and havdalah is made.
+    pub location: Location,
+    pub city: &'a City,
+    pub havdalah: Vec<HavdalahOpinion>,
+    pub polar_fallback: PolarFallback,
+    pub solar_algorithm: SolarAlgorithm,
+}
+
+impl Zmanim<'_> {
+    fn event(&self, night: DateTime<Utc>, time: ZmanTime) -> DayVal {
+        DayVal {
+            day: night,
+            name: Name::Zman(time),
+            candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
+        }
+    }
+}
+
+impl EventProvider for Zmanim<'_> {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        let first_night: DateTime<Utc> = year
+            .get_hebrew_date(HebrewMonth::Tishrei, NonZeroI8::new(1).unwrap())
+            .unwrap()
+            .into();
+        let last_night: DateTime<Utc> = year
+            .get_hebrew_date(HebrewMonth::Elul, NonZeroI8::new(29).unwrap())
+            .unwrap()
+            .into();
+        // Candles for Rosh Hashanah are lit the night before the year starts.
+        self.get_between(
+            year,
+            first_night - Duration::days(1),
+            last_night + Duration::days(1),
+        )
+    }
+
+    fn get_between(
+        &self,
+        year: &HebrewYear,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<DayVal> {
+        let mut days = vec![];
+        let first_night: DateTime<Utc> = year
+            .get_hebrew_date(HebrewMonth::Tishrei, NonZeroI8::new(1).unwrap())
+            .unwrap()
+            .into();
+        let last_night: DateTime<Utc> = year
+            .get_hebrew_date(HebrewMonth::Elul, NonZeroI8::new(29).unwrap())
+            .unwrap()
+            .into();
+        for time in &self.times {
+            if let TimeOfDay::Zman(zman) = time {
+                // The first night of the year inside the window.
+                let mut night = first_night;
+                if from > first_night {
+                    night +=
+                        Duration::days((from - first_night - Duration::seconds(1)).num_days() + 1);
+                }
+                while night <= last_night && night < until {
+                    let date = (night + Duration::days(1)).naive_utc().date();
+                    let time = get_zman(
+                        self.solar_algorithm.sun(),
+                        *zman,
+                        self.city,
+                        date,
+                        self.polar_fallback,
+                    );
+                    days.push(self.event(
+                        night,
+                        ZmanTime {
+                            time_of_day: TimeOfDay::Zman(*zman),
+                            opinion: None,
+                            time,
+                        },
+                    ));
+                    night += Duration::days(1);
+                }
+            }
+        }
+        let candles = self.times.contains(&TimeOfDay::CandleLighting);
+        let havdalah = self.times.contains(&TimeOfDay::Havdalah);
+        if candles || havdalah {
+            let readings = TorahReadings {
+                events: vec![TorahReadingType::Shabbos, TorahReadingType::YomTov],
+                location: self.location,
+                yom_tov_observance: self.location,
+                city: Some(self.city),
+                reading_cycle: None,
+                havdalah: self.havdalah.clone(),
+                polar_fallback: self.polar_fallback,
+                solar_algorithm: self.solar_algorithm,
+            }
+            .get(year);
+            // A Yom Tov on Shabbos is listed twice, once for Shabbos and once for Yom Tov.
+            type Time = (
+                DateTime<Utc>,
+                Option<HavdalahOpinion>,
+                Option<DateTime<FixedOffset>>,
+            );
+            let mut times: Vec<Time> = vec![];
+            for reading in readings {
+                if let (true, Some(Some(time))) = (candles, reading.candle_lighting) {
+                    // Candles are lit the afternoon before, which starts the previous night.
+                    times.push((reading.day - Duration::days(1), None, Some(time)));
+                }
+                if havdalah {
+                    for time in reading.havdalah {
+                        times.push((reading.day, Some(time.opinion), time.time));
+                    }
+                }
+            }
+            let key = |(night, opinion, _): &Time| {
+                let opinion = opinion.map(|x| self.havdalah.iter().position(|y| *y == x));
+                (*night, opinion)
+            };
+            times.retain(|(night, _, _)| *night >= from && *night < until);
+            times.sort_by_key(key);
+            times.dedup_by_key(|x| key(x));
+            let several_opinions = self.havdalah.len() > 1;
+            days.extend(times.into_iter().map(|(night, opinion, time)| {
+                self.event(
+                    night,
+                    ZmanTime {
+                        time_of_day: match opinion {
+                            Some(_) => TimeOfDay::Havdalah,
+                            None => TimeOfDay::CandleLighting,
+                        },
+                        opinion: opinion.filter(|_| several_opinions),
+                        time,
+                    },
+                )
+            }));
+        }
+        days
+    }
+
+    fn is_daily(&self) -> bool {
+        true
+    }
+}
+
+/// The days in the config file. Custom holidays on a date which doesn't exist in some year, and
+/// have no `ifNotExists`, are recorded in `missing_years`.
+pub struct CustomHolidays<'a> {
+    events: &'a [CustomHoliday],
+    /// Where the built-in events which custom holidays are relative to are listed.
+    location: Location,
+    missing_years: Mutex<Vec<Vec<u64>>>,
+}
+
+impl<'a> CustomHolidays<'a> {
+    pub fn new(events: &'a [CustomHoliday], location: Location) -> Self {
+        Self {
+            events,
+            location,
+            missing_years: Mutex::new(vec![vec![]; events.len()]),
+        }
+    }
+
+    /// The built-in events custom holidays can be relative to, whether or not they're listed.
+    fn built_in_events(&self, year: &HebrewYear) -> Vec<DayVal> {
+        let torah_readings = TorahReadings {
+            events: vec![
+                TorahReadingType::YomTov,
+                TorahReadingType::Chol,
+                TorahReadingType::Shabbos,
+                TorahReadingType::SpecialParsha,
+            ],
+            location: self.location,
+            yom_tov_observance: self.location,
+            city: None,
+            reading_cycle: None,
+            havdalah: vec![],
+            polar_fallback: PolarFallback::None,
+            solar_algorithm: SolarAlgorithm::Noaa,
+        };
+        let mut events = torah_readings.get(year);
+        events.extend(get_minor_holidays(year));
+        events.extend(get_omer(year).iter().cloned());
+        events.extend(israeli_holidays::get(year, false));
+        events.extend(chabad_holidays::get(year));
+        events.extend(DaveningChanges.get(year));
+        if self.location == Location::Israel {
+            events.extend(agricultural::get(year));
+        }
+        events
+    }
+
+    /// Returns the years each custom holiday was skipped in, in order.
+    pub fn missing_years(self) -> Vec<Vec<u64>> {
+        let mut missing_years = self.missing_years.into_inner().unwrap();
+        missing_years
+            .iter_mut()
+            .for_each(|years| years.sort_unstable());
+        missing_years
+    }
+}
+
+impl EventProvider for CustomHolidays<'_> {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        let mut ret = vec![];
+        let mut built_in_events = None;
+        self.events.iter().enumerate().for_each(|(index, x)| {
+            if !x.applies_to(year.year()) {
+                return;
+            }
+            let amnt_found = ret.len();
+            let date = match &x.date {
+                CustomDate::Hebrew(date) => date,
+                CustomDate::Relative { event, offset } => {
+                    let built_in_events =
+                        built_in_events.get_or_insert_with(|| self.built_in_events(year));
+                    // The id of an event is its type and its name in JSON.
+                    let found = built_in_events
+                        .iter()
+                        .find(|day| day.name.id().split(' ').nth(1) == Some(event.as_str()));
+                    match found {
+                        Some(day) => ret.push(DayVal {
+                            name: Name::CustomHoliday(Box::new(x.in_year(year.year()))),
+                            day: x.night_of(day.day + Duration::days(*offset)),
+                            candle_lighting: None,
+                            torah_portion: None,
+                            havdalah: vec![],
+                            time_range: None,
+                        }),
+                        None => self.missing_years.lock().unwrap()[index].push(year.year()),
+                    }
+                    return;
+                }
+                CustomDate::Weekday {
+                    month,
+                    nth,
+                    weekday,
+                } => {
+                    // Every night of the month starting a day on the weekday.
+                    let nights = (1..=30)
+                        .filter_map(|day| year.get_hebrew_date(*month, NonZeroI8::new(day)?).ok())
+                        .map(DateTime::<Utc>::from)
+                        .filter(|night| (*night + Duration::days(1)).weekday() == *weekday)
+                        .collect::<Vec<_>>();
+                    let night = match *nth {
+                        nth if nth > 0 => nights.get(nth as usize - 1),
+                        nth => nights
+                            .len()
+                            .checked_sub(nth.unsigned_abs() as usize)
+                            .and_then(|index| nights.get(index)),
+                    };
+                    match night {
+                        Some(night) => ret.push(DayVal {
+                            name: Name::CustomHoliday(Box::new(x.in_year(year.year()))),
+                            day: x.night_of(*night),
+                            candle_lighting: None,
+                            torah_portion: None,
+                            havdalah: vec![],
+                            time_range: None,
+                        }),
+                        None => self.missing_years.lock().unwrap()[index].push(year.year()),
+                    }
+                    return;
+                }
+            };
+            if let Ok(day) = year.get_hebrew_date(date.month, date.day) {
+                let d = DayVal {
+                    name: Name::CustomHoliday(Box::new(x.in_year(year.year()))),
+                    day: x.night_of(day.into()),
+                    candle_lighting: None,
+                    torah_portion: None,
+                    havdalah: vec![],
+                    time_range: None,
+                };
+                ret.push(d);
+            } else if let Some(not_exists) = &x.if_not_exists {
+                not_exists.iter().for_each(|day_month| {
+                    if let Ok(day) = year.get_hebrew_date(day_month.month, day_month.day) {
+                        let d = DayVal {
+                            name: Name::CustomHoliday(Box::new(x.in_year(year.year()))),
+                            day: x.night_of(day.into()),
+                            candle_lighting: None,
+                            torah_portion: None,
+                            havdalah: vec![],
+                            time_range: None,
+                        };
+                        ret.push(d);
+                    }
+                });
+            }
+            if ret.len() == amnt_found && x.if_not_exists.is_none() {
+                self.missing_years.lock().unwrap()[index].push(year.year());
+            }
+        });
+        ret
+    }
+}
@@ -0,0 +1,85 @@
+use crate::args::types::{DayVal, Name, ShabbosSummary, SpecialShabbos};
+use chrono::prelude::*;
+use heca_lib::prelude::*;
+use heca_lib::HebrewDate;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// Replaces the events on every Shabbos with a single `Name::Shabbos`, dropping the events on
+/// other days. A Shabbos without a parsha or Yom Tov reading (for instance because it was
+/// filtered out by `--category`) is skipped.
+pub fn only_shabbos(list: Vec<DayVal>) -> Vec<DayVal> {
+    let mut shabbosos: BTreeMap<DateTime<Utc>, Vec<DayVal>> = BTreeMap::new();
+    for day_val in list {
+        // Shabbos starts on the night of Friday.
+        if day_val.day.weekday() == Weekday::Fri {
+            shabbosos.entry(day_val.day).or_default().push(day_val);
+        }
+    }
+    shabbosos
+        .into_iter()
+        .filter_map(|(day, mut events)| {
+            let index = events
+                .iter()
+                .position(|x| matches!(x.name, Name::TorahReading(TorahReading::Shabbos(_))))
+                .or_else(|| {
+                    events
+                        .iter()
+                        .position(|x| matches!(x.name, Name::TorahReading(TorahReading::YomTov(_))))
+                })?;
+            let main = events.remove(index);
+            let reading = match main.name {
+                Name::TorahReading(reading) => reading,
+                _ => unreachable!(),
+            };
+            let candle_lighting = if let Some(Some(time)) = main.candle_lighting {
+                Some(Some(time))
+            } else {
+                events
+                    .iter()
+                    .find(|x| matches!(x.candle_lighting, Some(Some(_))))
+                    .map(|x| x.candle_lighting.unwrap())
+                    .or(main.candle_lighting)
+            };
+            let havdalah = if main.havdalah.is_empty() {
+                events
+                    .iter()
+                    .find(|x| !x.havdalah.is_empty())
+                    .map(|x| x.havdalah.clone())
+                    .unwrap_or_default()
+            } else {
+                main.havdalah
+            };
+            Some(DayVal {
+                day,
+                name: Name::Shabbos(ShabbosSummary {
+                    reading,
+                    special: special_shabbos(day, reading),
+                    events,
+                }),
+                candle_lighting,
+                torah_portion: main.torah_portion,
+                havdalah,
+                time_range: None,
+            })
+        })
+        .collect()
+}
+
+fn special_shabbos(day: DateTime<Utc>, reading: TorahReading) -> Vec<SpecialShabbos> {
+    let date = HebrewDate::try_from(day).unwrap();
+    let mut special = vec![];
+    if date.month() == HebrewMonth::Tishrei && (3..=9).contains(&date.day().get()) {
+        special.push(SpecialShabbos::Shuva);
+    }
+    if date.month() == HebrewMonth::Nissan && (8..=14).contains(&date.day().get()) {
+        special.push(SpecialShabbos::HaGadol);
+    }
+    match reading {
+        TorahReading::Shabbos(Parsha::Beshalach) => special.push(SpecialShabbos::Shira),
+        TorahReading::Shabbos(Parsha::Devarim) => special.push(SpecialShabbos::Chazon),
+        TorahReading::Shabbos(Parsha::Vaeschanan) => special.push(SpecialShabbos::Nachamu),
+        _ => {}
+    }
+    special
+}
@@ -0,0 +1,118 @@
+use super::write_date;
+use crate::args::types::{Category, DayVal, Language};
+use chrono::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// How many events of every category were listed, and the dates the list covers, printed with
+/// `--summary`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    pub counts: BTreeMap<Category, usize>,
+    /// The first day the list covers.
+    pub from: NaiveDate,
+    /// The last day the list covers, inclusive.
+    pub until: NaiveDate,
+}
+
+impl Summary {
+    pub fn new(list: &[DayVal], from: NaiveDate, until: NaiveDate) -> Self {
+        let mut counts = BTreeMap::new();
+        for d in list {
+            *counts.entry(d.name.category()).or_insert(0) += 1;
+        }
+        Summary {
+            counts,
+            from,
+            until,
+        }
+    }
+
+    /// Writes the counts and the dates as a footer after the list.
+    pub(super) fn pretty_print<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+    ) -> std::io::Result<()> {
+        match language {
+            Language::English => lock.write_all(b"\nSummary: ")?,
+            Language::Hebrew => lock.write_all("\nסיכום: ".as_bytes())?,
+        };
+        self.write_counts(lock, language)?;
+        lock.write_all(b"\n")?;
+        match language {
+            Language::English => lock.write_all(b"From ")?,
+            Language::Hebrew => lock.write_all("מ-".as_bytes())?,
+        };
+        write_date(lock, Utc.from_utc_date(&self.from).and_hms(0, 0, 0))?;
+        match language {
+            Language::English => lock.write_all(b" until ")?,
+            Language::Hebrew => lock.write_all(" עד ".as_bytes())?,
+        };
+        write_date(lock, Utc.from_utc_date(&self.until).and_hms(0, 0, 0))?;
+        lock.write_all(b"\n")
+    }
+
+    /// Writes the counts, like "22 Yomim Tovim, 5 fasts".
+    pub(super) fn write_counts<W: Write>(
+        &self,
+        lock: &mut W,
+        language: Language,
+    ) -> std::io::Result<()> {
+        if self.counts.is_empty() {
+            match language {
+                Language::English => lock.write_all(b"no events")?,
+                Language::Hebrew => lock.write_all("אין אירועים".as_bytes())?,
+            };
+        }
+        for (index, (category, count)) in self.counts.iter().enumerate() {
+            if index > 0 {
+                lock.write_all(b", ")?;
+            }
+            match language {
+                Language::English => {
+                    let (one, many) = english_name(*category);
+                    write!(lock, "{} {}", count, if *count == 1 { one } else { many })?
+                }
+                Language::Hebrew => write!(lock, "{}: {}", hebrew_name(*category), count)?,
+            };
+        }
+        Ok(())
+    }
+}
+
+/// The name of one event in the category, and of more than one.
+fn english_name(category: Category) -> (&'static str, &'static str) {
+    match category {
+        Category::YomTov => ("Yom Tov", "Yomim Tovim"),
+        Category::Chol => ("weekday reading", "weekday readings"),
+        Category::Fast => ("fast", "fasts"),
+        Category::RoshChodesh => ("day of Rosh Chodesh", "days of Rosh Chodesh"),
+        Category::Parsha => ("parsha", "parshiyos"),
+        Category::SpecialParsha => ("special parsha", "special parshiyos"),
+        Category::Minor => ("minor holiday", "minor holidays"),
+        Category::Custom => ("custom event", "custom events"),
+        Category::DailyStudy => ("daily study", "daily studies"),
+        Category::Agricultural => ("agricultural date", "agricultural dates"),
+        Category::DaveningChanges => ("davening change", "davening changes"),
+        Category::Zman => ("zman", "zmanim"),
+    }
+}
+
+fn hebrew_name(category: Category) -> &'static str {
+    match category {
+        Category::YomTov => "ימים טובים",
+        Category::Chol => "קריאות בימי חול",
+        Category::Fast => "תעניות",
+        Category::RoshChodesh => "ימי ראש חודש",
+        Category::Parsha => "פרשיות",
+        Category::SpecialParsha => "פרשיות מיוחדות",
+        Category::Minor => "ימים מיוחדים",
+        Category::Custom => "אירועים מותאמים",
+        Category::DailyStudy => "לימוד יומי",
+        Category::Agricultural => "מועדים חקלאיים",
+        Category::DaveningChanges => "שינויים בתפילה",
+        Category::Zman => "זמנים",
+    }
+}
@@ -1,22 +1,20 @@
-mod algorithms;
-mod args;
-mod convert;
-mod list;
-mod prelude;
-
-use crate::args::types;
-use crate::args::types::AppError;
-use crate::args::types::*;
-use crate::prelude::*;
+use heca::args;
+use heca::args::types::AppError;
+use heca::args::types::*;
+use heca::prelude::*;
 
 fn main() {
+    #[cfg(debug_assertions)]
+    heca::anchors::assert_anchors();
     start_benchmark();
     let output_type = output_type();
     if let Err(err) = app(std::env::args(), output_type) {
-        if output_type == OutputType::JSON {
+        let language = language();
+        if output_type.is_data() {
+            let err = LocalizedError(&err, language);
             eprintln!("{}", serde_json::to_string(&err).unwrap());
         } else {
-            eprintln!("{}", err);
+            eprintln!("{}", err.message(language));
         }
         stop_benchmark();
         std::process::exit(1);
@@ -42,24 +40,38 @@ fn stop_benchmark() {
 }
 
 fn output_type() -> OutputType {
+    let parse = |name: &str| match name {
+        "regular" | "pretty" => Some(OutputType::Pretty),
+        "json" => Some(OutputType::JSON),
+        "ics" => Some(OutputType::ICS),
+        "cbor" => Some(OutputType::CBOR),
+        "msgpack" => Some(OutputType::MessagePack),
+        #[cfg(feature = "parquet")]
+        "parquet" => Some(OutputType::Parquet),
+        _ => None,
+    };
     let mut args = std::env::args();
     loop {
         let arg = args.next();
         if arg == None {
             break;
         } else if let Some(arg) = arg {
-            if arg == "--print=json" {
-                return OutputType::JSON;
+            if let Some(name) = arg.strip_prefix("--print=") {
+                if let Some(output_type) = parse(name) {
+                    return output_type;
+                }
             } else if arg == "--print" {
-                if let Some(next) = args.next() {
-                    if next == "json" {
-                        return OutputType::JSON;
-                    }
+                if let Some(output_type) = args.next().as_deref().and_then(parse) {
+                    return output_type;
                 }
             }
         }
     }
 
+    if let Some(output_type) = std::env::var("HECA_OUTPUT").ok().as_deref().and_then(parse) {
+        return output_type;
+    }
+
     if let Ok(json_str) = std::env::var("JSON") {
         if json_str == "YES" {
             return OutputType::JSON;
@@ -69,6 +81,36 @@ fn output_type() -> OutputType {
     OutputType::Pretty
 }
 
+/// The language errors are printed in: the first language passed to `--language` (or set in
+/// `HECA_LANGUAGE`), or else the system's. The config file isn't read, since the error may be in
+/// it.
+fn language() -> Language {
+    let parse = |name: &str| match name.split(',').next() {
+        Some("he" | "he_IL") => Some(Language::Hebrew),
+        Some("en" | "en_US") => Some(Language::English),
+        _ => None,
+    };
+    let mut args = std::env::args();
+    while let Some(arg) = args.next() {
+        let language = match arg.strip_prefix("--language=") {
+            Some(name) => parse(name),
+            None if arg == "--language" => args.next().as_deref().and_then(parse),
+            None => None,
+        };
+        if let Some(language) = language {
+            return language;
+        }
+    }
+    if let Some(language) = std::env::var("HECA_LANGUAGE")
+        .ok()
+        .as_deref()
+        .and_then(parse)
+    {
+        return language;
+    }
+    system::system_language().unwrap_or(Language::English)
+}
+
 fn app<I, T>(args: I, output_type: OutputType) -> Result<(), AppError>
 where
     I: IntoIterator<Item = T>,
@@ -78,6 +120,26 @@ where
     match args.command {
         Command::List(ref sub_args) => sub_args.run(&args)?,
         Command::Convert(ref sub_args) => sub_args.run(&args)?,
+        Command::Limits(ref sub_args) => sub_args.run(&args)?,
+        Command::SelfTest(ref sub_args) => sub_args.run(&args)?,
+        Command::VerifyAnchors(ref sub_args) => sub_args.run(&args)?,
+        Command::Zmanim(ref sub_args) => sub_args.run(&args)?,
+        Command::Slug(ref sub_args) => sub_args.run(&args)?,
+        Command::DiffCalendars(ref sub_args) => sub_args.run(&args)?,
+        Command::Merge(ref sub_args) => sub_args.run(&args)?,
+        Command::Table(ref sub_args) => sub_args.run(&args)?,
+        Command::Stats(ref sub_args) => sub_args.run(&args)?,
+        Command::YearInfo(ref sub_args) => sub_args.run(&args)?,
+        Command::MonthInfo(ref sub_args) => sub_args.run(&args)?,
+        Command::Digest(ref sub_args) => sub_args.run(&args)?,
+        Command::StatusBar(ref sub_args) => sub_args.run(&args)?,
+        Command::Catchup(ref sub_args) => sub_args.run(&args)?,
+        Command::PlanSiyum(ref sub_args) => sub_args.run(&args)?,
+        Command::BarMitzvah(ref sub_args) => sub_args.run(&args)?,
+        Command::CheckDate(ref sub_args) => sub_args.run(&args)?,
+        Command::Find(ref sub_args) => sub_args.run(&args)?,
+        Command::Batch(ref sub_args) => sub_args.run(&args)?,
+        Command::Version(ref sub_args) => sub_args.run(&args)?,
     };
 
     Ok(())
@@ -0,0 +1,168 @@
+use crate::args::types::{AppError, MainArgs, MergeArgs, OutputType};
+use crate::json_calendar::{format_day, load, Event};
+use crate::list::ics;
+use crate::prelude::system::now;
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use chrono::prelude::*;
+use chrono::Duration;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Merged {
+    events: Vec<Event>,
+    #[serde(skip)]
+    ics: bool,
+    /// Whether to print the `source` of every event.
+    #[serde(skip)]
+    provenance: bool,
+}
+
+/// Two events are the same event if they're on the same night and have the same name. Events
+/// without a name (like Daf Yomi) are the same if everything but their details is the same.
+fn key(event: &Event) -> (NaiveDate, String) {
+    let id = match event.value.get("name") {
+        Some(Value::String(name)) => name.clone(),
+        _ => event.identity(),
+    };
+    (event.day.naive_utc().date(), id)
+}
+
+/// Merges the calendars in order. An event which is in several calendars is taken from the last
+/// one, in the place it had in the first one.
+fn merge(calendars: Vec<Vec<Event>>) -> Vec<Event> {
+    let mut events: Vec<Event> = vec![];
+    let mut indices: HashMap<(NaiveDate, String), usize> = HashMap::new();
+    for event in calendars.into_iter().flatten() {
+        match indices.get(&key(&event)) {
+            Some(&index) => events[index] = event,
+            None => {
+                indices.insert(key(&event), events.len());
+                events.push(event);
+            }
+        }
+    }
+    events.sort_by_key(|x| x.day);
+    events
+}
+
+/// Names every event without a `source` by the file it's in and its place in it, like
+/// `merge:family.json#12`. Events listed with `list --provenance` keep the rule which listed them.
+fn add_sources(path: &str, events: &mut [Event]) {
+    let file_name = Path::new(path)
+        .file_name()
+        .map_or_else(|| path.into(), |x| x.to_string_lossy());
+    for (index, event) in events.iter_mut().enumerate() {
+        event
+            .value
+            .entry("source")
+            .or_insert_with(|| format!("merge:{}#{}", file_name, index + 1).into());
+    }
+}
+
+/// Events from other calendars can have a `title`, which is printed instead of their label.
+fn summary(event: &Event, args: &MainArgs) -> String {
+    match event.value.get("title") {
+        Some(Value::String(title)) => title.clone(),
+        _ => event.label(args.language, args.transliteration),
+    }
+}
+
+impl Merged {
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        for event in &self.events {
+            write!(lock, "{}: {}", format_day(event.day), summary(event, args))?;
+            match event.value.get("source") {
+                Some(Value::String(source)) if self.provenance => writeln!(lock, " [{}]", source)?,
+                _ => writeln!(lock)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn ics_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let dtstamp = ics::begin_calendar(lock, now(args.today, None))?;
+        for (index, event) in self.events.iter().enumerate() {
+            let start = event.day + Duration::days(1);
+            let end = match event.value.get("end").and_then(Value::as_str) {
+                Some(end) => match DateTime::parse_from_rfc3339(end) {
+                    Ok(end) => end + Duration::days(1),
+                    Err(_) => start + Duration::days(1),
+                },
+                None => start + Duration::days(1),
+            };
+            let description = match event.value.get("description") {
+                Some(Value::String(description)) => description.as_str(),
+                _ => "",
+            };
+            // Events printed by heca keep their UIDs; others are numbered.
+            let uid = match event.value.get("uid") {
+                Some(Value::String(uid)) => uid.clone(),
+                _ => format!("{}-{}@heca", start.format("%Y%m%d"), index),
+            };
+            ics::write_event(
+                lock,
+                &dtstamp,
+                &uid,
+                (start.naive_utc().date(), end.naive_utc().date()),
+                &summary(event, args),
+                description,
+            )?;
+        }
+        ics::end_calendar(lock)
+    }
+}
+
+impl Printable for Merged {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::ICS => self.ics_print(args, &mut lock),
+            OutputType::Pretty | OutputType::Regular | OutputType::Parquet if self.ics => {
+                self.ics_print(args, &mut lock)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for MergeArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let calendars = self
+            .files
+            .iter()
+            .map(|path| {
+                let mut events = load(path)?;
+                if self.provenance {
+                    add_sources(path, &mut events);
+                }
+                Ok(events)
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        // Writing to an .ics file without --print makes an iCalendar file.
+        let ics = args.out.as_ref().is_some_and(|out| {
+            out.extension()
+                .is_some_and(|x| x.eq_ignore_ascii_case("ics"))
+        });
+        print_output(
+            &Merged {
+                events: merge(calendars),
+                ics,
+                provenance: self.provenance,
+            },
+            args,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use crate::args::types::{AppError, Language, MainArgs, MonthInfoArgs, OutputType};
+use crate::gematria::hebrew_numeral;
+use crate::prelude::constants::check_hebrew_year;
+use crate::prelude::print::{hebrew_month_english, hebrew_month_hebrew};
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use crate::year_info::civil_date;
+use crate::year_info::explain::{Explanation, ExplanationJson};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::HebrewMonth;
+use heca_lib::{HebrewDate, HebrewYear};
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::io::Write;
+use std::num::NonZeroI8;
+
+/// What a luach says about a Hebrew month.
+pub struct MonthInfo {
+    pub year: u64,
+    pub month: HebrewMonth,
+    pub leap: bool,
+    /// 29 or 30 days.
+    pub length: i8,
+    /// The Gregorian dates of Rosh Chodesh: the 30th of the month before, if it has one, and the
+    /// 1st. Tishrei has none, since its 1st is Rosh Hashanah.
+    pub rosh_chodesh: Vec<NaiveDate>,
+    /// The molad, in the time of Jerusalem, and its chalakim (1/1080 of an hour).
+    pub molad: (NaiveDateTime, u16),
+    /// The Gregorian dates of the first and the last day of the month.
+    pub first_day: NaiveDate,
+    pub last_day: NaiveDate,
+    /// Printed with `--explain`.
+    pub explanation: Option<Explanation>,
+}
+
+impl MonthInfo {
+    pub fn new(year: u64, month: HebrewMonth) -> Result<MonthInfo, AppError> {
+        let hebrew_year = HebrewYear::new(year)?;
+        let first_night: DateTime<Utc> =
+            HebrewDate::from_ymd(year, month, NonZeroI8::new(1).unwrap())?.into();
+        let first_day = civil_date(year, month, 1)?;
+        let length = if HebrewDate::from_ymd(year, month, NonZeroI8::new(30).unwrap()).is_ok() {
+            30
+        } else {
+            29
+        };
+        let mut rosh_chodesh = vec![];
+        if month != HebrewMonth::Tishrei {
+            let day_before = HebrewDate::try_from(first_night - Duration::days(1))?;
+            if day_before.day().get() == 30 {
+                rosh_chodesh.push(first_day.pred());
+            }
+            rosh_chodesh.push(first_day);
+        }
+        let molad = hebrew_year.get_molad(month)?;
+        Ok(MonthInfo {
+            year,
+            month,
+            leap: hebrew_year.is_leap_year(),
+            length,
+            rosh_chodesh,
+            molad: (molad.get_day_utc().naive_utc(), molad.get_chalakim()),
+            first_day,
+            last_day: first_day + Duration::days(length as i64 - 1),
+            explanation: None,
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Molad {
+    date: String,
+    hour: u32,
+    minute: u32,
+    chalakim: u16,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Json {
+    year: u64,
+    month: HebrewMonth,
+    leap: bool,
+    length: i8,
+    rosh_chodesh: Vec<String>,
+    molad: Molad,
+    first_day: String,
+    last_day: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation: Option<ExplanationJson>,
+}
+
+impl MonthInfo {
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let date = |date: NaiveDate| match args.language {
+            Language::English => date.format("%A %Y-%m-%d").to_string(),
+            Language::Hebrew => format!(
+                "יום {} {}",
+                hebrew_numeral(date.weekday().number_from_sunday()),
+                date.format("%Y-%m-%d")
+            ),
+        };
+        let (molad, chalakim) = self.molad;
+        let rosh_chodesh = self
+            .rosh_chodesh
+            .iter()
+            .map(|x| date(*x))
+            .collect::<Vec<String>>()
+            .join(", ");
+        match args.language {
+            Language::English => {
+                writeln!(
+                    lock,
+                    "Month: {} {}",
+                    hebrew_month_english(self.month),
+                    self.year
+                )?;
+                writeln!(lock, "Length: {} days", self.length)?;
+                writeln!(lock, "Leap year: {}", if self.leap { "yes" } else { "no" })?;
+                if rosh_chodesh.is_empty() {
+                    writeln!(lock, "Rosh Chodesh: none (Rosh Hashanah)")?;
+                } else {
+                    writeln!(lock, "Rosh Chodesh: {}", rosh_chodesh)?;
+                }
+                writeln!(
+                    lock,
+                    "Molad: {} {} and {} Chalakim",
+                    date(molad.date()),
+                    molad.format("%-H:%M"),
+                    chalakim
+                )?;
+                writeln!(
+                    lock,
+                    "Dates: {} to {}",
+                    date(self.first_day),
+                    date(self.last_day)
+                )
+            }
+            Language::Hebrew => {
+                writeln!(
+                    lock,
+                    "חודש: {} {}",
+                    hebrew_month_hebrew(self.month),
+                    self.year
+                )?;
+                writeln!(lock, "אורך: {} ימים", self.length)?;
+                writeln!(lock, "שנה מעוברת: {}", if self.leap { "כן" } else { "לא" })?;
+                if rosh_chodesh.is_empty() {
+                    writeln!(lock, "ראש חודש: אין (ראש השנה)")?;
+                } else {
+                    writeln!(lock, "ראש חודש: {}", rosh_chodesh)?;
+                }
+                writeln!(
+                    lock,
+                    "מולד: {} {} ו {} חלקים",
+                    date(molad.date()),
+                    molad.format("%-H:%M"),
+                    chalakim
+                )?;
+                writeln!(
+                    lock,
+                    "תאריכים: {} עד {}",
+                    date(self.first_day),
+                    date(self.last_day)
+                )
+            }
+        }?;
+        match &self.explanation {
+            Some(explanation) => explanation.pretty_print(args.language, &date, lock),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Printable for MonthInfo {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let format = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+                let (molad, chalakim) = self.molad;
+                let json = Json {
+                    year: self.year,
+                    month: self.month,
+                    leap: self.leap,
+                    length: self.length,
+                    rosh_chodesh: self.rosh_chodesh.iter().map(|x| format(*x)).collect(),
+                    molad: Molad {
+                        date: format(molad.date()),
+                        hour: molad.hour(),
+                        minute: molad.minute(),
+                        chalakim,
+                    },
+                    first_day: format(self.first_day),
+                    last_day: format(self.last_day),
+                    explanation: self.explanation.as_ref().map(Explanation::json),
+                };
+                write_data(&mut lock, args.output_type, &json)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for MonthInfoArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        check_hebrew_year(self.year as i64)?;
+        let mut info = MonthInfo::new(self.year, self.month)?;
+        if self.explain {
+            info.explanation = Some(Explanation::new(self.year)?);
+        }
+        print_output(&info, args)?;
+        Ok(())
+    }
+}
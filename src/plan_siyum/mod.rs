@@ -0,0 +1,158 @@
+use crate::args::types::{
+    AppError, Daf, DailyStudyOutput, DayVal, Language, MainArgs, Name, OutputType, PlanSiyumArgs,
+    StudySession, Transliteration,
+};
+use crate::list::{ics, write_date, Return};
+use crate::prelude::constants::GEMARAS_SECOND_CYCLE;
+use crate::prelude::{
+    night_of, print_output, write_data, write_error, CountingWriter, Printable, Runnable,
+};
+use chrono::prelude::*;
+use std::io::Write;
+
+/// The schedule printed by `heca plan-siyum`.
+struct SiyumPlan {
+    plan: Return,
+    /// The masechta, which is printed in the language of the output.
+    masechta: Daf,
+    dapim: usize,
+}
+
+impl PlanSiyumArgs {
+    fn plan(&self) -> Result<SiyumPlan, AppError> {
+        let last_daf = GEMARAS_SECOND_CYCLE[self.masechta as usize].3;
+        let dapim: Vec<Daf> = (self.start_daf..=last_daf)
+            .map(|daf| Daf::new(self.masechta, daf))
+            .collect();
+        let mut sessions = vec![];
+        let mut date = self.from;
+        while date <= self.by {
+            if self.days.contains(&date.weekday()) {
+                sessions.push(date);
+            }
+            date = date.succ();
+        }
+        if sessions.is_empty() {
+            return Err(AppError::ArgUndefinedError(format!(
+                "There are no days to learn on from {} until {}",
+                self.from, self.by
+            )));
+        }
+        // The dapim are spread as evenly as they can be, so sessions differ by a daf at most. With
+        // more sessions than dapim, some sessions are left out.
+        let list = sessions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, date)| {
+                let units: Vec<DailyStudyOutput> = dapim[index * dapim.len() / sessions.len()
+                    ..(index + 1) * dapim.len() / sessions.len()]
+                    .iter()
+                    .cloned()
+                    .map(DailyStudyOutput::Daf)
+                    .collect();
+                if units.is_empty() {
+                    return None;
+                }
+                Some(DayVal {
+                    day: night_of(*date),
+                    name: Name::StudySession(StudySession { units }),
+                    candle_lighting: None,
+                    torah_portion: None,
+                    havdalah: vec![],
+                    time_range: None,
+                })
+            })
+            .collect();
+        Ok(SiyumPlan {
+            plan: Return {
+                list,
+                icons: false,
+                location: ics::location_id(self.location, None),
+                time_zone: None,
+                fields: None,
+                short_names: false,
+                shabbos_conflicts: None,
+                headers: None,
+                summary: None,
+                meta: false,
+                alarms: vec![],
+                candle_lighting_minutes: None,
+                provenance: None,
+            },
+            masechta: dapim[0].clone(),
+            dapim: dapim.len(),
+        })
+    }
+}
+
+impl SiyumPlan {
+    /// The day of the last session, which is the day of the siyum.
+    fn siyum(&self) -> NaiveDate {
+        self.plan.list.last().unwrap().civil_date()
+    }
+
+    /// Writes the schedule, followed by the siyum, like "Siyum on Kesubos on 2026/3/19: 111
+    /// dapim in 64 sessions".
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        self.plan.pretty_print(args, lock)?;
+        let masechta = self
+            .masechta
+            .masechta_name(args.language, args.transliteration);
+        match args.language {
+            Language::English => write!(lock, "\nSiyum on {} on ", masechta)?,
+            Language::Hebrew => write!(lock, "\nסיום מסכת {} ב-", masechta)?,
+        };
+        write_date(lock, Utc.from_utc_date(&self.siyum()).and_hms(0, 0, 0))?;
+        match args.language {
+            Language::English => writeln!(
+                lock,
+                ": {} dapim in {} sessions",
+                self.dapim,
+                self.plan.list.len()
+            ),
+            Language::Hebrew => writeln!(
+                lock,
+                ": {} דפים ב-{} פעמים",
+                self.dapim,
+                self.plan.list.len()
+            ),
+        }
+    }
+}
+
+impl Printable for SiyumPlan {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        match args.output_type {
+            // The calendar formats only have the events.
+            OutputType::ICS | OutputType::Parquet => self.plan.print(args, out),
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let mut lock = CountingWriter::new(out);
+                write_data(
+                    &mut lock,
+                    args.output_type,
+                    &serde_json::json!({
+                        "masechta": self.masechta.masechta_name(Language::English, Transliteration::Sephardi),
+                        "siyum": self.siyum(),
+                        "dapim": self.dapim,
+                        "sessions": self.plan.list.len(),
+                        "events": self.plan.json_list(args),
+                    }),
+                )
+                .map_err(write_error)?;
+                Ok(lock.count)
+            }
+            OutputType::Pretty | OutputType::Regular => {
+                let mut lock = CountingWriter::new(out);
+                self.pretty_print(args, &mut lock).map_err(write_error)?;
+                Ok(lock.count)
+            }
+        }
+    }
+}
+
+impl Runnable for PlanSiyumArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        print_output(&self.plan()?, args)?;
+        Ok(())
+    }
+}
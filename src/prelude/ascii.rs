@@ -0,0 +1,131 @@
+//! Output for terminals which can't show UTF-8, selected with `--ascii` or by a locale with
+//! another encoding, such as `LANG=he_IL.ISO-8859-8`.
+
+use std::env;
+use std::io::{self, Write};
+
+/// Returns if the locale names an encoding other than UTF-8. A locale without an encoding (such
+/// as `C`, or no locale at all) isn't taken to mean the terminal can't show UTF-8.
+pub fn locale_is_not_utf8() -> bool {
+    // Like setlocale, the first of these which is set wins.
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|x| env::var(x).ok())
+        .find(|x| !x.is_empty());
+    match locale
+        .as_deref()
+        .and_then(|x| x.split('@').next()?.split_once('.'))
+    {
+        Some((_, encoding)) => {
+            let encoding = encoding.to_ascii_lowercase();
+            encoding != "utf-8" && encoding != "utf8"
+        }
+        None => false,
+    }
+}
+
+/// Transliterates Hebrew letter by letter, and replaces anything else outside of ASCII (such as
+/// the emoji of `--icons`) with `?`. Vowels and cantillation marks are dropped.
+pub fn to_ascii(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.chars() {
+        let transliteration = match c {
+            c if c.is_ascii() => {
+                ascii.push(c);
+                continue;
+            }
+            '\u{0591}'..='\u{05C7}' if c != '\u{05BE}' => continue,
+            'א' | 'ע' | '׳' => "'",
+            'ב' | 'ו' => "v",
+            'ג' => "g",
+            'ד' => "d",
+            'ה' => "h",
+            'ז' => "z",
+            'ח' | 'כ' | 'ך' => "ch",
+            'ט' | 'ת' => "t",
+            'י' => "y",
+            'ל' => "l",
+            'מ' | 'ם' => "m",
+            'נ' | 'ן' => "n",
+            'ס' => "s",
+            'פ' | 'ף' => "f",
+            'צ' | 'ץ' => "tz",
+            'ק' => "k",
+            'ר' => "r",
+            'ש' => "sh",
+            '״' => "\"",
+            '\u{05BE}' | '–' | '—' => "-",
+            _ => "?",
+        };
+        ascii.push_str(transliteration);
+    }
+    ascii
+}
+
+/// Writes everything through [`to_ascii`]. Characters split between writes are held back until
+/// the rest of them is written.
+pub struct AsciiWriter<W: Write> {
+    inner: W,
+    partial: Vec<u8>,
+}
+
+impl<W: Write> AsciiWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            partial: vec![],
+        }
+    }
+}
+
+impl<W: Write> Write for AsciiWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.partial.extend_from_slice(buf);
+        loop {
+            let (valid, invalid) = match std::str::from_utf8(&self.partial) {
+                Ok(_) => (self.partial.len(), None),
+                Err(e) => (e.valid_up_to(), e.error_len()),
+            };
+            let text = std::str::from_utf8(&self.partial[..valid]).unwrap();
+            self.inner.write_all(to_ascii(text).as_bytes())?;
+            match invalid {
+                Some(invalid) => {
+                    self.inner.write_all(b"?")?;
+                    self.partial.drain(..valid + invalid);
+                }
+                None => {
+                    self.partial.drain(..valid);
+                    return Ok(buf.len());
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn transliterates_hebrew() {
+        assert_eq!(to_ascii("ראש השנה"), "r'sh hshnh");
+        assert_eq!(to_ascii("ל\"ג בעומר"), "l\"g v'vmr");
+        assert_eq!(to_ascii("🎉 Pesach"), "? Pesach");
+    }
+
+    #[test]
+    fn characters_split_between_writes() {
+        let mut out = vec![];
+        {
+            let mut writer = AsciiWriter::new(&mut out);
+            let bytes = "שבת".as_bytes();
+            writer.write_all(&bytes[..3]).unwrap();
+            writer.write_all(&bytes[3..]).unwrap();
+        }
+        assert_eq!(out, b"shvt");
+    }
+}
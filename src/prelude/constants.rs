@@ -1,4 +1,4 @@
-use crate::args::types::{DayVal, MinorDays, Name};
+use crate::args::types::{AppError, DayVal, MinorDays, Name};
 use heca_lib::HebrewYear;
 
 use heca_lib::prelude::HebrewMonth;
@@ -7,6 +7,34 @@ use chrono::prelude::*;
 use chrono::DateTime;
 use std::num::NonZeroI8;
 
+/// The first Hebrew year the calendar can be calculated for.
+pub const MIN_HEBREW_YEAR: u64 = 3764;
+/// Past this, some events fall after the last date chrono can represent.
+pub const MAX_HEBREW_YEAR: u64 = 265_000;
+/// The first Gregorian year which falls entirely after `MIN_HEBREW_YEAR` started.
+pub const MIN_GREGORIAN_YEAR: u64 = 4;
+pub const MAX_GREGORIAN_YEAR: u64 = 261_000;
+
+pub fn check_hebrew_year(year: i64) -> Result<(), AppError> {
+    check_year(year, MIN_HEBREW_YEAR, MAX_HEBREW_YEAR)
+}
+
+pub fn check_gregorian_year(year: i64) -> Result<(), AppError> {
+    check_year(year, MIN_GREGORIAN_YEAR, MAX_GREGORIAN_YEAR)
+}
+
+fn check_year(year: i64, min: u64, max: u64) -> Result<(), AppError> {
+    if year < min as i64 || year > max as i64 {
+        Err(AppError::YearOutOfRange {
+            min: min as i64,
+            max: max as i64,
+            got: year,
+        })
+    } else {
+        Ok(())
+    }
+}
+
 pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
     let mut holidays = vec![
         DayVal {
@@ -16,6 +44,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::ErevYomKippur),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -24,6 +55,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::ErevSukkos),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -32,6 +66,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::ErevPesach),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -40,6 +77,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::PesachSheni),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -48,6 +88,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::LagBaOmer),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -56,6 +99,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::ErevShavuos),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -64,6 +110,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::ErevRoshHashanah),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -72,6 +121,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::FifteenShvat),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
         DayVal {
             day: year
@@ -80,6 +132,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::FifteenAv),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         },
     ];
 
@@ -91,6 +146,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::PurimKattan),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         });
         holidays.push(DayVal {
             day: year
@@ -99,6 +157,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
                 .into(),
             name: Name::MinorDays(MinorDays::ShushanPurimKattan),
             candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
         });
     }
     let first_day_of_pesach: DateTime<Utc> = year
@@ -120,6 +181,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
             .into(),
         name: Name::MinorDays(MinorDays::ShabbosHaGadol),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     });
 
     let day_of_taanis_bechoros = if first_day_of_pesach == Weekday::Sat {
@@ -138,8 +202,36 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
             .into(),
         name: Name::MinorDays(MinorDays::TaanisBechoros),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     });
 
+    // When Erev Pesach is on Shabbos, chametz is searched for on Thursday night and burnt on
+    // Friday.
+    let day_of_biur_chametz = if first_day_of_pesach == Weekday::Sat {
+        13
+    } else {
+        14
+    };
+
+    for name in &[MinorDays::BedikasChametz, MinorDays::BiurChametz] {
+        holidays.push(DayVal {
+            day: year
+                .get_hebrew_date(
+                    HebrewMonth::Nissan,
+                    NonZeroI8::new(day_of_biur_chametz).unwrap(),
+                )
+                .unwrap()
+                .into(),
+            name: Name::MinorDays(name.clone()),
+            candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
+        });
+    }
+
     let day_of_tisha_beav: DateTime<Utc> = year
         .get_hebrew_date(HebrewMonth::Av, NonZeroI8::new(9).unwrap())
         .unwrap()
@@ -161,6 +253,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
             .into(),
         name: Name::MinorDays(MinorDays::ShabbosChazon),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     });
 
     holidays.push(DayVal {
@@ -173,6 +268,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
             .into(),
         name: Name::MinorDays(MinorDays::ShabbosNachamu),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     });
 
     let day_of_rh: DateTime<Utc> = year
@@ -210,6 +308,9 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
             .into(),
         name: Name::MinorDays(MinorDays::LeilSlichos),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     });
     holidays.push(DayVal {
         day: year
@@ -221,14 +322,21 @@ pub fn get_minor_holidays(year: &HebrewYear) -> Vec<DayVal> {
             .into(),
         name: Name::MinorDays(MinorDays::ShabbosShuva),
         candle_lighting: None,
+        torah_portion: None,
+        havdalah: vec![],
+        time_range: None,
     });
 
     holidays
 }
 
+/// The masechtos in the order they're learned, with their English, Hebrew and JSON names and
+/// their last daf.
+pub type Gemaras = [(&'static str, &'static str, &'static str, u8); 37];
+
 macro_rules! make_list {
     ($name:ident, $val:expr) => {
-        pub const $name: [(&str, &str, &str, u8); 37] = [
+        pub const $name: Gemaras = [
             ("Berakhot", "ברכות", "Berakhot", 64),
             ("Shabbat", "שבת", "Shabbat", 157),
             ("Eruvin", "עירובין", "Eruvin", 105),
@@ -273,6 +381,68 @@ macro_rules! make_list {
 make_list!(GEMARAS_FIRST_CYCLE, 13);
 make_list!(GEMARAS_SECOND_CYCLE, 22);
 
+/// The Daf Yomi's eras, each a run of cycles learning the same tables, with the Gregorian date
+/// of its first daf. Shekalim has 13 dafim until the eighth cycle, and 22 (from the Yerushalmi)
+/// since. A day is counted from the start of its era, not the first cycle, so that the cycles of
+/// an era can be a different length than those of the last one.
+pub const DAF_YOMI_ERAS: [((i32, u32, u32), &Gemaras); 2] = [
+    ((1923, 9, 11), &GEMARAS_FIRST_CYCLE),
+    ((1975, 6, 24), &GEMARAS_SECOND_CYCLE),
+];
+
+/// Ashkenazi transliterations and abbreviations of the masechtos, in the same order as the Daf Yomi lists.
+pub const MASECHTOS: [(&str, &str, &str); 37] = [
+    ("Berakhot", "Berachos", "Ber."),
+    ("Shabbat", "Shabbos", "Shab."),
+    ("Eruvin", "Eruvin", "Eruv."),
+    ("Pesachim", "Pesachim", "Pes."),
+    ("Shekalim", "Shekalim", "Shek."),
+    ("Yoma", "Yoma", "Yoma"),
+    ("Sukkah", "Sukkah", "Suk."),
+    ("Beitzah", "Beitzah", "Beitz."),
+    ("RoshHashanah", "Rosh Hashanah", "R.H."),
+    ("Taanit", "Taanis", "Taan."),
+    ("Megillah", "Megillah", "Meg."),
+    ("MoedKatan", "Moed Katan", "M.K."),
+    ("Chagigah", "Chagigah", "Chag."),
+    ("Yevamot", "Yevamos", "Yev."),
+    ("Ketubot", "Kesubos", "Ket."),
+    ("Nedarim", "Nedarim", "Ned."),
+    ("Nazir", "Nazir", "Naz."),
+    ("Sotah", "Sotah", "Sot."),
+    ("Gittin", "Gittin", "Git."),
+    ("Kiddushin", "Kiddushin", "Kid."),
+    ("BavaKamma", "Bava Kamma", "B.K."),
+    ("BavaMetzia", "Bava Metzia", "B.M."),
+    ("BavaBatra", "Bava Basra", "B.B."),
+    ("Sanhedrin", "Sanhedrin", "San."),
+    ("Makkot", "Makkos", "Mak."),
+    ("Shevuot", "Shevuos", "Shev."),
+    ("AvodahZarah", "Avodah Zarah", "A.Z."),
+    ("Horayot", "Horayos", "Hor."),
+    ("Zevachim", "Zevachim", "Zev."),
+    ("Menachot", "Menachos", "Men."),
+    ("Chullin", "Chullin", "Chul."),
+    ("Bekhorot", "Bechoros", "Bech."),
+    ("Arakhin", "Erchin", "Arach."),
+    ("Temurah", "Temurah", "Tem."),
+    ("Keritot", "Kerisos", "Ker."),
+    ("Meilah", "Meilah", "Me'il."),
+    ("Niddah", "Niddah", "Nid."),
+];
+
+#[test]
+fn check_masechtos_match_gemaras() {
+    for ((first, second), masechta) in GEMARAS_FIRST_CYCLE
+        .iter()
+        .zip(GEMARAS_SECOND_CYCLE.iter())
+        .zip(MASECHTOS.iter())
+    {
+        assert_eq!(first.2, masechta.0);
+        assert_eq!(second.2, masechta.0);
+    }
+}
+
 #[test]
 fn check_rambam_json_title() {
     for g in RAMBAM.iter() {
@@ -1,10 +1,172 @@
+pub mod ascii;
 pub mod constants;
 pub mod get_omer;
+pub mod numbers;
 pub mod print;
-use crate::args::types::{AppError, MainArgs};
+pub mod sha256;
+pub mod system;
+pub mod warnings;
+use crate::args::types::{AppError, DayVal, MainArgs, OutputType};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
 pub use get_omer::get_omer;
+use heca_lib::{HebrewDate, HebrewYear};
 pub use print::*;
+use serde::Serialize;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
+pub use warnings::{Warning, Warnings};
 
 pub trait Runnable {
     fn run(&self, args: &MainArgs) -> Result<(), AppError>;
 }
+
+/// A source of events for `heca list`. `list::get_events` lists every provider in each
+/// Hebrew year, so a new kind of event only needs a new provider.
+pub trait EventProvider: Sync {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal>;
+
+    /// Lists the events in the year whose night is from `from` until (but not including)
+    /// `until`. Providers which generate every day should override this, so a single month
+    /// doesn't generate the whole year.
+    fn get_between(
+        &self,
+        year: &HebrewYear,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<DayVal> {
+        self.get(year)
+            .into_iter()
+            .filter(|x| x.day >= from && x.day < until)
+            .collect()
+    }
+
+    /// Whether the provider lists events on every day of the year. `list::get_events` generates
+    /// each month of the year separately for these providers, so that a single year still uses
+    /// every core. They must override `get_between`.
+    fn is_daily(&self) -> bool {
+        false
+    }
+}
+
+/// Returns the night before the civil date, which is what the day is stored as.
+pub fn night_of(date: NaiveDate) -> DateTime<Utc> {
+    Utc.from_utc_date(&date).and_hms(18, 0, 0) - Duration::days(1)
+}
+
+/// Returns the events the provider lists on the night before the civil date, and the Hebrew year
+/// that night is in.
+pub fn events_on(provider: &dyn EventProvider, date: NaiveDate) -> (Vec<DayVal>, HebrewYear) {
+    let night = night_of(date);
+    let year = HebrewYear::new(HebrewDate::try_from(night).unwrap().year()).unwrap();
+    let events = provider
+        .get(&year)
+        .into_iter()
+        .filter(|x| x.day == night)
+        .collect();
+    (events, year)
+}
+
+/// Output which can be written to any writer, so it can be printed to stdout, written to the
+/// file passed to `--out`, or captured in tests.
+pub trait Printable {
+    /// Writes the output, returning the amount of bytes written.
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError>;
+}
+
+/// Returns where to write the output to: the file passed to `--out`, or stdout.
+pub fn output(args: &MainArgs) -> Result<Box<dyn Write>, AppError> {
+    let out: Box<dyn Write> = match &args.out {
+        Some(path) => Box::new(BufWriter::with_capacity(
+            1024 * 1024,
+            File::create(path).map_err(|e| {
+                AppError::WriteError(format!("Can't create {}: {}", path.display(), e))
+            })?,
+        )),
+        None => Box::new(BufWriter::with_capacity(1024 * 1024, stdout())),
+    };
+    // JSON, ICS, CBOR and MessagePack are read by programs, not terminals.
+    Ok(match args.output_type {
+        OutputType::Pretty | OutputType::Regular if args.ascii => {
+            Box::new(ascii::AsciiWriter::new(out))
+        }
+        _ => out,
+    })
+}
+
+/// Prints the output to wherever `--out` says, and flushes it.
+pub fn print_output<P: Printable>(printable: &P, args: &MainArgs) -> Result<usize, AppError> {
+    let mut out = output(args)?;
+    let written = printable.print(args, &mut out)?;
+    out.flush().map_err(write_error)?;
+    Ok(written)
+}
+
+/// Writes the value as JSON on a line of its own, or as a single CBOR or MessagePack value,
+/// depending on `--print`. MessagePack keeps the field names, so it reads like the JSON.
+pub fn write_data<W: Write, T: Serialize>(
+    lock: &mut W,
+    output_type: OutputType,
+    value: &T,
+) -> std::io::Result<()> {
+    match output_type {
+        OutputType::CBOR => serde_cbor::to_writer(lock, value).map_err(std::io::Error::other),
+        OutputType::MessagePack => {
+            rmp_serde::encode::write_named(lock, value).map_err(std::io::Error::other)
+        }
+        _ => writeln!(lock, "{}", serde_json::to_string(value).unwrap()),
+    }
+}
+
+/// Writes the rows as a table, with every column as wide as its widest cell.
+pub fn write_table<W: Write>(lock: &mut W, rows: &[Vec<String>]) -> std::io::Result<()> {
+    let widths: Vec<usize> = (0..rows.first().map_or(0, Vec::len))
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap()
+        })
+        .collect();
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let padding = width - cell.chars().count();
+                format!("{}{}", cell, " ".repeat(padding))
+            })
+            .collect::<Vec<String>>()
+            .join("  ");
+        writeln!(lock, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+/// A writer which counts the bytes written through it.
+pub struct CountingWriter<'a> {
+    inner: &'a mut dyn Write,
+    pub count: usize,
+}
+
+impl<'a> CountingWriter<'a> {
+    pub fn new(inner: &'a mut dyn Write) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl Write for CountingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub fn write_error(source: std::io::Error) -> AppError {
+    AppError::WriteError(source.to_string())
+}
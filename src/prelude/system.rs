@@ -0,0 +1,86 @@
+//! The language and time zone the system is set to, which heca uses unless `--language` or
+//! `--time-zone` say otherwise.
+
+use crate::algorithms::solar::{local_time, tz};
+use crate::args::types::{Language, Today};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone as _, Utc};
+use std::convert::TryFrom;
+use std::env;
+use std::fs;
+use zmanim::prelude::tz::TimeZone;
+
+/// Returns the language of the locale messages are printed in, if heca prints in it: Hebrew for
+/// locales like `he_IL.UTF-8` (or the old `iw_IL`), and English for `en_US` or `C`.
+pub fn system_language() -> Option<Language> {
+    // Like setlocale, the first of these which is set wins.
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|x| env::var(x).ok())
+        .find(|x| !x.is_empty())?;
+    let language = locale.split(['_', '.', '@']).next()?;
+    match language {
+        "he" | "iw" => Some(Language::Hebrew),
+        "en" | "C" | "POSIX" => Some(Language::English),
+        _ => None,
+    }
+}
+
+/// Returns the time zone set in `TZ`, or else the one `/etc/localtime` links to, or else the one
+/// in `/etc/timezone`. Time zones which aren't named (like `EST5` in `TZ`) aren't returned.
+pub fn system_time_zone() -> Option<TimeZone> {
+    let from_path = |path: &str| -> Option<String> {
+        let (_, name) = path.split_once("zoneinfo/")?;
+        Some(name.into())
+    };
+    let name = match env::var("TZ") {
+        Ok(tz) if !tz.is_empty() => {
+            let tz = tz.trim_start_matches(':');
+            from_path(tz).unwrap_or_else(|| tz.into())
+        }
+        _ => fs::read_link("/etc/localtime")
+            .ok()
+            .and_then(|path| from_path(&path.to_string_lossy()))
+            .or_else(|| {
+                let name = fs::read_to_string("/etc/timezone").ok()?;
+                Some(name.trim().into())
+            })?,
+    };
+    TimeZone::try_from(name.as_str()).ok()
+}
+
+/// Returns the time it is now, or the time `--today` sets the clock to. A time without an offset is
+/// the time in `time_zone`, or in UTC.
+pub fn now(today: Option<Today>, time_zone: Option<&TimeZone>) -> DateTime<Utc> {
+    match today {
+        Some(Today::Exact(now)) => now,
+        Some(Today::Local(now)) => match time_zone {
+            Some(time_zone) => from_local(time_zone, now),
+            None => Utc.from_utc_datetime(&now),
+        },
+        None => Utc::now(),
+    }
+}
+
+/// Returns the date it is at `now` in the time zone.
+pub fn date_in(time_zone: &TimeZone, now: DateTime<Utc>) -> NaiveDate {
+    local_time(time_zone, now).date().naive_local()
+}
+
+/// Returns when the date starts in the time zone.
+pub fn midnight_in(time_zone: &TimeZone, date: NaiveDate) -> DateTime<Utc> {
+    from_local(time_zone, date.and_hms(0, 0, 0))
+}
+
+/// Returns when it's the local time in the time zone. A time skipped when the clocks go forward
+/// is taken as the time an hour later, and a time repeated when they go back as the first one.
+fn from_local(time_zone: &TimeZone, local: NaiveDateTime) -> DateTime<Utc> {
+    let tz = tz(time_zone);
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|x| x.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+}
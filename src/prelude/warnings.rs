@@ -0,0 +1,82 @@
+use crate::args::types::{AppError, OutputType};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Something that was left out of the output, which isn't worth failing over unless
+/// `--deny-warnings` was passed.
+#[derive(Debug, Clone)]
+pub enum Warning {
+    /// A custom holiday on a day which doesn't exist in some of the listed years.
+    SkippedCustomHoliday(String),
+    /// Custom holidays in the config file with the same id or date, or the id of a built-in event.
+    CustomHolidayConflict(String),
+    /// The system's time zone couldn't be found, so UTC was used instead.
+    UnknownTimeZone(String),
+    /// A custom holiday which lands on Shabbos or Yom Tov, with `--warn-shabbos-conflicts`.
+    ShabbosConflict(String),
+}
+
+impl Warning {
+    fn message(&self) -> &str {
+        match self {
+            Warning::SkippedCustomHoliday(message)
+            | Warning::CustomHolidayConflict(message)
+            | Warning::UnknownTimeZone(message)
+            | Warning::ShabbosConflict(message) => message,
+        }
+    }
+}
+
+impl Serialize for Warning {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Warning", 2)?;
+        match self {
+            Warning::SkippedCustomHoliday(_) => {
+                state.serialize_field("warning", "SkippedCustomHoliday")?
+            }
+            Warning::CustomHolidayConflict(_) => {
+                state.serialize_field("warning", "CustomHolidayConflict")?
+            }
+            Warning::UnknownTimeZone(_) => state.serialize_field("warning", "UnknownTimeZone")?,
+            Warning::ShabbosConflict(_) => state.serialize_field("warning", "ShabbosConflict")?,
+        };
+        state.serialize_field("message", self.message())?;
+        state.end()
+    }
+}
+
+impl From<Warning> for AppError {
+    fn from(warning: Warning) -> Self {
+        match warning {
+            Warning::SkippedCustomHoliday(message) => AppError::MissingCustomHoliday(message),
+            Warning::CustomHolidayConflict(message) => AppError::ConfigError(message),
+            Warning::UnknownTimeZone(message) => AppError::InvalidTimeZone(message),
+            Warning::ShabbosConflict(message) => AppError::ConfigError(message),
+        }
+    }
+}
+
+/// How warnings are reported: a line on stderr, which is a JSON object with `--print json`, or
+/// an error with `--deny-warnings`.
+#[derive(Clone, Copy)]
+pub struct Warnings {
+    pub output_type: OutputType,
+    pub deny: bool,
+}
+
+impl Warnings {
+    pub fn warn(&self, warning: Warning) -> Result<(), AppError> {
+        if self.deny {
+            return Err(warning.into());
+        }
+        if self.output_type.is_data() {
+            eprintln!("{}", serde_json::to_string(&warning).unwrap());
+        } else {
+            eprintln!("Warning: {}", warning.message());
+        }
+        Ok(())
+    }
+}
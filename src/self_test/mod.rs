@@ -0,0 +1,205 @@
+use crate::args::types::{
+    AppError, Command, DailyStudy, Event, Language, ListArgs, MainArgs, MinorHoliday, OutputType,
+    PolarFallback, SelfTestArgs, SolarAlgorithm, Transliteration, YearType,
+};
+use crate::prelude::{
+    print_output, write_data, write_error, CountingWriter, Printable, Runnable, Warnings,
+};
+use heca_lib::prelude::{Location, TorahReadingType};
+use serde::Serialize;
+use std::io::Write;
+
+/// A calendar with known-good output, checked line by line by `heca self-test`.
+struct Golden {
+    name: &'static str,
+    year: u64,
+    location: Location,
+    events: &'static [Event],
+    expected: &'static str,
+}
+
+const TORAH_READINGS_AND_MINOR_DAYS: &[Event] = &[
+    Event::TorahReadingType(TorahReadingType::YomTov),
+    Event::TorahReadingType(TorahReadingType::Chol),
+    Event::TorahReadingType(TorahReadingType::Shabbos),
+    Event::TorahReadingType(TorahReadingType::SpecialParsha),
+    Event::MinorHoliday(MinorHoliday::Minor),
+    Event::MinorHoliday(MinorHoliday::Omer),
+];
+
+const GOLDENS: &[Golden] = &[
+    Golden {
+        name: "5781 in Israel (Purim Meshulash)",
+        year: 5781,
+        location: Location::Israel,
+        events: TORAH_READINGS_AND_MINOR_DAYS,
+        expected: include_str!("golden/5781_israel.txt"),
+    },
+    Golden {
+        name: "5782 in Chutz La'aretz (leap year)",
+        year: 5782,
+        location: Location::Chul,
+        events: TORAH_READINGS_AND_MINOR_DAYS,
+        expected: include_str!("golden/5782_chul.txt"),
+    },
+    Golden {
+        name: "5785 in Chutz La'aretz",
+        year: 5785,
+        location: Location::Chul,
+        events: TORAH_READINGS_AND_MINOR_DAYS,
+        expected: include_str!("golden/5785_chul.txt"),
+    },
+    Golden {
+        name: "5785 Daf Yomi",
+        year: 5785,
+        location: Location::Chul,
+        events: &[Event::DailyStudy(DailyStudy::DafYomi)],
+        expected: include_str!("golden/5785_daf_yomi.txt"),
+    },
+];
+
+#[derive(Serialize)]
+struct Check {
+    name: &'static str,
+    ok: bool,
+    /// The first lines which differ, as (expected, got).
+    differences: Vec<(String, String)>,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Checks(Vec<Check>);
+
+impl Golden {
+    fn check(&self) -> Result<Check, AppError> {
+        let list_args = ListArgs {
+            year: YearType::Hebrew(self.year),
+            location: self.location,
+            yom_tov_observance: self.location,
+            events: self.events.to_vec(),
+            amnt_years: 1,
+            no_sort: false,
+            exact_days: false,
+            categories: None,
+            excluded_categories: vec![],
+            city: None,
+            reading_cycle: None,
+            merge_spans: false,
+            merge_rosh_chodesh: false,
+            strict: true,
+            month: None,
+            only_shabbos: false,
+            havdalah: vec![],
+            polar_fallback: PolarFallback::None,
+            solar_algorithm: SolarAlgorithm::Noaa,
+            icons: false,
+            fields: None,
+            short_names: false,
+            headers: None,
+            summary: false,
+            meta: false,
+            check: false,
+            warn_shabbos_conflicts: false,
+            provenance: false,
+            links: None,
+            alarms: vec![],
+            routes: vec![],
+            append_since_last_run: None,
+            warnings: Warnings {
+                output_type: OutputType::Pretty,
+                deny: true,
+            },
+        };
+        // The goldens are always in English with the default transliteration, whatever the
+        // command line or config file say.
+        let golden_args = MainArgs {
+            custom_days: None,
+            output_type: OutputType::Regular,
+            language: Language::English,
+            second_language: None,
+            transliteration: Transliteration::Sephardi,
+            out: None,
+            ascii: false,
+            today: None,
+            command: Command::SelfTest(SelfTestArgs),
+        };
+        let mut got = vec![];
+        list_args.list()?.print(&golden_args, &mut got)?;
+        let got = String::from_utf8(got).unwrap();
+        let mut differences = vec![];
+        let mut expected_lines = self.expected.lines();
+        let mut got_lines = got.lines();
+        loop {
+            match (expected_lines.next(), got_lines.next()) {
+                (None, None) => break,
+                (expected, got) if expected != got => differences.push((
+                    expected.unwrap_or_default().to_string(),
+                    got.unwrap_or_default().to_string(),
+                )),
+                _ => {}
+            }
+            if differences.len() == 5 {
+                break;
+            }
+        }
+        Ok(Check {
+            name: self.name,
+            ok: differences.is_empty(),
+            differences,
+        })
+    }
+}
+
+impl Checks {
+    fn pretty_print<W: Write>(&self, lock: &mut W) -> std::io::Result<()> {
+        for check in &self.0 {
+            if check.ok {
+                writeln!(lock, "ok: {}", check.name)?;
+            } else {
+                writeln!(lock, "FAILED: {}", check.name)?;
+                for (expected, got) in &check.differences {
+                    writeln!(lock, "  expected: {}", expected)?;
+                    writeln!(lock, "  got:      {}", got)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Printable for Checks {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(&mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for SelfTestArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let checks = Checks(
+            GOLDENS
+                .iter()
+                .map(|golden| golden.check())
+                .collect::<Result<Vec<Check>, AppError>>()?,
+        );
+        print_output(&checks, args)?;
+        let failed = checks.0.iter().filter(|check| !check.ok).count();
+        if failed != 0 {
+            return Err(AppError::SelfTestFailed(format!(
+                "{} of {} calendars",
+                failed,
+                checks.0.len()
+            )));
+        }
+        Ok(())
+    }
+}
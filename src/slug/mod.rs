@@ -0,0 +1,107 @@
+use crate::args::types::{
+    AppError, DailyStudy, DailyStudyOutput, Language, MainArgs, Name, OutputType, SlugArgs,
+    SlugSeries, Transliteration,
+};
+use crate::list::providers::{weekly_reading, DailyStudies};
+use crate::prelude::{
+    events_on, print_output, write_data, write_error, CountingWriter, Printable, Runnable,
+};
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Serialize)]
+struct Slug {
+    date: String,
+    slug: String,
+}
+
+/// Lowercases the name and replaces everything but letters and digits with single dashes.
+pub(crate) fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    push_slug(&mut slug, name);
+    slug
+}
+
+/// Appends the slug of the name to `out`. Every UID is made of slugs, so this doesn't allocate.
+pub(crate) fn push_slug(out: &mut String, name: &str) {
+    let start = out.len();
+    let mut dash = false;
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            if dash && out.len() > start {
+                out.push('-');
+            }
+            dash = false;
+            out.push(c);
+        } else {
+            dash = true;
+        }
+    }
+}
+
+/// Names are always in English with the Ashkenazi transliteration, so that the same week gets
+/// the same slug whatever the language settings are.
+fn english_name(name: &Name) -> String {
+    let mut buf = vec![];
+    name.pretty_print(&mut buf, Language::English, Transliteration::Ashkenazi)
+        .unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+impl SlugArgs {
+    /// The Hebrew year and the reading of the Shabbos on or after the date, like `5785-yisro`.
+    /// If that Shabbos is Yom Tov or Chol Hamoed, its reading is used instead of a parsha.
+    fn parsha_slug(&self) -> String {
+        let (reading, year) = weekly_reading(self.date, self.location);
+        format!("{}-{}", year.year(), slugify(&english_name(&reading.name)))
+    }
+
+    /// The masechta and daf learned on the date, like `berachos-012a`. The daf is named by its
+    /// first amud, so the slugs sort together with the slugs of amud-by-amud series.
+    fn daf_yomi_slug(&self) -> String {
+        let provider = DailyStudies {
+            events: vec![DailyStudy::DafYomi],
+            location: self.location,
+        };
+        let (events, _) = events_on(&provider, self.date);
+        match events.first().map(|x| &x.name) {
+            Some(Name::DailyStudy(DailyStudyOutput::Daf(daf))) => {
+                format!("{}-{:03}a", slugify(daf.masechta_ashkenazi()), daf.daf())
+            }
+            _ => unreachable!("There is a Daf Yomi every day"),
+        }
+    }
+}
+
+impl Printable for Slug {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                writeln!(lock, "{}", self.slug)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for SlugArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let slug = match self.series {
+            SlugSeries::Parsha => self.parsha_slug(),
+            SlugSeries::DafYomi => self.daf_yomi_slug(),
+        };
+        print_output(
+            &Slug {
+                date: self.date.format("%Y-%m-%d").to_string(),
+                slug,
+            },
+            args,
+        )?;
+        Ok(())
+    }
+}
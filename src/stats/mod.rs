@@ -0,0 +1,286 @@
+use crate::args::types::{AppError, EventStats, MainArgs, OutputType, StatsArgs, StatsBy};
+use crate::find::{days_of_event, event_filters, printed_name};
+use crate::prelude::constants::check_hebrew_year;
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use crate::year_info::YearInfo;
+use chrono::prelude::*;
+use rayon::prelude::*;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::io::Write;
+
+const WEEKDAYS: [(Weekday, &str); 7] = [
+    (Weekday::Sun, "Sunday"),
+    (Weekday::Mon, "Monday"),
+    (Weekday::Tue, "Tuesday"),
+    (Weekday::Wed, "Wednesday"),
+    (Weekday::Thu, "Thursday"),
+    (Weekday::Fri, "Friday"),
+    (Weekday::Sat, "Shabbos"),
+];
+
+/// How many years have each value, in the order of the values.
+struct Counts(Vec<(String, usize)>);
+
+impl Counts {
+    fn new<I: IntoIterator<Item = String>>(values: I) -> Counts {
+        let mut values: Vec<String> = values.into_iter().collect();
+        values.sort_unstable();
+        let mut counts: Vec<(String, usize)> = vec![];
+        for value in values {
+            match counts.iter_mut().find(|(x, _)| *x == value) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((value, 1)),
+            }
+        }
+        Counts(counts)
+    }
+}
+
+impl Serialize for Counts {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (value, count) in &self.0 {
+            map.serialize_entry(value, count)?;
+        }
+        map.end()
+    }
+}
+
+/// A Gregorian day of the year, and the first Hebrew year the holiday falls on it.
+#[derive(Serialize)]
+struct Extreme {
+    date: String,
+    year: u64,
+}
+
+#[derive(Serialize)]
+struct Holiday {
+    /// How often the first day falls on each day of the week, from Sunday to Shabbos.
+    weekdays: Counts,
+    earliest: Extreme,
+    latest: Extreme,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Stats {
+    from: u64,
+    to: u64,
+    years: usize,
+    rosh_hashanah: Holiday,
+    pesach: Holiday,
+    year_types: Counts,
+    lengths: Counts,
+}
+
+fn holiday(years: &[YearInfo], date: fn(&YearInfo) -> NaiveDate) -> Holiday {
+    let weekdays = WEEKDAYS
+        .iter()
+        .map(|(weekday, name)| {
+            let count = years
+                .iter()
+                .filter(|x| date(x).weekday() == *weekday)
+                .count();
+            (name.to_string(), count)
+        })
+        .collect();
+    // Years are compared by their day in the Gregorian year, and the first year wins a tie.
+    let day_of_year = |x: &&YearInfo| (date(x).month(), date(x).day());
+    let extreme = |x: &YearInfo| Extreme {
+        date: date(x).format("%m-%d").to_string(),
+        year: x.year,
+    };
+    Holiday {
+        weekdays: Counts(weekdays),
+        earliest: extreme(years.iter().min_by_key(day_of_year).unwrap()),
+        latest: extreme(years.iter().rev().max_by_key(day_of_year).unwrap()),
+    }
+}
+
+/// How often the days of an event fall on each day of the week, printed by `--event`.
+#[derive(Serialize)]
+struct EventCounts {
+    event: String,
+    from: u64,
+    to: u64,
+    years: usize,
+    /// The amount of days of the event in the years.
+    days: usize,
+    weekdays: Counts,
+    #[serde(skip)]
+    csv: bool,
+}
+
+impl StatsArgs {
+    /// Lists the days of the event in each year in parallel.
+    fn count_event(&self, event: &EventStats, args: &MainArgs) -> Result<EventCounts, AppError> {
+        let filters = event_filters(&event.event, self.from, self.to, event.location)?;
+        let days: Vec<(NaiveDate, String)> = (self.from..=self.to)
+            .into_par_iter()
+            .map(|year| {
+                Ok(days_of_event(year, &event.event, &filters, event.location)?
+                    .into_iter()
+                    .map(|(date, name)| {
+                        (
+                            date,
+                            printed_name(&name, args.language, args.transliteration),
+                        )
+                    })
+                    .collect::<Vec<(NaiveDate, String)>>())
+            })
+            .collect::<Result<Vec<Vec<(NaiveDate, String)>>, AppError>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+        let weekdays = match event.by {
+            StatsBy::Weekday => WEEKDAYS
+                .iter()
+                .map(|(weekday, name)| {
+                    let count = days.iter().filter(|(x, _)| x.weekday() == *weekday).count();
+                    (name.to_string(), count)
+                })
+                .collect(),
+        };
+        Ok(EventCounts {
+            // The event was found in the years, so it has a day.
+            event: days[0].1.clone(),
+            from: self.from,
+            to: self.to,
+            years: (self.to - self.from + 1) as usize,
+            days: days.len(),
+            weekdays: Counts(weekdays),
+            csv: event.csv,
+        })
+    }
+}
+
+impl Runnable for StatsArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        check_hebrew_year(self.from as i64)?;
+        check_hebrew_year(self.to as i64)?;
+        if let Some(event) = &self.event {
+            print_output(&self.count_event(event, args)?, args)?;
+            return Ok(());
+        }
+        let years = (self.from..=self.to)
+            .into_par_iter()
+            .map(YearInfo::new)
+            .collect::<Result<Vec<YearInfo>, AppError>>()?;
+        let stats = Stats {
+            from: self.from,
+            to: self.to,
+            years: years.len(),
+            rosh_hashanah: holiday(&years, |x| x.rosh_hashanah),
+            pesach: holiday(&years, |x| x.pesach),
+            year_types: Counts::new(years.iter().map(|x| format!("{:?}", x.year_type))),
+            lengths: Counts::new(years.iter().map(|x| x.length.to_string())),
+        };
+        print_output(&stats, args)?;
+        Ok(())
+    }
+}
+
+impl Stats {
+    fn pretty_print<W: Write>(&self, lock: &mut W) -> std::io::Result<()> {
+        writeln!(
+            lock,
+            "{} years, from {} to {}",
+            self.years, self.from, self.to
+        )?;
+        for (name, holiday) in &[
+            ("Rosh Hashanah", &self.rosh_hashanah),
+            ("Pesach", &self.pesach),
+        ] {
+            writeln!(lock, "{}: {}", name, format_counts(&holiday.weekdays))?;
+            writeln!(
+                lock,
+                "{}: earliest {} ({}), latest {} ({})",
+                name,
+                holiday.earliest.date,
+                holiday.earliest.year,
+                holiday.latest.date,
+                holiday.latest.year
+            )?;
+        }
+        writeln!(lock, "Year types: {}", format_counts(&self.year_types))?;
+        writeln!(lock, "Year lengths: {}", format_counts(&self.lengths))
+    }
+}
+
+fn format_counts(counts: &Counts) -> String {
+    counts
+        .0
+        .iter()
+        .map(|(value, count)| format!("{} {}", value, count))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl Printable for Stats {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(&mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl EventCounts {
+    fn percent(&self, count: usize) -> f64 {
+        count as f64 * 100.0 / self.days as f64
+    }
+
+    fn pretty_print<W: Write>(&self, lock: &mut W) -> std::io::Result<()> {
+        writeln!(
+            lock,
+            "{}: {} days in {} years, from {} to {}",
+            self.event, self.days, self.years, self.from, self.to
+        )?;
+        for (weekday, count) in &self.weekdays.0 {
+            writeln!(
+                lock,
+                "{}: {} ({:.1}%)",
+                weekday,
+                count,
+                self.percent(*count)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn csv_print<W: Write>(&self, lock: &mut W) -> std::io::Result<()> {
+        writeln!(lock, "weekday,count,percent")?;
+        for (weekday, count) in &self.weekdays.0 {
+            writeln!(lock, "{},{},{:.1}", weekday, count, self.percent(*count))?;
+        }
+        Ok(())
+    }
+}
+
+impl Printable for EventCounts {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            _ if self.csv => self.csv_print(&mut lock),
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(&mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
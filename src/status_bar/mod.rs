@@ -0,0 +1,246 @@
+use crate::args::types::{AppError, Category, Language, MainArgs, OutputType, StatusBarArgs, Zman};
+use crate::list::Return;
+use crate::prelude::print;
+use crate::prelude::system::{date_in, midnight_in, now};
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use crate::zmanim_table::get_zman;
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::HebrewDate;
+use serde::Serialize;
+use std::convert::TryInto;
+use std::io::Write;
+use std::process;
+use std::thread;
+
+/// A day's Hebrew date and events, printed on one line for status bars. Only the events of the
+/// day and the night after it are listed, so it's cheap to run every few minutes.
+struct StatusBar {
+    date: NaiveDate,
+    /// The Hebrew date during the day.
+    hebrew: HebrewDate,
+    /// The events observed during the day, including those which started the night before.
+    today: Return,
+    /// The events starting on the night after the day.
+    tonight: Return,
+}
+
+/// The JSON Waybar reads from custom modules with `"return-type": "json"`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Waybar {
+    text: String,
+    tooltip: String,
+    class: &'static str,
+    alt: &'static str,
+}
+
+impl StatusBar {
+    fn hebrew_date(&self, args: &MainArgs) -> String {
+        let month = match args.language {
+            Language::English => print::hebrew_month_english(self.hebrew.month()),
+            Language::Hebrew => print::hebrew_month_hebrew(self.hebrew.month()),
+        };
+        format!("{} {} {}", self.hebrew.day(), month, self.hebrew.year())
+    }
+
+    /// The Hebrew date and the short names of the day's events.
+    fn text(&self, args: &MainArgs) -> String {
+        let mut text = self.hebrew_date(args);
+        for (index, d) in self.today.list.iter().enumerate() {
+            text.push_str(if index == 0 { " - " } else { ", " });
+            let mut name = vec![];
+            d.name
+                .short_print(&mut name, args.language, args.transliteration)
+                .unwrap();
+            text.push_str(&String::from_utf8(name).unwrap());
+        }
+        text
+    }
+
+    /// The civil and Hebrew dates, followed by the day's and the night's events in full, with
+    /// their candle lighting and havdalah.
+    fn tooltip(&self, args: &MainArgs) -> String {
+        let mut tooltip = vec![];
+        writeln!(
+            tooltip,
+            "{}, {}",
+            self.date.format("%A %Y/%-m/%-d"),
+            self.hebrew_date(args)
+        )
+        .unwrap();
+        self.today.pretty_print(args, &mut tooltip).unwrap();
+        self.tonight.pretty_print(args, &mut tooltip).unwrap();
+        String::from_utf8(tooltip).unwrap().trim_end().into()
+    }
+
+    /// The CSS class of the module, so that Shabbos and Yom Tov can be styled differently.
+    fn class(&self) -> &'static str {
+        let is = |category| {
+            self.today
+                .list
+                .iter()
+                .any(|x| x.name.category() == category)
+        };
+        if is(Category::YomTov) {
+            "yomtov"
+        } else if self.date.weekday() == Weekday::Sat {
+            "shabbos"
+        } else if is(Category::Fast) {
+            "fast"
+        } else {
+            "weekday"
+        }
+    }
+}
+
+impl Printable for StatusBar {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let waybar = Waybar {
+                    text: self.text(args),
+                    tooltip: self.tooltip(args),
+                    class: self.class(),
+                    alt: self.class(),
+                };
+                write_data(&mut lock, args.output_type, &waybar)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                writeln!(lock, "{}", self.text(args))
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl StatusBarArgs {
+    /// The day it is at the time, which starts at sunset if there's a city, or `--date`.
+    fn day(&self, now: DateTime<Utc>) -> NaiveDate {
+        if let Some(date) = self.date {
+            return date;
+        }
+        let date = self.civil_date(now);
+        match self.sunset(date) {
+            Some(sunset) if now >= sunset => date.succ(),
+            _ => date,
+        }
+    }
+
+    /// The date it is at the time in `--time-zone`, the city or the system, or else in UTC.
+    fn civil_date(&self, now: DateTime<Utc>) -> NaiveDate {
+        match &self.time_zone {
+            Some(time_zone) => date_in(time_zone, now),
+            None => now.naive_utc().date(),
+        }
+    }
+
+    fn sunset(&self, date: NaiveDate) -> Option<DateTime<Utc>> {
+        let city = self.list.city.as_ref()?;
+        let sunset = get_zman(
+            self.list.solar_algorithm.sun(),
+            Zman::Sunset,
+            city,
+            date,
+            self.list.polar_fallback,
+        )?;
+        Some(sunset.with_timezone(&Utc))
+    }
+
+    /// When the day changes next after `now`: at sunset, or at midnight.
+    fn next_change(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let date = self.civil_date(now);
+        let midnight = match &self.time_zone {
+            Some(time_zone) => midnight_in(time_zone, date.succ()),
+            None => Utc.from_utc_date(&date.succ()).and_hms(0, 0, 0),
+        };
+        match self.sunset(date) {
+            Some(sunset) if sunset > now && sunset < midnight => sunset,
+            _ => midnight,
+        }
+    }
+
+    fn status_bar(&self, date: NaiveDate) -> Result<StatusBar, AppError> {
+        let events = self
+            .list
+            .list_between(date - Duration::days(1), date + Duration::days(1))?;
+        let location = events.location;
+        let (tonight, today) = events
+            .list
+            .into_iter()
+            .filter(|x| x.name.starts_at_night() || x.civil_date() == date)
+            .partition(|x| x.name.starts_at_night() && x.civil_date() == date);
+        let list = |list| Return {
+            list,
+            icons: false,
+            location: location.clone(),
+            time_zone: None,
+            fields: None,
+            short_names: false,
+            shabbos_conflicts: None,
+            headers: None,
+            summary: None,
+            meta: false,
+            alarms: vec![],
+            candle_lighting_minutes: None,
+            provenance: None,
+        };
+        Ok(StatusBar {
+            date,
+            hebrew: Utc.from_utc_date(&date).and_hms(0, 0, 1).try_into()?,
+            today: list(today),
+            tonight: list(tonight),
+        })
+    }
+}
+
+/// Runs the program with the output on its stdin. The program failing doesn't stop `--watch`,
+/// but not being able to run it does.
+fn exec(program: &str, status_bar: &StatusBar, args: &MainArgs) -> Result<(), AppError> {
+    let mut output = vec![];
+    status_bar.print(args, &mut output)?;
+    let cannot_run =
+        |e: std::io::Error| AppError::WriteError(format!("Cannot run {}: {}", program, e));
+    let mut child = process::Command::new(program)
+        .stdin(process::Stdio::piped())
+        .spawn()
+        .map_err(cannot_run)?;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(&output)
+        .map_err(cannot_run)?;
+    child.wait().map_err(cannot_run)?;
+    Ok(())
+}
+
+impl Runnable for StatusBarArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        let mut last = None;
+        loop {
+            let now = now(args.today, self.time_zone.as_ref());
+            let date = self.day(now);
+            // The day which started at sunset is still the same day after midnight, so it isn't
+            // printed again then.
+            if last != Some(date) {
+                let status_bar = self.status_bar(date)?;
+                match &self.exec {
+                    Some(program) => exec(program, &status_bar, args)?,
+                    None => {
+                        print_output(&status_bar, args)?;
+                    }
+                }
+                last = Some(date);
+            }
+            // The clock doesn't move when `--today` sets it.
+            if !self.watch || args.today.is_some() {
+                return Ok(());
+            }
+            let wait = self.next_change(now) - Utc::now() + Duration::seconds(1);
+            thread::sleep(wait.to_std().unwrap_or_default());
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::args::types::{AppError, MainArgs, OutputType, VersionArgs};
+use crate::limits::Limits;
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use serde::Serialize;
+use std::io::Write;
+
+/// The versions of the JSON output this build prints. A new version is added when a field is
+/// removed or changes its meaning, not when one is added.
+const SCHEMA_VERSIONS: &[u32] = &[1];
+
+/// What this build of heca can do, so scripts can check before relying on it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Version {
+    version: &'static str,
+    /// The commit heca was built from, if it was built from a git checkout.
+    git_hash: Option<&'static str>,
+    features: Vec<&'static str>,
+    schema_versions: &'static [u32],
+    years: Limits,
+}
+
+impl Version {
+    fn new() -> Self {
+        // Zmanim and ICS output are always built in; cargo features are only listed if enabled.
+        let mut features = vec!["zmanim", "ics"];
+        if cfg!(feature = "profile") {
+            features.push("profile");
+        }
+        if cfg!(feature = "parquet") {
+            features.push("parquet");
+        }
+        Version {
+            version: env!("CARGO_PKG_VERSION"),
+            git_hash: option_env!("HECA_GIT_HASH"),
+            features,
+            schema_versions: SCHEMA_VERSIONS,
+            years: Limits::new(),
+        }
+    }
+}
+
+impl Runnable for VersionArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        print_output(&Version::new(), args)?;
+        Ok(())
+    }
+}
+
+impl Printable for Version {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                match self.git_hash {
+                    Some(hash) => writeln!(lock, "heca {} ({})", self.version, hash),
+                    None => writeln!(lock, "heca {}", self.version),
+                }
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
@@ -0,0 +1,300 @@
+//! Which parshiyos are read together in a year, and why, printed by `--doubled`.
+//!
+//! There are fewer Shabbosos in a year than parshiyos, and fewer still when Yom Tov is on
+//! Shabbos, so seven pairs of parshiyos are read together in the years which have no Shabbos for
+//! each of them (Orach Chaim 428:4). Whether they are depends on whether the year is a leap year,
+//! on its length and the days of the week of its Rosh Hashanah and the next, and on whether the
+//! second days of Yom Tov of Chutz La'aretz take the Shabbosos they're on.
+
+use crate::args::types::{AppError, Language};
+use crate::year_info::{civil_date, YearInfo};
+use chrono::prelude::*;
+use heca_lib::prelude::{HebrewMonth, Location, Parsha, TorahReading, TorahReadingType};
+use heca_lib::HebrewYear;
+use serde::Serialize;
+
+/// The parshiyos which are read together in some years, in the order they're read.
+pub const PAIRS: [Parsha; 7] = [
+    Parsha::VayakhelPikudei,
+    Parsha::TazriyaMetzorah,
+    Parsha::AchareiMosKedoshim,
+    Parsha::BeharBechukosai,
+    Parsha::ChukasBalak,
+    Parsha::MatosMaasei,
+    Parsha::NitzavimVayelech,
+];
+
+/// Why a pair of parshiyos is read together in a year, or separately.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum DoublingReason {
+    /// A leap year has the four Shabbosos of Adar I more, so every parsha before Shavuos has its
+    /// own.
+    LeapYear,
+    /// A regular year doesn't have them.
+    RegularYear,
+    /// A regular year of 355 days which starts on Thursday has a Shabbos more before Pesach.
+    FullYearFromThursday,
+    /// The eighth day of Pesach is on Shabbos, so Israel reads the next parsha on it, and
+    /// separates a pair which Chutz La'aretz reads together to catch up.
+    EighthDayOfPesachOnShabbos,
+    /// In Chutz La'aretz, the second day of Shavuos is on Shabbos.
+    SecondDayOfShavuosOnShabbos,
+    /// In Chutz La'aretz, the second day of Shavuos isn't on Shabbos.
+    SecondDayOfShavuosNotOnShabbos,
+    /// Israel keeps one day of Shavuos, so it never takes the Shabbos of Chukas.
+    OneDayOfShavuos,
+    /// A leap year of 383 or 385 days which starts on Thursday has no Yom Tov on Shabbos from
+    /// Pesach to Tisha B'Av.
+    LeapYearFromThursday,
+    /// Devarim is read on the Shabbos before Tisha B'Av.
+    DevarimBeforeTishaBav,
+    /// The next Rosh Hashanah is on Monday or Tuesday, so there are two Shabbosos between it and
+    /// Sukkos, for Vayelech and Haazinu, and Nitzavim is read alone before it.
+    RoshHashanahOnMondayOrTuesday,
+    /// The next Rosh Hashanah is on Thursday or Shabbos, so there's only one Shabbos between it
+    /// and Sukkos, for Haazinu, and Vayelech is read with Nitzavim before it.
+    RoshHashanahOnThursdayOrShabbos,
+}
+
+impl DoublingReason {
+    /// Whether the pair is read together for this reason.
+    pub fn doubles(self) -> bool {
+        matches!(
+            self,
+            DoublingReason::RegularYear
+                | DoublingReason::SecondDayOfShavuosOnShabbos
+                | DoublingReason::DevarimBeforeTishaBav
+                | DoublingReason::RoshHashanahOnThursdayOrShabbos
+        )
+    }
+
+    /// The reason in lowercase with dashes, like `regular-year`, as `list --provenance` names it.
+    pub fn id(self) -> &'static str {
+        match self {
+            DoublingReason::LeapYear => "leap-year",
+            DoublingReason::RegularYear => "regular-year",
+            DoublingReason::FullYearFromThursday => "full-year-from-thursday",
+            DoublingReason::EighthDayOfPesachOnShabbos => "eighth-day-of-pesach-on-shabbos",
+            DoublingReason::SecondDayOfShavuosOnShabbos => "second-day-of-shavuos-on-shabbos",
+            DoublingReason::SecondDayOfShavuosNotOnShabbos => {
+                "second-day-of-shavuos-not-on-shabbos"
+            }
+            DoublingReason::OneDayOfShavuos => "one-day-of-shavuos",
+            DoublingReason::LeapYearFromThursday => "leap-year-from-thursday",
+            DoublingReason::DevarimBeforeTishaBav => "devarim-before-tisha-bav",
+            DoublingReason::RoshHashanahOnMondayOrTuesday => "rosh-hashanah-on-monday-or-tuesday",
+            DoublingReason::RoshHashanahOnThursdayOrShabbos => {
+                "rosh-hashanah-on-thursday-or-shabbos"
+            }
+        }
+    }
+
+    pub fn reason(self, language: Language) -> &'static str {
+        match (self, language) {
+            (DoublingReason::LeapYear, Language::English) => {
+                "a leap year has four more Shabbosos, in Adar I, so each is read on its own Shabbos"
+            }
+            (DoublingReason::LeapYear, Language::Hebrew) => {
+                "בשנה מעוברת ארבע שבתות יותר, באדר א', ולכן כל אחת נקראת בשבת שלה"
+            }
+            (DoublingReason::RegularYear, Language::English) => {
+                "a regular year has no Adar I, so there aren't enough Shabbosos to read them separately"
+            }
+            (DoublingReason::RegularYear, Language::Hebrew) => {
+                "בשנה פשוטה אין אדר א', ואין די שבתות לקרוא אותן בנפרד"
+            }
+            (DoublingReason::FullYearFromThursday, Language::English) => {
+                "a regular year of 355 days which starts on Thursday has a Shabbos more before Pesach"
+            }
+            (DoublingReason::FullYearFromThursday, Language::Hebrew) => {
+                "בשנה פשוטה שלמה שמתחילה ביום ה' יש שבת נוספת לפני פסח"
+            }
+            (DoublingReason::EighthDayOfPesachOnShabbos, Language::English) => {
+                "the eighth day of Pesach is on Shabbos, which isn't Yom Tov in Israel, so Israel reads the next parsha on it and reads these separately while Chutz La'aretz catches up"
+            }
+            (DoublingReason::EighthDayOfPesachOnShabbos, Language::Hebrew) => {
+                "אחרון של פסח חל בשבת, ואינו יום טוב בארץ ישראל, ולכן בארץ ישראל קוראים בו את הפרשה הבאה וקוראים אלו בנפרד עד שחוץ לארץ משלימים"
+            }
+            (DoublingReason::SecondDayOfShavuosOnShabbos, Language::English) => {
+                "the second day of Shavuos is on Shabbos, so Chutz La'aretz reads them together to catch up with Israel"
+            }
+            (DoublingReason::SecondDayOfShavuosOnShabbos, Language::Hebrew) => {
+                "יום שני של שבועות חל בשבת, ולכן בחוץ לארץ מחברים אותן כדי להשוות לארץ ישראל"
+            }
+            (DoublingReason::SecondDayOfShavuosNotOnShabbos, Language::English) => {
+                "the second day of Shavuos isn't on Shabbos, so there's a Shabbos for each"
+            }
+            (DoublingReason::SecondDayOfShavuosNotOnShabbos, Language::Hebrew) => {
+                "יום שני של שבועות אינו חל בשבת, ולכן יש שבת לכל אחת"
+            }
+            (DoublingReason::OneDayOfShavuos, Language::English) => {
+                "Israel keeps one day of Shavuos, so there's always a Shabbos for each"
+            }
+            (DoublingReason::OneDayOfShavuos, Language::Hebrew) => {
+                "בארץ ישראל שבועות יום אחד, ולכן תמיד יש שבת לכל אחת"
+            }
+            (DoublingReason::LeapYearFromThursday, Language::English) => {
+                "a leap year of 383 or 385 days which starts on Thursday has no Yom Tov on Shabbos from Pesach to Tisha B'Av, so there's a Shabbos for each"
+            }
+            (DoublingReason::LeapYearFromThursday, Language::Hebrew) => {
+                "בשנה מעוברת של שפ\"ג או שפ\"ה ימים שמתחילה ביום ה' אין יום טוב שחל בשבת מפסח עד תשעה באב, ולכן יש שבת לכל אחת"
+            }
+            (DoublingReason::DevarimBeforeTishaBav, Language::English) => {
+                "they're read together so that Devarim is read on the Shabbos before Tisha B'Av"
+            }
+            (DoublingReason::DevarimBeforeTishaBav, Language::Hebrew) => {
+                "מחברים אותן כדי שפרשת דברים תיקרא בשבת שלפני תשעה באב"
+            }
+            (DoublingReason::RoshHashanahOnMondayOrTuesday, Language::English) => {
+                "the next Rosh Hashanah is on Monday or Tuesday, so there are two Shabbosos between it and Sukkos, and Vayelech is read on the first and Haazinu on the second"
+            }
+            (DoublingReason::RoshHashanahOnMondayOrTuesday, Language::Hebrew) => {
+                "ראש השנה הבא חל ביום ב' או ג', ולכן יש שתי שבתות בינו לבין סוכות, וקוראים וילך בראשונה והאזינו בשנייה"
+            }
+            (DoublingReason::RoshHashanahOnThursdayOrShabbos, Language::English) => {
+                "the next Rosh Hashanah is on Thursday or Shabbos, so there's only one Shabbos between it and Sukkos, for Haazinu, and Vayelech is read with Nitzavim before it"
+            }
+            (DoublingReason::RoshHashanahOnThursdayOrShabbos, Language::Hebrew) => {
+                "ראש השנה הבא חל ביום ה' או בשבת, ולכן יש רק שבת אחת בינו לבין סוכות, להאזינו, וקוראים וילך עם נצבים לפניו"
+            }
+        }
+    }
+}
+
+/// Whether a pair of parshiyos is read together in a year, and why.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Doubling {
+    /// The parshiyos read together, such as `VayakhelPikudei`.
+    pub parsha: Parsha,
+    /// The Shabbos they're read together on, or `None` if they're read on two Shabbosos.
+    pub date: Option<NaiveDate>,
+    pub reason: DoublingReason,
+}
+
+impl Doubling {
+    pub fn doubled(&self) -> bool {
+        self.date.is_some()
+    }
+}
+
+/// Why `parsha`, one of the [`PAIRS`], is read together or separately in `year` in `location`,
+/// by the rules heca_lib reads the parshiyos by.
+pub fn doubling_reason(
+    parsha: Parsha,
+    year: &YearInfo,
+    next_rosh_hashanah: Weekday,
+    location: Location,
+) -> DoublingReason {
+    let rosh_hashanah = year.rosh_hashanah.weekday();
+    match parsha {
+        Parsha::ChukasBalak => {
+            if location == Location::Israel {
+                DoublingReason::OneDayOfShavuos
+            } else if next_rosh_hashanah == Weekday::Sat {
+                // The second day of Shavuos is on the day of the week of the next Rosh Hashanah.
+                DoublingReason::SecondDayOfShavuosOnShabbos
+            } else {
+                DoublingReason::SecondDayOfShavuosNotOnShabbos
+            }
+        }
+        Parsha::MatosMaasei => {
+            if rosh_hashanah == Weekday::Thu && (year.length == 383 || year.length == 385) {
+                DoublingReason::LeapYearFromThursday
+            } else if location == Location::Israel
+                && ((rosh_hashanah == Weekday::Mon && year.length == 385)
+                    || (rosh_hashanah == Weekday::Tue && year.length == 384))
+            {
+                DoublingReason::EighthDayOfPesachOnShabbos
+            } else {
+                DoublingReason::DevarimBeforeTishaBav
+            }
+        }
+        Parsha::NitzavimVayelech => match next_rosh_hashanah {
+            Weekday::Mon | Weekday::Tue => DoublingReason::RoshHashanahOnMondayOrTuesday,
+            _ => DoublingReason::RoshHashanahOnThursdayOrShabbos,
+        },
+        _ if year.leap() => DoublingReason::LeapYear,
+        Parsha::VayakhelPikudei if year.length == 355 && rosh_hashanah == Weekday::Thu => {
+            DoublingReason::FullYearFromThursday
+        }
+        Parsha::BeharBechukosai
+            if location == Location::Israel
+                && year.length == 354
+                && rosh_hashanah == Weekday::Thu =>
+        {
+            DoublingReason::EighthDayOfPesachOnShabbos
+        }
+        _ => DoublingReason::RegularYear,
+    }
+}
+
+/// Whether each of the [`PAIRS`] is read together in `year` in `location`, with the Shabbos it's
+/// read on and why.
+pub fn doubled_parshiyos(year: u64, location: Location) -> Result<Vec<Doubling>, AppError> {
+    let info = YearInfo::new(year)?;
+    let next_rosh_hashanah = civil_date(year + 1, HebrewMonth::Tishrei, 1)?.weekday();
+    let shabbosos = HebrewYear::new(year)?.get_holidays(location, &[TorahReadingType::Shabbos]);
+    Ok(PAIRS
+        .iter()
+        .map(|&parsha| Doubling {
+            parsha,
+            date: shabbosos
+                .iter()
+                .find(|x| x.name() == TorahReading::Shabbos(parsha))
+                .map(|x| {
+                    let night: DateTime<Utc> = x.day().into();
+                    night.naive_utc().date().succ()
+                }),
+            reason: doubling_reason(parsha, &info, next_rosh_hashanah, location),
+        })
+        .collect())
+}
+
+/// A pair in JSON, with its reason and the Shabbos it's read together on.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoublingJson {
+    parsha: Parsha,
+    doubled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    reason: DoublingReason,
+}
+
+impl Doubling {
+    pub fn json(&self) -> DoublingJson {
+        DoublingJson {
+            parsha: self.parsha,
+            doubled: self.doubled(),
+            date: self.date.map(|date| date.format("%Y-%m-%d").to_string()),
+            reason: self.reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reasons_match_the_readings() {
+        for year in 5600..6000 {
+            for &location in &[Location::Chul, Location::Israel] {
+                for doubling in doubled_parshiyos(year, location).unwrap() {
+                    assert_eq!(
+                        doubling.doubled(),
+                        doubling.reason.doubles(),
+                        "{} in {}: {:?}",
+                        year,
+                        if location == Location::Israel {
+                            "Israel"
+                        } else {
+                            "Chutz La'aretz"
+                        },
+                        doubling
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,422 @@
+//! Why Rosh Hashanah of a year is on its day, and why Cheshvan and Kislev have their lengths,
+//! printed by `--explain`.
+//!
+//! Rosh Hashanah is on the day of the molad of Tishrei, unless one of the dechiyos postpones it
+//! (Rambam, Hilchos Kiddush HaChodesh 7). The length of the year is then the amount of days until
+//! the next Rosh Hashanah, and Cheshvan and Kislev are the months which give or take the days it
+//! has more or less than a regular year.
+
+use crate::algorithms::arithmetic::days_in_month;
+use crate::args::types::{AppError, Language};
+use crate::gematria::hebrew_numeral;
+use crate::year_info::civil_date;
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::HebrewMonth;
+use heca_lib::HebrewYear;
+use serde::Serialize;
+use std::io::Write;
+
+const CHALAKIM_PER_HOUR: u32 = 1080;
+
+/// A rule which postpones Rosh Hashanah from the day of the molad.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum Dechiyah {
+    /// The molad is at noon or later, 18 hours into the day.
+    MoladZaken,
+    /// In a regular year, the molad is on Tuesday at 9 hours and 204 chalakim or later.
+    Gatarad,
+    /// After a leap year, the molad is on Monday at 15 hours and 589 chalakim or later.
+    Betutakpat,
+    /// Rosh Hashanah isn't on Sunday, Wednesday or Friday.
+    LoAdu,
+}
+
+impl Dechiyah {
+    /// The dechiyah in lowercase with dashes, like `lo-adu-rosh`, as `list --provenance` names it.
+    pub fn id(self) -> &'static str {
+        match self {
+            Dechiyah::MoladZaken => "molad-zaken",
+            Dechiyah::Gatarad => "gatarad",
+            Dechiyah::Betutakpat => "betutakpat",
+            Dechiyah::LoAdu => "lo-adu-rosh",
+        }
+    }
+
+    pub fn name(self, language: Language) -> &'static str {
+        match (self, language) {
+            (Dechiyah::MoladZaken, Language::English) => "Molad Zaken",
+            (Dechiyah::MoladZaken, Language::Hebrew) => "מולד זקן",
+            (Dechiyah::Gatarad, Language::English) => "GaTaRaD",
+            (Dechiyah::Gatarad, Language::Hebrew) => "גטר\"ד",
+            (Dechiyah::Betutakpat, Language::English) => "BeTUTaKPaT",
+            (Dechiyah::Betutakpat, Language::Hebrew) => "בטו\"תקפט",
+            (Dechiyah::LoAdu, Language::English) => "Lo ADU Rosh",
+            (Dechiyah::LoAdu, Language::Hebrew) => "לא אד\"ו ראש",
+        }
+    }
+
+    pub fn reason(self, language: Language) -> &'static str {
+        match (self, language) {
+            (Dechiyah::MoladZaken, Language::English) => {
+                "the molad is at noon or later, so Rosh Hashanah is postponed to the next day"
+            }
+            (Dechiyah::MoladZaken, Language::Hebrew) => {
+                "המולד בחצות היום או אחריו, ולכן ראש השנה נדחה ליום שאחריו"
+            }
+            (Dechiyah::Gatarad, Language::English) => {
+                "in a regular year, a molad on Tuesday at 9 hours and 204 chalakim or later postpones Rosh Hashanah to Wednesday, as the year would otherwise have 356 days"
+            }
+            (Dechiyah::Gatarad, Language::Hebrew) => {
+                "בשנה פשוטה, מולד ביום ג' בט' שעות ור\"ד חלקים או אחריו דוחה את ראש השנה ליום ד', שאם לא כן תהיה השנה בת שנ\"ו ימים"
+            }
+            (Dechiyah::Betutakpat, Language::English) => {
+                "after a leap year, a molad on Monday at 15 hours and 589 chalakim or later postpones Rosh Hashanah to Tuesday, as the leap year would otherwise have 382 days"
+            }
+            (Dechiyah::Betutakpat, Language::Hebrew) => {
+                "אחרי שנה מעוברת, מולד ביום ב' בט\"ו שעות ותקפ\"ט חלקים או אחריו דוחה את ראש השנה ליום ג', שאם לא כן תהיה השנה המעוברת בת שפ\"ב ימים"
+            }
+            (Dechiyah::LoAdu, Language::English) => {
+                "Rosh Hashanah isn't on Sunday, Wednesday or Friday, so it's postponed to the next day"
+            }
+            (Dechiyah::LoAdu, Language::Hebrew) => {
+                "ראש השנה אינו חל בימים א' ד' ו', ולכן נדחה ליום שאחריו"
+            }
+        }
+    }
+}
+
+/// How the day of Rosh Hashanah of a year is fixed.
+pub struct RoshHashanah {
+    pub year: u64,
+    /// The molad of Tishrei, in the time of Jerusalem, and its chalakim (1/1080 of an hour).
+    pub molad: (NaiveDateTime, u16),
+    /// The day of the week of the molad, whose day starts at 6 in the evening before it.
+    pub molad_weekday: Weekday,
+    /// The hours and chalakim from 6 in the evening to the molad.
+    pub molad_hours: u32,
+    pub molad_chalakim: u32,
+    /// The dechiyos which postponed Rosh Hashanah, in the order they're applied.
+    pub dechiyos: Vec<Dechiyah>,
+    /// The Gregorian date of the first day of Rosh Hashanah.
+    pub date: NaiveDate,
+}
+
+impl RoshHashanah {
+    pub fn new(year: u64) -> Result<RoshHashanah, AppError> {
+        let molad = HebrewYear::new(year)?.get_molad(HebrewMonth::Tishrei)?;
+        let time = molad.get_day_utc().naive_utc();
+        let chalakim = molad.get_chalakim();
+        // The day of the molad starts at 6 in the evening.
+        let day_of_molad = time + Duration::hours(6);
+        let parts = day_of_molad.hour() * CHALAKIM_PER_HOUR
+            + day_of_molad.minute() * (CHALAKIM_PER_HOUR / 60)
+            + chalakim as u32;
+        let leap =
+            |year: u64| -> Result<bool, AppError> { Ok(HebrewYear::new(year)?.is_leap_year()) };
+        let weekday = day_of_molad.weekday();
+        let mut dechiyos = vec![];
+        if parts >= 18 * CHALAKIM_PER_HOUR {
+            dechiyos.push(Dechiyah::MoladZaken);
+        } else if weekday == Weekday::Tue && parts >= 9 * CHALAKIM_PER_HOUR + 204 && !leap(year)? {
+            dechiyos.push(Dechiyah::Gatarad);
+        } else if weekday == Weekday::Mon
+            && parts >= 15 * CHALAKIM_PER_HOUR + 589
+            && year > 1
+            && leap(year - 1)?
+        {
+            dechiyos.push(Dechiyah::Betutakpat);
+        }
+        let postponed = day_of_molad.date() + Duration::days(dechiyos.len() as i64);
+        if matches!(
+            postponed.weekday(),
+            Weekday::Sun | Weekday::Wed | Weekday::Fri
+        ) {
+            dechiyos.push(Dechiyah::LoAdu);
+        }
+        Ok(RoshHashanah {
+            year,
+            molad: (time, chalakim),
+            molad_weekday: weekday,
+            molad_hours: parts / CHALAKIM_PER_HOUR,
+            molad_chalakim: parts % CHALAKIM_PER_HOUR,
+            dechiyos,
+            date: civil_date(year, HebrewMonth::Tishrei, 1)?,
+        })
+    }
+}
+
+/// Why a year has its length, and Cheshvan and Kislev theirs.
+pub struct Explanation {
+    pub rosh_hashanah: RoshHashanah,
+    pub next_rosh_hashanah: RoshHashanah,
+    pub length: i64,
+    pub leap: bool,
+    pub cheshvan: i8,
+    pub kislev: i8,
+}
+
+impl Explanation {
+    pub fn new(year: u64) -> Result<Explanation, AppError> {
+        let rosh_hashanah = RoshHashanah::new(year)?;
+        let next_rosh_hashanah = RoshHashanah::new(year + 1)?;
+        Ok(Explanation {
+            length: (next_rosh_hashanah.date - rosh_hashanah.date).num_days(),
+            leap: HebrewYear::new(year)?.is_leap_year(),
+            cheshvan: days_in_month(year, HebrewMonth::Cheshvan)?,
+            kislev: days_in_month(year, HebrewMonth::Kislev)?,
+            rosh_hashanah,
+            next_rosh_hashanah,
+        })
+    }
+
+    /// The length of a regular year, 354 days, or of a regular leap year, 384, in which
+    /// Cheshvan has 29 days and Kislev 30.
+    pub fn regular_length(&self) -> i64 {
+        if self.leap {
+            384
+        } else {
+            354
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RoshHashanahJson {
+    year: u64,
+    molad: String,
+    molad_weekday: String,
+    molad_hours: u32,
+    molad_chalakim: u32,
+    dechiyos: Vec<Dechiyah>,
+    date: String,
+}
+
+/// The explanation in JSON, with the molad and dechiyos of both Rosh Hashanahs.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplanationJson {
+    rosh_hashanah: RoshHashanahJson,
+    next_rosh_hashanah: RoshHashanahJson,
+    length: i64,
+    regular_length: i64,
+    cheshvan: i8,
+    kislev: i8,
+}
+
+impl RoshHashanah {
+    fn json(&self) -> RoshHashanahJson {
+        let (molad, _) = self.molad;
+        RoshHashanahJson {
+            year: self.year,
+            molad: molad.format("%Y-%m-%dT%H:%M").to_string(),
+            molad_weekday: format!("{:?}", self.molad_weekday),
+            molad_hours: self.molad_hours,
+            molad_chalakim: self.molad_chalakim,
+            dechiyos: self.dechiyos.clone(),
+            date: self.date.format("%Y-%m-%d").to_string(),
+        }
+    }
+
+    /// The molad like a luach writes it, such as `Tuesday, 5 hours and 204 chalakim`.
+    fn molad_text(&self, language: Language) -> String {
+        match language {
+            Language::English => format!(
+                "{}, {} hours and {} chalakim",
+                self.molad_weekday_name(),
+                self.molad_hours,
+                self.molad_chalakim
+            ),
+            Language::Hebrew => format!(
+                "יום {}, {} שעות ו{} חלקים",
+                hebrew_numeral(self.molad_weekday.number_from_sunday()),
+                self.molad_hours,
+                self.molad_chalakim
+            ),
+        }
+    }
+
+    fn molad_weekday_name(&self) -> String {
+        NaiveDate::from_isoywd(2020, 1, self.molad_weekday)
+            .format("%A")
+            .to_string()
+    }
+
+    fn dechiyos_text(&self, language: Language) -> String {
+        self.dechiyos
+            .iter()
+            .map(|x| x.name(language))
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+}
+
+impl Explanation {
+    pub fn json(&self) -> ExplanationJson {
+        ExplanationJson {
+            rosh_hashanah: self.rosh_hashanah.json(),
+            next_rosh_hashanah: self.next_rosh_hashanah.json(),
+            length: self.length,
+            regular_length: self.regular_length(),
+            cheshvan: self.cheshvan,
+            kislev: self.kislev,
+        }
+    }
+
+    pub fn pretty_print<W: Write>(
+        &self,
+        language: Language,
+        date: &dyn Fn(NaiveDate) -> String,
+        lock: &mut W,
+    ) -> std::io::Result<()> {
+        let rosh_hashanah = &self.rosh_hashanah;
+        let next = &self.next_rosh_hashanah;
+        let difference = self.length - self.regular_length();
+        match language {
+            Language::English => {
+                writeln!(lock, "Explanation:")?;
+                writeln!(
+                    lock,
+                    "Molad of Tishrei: {} ({} {} and {} Chalakim)",
+                    rosh_hashanah.molad_text(language),
+                    date(rosh_hashanah.molad.0.date()),
+                    rosh_hashanah.molad.0.format("%-H:%M"),
+                    rosh_hashanah.molad.1
+                )?;
+                if rosh_hashanah.dechiyos.is_empty() {
+                    writeln!(
+                        lock,
+                        "No dechiyah: Rosh Hashanah is on the day of the molad"
+                    )?;
+                }
+                for dechiyah in &rosh_hashanah.dechiyos {
+                    writeln!(
+                        lock,
+                        "{}: {}",
+                        dechiyah.name(language),
+                        dechiyah.reason(language)
+                    )?;
+                }
+                writeln!(lock, "Rosh Hashanah: {}", date(rosh_hashanah.date))?;
+                write!(
+                    lock,
+                    "Next Rosh Hashanah: {}, from the molad on {}",
+                    date(next.date),
+                    next.molad_text(language)
+                )?;
+                if next.dechiyos.is_empty() {
+                    writeln!(lock)?;
+                } else {
+                    writeln!(lock, ", postponed by {}", next.dechiyos_text(language))?;
+                }
+                let months = match difference {
+                    -1 => "one day less, so Kislev has 29 days",
+                    0 => "as many, so Cheshvan has 29 days and Kislev 30",
+                    _ => "one day more, so Cheshvan has 30 days",
+                };
+                writeln!(
+                    lock,
+                    "Cheshvan and Kislev: a {} has {} days, with 29 in Cheshvan and 30 in Kislev; this year has {} days, {}",
+                    if self.leap { "leap year" } else { "regular year" },
+                    self.regular_length(),
+                    self.length,
+                    months
+                )
+            }
+            Language::Hebrew => {
+                writeln!(lock, "הסבר:")?;
+                writeln!(
+                    lock,
+                    "מולד תשרי: {} ({} {} ו {} חלקים)",
+                    rosh_hashanah.molad_text(language),
+                    date(rosh_hashanah.molad.0.date()),
+                    rosh_hashanah.molad.0.format("%-H:%M"),
+                    rosh_hashanah.molad.1
+                )?;
+                if rosh_hashanah.dechiyos.is_empty() {
+                    writeln!(lock, "אין דחייה: ראש השנה ביום המולד")?;
+                }
+                for dechiyah in &rosh_hashanah.dechiyos {
+                    writeln!(
+                        lock,
+                        "{}: {}",
+                        dechiyah.name(language),
+                        dechiyah.reason(language)
+                    )?;
+                }
+                writeln!(lock, "ראש השנה: {}", date(rosh_hashanah.date))?;
+                write!(
+                    lock,
+                    "ראש השנה הבא: {}, ממולד {}",
+                    date(next.date),
+                    next.molad_text(language)
+                )?;
+                if next.dechiyos.is_empty() {
+                    writeln!(lock)?;
+                } else {
+                    writeln!(lock, ", נדחה ב{}", next.dechiyos_text(language))?;
+                }
+                let months = match difference {
+                    -1 => "יום אחד פחות, ולכן בכסלו כ\"ט ימים",
+                    0 => "כמותה, ולכן בחשון כ\"ט ימים ובכסלו ל'",
+                    _ => "יום אחד יותר, ולכן בחשון ל' ימים",
+                };
+                writeln!(
+                    lock,
+                    "חשון וכסלו: ב{} {} ימים, כ\"ט בחשון ול' בכסלו; בשנה זו {} ימים, {}",
+                    if self.leap {
+                        "שנה מעוברת"
+                    } else {
+                        "שנה פשוטה"
+                    },
+                    self.regular_length(),
+                    self.length,
+                    months
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dechiyos_fix_rosh_hashanah() {
+        // The dechiyos, applied to the molad, give the day the calendar has.
+        for year in 3764..7000 {
+            let rosh_hashanah = RoshHashanah::new(year).unwrap();
+            let molad_day = (rosh_hashanah.molad.0 + Duration::hours(6)).date();
+            assert_eq!(
+                rosh_hashanah.date,
+                molad_day + Duration::days(rosh_hashanah.dechiyos.len() as i64),
+                "{}",
+                year
+            );
+        }
+    }
+
+    #[test]
+    fn months() {
+        for year in 5700..5900 {
+            let explanation = Explanation::new(year).unwrap();
+            assert_eq!(
+                explanation.cheshvan + explanation.kislev - 59,
+                (explanation.length - explanation.regular_length()) as i8
+            );
+        }
+        // 5785 started on Thursday, the day of its molad, and 5786 on Tuesday, from a molad on
+        // Monday afternoon.
+        let explanation = Explanation::new(5785).unwrap();
+        assert_eq!(explanation.rosh_hashanah.molad_weekday, Weekday::Thu);
+        assert!(explanation.rosh_hashanah.dechiyos.is_empty());
+        assert_eq!(explanation.length, 355);
+        assert_eq!(explanation.cheshvan, 30);
+        assert_eq!(
+            explanation.next_rosh_hashanah.dechiyos,
+            vec![Dechiyah::MoladZaken]
+        );
+    }
+}
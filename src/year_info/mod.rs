@@ -0,0 +1,406 @@
+use crate::algorithms::arithmetic::days_in_year;
+use crate::args::types::{AppError, Language, MainArgs, OutputType, YearInfoArgs};
+use crate::gematria::hebrew_numeral;
+use crate::prelude::constants::check_hebrew_year;
+use crate::prelude::numbers::english_ordinal;
+use crate::prelude::print::torah_reading;
+use crate::prelude::{print_output, write_data, write_error, CountingWriter, Printable, Runnable};
+use chrono::prelude::*;
+use chrono::Duration;
+use heca_lib::prelude::{HebrewMonth, Location, MonthSchedule, TorahReading};
+use heca_lib::{HebrewDate, HebrewYear};
+use serde::Serialize;
+use std::io::Write;
+use std::num::NonZeroI8;
+
+pub mod doubled;
+pub mod explain;
+
+use doubled::{Doubling, DoublingJson};
+use explain::{Explanation, ExplanationJson};
+
+const WEEKDAY_LETTERS: [char; 7] = ['א', 'ב', 'ג', 'ד', 'ה', 'ו', 'ז'];
+
+/// What the header of a luach says about its Hebrew year.
+pub struct YearInfo {
+    pub year: u64,
+    pub year_type: MonthSchedule,
+    /// The Gregorian date of the first day of Rosh Hashanah.
+    pub rosh_hashanah: NaiveDate,
+    /// The Gregorian date of the first day of Pesach.
+    pub pesach: NaiveDate,
+    /// The amount of days in the year.
+    pub length: i64,
+    /// Printed with `--explain`.
+    pub explanation: Option<Explanation>,
+    /// Printed with `--doubled`, for the location whose parshiyos they are.
+    pub doubled: Option<(Location, Vec<Doubling>)>,
+}
+
+/// The Gregorian date of the day of the Hebrew date.
+pub fn civil_date(year: u64, month: HebrewMonth, day: i8) -> Result<NaiveDate, AppError> {
+    let night: DateTime<Utc> =
+        HebrewDate::from_ymd(year, month, NonZeroI8::new(day).unwrap())?.into();
+    Ok((night + Duration::days(1)).naive_utc().date())
+}
+
+impl YearInfo {
+    pub fn new(year: u64) -> Result<YearInfo, AppError> {
+        let rosh_hashanah = civil_date(year, HebrewMonth::Tishrei, 1)?;
+        Ok(YearInfo {
+            year,
+            year_type: HebrewYear::new(year)?.year_type(),
+            rosh_hashanah,
+            pesach: civil_date(year, HebrewMonth::Nissan, 15)?,
+            length: days_in_year(year)?,
+            explanation: None,
+            doubled: None,
+        })
+    }
+
+    /// Leap years have 383 to 385 days, and regular years 353 to 355.
+    pub fn leap(&self) -> bool {
+        self.length > 355
+    }
+
+    /// The keviyah of the year, such as `בשה`: the day of the week of Rosh Hashanah, whether
+    /// Cheshvan and Kislev are both short (ח), regular (כ) or both full (ש), and the day of the
+    /// week of Pesach.
+    pub fn keviyah(&self) -> String {
+        let weekday =
+            |date: NaiveDate| WEEKDAY_LETTERS[date.weekday().num_days_from_sunday() as usize];
+        let months = match self.length % 10 {
+            3 => 'ח',
+            4 => 'כ',
+            _ => 'ש',
+        };
+        [weekday(self.rosh_hashanah), months, weekday(self.pesach)]
+            .iter()
+            .collect()
+    }
+
+    /// The keviyah and the kind of year, like `השא: a complete year of 355 days`, as
+    /// printed in the year headers of `list --headers`.
+    pub fn description(&self, language: Language) -> String {
+        match language {
+            Language::English => format!(
+                "{}: a {}{} year of {} days",
+                self.keviyah(),
+                match self.length % 10 {
+                    3 => "deficient",
+                    4 => "regular",
+                    _ => "complete",
+                },
+                if self.leap() { " leap" } else { "" },
+                self.length
+            ),
+            Language::Hebrew => format!(
+                "{}: שנה {} {}, {} ימים",
+                self.keviyah(),
+                if self.leap() {
+                    "מעוברת"
+                } else {
+                    "פשוטה"
+                },
+                match self.length % 10 {
+                    3 => "חסרה",
+                    4 => "כסדרה",
+                    _ => "שלמה",
+                },
+                self.length
+            ),
+        }
+    }
+}
+
+/// A holiday which is on the day of the week of a day of Pesach, by the mnemonic א"ת ב"ש
+/// (Orach Chaim 428:3): each day of Pesach is paired with a letter, as the letters of the
+/// alphabet are paired from both ends, and the letter starts the name of the holiday.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum AtBashHoliday {
+    TishaBav,
+    Shavuos,
+    RoshHashanah,
+    /// Kerias HaTorah, which is Simchas Torah in Chutz La'aretz.
+    SimchasTorah,
+    YomKippur,
+    Purim,
+}
+
+impl AtBashHoliday {
+    /// In the order of the days of Pesach they're paired with.
+    pub const ALL: [AtBashHoliday; 6] = [
+        AtBashHoliday::TishaBav,
+        AtBashHoliday::Shavuos,
+        AtBashHoliday::RoshHashanah,
+        AtBashHoliday::SimchasTorah,
+        AtBashHoliday::YomKippur,
+        AtBashHoliday::Purim,
+    ];
+
+    /// The pair of letters, like א"ת.
+    pub fn letters(self) -> &'static str {
+        match self {
+            AtBashHoliday::TishaBav => "א\"ת",
+            AtBashHoliday::Shavuos => "ב\"ש",
+            AtBashHoliday::RoshHashanah => "ג\"ר",
+            AtBashHoliday::SimchasTorah => "ד\"ק",
+            AtBashHoliday::YomKippur => "ה\"צ",
+            AtBashHoliday::Purim => "ו\"פ",
+        }
+    }
+
+    pub fn name(self, language: Language) -> &'static str {
+        match (self, language) {
+            (AtBashHoliday::TishaBav, Language::English) => "Tisha B'Av",
+            (AtBashHoliday::TishaBav, Language::Hebrew) => "תשעה באב",
+            (AtBashHoliday::Shavuos, Language::English) => "Shavuos",
+            (AtBashHoliday::Shavuos, Language::Hebrew) => "שבועות",
+            (AtBashHoliday::RoshHashanah, Language::English) => "Rosh Hashanah",
+            (AtBashHoliday::RoshHashanah, Language::Hebrew) => "ראש השנה",
+            (AtBashHoliday::SimchasTorah, Language::English) => "Simchas Torah",
+            (AtBashHoliday::SimchasTorah, Language::Hebrew) => "קריאת התורה",
+            (AtBashHoliday::YomKippur, Language::English) => "Yom Kippur",
+            (AtBashHoliday::YomKippur, Language::Hebrew) => "צום כיפור",
+            (AtBashHoliday::Purim, Language::English) => "Purim",
+            (AtBashHoliday::Purim, Language::Hebrew) => "פורים",
+        }
+    }
+}
+
+impl YearInfo {
+    /// The holidays on the days of the week of the first six days of Pesach, with their dates:
+    /// Purim before it, Shavuos and Tisha B'Av after it, and the Yamim Tovim of Tishrei of the
+    /// next year. Tisha B'Av is the 9th of Av even when the fast is postponed.
+    pub fn at_bash(&self) -> Result<Vec<(AtBashHoliday, NaiveDate)>, AppError> {
+        let adar = if self.leap() {
+            HebrewMonth::Adar2
+        } else {
+            HebrewMonth::Adar
+        };
+        AtBashHoliday::ALL
+            .iter()
+            .map(|&holiday| {
+                let date = match holiday {
+                    AtBashHoliday::TishaBav => civil_date(self.year, HebrewMonth::Av, 9)?,
+                    AtBashHoliday::Shavuos => civil_date(self.year, HebrewMonth::Sivan, 6)?,
+                    AtBashHoliday::RoshHashanah => {
+                        civil_date(self.year + 1, HebrewMonth::Tishrei, 1)?
+                    }
+                    AtBashHoliday::SimchasTorah => {
+                        civil_date(self.year + 1, HebrewMonth::Tishrei, 23)?
+                    }
+                    AtBashHoliday::YomKippur => {
+                        civil_date(self.year + 1, HebrewMonth::Tishrei, 10)?
+                    }
+                    AtBashHoliday::Purim => civil_date(self.year, adar, 14)?,
+                };
+                Ok((holiday, date))
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AtBashJson {
+    pesach_day: usize,
+    letters: &'static str,
+    holiday: AtBashHoliday,
+    date: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Json {
+    year: u64,
+    keviyah: String,
+    year_type: MonthSchedule,
+    length: i64,
+    leap: bool,
+    rosh_hashanah: String,
+    pesach: String,
+    at_bash: Vec<AtBashJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanation: Option<ExplanationJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doubled: Option<DoubledJson>,
+}
+
+#[derive(Serialize)]
+struct DoubledJson {
+    location: Location,
+    parshiyos: Vec<DoublingJson>,
+}
+
+impl YearInfo {
+    fn pretty_print<W: Write>(
+        &self,
+        args: &MainArgs,
+        at_bash: &[(AtBashHoliday, NaiveDate)],
+        lock: &mut W,
+    ) -> std::io::Result<()> {
+        let date = |date: NaiveDate| match args.language {
+            Language::English => date.format("%A %Y-%m-%d").to_string(),
+            Language::Hebrew => format!(
+                "יום {} {}",
+                hebrew_numeral(date.weekday().number_from_sunday()),
+                date.format("%Y-%m-%d")
+            ),
+        };
+        match args.language {
+            Language::English => {
+                writeln!(lock, "Year: {}", self.year)?;
+                writeln!(lock, "Keviyah: {}", self.keviyah())?;
+                writeln!(lock, "Length: {} days", self.length)?;
+                writeln!(
+                    lock,
+                    "Leap year: {}",
+                    if self.leap() { "yes" } else { "no" }
+                )?;
+                writeln!(lock, "Rosh Hashanah: {}", date(self.rosh_hashanah))?;
+                writeln!(lock, "Pesach: {}", date(self.pesach))?;
+                writeln!(lock, "At-Bash:")?;
+                for (day, (holiday, holiday_date)) in at_bash.iter().enumerate() {
+                    writeln!(
+                        lock,
+                        "{} day of Pesach ({}): {}, {}",
+                        english_ordinal(day as u32 + 1),
+                        holiday.letters(),
+                        holiday.name(args.language),
+                        date(*holiday_date)
+                    )?;
+                }
+                if let Some(explanation) = &self.explanation {
+                    explanation.pretty_print(args.language, &date, lock)?;
+                }
+                self.print_doubled(args.language, &date, lock)
+            }
+            Language::Hebrew => {
+                writeln!(lock, "שנה: {}", self.year)?;
+                writeln!(lock, "קביעות: {}", self.keviyah())?;
+                writeln!(lock, "אורך: {} ימים", self.length)?;
+                writeln!(
+                    lock,
+                    "שנה מעוברת: {}",
+                    if self.leap() { "כן" } else { "לא" }
+                )?;
+                writeln!(lock, "ראש השנה: {}", date(self.rosh_hashanah))?;
+                writeln!(lock, "פסח: {}", date(self.pesach))?;
+                writeln!(lock, "א\"ת ב\"ש:")?;
+                for (day, (holiday, holiday_date)) in at_bash.iter().enumerate() {
+                    writeln!(
+                        lock,
+                        "{} של פסח ({}): {}, {}",
+                        hebrew_numeral(day as u32 + 1),
+                        holiday.letters(),
+                        holiday.name(args.language),
+                        date(*holiday_date)
+                    )?;
+                }
+                if let Some(explanation) = &self.explanation {
+                    explanation.pretty_print(args.language, &date, lock)?;
+                }
+                self.print_doubled(args.language, &date, lock)
+            }
+        }
+    }
+}
+
+impl YearInfo {
+    fn print_doubled<W: Write>(
+        &self,
+        language: Language,
+        date: &dyn Fn(NaiveDate) -> String,
+        lock: &mut W,
+    ) -> std::io::Result<()> {
+        let (location, doubled) = match &self.doubled {
+            Some(doubled) => doubled,
+            None => return Ok(()),
+        };
+        match (language, location) {
+            (Language::English, Location::Israel) => {
+                writeln!(lock, "Doubled parshiyos in Israel:")?
+            }
+            (Language::English, Location::Chul) => {
+                writeln!(lock, "Doubled parshiyos in Chutz La'aretz:")?
+            }
+            (Language::Hebrew, Location::Israel) => writeln!(lock, "פרשיות מחוברות בארץ ישראל:")?,
+            (Language::Hebrew, Location::Chul) => writeln!(lock, "פרשיות מחוברות בחוץ לארץ:")?,
+        }
+        for doubling in doubled {
+            let name = torah_reading(TorahReading::Shabbos(doubling.parsha), language);
+            let reason = doubling.reason.reason(language);
+            match (language, doubling.date) {
+                (Language::English, Some(day)) => {
+                    writeln!(lock, "{}: together on {}; {}", name, date(day), reason)?
+                }
+                (Language::English, None) => writeln!(lock, "{}: separate; {}", name, reason)?,
+                (Language::Hebrew, Some(day)) => {
+                    writeln!(lock, "{}: מחוברות ב{}; {}", name, date(day), reason)?
+                }
+                (Language::Hebrew, None) => writeln!(lock, "{}: נפרדות; {}", name, reason)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Printable for YearInfo {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        let at_bash = self.at_bash()?;
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                let json = Json {
+                    year: self.year,
+                    keviyah: self.keviyah(),
+                    year_type: self.year_type,
+                    length: self.length,
+                    leap: self.leap(),
+                    rosh_hashanah: self.rosh_hashanah.format("%Y-%m-%d").to_string(),
+                    pesach: self.pesach.format("%Y-%m-%d").to_string(),
+                    at_bash: at_bash
+                        .iter()
+                        .enumerate()
+                        .map(|(day, (holiday, date))| AtBashJson {
+                            pesach_day: day + 1,
+                            letters: holiday.letters(),
+                            holiday: *holiday,
+                            date: date.format("%Y-%m-%d").to_string(),
+                        })
+                        .collect(),
+                    explanation: self.explanation.as_ref().map(Explanation::json),
+                    doubled: self
+                        .doubled
+                        .as_ref()
+                        .map(|(location, doubled)| DoubledJson {
+                            location: *location,
+                            parshiyos: doubled.iter().map(Doubling::json).collect(),
+                        }),
+                };
+                write_data(&mut lock, args.output_type, &json)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &at_bash, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
+
+impl Runnable for YearInfoArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        check_hebrew_year(self.year as i64)?;
+        let mut info = YearInfo::new(self.year)?;
+        if self.explain {
+            info.explanation = Some(Explanation::new(self.year)?);
+        }
+        if let Some(location) = self.doubled {
+            info.doubled = Some((location, doubled::doubled_parshiyos(self.year, location)?));
+        }
+        print_output(&info, args)?;
+        Ok(())
+    }
+}
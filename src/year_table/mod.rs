@@ -0,0 +1,86 @@
+use crate::args::types::{AppError, Language, MainArgs, OutputType, TableArgs};
+use crate::prelude::constants::check_hebrew_year;
+use crate::prelude::{
+    print_output, write_data, write_error, write_table, CountingWriter, Printable, Runnable,
+};
+use crate::year_info::YearInfo;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Year {
+    year: u64,
+    /// The Gregorian date of the first day of Rosh Hashanah.
+    rosh_hashanah: String,
+    /// The amount of days in the year.
+    length: i64,
+    leap: bool,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Table {
+    years: Vec<Year>,
+}
+
+fn get_year(year: u64) -> Result<Year, AppError> {
+    let info = YearInfo::new(year)?;
+    Ok(Year {
+        year,
+        rosh_hashanah: info.rosh_hashanah.format("%Y-%m-%d").to_string(),
+        length: info.length,
+        leap: info.leap(),
+    })
+}
+
+impl Runnable for TableArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        check_hebrew_year(self.from as i64)?;
+        check_hebrew_year(self.to as i64)?;
+        let years = (self.from..=self.to)
+            .into_par_iter()
+            .map(get_year)
+            .collect::<Result<Vec<Year>, AppError>>()?;
+        print_output(&Table { years }, args)?;
+        Ok(())
+    }
+}
+
+impl Table {
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        let (header, yes, no) = match args.language {
+            Language::English => (["Year", "Rosh Hashanah", "Days", "Leap"], "yes", "no"),
+            Language::Hebrew => (["שנה", "ראש השנה", "ימים", "מעוברת"], "כן", "לא"),
+        };
+        let rows: Vec<Vec<String>> =
+            std::iter::once(header.iter().map(|x| x.to_string()).collect())
+                .chain(self.years.iter().map(|year| {
+                    vec![
+                        year.year.to_string(),
+                        year.rosh_hashanah.clone(),
+                        year.length.to_string(),
+                        if year.leap { yes } else { no }.to_string(),
+                    ]
+                }))
+                .collect();
+        write_table(lock, &rows)
+    }
+}
+
+impl Printable for Table {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match args.output_type {
+            OutputType::JSON | OutputType::CBOR | OutputType::MessagePack => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet => {
+                self.pretty_print(args, &mut lock)
+            }
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
@@ -0,0 +1,265 @@
+use crate::algorithms::candle_lighting::City;
+use crate::algorithms::solar::{local_time, Sun};
+use crate::args::types::{
+    AppError, Language, MainArgs, OutputType, PolarFallback, TableFormat, Zman, ZmanimArgs,
+};
+use crate::prelude::constants::check_gregorian_year;
+use crate::prelude::print::zman_name;
+use crate::prelude::{
+    print_output, write_data, write_error, write_table, CountingWriter, Printable, Runnable,
+};
+use chrono::prelude::*;
+use chrono::Duration;
+use rayon::prelude::*;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use std::io::Write;
+
+/// The zmanim of a single day, in the order they were asked for. A zman is `None` if it doesn't
+/// happen on that day, which happens far from the equator.
+pub(crate) struct Day {
+    date: NaiveDate,
+    times: Vec<Option<DateTime<FixedOffset>>>,
+}
+
+pub(crate) struct Luach<'a> {
+    pub zmanim: &'a [Zman],
+    pub days: Vec<Day>,
+    pub table: Option<TableFormat>,
+}
+
+pub(crate) fn get_zman(
+    sun: &dyn Sun,
+    zman: Zman,
+    city: &City,
+    date: NaiveDate,
+    polar_fallback: PolarFallback,
+) -> Option<DateTime<FixedOffset>> {
+    let fallback = |midnight: NaiveDate| match polar_fallback {
+        PolarFallback::None => None,
+        PolarFallback::Midnight => Some(sun.solar_midnight(city.longitude, midnight)),
+    };
+    // Alos and misheyakir fall back to the midnight before them.
+    let morning = |degrees: f64| {
+        sun.morning_depression(city.latitude, city.longitude, date, degrees)
+            .or_else(|| fallback(date.pred()))
+            .map(|x| local_time(&city.time_zone, x))
+    };
+    let evening = |degrees: f64| {
+        sun.evening_depression(city.latitude, city.longitude, date, degrees)
+            .or_else(|| fallback(date))
+            .map(|x| local_time(&city.time_zone, x))
+    };
+    // A halachic hour is a twelfth of the time between sunrise and sunset, so these don't occur
+    // on days without either.
+    let hours_after_sunrise = |hours: f64| {
+        let sunrise = sun.sunrise(city, date)?;
+        let hour = (sun.sunset(city, date)? - sunrise).num_milliseconds() as f64 / 12.0;
+        Some(sunrise + Duration::milliseconds((hour * hours) as i64))
+    };
+    match zman {
+        Zman::AlosHashachar => morning(16.1),
+        Zman::Misheyakir => morning(11.5),
+        Zman::Sunrise => sun.sunrise(city, date),
+        Zman::SofZmanShma => hours_after_sunrise(3.0),
+        Zman::SofZmanTefillah => hours_after_sunrise(4.0),
+        // The sun is highest at chatzos even on days it doesn't rise or set.
+        Zman::Chatzos => hours_after_sunrise(6.0).or_else(|| {
+            Some(local_time(
+                &city.time_zone,
+                sun.transit(city.longitude, date),
+            ))
+        }),
+        Zman::MinchaGedola => hours_after_sunrise(6.5),
+        Zman::MinchaKetana => hours_after_sunrise(9.5),
+        Zman::PlagHamincha => hours_after_sunrise(10.75),
+        Zman::Sunset => sun.sunset(city, date),
+        Zman::TzeisHakochavim => evening(8.5),
+        Zman::SofZmanAchilasChametz => hours_after_sunrise(4.0),
+        Zman::SofZmanBiurChametz => hours_after_sunrise(5.0),
+    }
+}
+
+pub(crate) fn get_day(
+    sun: &dyn Sun,
+    city: &City,
+    date: NaiveDate,
+    zmanim: &[Zman],
+    polar_fallback: PolarFallback,
+) -> Day {
+    let times = zmanim
+        .iter()
+        .map(|zman| get_zman(sun, *zman, city, date, polar_fallback))
+        .collect();
+    Day { date, times }
+}
+
+impl Runnable for ZmanimArgs {
+    fn run(&self, args: &MainArgs) -> Result<(), AppError> {
+        check_gregorian_year(self.year as i64)?;
+        // Every month is generated in parallel.
+        let months: Vec<Vec<Day>> = (1..13u32)
+            .into_par_iter()
+            .map(|month| {
+                let mut days = vec![];
+                let mut date = NaiveDate::from_ymd(self.year, month, 1);
+                while date.month() == month {
+                    days.push(get_day(
+                        self.solar_algorithm.sun(),
+                        &self.city,
+                        date,
+                        &self.zmanim,
+                        self.polar_fallback,
+                    ));
+                    date = date.succ();
+                }
+                days
+            })
+            .collect();
+        let luach = Luach {
+            zmanim: &self.zmanim,
+            days: months.into_iter().flatten().collect(),
+            table: self.table,
+        };
+        print_output(&luach, args)?;
+        Ok(())
+    }
+}
+
+fn format_time(time: &Option<DateTime<FixedOffset>>, missing: &str) -> String {
+    match time {
+        Some(time) => format!("{}:{:02}", time.hour(), time.minute()),
+        None => missing.into(),
+    }
+}
+
+impl Luach<'_> {
+    fn pretty_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        for day in &self.days {
+            write!(
+                lock,
+                "{}/{}/{}:",
+                day.date.year(),
+                day.date.month(),
+                day.date.day()
+            )?;
+            for (index, (zman, time)) in self.zmanim.iter().zip(&day.times).enumerate() {
+                let separator = if index == 0 { " " } else { ", " };
+                write!(
+                    lock,
+                    "{}{} {}",
+                    separator,
+                    zman_name(*zman, args.language),
+                    format_time(time, "-")
+                )?;
+            }
+            writeln!(lock)?;
+        }
+        Ok(())
+    }
+
+    /// Returns a row for every day, after a row with the names of the zmanim.
+    pub(crate) fn rows(&self, language: Language) -> Vec<Vec<String>> {
+        let date_header = match language {
+            Language::English => "Date",
+            Language::Hebrew => "תאריך",
+        };
+        let mut rows: Vec<Vec<String>> = vec![std::iter::once(date_header.to_string())
+            .chain(
+                self.zmanim
+                    .iter()
+                    .map(|zman| zman_name(*zman, language).to_string()),
+            )
+            .collect()];
+        for day in &self.days {
+            rows.push(
+                std::iter::once(format!(
+                    "{}/{}/{}",
+                    day.date.year(),
+                    day.date.month(),
+                    day.date.day()
+                ))
+                .chain(day.times.iter().map(|time| format_time(time, "-")))
+                .collect(),
+            );
+        }
+        rows
+    }
+
+    fn table_print<W: Write>(&self, args: &MainArgs, lock: &mut W) -> std::io::Result<()> {
+        write_table(lock, &self.rows(args.language))
+    }
+
+    fn csv_print<W: Write>(&self, lock: &mut W) -> std::io::Result<()> {
+        write!(lock, "date")?;
+        for zman in self.zmanim {
+            write!(lock, ",{}", zman.arg_name())?;
+        }
+        writeln!(lock)?;
+        for day in &self.days {
+            write!(lock, "{}", day.date.format("%Y-%m-%d"))?;
+            for time in &day.times {
+                match time {
+                    Some(time) => write!(lock, ",{}", time.format("%H:%M"))?,
+                    None => write!(lock, ",")?,
+                }
+            }
+            writeln!(lock)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Luach<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.days.len()))?;
+        for day in &self.days {
+            seq.serialize_element(&DayJson {
+                zmanim: self.zmanim,
+                day,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+struct DayJson<'a> {
+    zmanim: &'a [Zman],
+    day: &'a Day,
+}
+
+impl Serialize for DayJson<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.zmanim.len() + 1))?;
+        map.serialize_entry("date", &self.day.date.format("%Y-%m-%d").to_string())?;
+        for (zman, time) in self.zmanim.iter().zip(&self.day.times) {
+            map.serialize_entry(zman, &time.map(|time| time.to_rfc3339()))?;
+        }
+        map.end()
+    }
+}
+
+impl Printable for Luach<'_> {
+    fn print(&self, args: &MainArgs, out: &mut dyn Write) -> Result<usize, AppError> {
+        let mut lock = CountingWriter::new(out);
+        match (args.output_type, self.table) {
+            (OutputType::JSON | OutputType::CBOR | OutputType::MessagePack, _) => {
+                write_data(&mut lock, args.output_type, self)
+            }
+            (_, Some(TableFormat::Csv)) => self.csv_print(&mut lock),
+            (_, Some(TableFormat::Pretty)) => self.table_print(args, &mut lock),
+            (
+                OutputType::Pretty | OutputType::Regular | OutputType::ICS | OutputType::Parquet,
+                None,
+            ) => self.pretty_print(args, &mut lock),
+        }
+        .map_err(write_error)?;
+        Ok(lock.count)
+    }
+}
@@ -0,0 +1,86 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn bar_mitzvah() {
+    // A Thursday reads on the Shabbos after it, which is Shabbos Shekalim.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("bar-mitzvah")
+        .arg("--date")
+        .arg("2026-02-12");
+    cmd.assert().success().stdout(concat!(
+        "Shabbos 2026/2/14: Mishpatim\n",
+        "Torah reading: Exodus 21:1-24:18 (118 verses)\n",
+        "Rishon: Exodus 21:1-21:19 (19 verses)\n",
+        "Sheni: Exodus 21:20-22:3 (21 verses)\n",
+        "Shlishi: Exodus 22:4-22:26 (23 verses)\n",
+        "Revi'i: Exodus 22:27-23:5 (9 verses)\n",
+        "Chamishi: Exodus 23:6-23:19 (14 verses)\n",
+        "Shishi: Exodus 23:20-23:25 (6 verses)\n",
+        "Shevi'i: Exodus 23:26-24:18 (26 verses)\n",
+        "Maftir: Exodus 30:11-30:16 (6 verses) - Parshas Shekalim\n",
+        "Haftarah: II Kings 12:1-12:17 - Parshas Shekalim\n",
+        "\n",
+        "Last read: Mishpatim on 2025/2/22\n",
+    ));
+}
+
+#[test]
+fn bar_mitzvah_doubled() {
+    // Vayakhel and Pikudei were read apart the year before.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("bar-mitzvah")
+        .arg("--date")
+        .arg("5786-Adar-25");
+    let out: serde_json::Value = serde_json::from_slice(&cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(out["shabbos"], "2026-03-14");
+    assert_eq!(out["parsha"], "VayakhelPikudei");
+    let leining = &out["leining"];
+    assert_eq!(leining["torah"]["verses"], "Exodus 35:1-40:38");
+    assert_eq!(leining["torah"]["count"], 214);
+    assert_eq!(leining["aliyos"], serde_json::Value::Null);
+    assert_eq!(leining["maftirOccasion"], "HaChodesh");
+    assert_eq!(
+        leining["practice"],
+        serde_json::json!([
+            {"parsha": "Vayakhel", "date": "2025-03-22"},
+            {"parsha": "Pikudei", "date": "2025-03-29"},
+        ])
+    );
+
+    // The second of the three haftaros of affliction is read on Rosh Chodesh Av.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("bar-mitzvah")
+        .arg("--date")
+        .arg("2025-07-26");
+    let out: serde_json::Value = serde_json::from_slice(&cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(out["leining"]["maftirOccasion"], "RoshChodeshAv");
+    assert_eq!(
+        out["leining"]["haftarah"],
+        serde_json::json!(["Jeremiah 2:4-2:28", "Jeremiah 3:4"])
+    );
+    assert_eq!(out["leining"]["haftarahOccasion"], serde_json::Value::Null);
+}
+
+#[test]
+fn bar_mitzvah_yom_tov() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("bar-mitzvah")
+        .arg("--date")
+        .arg("2026-04-04");
+    cmd.assert()
+        .success()
+        .stdout("Shabbos 2026/4/4: 3rd day of Pesach\nThere's no parsha on this Shabbos\n");
+}
@@ -0,0 +1,72 @@
+use assert_cmd::Command;
+
+#[test]
+fn batch_answers_every_line() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("batch")
+        .write_stdin(concat!(
+            r#"{"cmd":"list","year":5785,"show":["shabbos"],"month":"Kislev"}"#,
+            "\n\n",
+            r#"{"cmd":"list","year":5784,"years":2,"location":"Israel"}"#,
+            "\n",
+        ));
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    let kislev = lines[0].as_array().unwrap();
+    assert_eq!(kislev.len(), 4);
+    assert_eq!(kislev[0]["name"], "Vayetzei");
+    let years = lines[1].as_array().unwrap();
+    assert_eq!(years[0]["day"], "2023-09-15T18:00:00Z");
+    assert_eq!(
+        years.last().unwrap()["uid"],
+        "yomtov-shavuos1-5785-sivan-6-israel@heca"
+    );
+}
+
+#[test]
+fn batch_answers_bad_requests_with_errors() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("batch")
+        .write_stdin(concat!(
+            r#"{"cmd":"convert"}"#,
+            "\n",
+            r#"{"cmd":"list","year":"nope"}"#,
+            "\n",
+            r#"{"cmd":"list","year":5785}"#,
+            "\n",
+        ));
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let lines: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines[0]["error"]["code"], "E104");
+    assert_eq!(lines[1]["error"]["error"], "year must be a number");
+    assert!(lines[2].is_array());
+}
+
+#[test]
+fn batch_needs_data_output() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("batch")
+        .write_stdin("");
+    cmd.assert().failure();
+}
@@ -207,6 +207,35 @@ fn candle_lighting_shabbos_always_wins() {
     }
 }
 
+#[test]
+fn candle_lighting_minutes_from_config() {
+    let list = |config: &str| -> Vec<serde_json::Value> {
+        let out = Command::cargo_bin(env!("CARGO_PKG_NAME"))
+            .unwrap()
+            .args(["--config", config, "--print", "json", "list", "5785"])
+            .args(["--show=shabbos", "--city", "Jerusalem"])
+            .output()
+            .unwrap();
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        serde_json::from_slice(&out.stdout).unwrap()
+    };
+    let built_in = list("/dev/null");
+    let community = list("tests/candle_lighting_config.toml");
+    assert_eq!(built_in.len(), community.len());
+    for (built_in, community) in built_in.iter().zip(&community) {
+        assert_eq!(built_in["candleLightingMinutes"], 40);
+        assert_eq!(community["candleLightingMinutes"], 20);
+        let time = |day: &serde_json::Value| {
+            DateTime::parse_from_rfc3339(day["candleLighting"].as_str().unwrap()).unwrap()
+        };
+        assert_eq!(time(community) - time(built_in), Duration::minutes(20));
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 struct ResHeca {
     day: String,
@@ -223,3 +252,70 @@ struct ResSemantic {
     candle_lighting_hour: u8,
     candle_lighting_minute: u8,
 }
+
+#[test]
+fn candle_lighting_before_clocks_change() {
+    // The clocks change on Sunday at 2:00, so Shabbos is still on standard time in the spring
+    // and on daylight saving time in the fall.
+    for (city, month, expected) in &[
+        (
+            "Chicago",
+            "3",
+            "Night of 2025/3/7: Tetzaveh. Candle lighting 17:31. Havdalah 18:31",
+        ),
+        (
+            "LosAngeles",
+            "3",
+            "Night of 2025/3/7: Tetzaveh. Candle lighting 17:37. Havdalah 18:37",
+        ),
+        (
+            "Chicago",
+            "10",
+            "Night of 2025/10/31: Lech Lecha. Candle lighting 17:28. Havdalah 18:26",
+        ),
+        (
+            "LosAngeles",
+            "10",
+            "Night of 2025/10/31: Lech Lecha. Candle lighting 17:43. Havdalah 18:41",
+        ),
+    ] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("list")
+            .arg("2025")
+            .arg("--gmonth")
+            .arg(month)
+            .arg("--city")
+            .arg(city)
+            .arg("--show")
+            .arg("shabbos");
+        let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+        assert!(
+            stdout.lines().any(|x| x == *expected),
+            "{} not in {}",
+            expected,
+            stdout
+        );
+    }
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--city")
+        .arg("Chicago")
+        .arg("--zmanim")
+        .arg("sunset");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_slice(&cmd.output().unwrap().stdout).unwrap();
+    for (date, sunset) in &[
+        ("2025-03-08", "2025-03-08T17:49:27-06:00"),
+        ("2025-03-09", "2025-03-09T18:50:36-05:00"),
+        ("2025-11-01", "2025-11-01T17:44:13-05:00"),
+        ("2025-11-02", "2025-11-02T16:42:58-06:00"),
+    ] {
+        let day = res.iter().find(|x| x["date"] == *date).unwrap();
+        assert_eq!(day["Sunset"], *sunset);
+    }
+}
@@ -0,0 +1,63 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn catchup() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("catchup")
+        .arg("--behind")
+        .arg("3")
+        .arg("--pace")
+        .arg("2/day")
+        .arg("--date")
+        .arg("2025-01-05");
+    cmd.assert().success().stdout(
+        "2025/1/5: Sanhedrin 16-17\n2025/1/6: Sanhedrin 18-19\n2025/1/7: Sanhedrin 20-21\n\nBack on schedule on 2025/1/7, with Sanhedrin 21\n",
+    );
+
+    // The last day only learns until where the cycle is up to, and dapim of different
+    // masechtos aren't a range.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("catchup")
+        .arg("--behind")
+        .arg("3")
+        .arg("--pace")
+        .arg("3")
+        .arg("--date")
+        .arg("2024-12-20");
+    let out: serde_json::Value = serde_json::from_slice(&cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(out["rejoin"], "2024-12-21");
+    assert_eq!(out["days"], 2);
+    let units = |day: usize| -> Vec<String> {
+        out["events"][day]["units"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|x| format!("{} {}", x["topic"]["masechta"], x["topic"]["daf"]))
+            .collect()
+    };
+    assert_eq!(
+        units(0),
+        vec![
+            r#""BavaBatra" 175"#,
+            r#""BavaBatra" 176"#,
+            r#""Sanhedrin" 2"#
+        ]
+    );
+    assert_eq!(units(1), vec![r#""Sanhedrin" 3"#, r#""Sanhedrin" 4"#]);
+    assert_eq!(out["events"][0]["type"], "StudySession");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("catchup")
+        .arg("--behind")
+        .arg("3")
+        .arg("--pace")
+        .arg("1/day");
+    cmd.assert().failure();
+}
@@ -0,0 +1,71 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn check_date_three_weeks() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("check-date")
+        .arg("2026-07-14")
+        .arg("--for")
+        .arg("wedding");
+    cmd.assert().success().stdout(concat!(
+        "Restrictions on a wedding on 2026/7/14: the Three Weeks\n",
+        "Nearest dates without them: 2026/7/1 and 2026/7/26\n",
+    ));
+}
+
+#[test]
+fn check_date_sefirah() {
+    // Lag BaOmer is allowed by every custom, and the days after it only by the first.
+    let check = |date: &str, sefirah: &str| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("--print")
+            .arg("json")
+            .arg("check-date")
+            .arg(date)
+            .arg("--for")
+            .arg("wedding")
+            .arg("--sefirah")
+            .arg(sefirah);
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["restrictions"].as_array().unwrap().len()
+    };
+    // Lag BaOmer 5786 is on 2026/5/5.
+    assert_eq!(check("2026-05-05", "first-days"), 0);
+    assert_eq!(check("2026-05-05", "whole"), 0);
+    assert_eq!(check("2026-05-06", "first-days"), 0);
+    assert_eq!(check("2026-05-06", "last-days"), 1);
+    assert_eq!(check("2026-04-12", "last-days"), 0);
+    assert_eq!(check("2026-04-13", "last-days"), 0);
+    assert_eq!(check("2026-04-13", "first-days"), 1);
+}
+
+#[test]
+fn check_date_chol_hamoed() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("check-date")
+        .arg("5787-Tishrei-17")
+        .arg("--for")
+        .arg("wedding")
+        .arg("--location")
+        .arg("Israel");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json["restrictions"],
+        serde_json::json!([{ "type": "CholHamoed", "day": "Sukkos3" }])
+    );
+    // Shmini Atzeres is on Shabbos, so in Israel the next day is free.
+    assert_eq!(json["nearest"]["after"], "2026-10-04");
+}
@@ -88,7 +88,7 @@ fn convert_adar2_in_regular_year_json() -> Result<(), Box<dyn std::error::Error>
         .arg("convert")
         .arg("4-adar2-5750");
     cmd.assert().failure().stderr(predicate::str::contains(
-        r#"{"type":"ConversionError","error":"IsNotLeapYear"}"#,
+        r#"{"type":"ConversionError","error":"IsNotLeapYear","code":"E209""#,
     ));
 
     Ok(())
@@ -105,7 +105,7 @@ fn convert_regular_in_leap_year_json() -> Result<(), Box<dyn std::error::Error>>
         .arg("convert")
         .arg("4-adar-5752");
     cmd.assert().failure().stderr(predicate::str::contains(
-        r#"{"type":"ConversionError","error":"IsLeapYear"}"#,
+        r#"{"type":"ConversionError","error":"IsLeapYear","code":"E209""#,
     ));
 
     Ok(())
@@ -125,6 +125,35 @@ fn convert_regular_in_leap_year_no_json() -> Result<(), Box<dyn std::error::Erro
     Ok(())
 }
 
+#[test]
+fn limits() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--print").arg("json").arg("limits");
+    cmd.assert().success().stdout(predicate::str::contains(
+        r#"{"hebrew":{"min":3764,"max":265000},"gregorian":{"min":4,"max":261000}}"#,
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--print").arg("json").arg("list").arg("3763");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        r#""type":"YearOutOfRange","error":"Year 3763 is out of range. Supported years are 3764 to 265000 (see `heca limits`)","min":3764,"max":265000,"got":3763,"code":"E211""#,
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("list").arg("265000").arg("--years").arg("2");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Year 265001 is out of range"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("convert").arg("1/Tishrei/999999");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Year 999999 is out of range"));
+
+    Ok(())
+}
+
 #[test]
 fn convert_year_too_small_json() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
@@ -135,9 +164,9 @@ fn convert_year_too_small_json() -> Result<(), Box<dyn std::error::Error>> {
         .arg("json")
         .arg("convert")
         .arg("0/1/2");
-    cmd.assert().failure().stderr(predicate::str::contains(
-        r#"{"type":"ConversionError","error":"YearTooSmall"}"#,
-    ));
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains(r#""type":"YearOutOfRange""#));
 
     Ok(())
 }
@@ -150,7 +179,7 @@ fn convert_year_too_small_no_json() -> Result<(), Box<dyn std::error::Error>> {
         .arg("convert")
         .arg("0/1/2");
     cmd.assert().failure().stderr(predicate::str::contains(
-        r#"Cannot build calendar for years below 3764 (After Creation)"#,
+        r#"Year 0 is out of range. Supported years are 4 to 261000"#,
     ));
 
     Ok(())
@@ -198,7 +227,7 @@ fn convert_day_too_large_json() -> Result<(), Box<dyn std::error::Error>> {
         .arg("convert")
         .arg("5/1/33");
     cmd.assert().failure().stderr(predicate::str::contains(
-        r#"{"type":"InvalidGregorianDay","error":"5/1/33"}"#,
+        r#"{"type":"InvalidGregorianDay","error":"5/1/33","code":"E208""#,
     ));
 
     Ok(())
@@ -217,3 +246,124 @@ fn convert_day_too_large_no_json() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn convert_json_context() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--print")
+        .arg("json")
+        .arg("convert")
+        .arg("25-kislev-5785");
+    let output = cmd.output()?;
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(res["input"]["month"], "Kislev");
+    assert_eq!(
+        res["gregorian"],
+        serde_json::json!(["2024-12-25T18:00:00Z", "2024-12-26T18:00:00Z"])
+    );
+    assert_eq!(res["dayOfWeek"], "Thursday");
+    assert_eq!(res["yearType"], "HaShA");
+    assert_eq!(res["leapYear"], false);
+    assert_eq!(res["yearLength"], 355);
+    assert_eq!(res["parsha"]["name"], "Miketz");
+    assert_eq!(res["holidays"][0]["name"], "Chanukah1");
+
+    // The eighth day of Pesach is only a holiday outside of Israel.
+    for (location, holidays) in &[("Chul", 1), ("Israel", 0)] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+        cmd.arg("--print")
+            .arg("json")
+            .arg("convert")
+            .arg("2025/4/20")
+            .arg("--location")
+            .arg(location);
+        let output = cmd.output()?;
+        let res: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        assert_eq!(res["input"], "2025-04-20");
+        assert_eq!(res["hebrew"][0]["day"], 22);
+        assert_eq!(res["holidays"].as_array().unwrap().len(), *holidays);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn convert_hebrew_numerals() -> Result<(), Box<dyn std::error::Error>> {
+    for date in &["ט\"ו/שבט/תשפ\"ו", "ט״ו/שבט/ה׳תשפ״ו", "15/שבט/5786"] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+        cmd.arg("convert").arg(date);
+        cmd.assert().success().stdout(predicate::str::starts_with(
+            "15 Shvat 5786 -> From sunset Sunday February 1 2026",
+        ));
+    }
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("convert").arg("אב/שבט/תשפ\"ו");
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[test]
+fn convert_error_in_hebrew() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--language")
+        .arg("he")
+        .arg("convert")
+        .arg("2025/2/30");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "2025/2/30 (שנה/חודש/יום) אינו תאריך לועזי תקין",
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--language")
+        .arg("he")
+        .arg("--print")
+        .arg("json")
+        .arg("convert")
+        .arg("2025/2/30");
+    let output = cmd.output()?;
+    assert!(!output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stderr)?;
+    assert_eq!(res["type"], "InvalidGregorianDay");
+    assert_eq!(res["code"], "E208");
+    assert_eq!(
+        res["message"],
+        "2025/2/30 (שנה/חודש/יום) אינו תאריך לועזי תקין"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn convert_day_not_in_month() -> Result<(), Box<dyn std::error::Error>> {
+    // Cheshvan has 30 days in 5785, and 29 in 5786.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--language")
+        .arg("en_US")
+        .arg("convert")
+        .arg("30-cheshvan-5785");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--language")
+        .arg("en_US")
+        .arg("convert")
+        .arg("30-cheshvan-5786");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "Cheshvan 5786 has 29 days, so there's no 30 Cheshvan 5786",
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+    cmd.arg("--language")
+        .arg("en_US")
+        .arg("--print")
+        .arg("json")
+        .arg("convert")
+        .arg("30-cheshvan-5786");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        r#""year":5786,"month":"Cheshvan","day":30,"days":29,"code":"E212""#,
+    ));
+
+    Ok(())
+}
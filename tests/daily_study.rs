@@ -1,5 +1,5 @@
 use assert_cmd::prelude::CommandCargoExt;
-use chrono::{Duration, NaiveDate};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Weekday};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::process::Command;
@@ -41,6 +41,79 @@ fn yerushalmi_yomi_test() {
     );
 }
 
+#[test]
+fn chumash_test() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=chumash");
+    let s = &String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    let res: Vec<ChumashRes> = serde_json::from_str(s).unwrap();
+    let get = |day: &str| &res.iter().find(|x| x.day == day).unwrap().topic;
+    assert_eq!(
+        get("2025-04-26T18:00:00Z"),
+        &ChumashTopic {
+            parshiyos: vec!["Tazriya".into(), "Metzorah".into()],
+            aliyah: 1
+        }
+    );
+    assert_eq!(
+        get("2025-05-02T18:00:00Z"),
+        &ChumashTopic {
+            parshiyos: vec!["Tazriya".into(), "Metzorah".into()],
+            aliyah: 7
+        }
+    );
+    assert_eq!(
+        get("2024-10-05T18:00:00Z"),
+        &ChumashTopic {
+            parshiyos: vec!["VezosHaberachah".into()],
+            aliyah: 1
+        }
+    );
+    assert_eq!(
+        get("2024-10-24T18:00:00Z"),
+        &ChumashTopic {
+            parshiyos: vec!["Bereishis".into()],
+            aliyah: 6
+        }
+    );
+}
+
+#[test]
+fn daf_yomi_transliteration_test() {
+    for (transliteration, expected) in &[
+        ("sephardi", "2024/1/1: Bava Kamma 60"),
+        ("ashkenazi", "2024/1/1: Bava Kamma 60"),
+        ("abbreviated", "2024/1/1: B.K. 60"),
+        ("hebrew", "2024/1/1: בבא קמא 60"),
+    ] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--language")
+            .arg("en_US")
+            .arg("--transliteration")
+            .arg(transliteration)
+            .arg("list")
+            .arg("2024")
+            .arg("--show=daf-yomi");
+        let s = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+        assert_eq!(s.lines().next().unwrap(), *expected);
+    }
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--language")
+        .arg("en_US")
+        .arg("--transliteration")
+        .arg("ashkenazi")
+        .arg("list")
+        .arg("2024")
+        .arg("--show=daf-yomi");
+    let s = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(s.contains(": Bava Basra 2\n"));
+    assert!(!s.contains("Bava Batra"));
+}
+
 #[test]
 fn daf_yomi_test() {
     let hebcal_daf_yomi = include_str!("daf_yomi.txt");
@@ -343,3 +416,165 @@ pub struct RambamTopic {
     chapter: u8,
     halacha: String,
 }
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+pub struct ChumashRes {
+    day: String,
+    topic: ChumashTopic,
+}
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+pub struct ChumashTopic {
+    parshiyos: Vec<String>,
+    aliyah: u8,
+}
+
+#[test]
+fn daf_hashavua() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("2020")
+        .arg("--show")
+        .arg("daf-hashavua");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "2020/1/5: Daf Hashavua: Berakhot 2");
+    assert_eq!(lines[1], "2020/1/12: Daf Hashavua: Berakhot 3");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show")
+        .arg("daf-hashavua")
+        .arg("--week-start")
+        .arg("shabbos");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    assert!(res.iter().all(|x| x["type"] == "DafHashavua"
+        && DateTime::parse_from_rfc3339(x["day"].as_str().unwrap())
+            .unwrap()
+            .weekday()
+            == Weekday::Fri));
+    assert_eq!(res[0]["topic"]["masechta"], "Eruvin");
+    assert_eq!(res[0]["topic"]["daf"], 31);
+}
+
+#[test]
+fn daf_yomi_cycle_switches() {
+    // The first day of every cycle, including the switch to the 22 dafim of Shekalim in 1975.
+    for (year, month, day) in &[
+        (1931, 2, 3),
+        (1968, 1, 30),
+        (1975, 6, 24),
+        (1982, 11, 25),
+        (2012, 8, 3),
+        (2020, 1, 5),
+    ] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("list")
+            .arg(year.to_string())
+            .arg("--gmonth")
+            .arg(month.to_string())
+            .arg("--show")
+            .arg("daf-yomi");
+        let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+        let first_day = format!("{}/{}/{}: Berakhot 2", year, month, day);
+        let last_day = format!("{}/{}/{}: Niddah 73", year, month, day - 1);
+        let lines: Vec<&str> = stdout.lines().collect();
+        let first = lines.iter().position(|x| *x == first_day);
+        assert!(first.is_some(), "{} not in {}", first_day, stdout);
+        assert_eq!(lines[first.unwrap() - 1], last_day);
+    }
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("1923")
+        .arg("--gmonth")
+        .arg("9")
+        .arg("--show")
+        .arg("daf-yomi");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.starts_with("1923/9/11: Berakhot 2\n"));
+}
+
+#[test]
+fn sefaria_links() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5786")
+        .arg("--show")
+        .arg("daf-yomi,chumash,rambam-1-chapter,yom-tov")
+        .arg("--links")
+        .arg("csv");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "date,name,link");
+    // Only the daily study is printed, and every day of it has a link.
+    assert_eq!(lines.len(), 1 + 3 * 354);
+    assert!(lines[1..]
+        .iter()
+        .all(|x| x.contains(",https://www.sefaria.org/")));
+    assert!(lines.contains(&"2025-09-23,Zevachim 9,https://www.sefaria.org/Zevachim.9"));
+    assert!(lines.contains(
+        &"2025-10-12,Chumash: Vezos Haberachah - Rishon,https://www.sefaria.org/Deuteronomy.33.1-7"
+    ));
+    // A doubled parsha has a link to each of its parshiyos.
+    assert!(lines.contains(&"2026-03-08,Chumash: Vayakhel/Pikudei - Rishon,https://www.sefaria.org/Exodus.35.1-20 https://www.sefaria.org/Exodus.38.21-31"));
+    assert!(lines
+        .contains(&"2025-09-23,Creditor and Debtor 4,https://www.sefaria.org/Mishneh_Torah%2C_Creditor_and_Debtor.4"));
+
+    // Three chapters of the Rambam in two halachos have a link to each, and the parts of its
+    // introduction, which Sefaria divides into mitzvos, link to the whole part. Sefaria doesn't
+    // number the Yerushalmi by daf, so the Yerushalmi Yomi links to the masechta.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5785")
+        .arg("--show")
+        .arg("rambam-3-chapters,yerushalmi-yomi")
+        .arg("--links")
+        .arg("csv");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    for expected in &[
+        "2024-10-03,Offerings for Those with Incomplete Atonement 3 - Offerings for Those with Incomplete Atonement 5,https://www.sefaria.org/Mishneh_Torah%2C_Offerings_for_Those_with_Incomplete_Atonement.3-5",
+        "2024-10-05,Substitution 4 - Defilement by a Corpse 2,https://www.sefaria.org/Mishneh_Torah%2C_Substitution.4 https://www.sefaria.org/Mishneh_Torah%2C_Defilement_by_a_Corpse.1-2",
+        "2025-03-01,Positive Mitzvot 1 - Positive Mitzvot 3,https://www.sefaria.org/Mishneh_Torah%2C_Positive_Mitzvot",
+        "2024-10-03,Jerusalem Talmud Yoma 22,https://www.sefaria.org/Jerusalem_Talmud_Yoma",
+    ] {
+        assert!(
+            stdout.lines().any(|line| line == *expected),
+            "{} not in {}",
+            expected,
+            stdout
+        );
+    }
+    assert!(stdout.contains(
+        "2024-11-20,Jerusalem Talmud Ta'anit 1,https://www.sefaria.org/Jerusalem_Talmud_Taanit\n"
+    ));
+
+    // Tamid is learned after Meilah.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5787")
+        .arg("--show")
+        .arg("daf-yomi")
+        .arg("--links")
+        .arg("json");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let tamid = res
+        .iter()
+        .find(|x| x["topic"]["masechta"] == "Meilah" && x["topic"]["daf"] == 26)
+        .unwrap();
+    assert_eq!(
+        tamid["links"],
+        serde_json::json!(["https://www.sefaria.org/Tamid.26"])
+    );
+}
@@ -0,0 +1,111 @@
+use assert_cmd::prelude::*;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn write_calendar(name: &str, config: &str, args: &[&str]) -> PathBuf {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg(config)
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5782")
+        .arg("--show=shabbos")
+        .args(args);
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let path = std::env::temp_dir().join(format!("heca_diff_{}_{}.json", std::process::id(), name));
+    std::fs::write(&path, output.stdout).unwrap();
+    path
+}
+
+#[test]
+fn diff_calendars() {
+    // In 5782, the eighth day of Pesach was on Shabbos, so Chutz La'aretz was a week behind
+    // Israel until Matos and Maasei.
+    let chul = write_calendar("chul", "/dev/null", &["--location", "Chul"]);
+    let israel = write_calendar("israel", "/dev/null", &["--location", "Israel"]);
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("diff-calendars").arg(&chul).arg(&israel);
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    for expected in &[
+        "+ 2022/7/23: Matos",
+        "- 2022/7/30: Matos/Maasei",
+        "> 2022/4/30 -> 2022/4/23: Acharei Mos",
+        "2 added, 1 removed, 13 moved, 0 changed",
+    ] {
+        assert!(
+            stdout.lines().any(|line| line == *expected),
+            "{} not in {}",
+            expected,
+            stdout
+        );
+    }
+
+    // Events are titled in the language, like in list.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--language")
+        .arg("he")
+        .arg("diff-calendars")
+        .arg(&chul)
+        .arg(&israel);
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.starts_with("+ 2022/7/23: מטות\n"), "{}", stdout);
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("diff-calendars")
+        .arg(&chul)
+        .arg(&chul);
+    let res: serde_json::Value =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    for kind in &["added", "removed", "moved", "changed"] {
+        assert_eq!(res[kind].as_array().unwrap().len(), 0);
+    }
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("diff-calendars")
+        .arg(&chul)
+        .arg("/calendar_does_not_exist.json");
+    cmd.assert().failure();
+
+    std::fs::remove_file(chul).unwrap();
+    std::fs::remove_file(israel).unwrap();
+}
+
+#[test]
+fn diff_calendars_candle_lighting_minutes() {
+    // The config lights candles 20 minutes before sunset in Jerusalem, instead of 40.
+    let forty = write_calendar("forty", "/dev/null", &["--city", "Jerusalem"]);
+    let twenty = write_calendar(
+        "twenty",
+        "tests/candle_lighting_config.toml",
+        &["--city", "Jerusalem"],
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("diff-calendars").arg(&forty).arg(&twenty);
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(
+        stdout.ends_with("0 added, 0 removed, 0 moved, 52 changed\n"),
+        "{}",
+        stdout
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("diff-calendars")
+        .arg(&forty)
+        .arg(&twenty);
+    let res: serde_json::Value =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let changed = &res["changed"][0];
+    assert_eq!(changed["old"]["candleLightingMinutes"], 40);
+    assert_eq!(changed["new"]["candleLightingMinutes"], 20);
+
+    std::fs::remove_file(forty).unwrap();
+    std::fs::remove_file(twenty).unwrap();
+}
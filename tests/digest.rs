@@ -0,0 +1,55 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn digest_email() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("digest")
+        .arg("--week")
+        .arg("--date")
+        .arg("2025-01-08")
+        .arg("--city")
+        .arg("Chicago")
+        .arg("--to")
+        .arg("family@example.com");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let email = String::from_utf8(output.stdout).unwrap();
+    assert!(email.starts_with(
+        "To: family@example.com\nSubject: Week of Vayechi, 2025/1/5 to 2025/1/11\nMIME-Version: 1.0\n\
+         Content-Type: multipart/alternative; boundary=\"heca-digest-2025-01-05\"\n"
+    ));
+    assert!(email.contains("Content-Type: text/plain; charset=utf-8"));
+    assert!(email.contains("Content-Type: text/html; charset=utf-8"));
+    assert!(email.contains("\n2025/1/10: Tenth of Teves. Starts 5:48, ends 17:25\n"));
+    assert!(email.contains("Night of 2025/1/10: Vayechi. Candle lighting 16:22. Havdalah 17:22"));
+    assert!(email.contains("\n2025/1/5: Sanhedrin 19\n"));
+    assert!(email.contains("<li>2025/1/11: Sanhedrin 25</li>"));
+    assert!(email.contains("Zmanim in Chicago"));
+    assert!(email.ends_with("\n--heca-digest-2025-01-05--\n"));
+}
+
+#[test]
+fn digest_json() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("digest")
+        .arg("--week")
+        .arg("--date")
+        .arg("2025-01-11")
+        .arg("--show")
+        .arg("shabbos");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res["week"], "2025-01-05");
+    assert_eq!(res["reading"]["name"], "Vayechi");
+    assert_eq!(res["events"].as_array().unwrap().len(), 1);
+    assert!(res["study"].as_array().unwrap().is_empty());
+    assert!(res["zmanim"].is_null());
+}
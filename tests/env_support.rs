@@ -94,3 +94,142 @@ fn env_location_type_yomi_test() {
 
     assert_eq!(s1, s2);
 }
+
+#[test]
+fn ascii_output() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.env("LANG", "he_IL.ISO-8859-8")
+        .arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Tishrei");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.is_ascii());
+    assert!(stdout.starts_with("Night of 2024/10/2: 1st day of Rosh Hashanah"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--ascii")
+        .arg("--config")
+        .arg("./tests/sample_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=custom-holidays")
+        .arg("--icons");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.is_ascii());
+
+    // UTF-8 locales, and locales which don't name an encoding, are left alone.
+    for lang in &["he_IL.UTF-8", "C"] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.env("LANG", lang)
+            .arg("--language")
+            .arg("he")
+            .arg("list")
+            .arg("5785")
+            .arg("--month")
+            .arg("Tishrei");
+        let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+        assert!(!stdout.is_ascii());
+    }
+}
+
+#[test]
+fn language_from_locale() {
+    let convert = |vars: &[(&str, &str)]| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.env_clear();
+        for (key, value) in vars {
+            cmd.env(key, value);
+        }
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("convert")
+            .arg("2025-01-01");
+        let o = cmd.output().unwrap();
+        assert!(o.status.success());
+        String::from_utf8(o.stdout).unwrap()
+    };
+    let hebrew = "Wednesday January 1 2025: 1 טבת 5785 - 2 טבת 5785.\n";
+    let english = "Wednesday January 1 2025: From 1 Teves 5785 to 2 Teves 5785.\n";
+    assert_eq!(convert(&[("LANG", "he_IL.utf8")]), hebrew);
+    assert_eq!(
+        convert(&[("LANG", "en_US.UTF-8"), ("LC_ALL", "he_IL.UTF-8")]),
+        hebrew
+    );
+    assert_eq!(
+        convert(&[("LANG", "he_IL.UTF-8"), ("LC_MESSAGES", "C")]),
+        english
+    );
+    assert_eq!(convert(&[]), english);
+}
+
+#[test]
+fn options_from_env() {
+    let list = |vars: &[(&str, &str)], args: &[&str]| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.env_clear();
+        for (key, value) in vars {
+            cmd.env(key, value);
+        }
+        cmd.arg("--config").arg("/dev/null").args(args);
+        cmd.output().unwrap()
+    };
+    let tishrei = ["list", "5785", "--month", "Tishrei"];
+    let first_line = |o: std::process::Output| {
+        assert!(o.status.success());
+        String::from_utf8(o.stdout)
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .to_string()
+    };
+
+    assert_eq!(
+        first_line(list(&[("HECA_LANGUAGE", "he")], &tishrei)),
+        "ליל 2024/10/2: יום א' של ראש השנה. הדלקת נרות"
+    );
+    // Arguments come before the environment.
+    let mut args = vec!["--language", "en"];
+    args.extend(&tishrei);
+    assert_eq!(
+        first_line(list(&[("HECA_LANGUAGE", "he")], &args)),
+        "Night of 2024/10/2: 1st day of Rosh Hashanah. Candle lighting"
+    );
+    assert!(first_line(list(&[("HECA_OUTPUT", "json")], &tishrei)).starts_with("[{"));
+
+    // Options of the subcommand, and flags.
+    let mut args = tishrei.to_vec();
+    args.extend(&["--show", "shabbos"]);
+    assert_eq!(
+        first_line(list(&[("HECA_CITY", "Chicago")], &args)),
+        "Night of 2024/10/4: Haazinu. Candle lighting 18:09. Havdalah 19:06"
+    );
+    let iyar = [
+        "list",
+        "5784",
+        "--month",
+        "Iyar",
+        "--show",
+        "israeli-holidays",
+    ];
+    assert_eq!(
+        first_line(list(
+            &[("HECA_LOCATION", "Israel"), ("HECA_EXACT_DAYS", "1")],
+            &iyar
+        )),
+        "Night of 2024/5/11: Yom HaZikaron"
+    );
+
+    // Options the subcommand doesn't take, or which conflict with the arguments, are left out.
+    let o = list(&[("HECA_CITY", "Chicago")], &["convert", "2025-01-01"]);
+    assert!(o.status.success());
+    args.extend(&["--coordinates", "40,-73"]);
+    assert_eq!(
+        first_line(list(&[("HECA_CITY", "Chicago")], &args)),
+        "Night of 2024/10/4: Haazinu. Candle lighting 18:11. Havdalah 19:09"
+    );
+
+    let o = list(&[("HECA_LANGUAGE", "xx")], &tishrei);
+    assert!(!o.status.success());
+}
@@ -0,0 +1,137 @@
+use chrono::{Datelike, Weekday};
+use heca::args::types::{
+    CustomDate, CustomHoliday, DailyStudy, DayMonth, DayVal, Event, MinorDays, MinorHoliday, Name,
+    PolarFallback, SolarAlgorithm, TimeOfDay,
+};
+use heca::list::providers::{MinorHolidays, Omer};
+use heca::list::{get_events, EventFilter, HebrewYearExt};
+use heca::EventProvider;
+use heca_lib::prelude::{HebrewMonth, Location, TorahReadingType};
+use heca_lib::HebrewYear;
+use std::num::NonZeroI8;
+
+/// A provider defined outside of heca, listing the 25th of Elul.
+struct CreationOfTheWorld;
+
+impl EventProvider for CreationOfTheWorld {
+    fn get(&self, year: &HebrewYear) -> Vec<DayVal> {
+        let date = DayMonth {
+            month: HebrewMonth::Elul,
+            day: NonZeroI8::new(25).unwrap(),
+        };
+        vec![DayVal {
+            day: year.get_hebrew_date(date.month, date.day).unwrap().into(),
+            name: Name::CustomHoliday(Box::new(CustomHoliday {
+                printable: "Creation of the world".into(),
+                json: "CreationOfTheWorld".into(),
+                date: CustomDate::Hebrew(date),
+                if_not_exists: None,
+                from_year: None,
+                until_year: None,
+                origin: None,
+                count: None,
+                modifier: None,
+                source: None,
+            })),
+            candle_lighting: None,
+            torah_portion: None,
+            havdalah: vec![],
+            time_range: None,
+        }]
+    }
+}
+
+#[test]
+fn custom_event_provider() {
+    let minor_holidays = MinorHolidays {
+        city: None,
+        polar_fallback: PolarFallback::None,
+        solar_algorithm: SolarAlgorithm::Noaa,
+    };
+    let events = get_events(5780, 5782, &[&Omer, &minor_holidays, &CreationOfTheWorld]).unwrap();
+    let creation = events
+        .iter()
+        .filter(|x| matches!(&x.name, Name::CustomHoliday(c) if c.json == "CreationOfTheWorld"))
+        .map(|x| x.day.to_rfc3339())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        creation,
+        vec!["2020-09-13T18:00:00+00:00", "2021-09-01T18:00:00+00:00"]
+    );
+    assert_eq!(
+        events
+            .iter()
+            .filter(|x| matches!(x.name, Name::MinorDays(MinorDays::LagBaOmer)))
+            .count(),
+        2
+    );
+    assert_eq!(
+        events
+            .iter()
+            .filter(|x| matches!(&x.name, Name::MinorDays(day) if day.is_omer()))
+            .count(),
+        98
+    );
+}
+
+#[test]
+fn iter_events_stops_at_the_first_match() {
+    let filter = EventFilter::new(vec![Event::MinorHoliday(MinorHoliday::Minor)]);
+    // The first Lag BaOmer starting on Thursday night.
+    let lag_baomer = (5785..)
+        .flat_map(|year| {
+            HebrewYear::new(year)
+                .unwrap()
+                .iter_events(Location::Chul, &filter)
+                .unwrap()
+        })
+        .find(|x| {
+            matches!(x.name, Name::MinorDays(MinorDays::LagBaOmer))
+                && x.day.weekday() == Weekday::Thu
+        })
+        .unwrap();
+    assert_eq!(lag_baomer.day.to_rfc3339(), "2025-05-15T18:00:00+00:00");
+
+    let events: Vec<DayVal> = HebrewYear::new(5785)
+        .unwrap()
+        .iter_events(Location::Chul, &filter)
+        .unwrap()
+        .collect();
+    assert!(events.windows(2).all(|x| x[0].day <= x[1].day));
+}
+
+#[test]
+fn iter_events_lists_daily_study_with_the_rest() {
+    let filter = EventFilter::new(vec![
+        Event::TorahReadingType(TorahReadingType::YomTov),
+        Event::DailyStudy(DailyStudy::DafYomi),
+    ]);
+    let events: Vec<DayVal> = HebrewYear::new(5785)
+        .unwrap()
+        .iter_events(Location::Chul, &filter)
+        .unwrap()
+        .collect();
+    assert!(events.windows(2).all(|x| x[0].day <= x[1].day));
+    // A daf on every one of the 355 days of 5785, and the same Yom Tov as get_events lists.
+    let dafim = events
+        .iter()
+        .filter(|x| matches!(x.name, Name::DailyStudy(_)))
+        .count();
+    assert_eq!(dafim, 355);
+    let yom_tov: Vec<String> = events
+        .iter()
+        .filter(|x| matches!(x.name, Name::TorahReading(_)))
+        .map(|x| x.name.id())
+        .collect();
+    assert_eq!(yom_tov.first().unwrap(), "YomTov RoshHashanah1");
+    assert_eq!(yom_tov.last().unwrap(), "YomTov Shavuos2");
+}
+
+#[test]
+fn iter_events_rejects_zmanim() {
+    let filter = EventFilter::new(vec![Event::Zman(TimeOfDay::CandleLighting)]);
+    assert!(HebrewYear::new(5785)
+        .unwrap()
+        .iter_events(Location::Chul, &filter)
+        .is_err());
+}
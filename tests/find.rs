@@ -0,0 +1,125 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn find_erev_pesach_on_shabbos() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("find")
+        .arg("--event")
+        .arg("erev-pesach")
+        .arg("--weekday")
+        .arg("shabbos")
+        .arg("--limit")
+        .arg("3")
+        .arg("--from")
+        .arg("5786");
+    cmd.assert().success().stdout(concat!(
+        "5805: Erev Pesach, Saturday 2045-04-01\n",
+        "5808: Erev Pesach, Saturday 2048-03-28\n",
+        "5812: Erev Pesach, Saturday 2052-04-13\n",
+    ));
+}
+
+#[test]
+fn find_names() {
+    // The same event, by its English name, its JSON name and its Hebrew name.
+    for name in &["1st day of Pesach", "Pesach1", "יום א' של חג הפסח"] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("--print")
+            .arg("json")
+            .arg("find")
+            .arg("--event")
+            .arg(name)
+            .arg("--limit")
+            .arg("2")
+            .arg("--from")
+            .arg("5786");
+        let output = cmd.output().unwrap();
+        let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(
+            res["found"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|x| x["date"].as_str().unwrap())
+                .collect::<Vec<&str>>(),
+            vec!["2026-04-02", "2027-04-22"],
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn find_nothing() {
+    // Pesach is never on Monday, Wednesday or Friday.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("find")
+        .arg("--event")
+        .arg("pesach1")
+        .arg("--weekday")
+        .arg("monday")
+        .arg("--from")
+        .arg("5786")
+        .arg("--until")
+        .arg("5886");
+    cmd.assert()
+        .success()
+        .stdout("Not found from 5786 until 5886\n");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("find")
+        .arg("--event")
+        .arg("erev-nothing");
+    cmd.assert().failure();
+}
+
+#[test]
+fn find_unknown_event() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("find")
+        .arg("--event")
+        .arg("Purim Katan Gadol")
+        .arg("--from")
+        .arg("5786")
+        .arg("--until")
+        .arg("5790");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(res["code"], "E300");
+    assert_eq!(res["type"], "UnknownEvent");
+    assert_eq!(res["error"], "Purim Katan Gadol");
+}
+
+#[test]
+fn find_no_days() {
+    for limit in &["0", "three"] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("--print")
+            .arg("json")
+            .arg("find")
+            .arg("--event")
+            .arg("pesach1")
+            .arg("--limit")
+            .arg(limit);
+        let output = cmd.output().unwrap();
+        assert!(!output.status.success());
+        let res: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+        assert_eq!(res["type"], "ArgUndefinedError", "{}", limit);
+    }
+}
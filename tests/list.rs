@@ -146,6 +146,8 @@ static HEBCAL_TABLE: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     m.insert("ErevShavuos", "Erev Shavuot");
     m.insert("FifteenShvat", "Tu B'Shvat");
     m.insert("FifteenAv", "");
+    m.insert("BedikasChametz", "");
+    m.insert("BiurChametz", "");
     m.insert("LagBaOmer", "Lag B'Omer");
     m.insert("Omer1", "");
     m.insert("Omer2", "");
@@ -777,10 +779,12 @@ fn custom_day_check_and_avoid_crash_v1() {
         .arg("500")
         .arg("5750")
         .arg("--show=yom-tov,shabbos,special-parshas,chol,minor-holidays,omer,custom-holidays");
-    assert_eq!(
-        &String::from_utf8(cmd.output().unwrap().stderr).unwrap(),
-        ""
-    );
+    // The V1 format has no ifNotExists, so days that don't exist every year are warned about.
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().lines().all(|x| {
+        serde_json::from_str::<serde_json::Value>(x).unwrap()["warning"] == "SkippedCustomHoliday"
+    }));
 }
 
 #[test]
@@ -920,15 +924,1894 @@ fn just_shabbos_works_gregorian() {
     );
 }
 
-#[derive(Deserialize, Debug, Eq, PartialEq)]
-pub struct Res {
-    day: String,
-    name: String,
-    r#type: String,
+#[test]
+fn triennial_reading_cycle() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=shabbos")
+        .arg("--reading-cycle")
+        .arg("triennial");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let find = |name: &str| {
+        res.iter()
+            .find(|x| x["name"] == name)
+            .unwrap_or_else(|| panic!("{} not found", name))["torahPortion"]
+            .clone()
+    };
+    let haazinu = find("Haazinu");
+    assert_eq!(haazinu["triennialYear"], 2);
+    let bereishis = find("Bereishis");
+    assert_eq!(bereishis["fullKriah"][0], "Genesis 1:1-6:8");
+    assert_eq!(bereishis["triennialYear"], 3);
+    assert_eq!(bereishis["triennial"][0], "Genesis 3:22-6:8");
+    let tazriya_metzorah = find("TazriyaMetzorah");
+    assert_eq!(tazriya_metzorah["fullKriah"].as_array().unwrap().len(), 2);
+    assert_eq!(tazriya_metzorah["triennial"].as_array().unwrap().len(), 2);
 }
 
-#[derive(Deserialize, Debug, Eq, PartialEq)]
-struct Err {
-    error: String,
-    r#type: String,
+#[test]
+fn annual_reading_cycle_has_no_triennial_portion() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=shabbos")
+        .arg("--reading-cycle")
+        .arg("annual");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    for r in res.iter().filter(|x| x["type"] == "Shabbos") {
+        assert!(r["torahPortion"]["fullKriah"].is_array());
+        assert!(r["torahPortion"]["triennialYear"].is_null());
+    }
+}
+
+#[test]
+fn bilingual_output() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("--language")
+        .arg("en,he")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=shabbos");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let haazinu = res.iter().find(|x| x["name"]["en"] == "Haazinu").unwrap();
+    assert_eq!(haazinu["name"]["he"], "האזינו");
+    assert_eq!(haazinu["type"], "Shabbos");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--language")
+        .arg("en,he")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=shabbos");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(
+        out.lines().next().unwrap(),
+        "Night of 2024/10/4: Haazinu | האזינו. Candle lighting"
+    );
+}
+
+#[test]
+fn same_language_twice_is_not_bilingual() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("--language")
+        .arg("en,en_US")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=shabbos");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    assert_eq!(res[0]["name"], "Haazinu");
+}
+
+#[test]
+fn category_tags_and_filters() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,shabbos,chol,minor-holidays,daf-yomi");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let category = |name: &str| {
+        res.iter()
+            .find(|x| x["name"] == name)
+            .map(|x| x["category"].clone())
+            .unwrap()
+    };
+    assert_eq!(category("RoshHashanah1"), "yom_tov");
+    assert_eq!(category("TzomGedalia"), "fast");
+    assert_eq!(category("TaanisBechoros"), "fast");
+    assert_eq!(category("RoshChodeshCheshvan1"), "rosh_chodesh");
+    assert_eq!(category("Chanukah1"), "chol");
+    assert_eq!(category("Haazinu"), "parsha");
+    assert_eq!(category("LagBaOmer"), "minor");
+    assert!(res
+        .iter()
+        .filter(|x| x["type"] == "DafYomi")
+        .all(|x| x["category"] == "daily_study"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,chol,minor-holidays")
+        .arg("--category=fast");
+    let res: Vec<Res> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let names: Vec<&str> = res.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "TzomGedalia",
+            "TenTeves",
+            "TaanisEsther",
+            "TaanisBechoros",
+            "SeventeenTammuz",
+            "NineAv"
+        ]
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=chol")
+        .arg("--exclude-category=rosh_chodesh,fast");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    assert!(!res.is_empty());
+    assert!(res.iter().all(|x| x["category"] == "chol"));
+}
+
+#[test]
+fn merge_spans() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,chol,omer")
+        .arg("--merge-spans");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let spans: Vec<(&str, &str, &str)> = res
+        .iter()
+        .filter(|x| x["type"] == "Span")
+        .map(|x| {
+            (
+                x["name"].as_str().unwrap(),
+                x["day"].as_str().unwrap(),
+                x["end"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            ("Sukkos", "2024-10-16T18:00:00Z", "2024-10-23T18:00:00Z"),
+            ("Chanukah", "2024-12-25T18:00:00Z", "2025-01-02T18:00:00Z"),
+            ("Pesach", "2025-04-12T18:00:00Z", "2025-04-20T18:00:00Z"),
+            ("Omer", "2025-04-13T18:00:00Z", "2025-06-01T18:00:00Z"),
+        ]
+    );
+    assert!(res.iter().all(|x| x["name"] != "Pesach2"));
+    assert!(res.iter().any(|x| x["name"] == "ShminiAtzeres"));
+    let chanukah = res.iter().find(|x| x["name"] == "Chanukah").unwrap();
+    let nights = chanukah["nights"].as_array().unwrap();
+    assert_eq!(nights.len(), 8);
+    assert_eq!(nights[0]["day"], "2024-12-25T18:00:00Z");
+    assert_eq!(nights[7]["name"], "Chanukah8");
+    assert!(res
+        .iter()
+        .filter(|x| x["type"] == "Span" && x["name"] != "Chanukah")
+        .all(|x| x.get("nights").is_none()));
+}
+
+#[test]
+fn headers() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--years")
+        .arg("2")
+        .arg("--show=yom-tov")
+        .arg("--headers")
+        .arg("hebrew-year");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(
+        out.starts_with("5785 (השא: a complete year of 355 days)\nNight of 2024/10/2: 1st day of Rosh Hashanah. Candle lighting\n")
+    );
+    assert!(out.contains(
+        "Night of 2025/6/2: 2nd day of Shavuos. Candle lighting
+
+5786 (גכה: a regular year of 354 days)
+Night of 2025/9/22: 1st day of Rosh Hashanah. Candle lighting
+"
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov")
+        .arg("--headers")
+        .arg("gregorian-year");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(out.starts_with("2024\n"));
+    assert!(out.contains("Night of 2024/10/24: Simchas Torah. Candle lighting\n\n2025\n"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--language")
+        .arg("he")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov")
+        .arg("--headers")
+        .arg("month");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(out.starts_with("5785 (השא: שנה פשוטה שלמה, 355 ימים)\nתשרי 5785\n"));
+    assert_eq!(out.matches("\n\n").count(), 2);
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--years")
+        .arg("2")
+        .arg("--show=yom-tov")
+        .arg("--headers")
+        .arg("month");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(out.matches("5785 (השא").count(), 1);
+    assert!(out.contains(
+        "Night of 2025/6/2: 2nd day of Shavuos. Candle lighting
+
+5786 (גכה: a regular year of 354 days)
+Tishrei 5786
+"
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--language")
+        .arg("he")
+        .arg("list")
+        .arg("5784")
+        .arg("--show=yom-tov")
+        .arg("--headers")
+        .arg("hebrew-year");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(out.starts_with("5784 (זחג: שנה מעוברת חסרה, 383 ימים)\n"));
+}
+
+#[test]
+fn summary() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/shabbos_conflicts_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,chol,custom-holidays")
+        .arg("--summary");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(out.ends_with(
+        "
+
+Summary: 22 Yomim Tovim, 10 weekday readings, 5 fasts, 18 days of Rosh Chodesh, 3 custom events
+From 2024/10/3 until 2025/9/22
+"
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Kislev")
+        .arg("--show=chol")
+        .arg("--summary");
+    let res: serde_json::Value =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    assert_eq!(res["events"].as_array().unwrap().len(), 8);
+    assert_eq!(
+        res["meta"],
+        serde_json::json!({
+            "counts": { "chol": 6, "rosh_chodesh": 2 },
+            "from": "2024-12-02",
+            "until": "2024-12-31",
+        })
+    );
+}
+
+#[test]
+fn check() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/missing_custom_config.toml")
+        .arg("list")
+        .arg("5783")
+        .arg("--years")
+        .arg("4")
+        .arg("--show=yom-tov,custom-holidays")
+        .arg("--city")
+        .arg("Chicago")
+        .arg("--check");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Location: Chutz La'aretz
+City: Chicago (41.8781, -87.6298)
+Custom days: 2
+Hebrew years: 5783 to 5786
+Dates: 2022/9/26 to 2026/9/11
+Events: 88 Yomim Tovim, 6 custom events
+"
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("Warning: 30th of Cheshvan (30Cheshvan) was skipped in 5784, 5786"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--location")
+        .arg("Israel")
+        .arg("--show=yom-tov")
+        .arg("--check");
+    let res: serde_json::Value =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        serde_json::json!({
+            "location": "Israel",
+            "city": null,
+            "customDays": 0,
+            "firstHebrewYear": 5785,
+            "lastHebrewYear": 5785,
+            "counts": { "yom_tov": 19 },
+            "from": "2024-10-03",
+            "until": "2025-09-22",
+        })
+    );
+}
+
+#[test]
+fn year_not_a_number() {
+    for args in &[
+        &["abc"][..],
+        &["5785", "--years", "abc"],
+        &["5785", "--until", "abc"],
+    ] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("--print")
+            .arg("json")
+            .arg("list")
+            .args(*args);
+        let output = cmd.output().unwrap();
+        assert_eq!(output.status.code(), Some(1), "{:?}", args);
+        let res: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+        assert_eq!(res["type"], "YearIsNotANumber");
+        assert_eq!(res["code"], "E207");
+        assert_eq!(res["error"], "abc");
+    }
+}
+
+#[test]
+fn conflicting_args() {
+    let run = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("--print")
+            .arg("json")
+            .arg("list")
+            .arg("5785")
+            .args(args);
+        let output = cmd.output().unwrap();
+        assert!(!output.status.success(), "{:?}", args);
+        let res: Err = serde_json::from_str(&String::from_utf8(output.stderr).unwrap()).unwrap();
+        res
+    };
+    let res = run(&["--until", "2026"]);
+    assert_eq!(res.r#type, "InvalidYearRange");
+    assert_eq!(
+        res.error,
+        "--until 2026 looks like a Gregorian year, but 5785 is a Hebrew year. Pass --until a year of the same calendar, or use --years"
+    );
+    let res = run(&["--preset", "orthodox-diaspora", "--location", "Israel"]);
+    assert_eq!(res.r#type, "ConflictingArgs");
+    assert!(res.error.ends_with("Use --preset israel"));
+    let res = run(&["--preset", "israel", "--location", "Chul"]);
+    assert!(res.error.ends_with("Use --preset orthodox-diaspora"));
+
+    // Arguments which wouldn't change anything are only errors with --strict.
+    let res = run(&["--strict", "--exact-days"]);
+    assert_eq!(res.r#type, "ConflictingArgs");
+    assert_eq!(
+        res.error,
+        "--exact-days only changes the dates of israeli-holidays. Add israeli-holidays to --show, or don't pass it"
+    );
+    run(&["--strict", "--reading-cycle", "annual"]);
+    run(&["--strict", "--week-start", "shabbos"]);
+    run(&["--strict", "--havdalah-opinion", "72"]);
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5785")
+        .arg("--exact-days");
+    assert!(cmd.output().unwrap().status.success());
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5785")
+        .arg("--strict")
+        .arg("--show=shabbos")
+        .arg("--reading-cycle")
+        .arg("annual");
+    assert!(cmd.output().unwrap().status.success());
+}
+
+#[test]
+fn no_sort_order() {
+    let list = || {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("--print")
+            .arg("json")
+            .arg("list")
+            .arg("5780")
+            .arg("--years")
+            .arg("10")
+            .arg("--show=yom-tov,omer,daf-yomi")
+            .arg("--no-sort");
+        String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+    };
+    let out = list();
+    assert_eq!(out, list());
+    let res: Vec<serde_json::Value> = serde_json::from_str(&out).unwrap();
+    // Every year is listed after the one before it, with Yom Tov before the Omer and the Daf
+    // Yomi.
+    let rosh_hashanah: Vec<usize> = res
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| x["name"] == "RoshHashanah1")
+        .map(|(index, _)| index)
+        .collect();
+    assert_eq!(rosh_hashanah.len(), 10);
+    assert_eq!(rosh_hashanah[0], 0);
+    for year in rosh_hashanah.windows(2) {
+        let year = &res[year[0]..year[1]];
+        assert_eq!(year.iter().filter(|x| x["name"] == "Pesach1").count(), 1);
+        let first_omer = year.iter().position(|x| x["type"] == "MinorDays").unwrap();
+        let first_daf = year.iter().position(|x| x["type"] == "DafYomi").unwrap();
+        assert!(year[..first_omer].iter().all(|x| x["type"] == "YomTov"));
+        assert!(first_omer < first_daf);
+    }
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--no-sort")
+        .arg("--headers")
+        .arg("month");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    let res: Err = serde_json::from_str(&String::from_utf8(output.stderr).unwrap()).unwrap();
+    assert_eq!(res.r#type, "ConflictingArgs");
+}
+
+#[test]
+fn merge_rosh_chodesh() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--show=chol")
+        .arg("--merge-rosh-chodesh");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(out.contains(
+        "Night of 2025/1/29: Rosh Chodesh Shvat
+Night of 2025/2/27 until night of 2025/3/1: Rosh Chodesh Adar (2 days)
+"
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=chol")
+        .arg("--merge-rosh-chodesh");
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let teves = res.iter().find(|x| x["month"] == "Teves").unwrap();
+    assert_eq!(teves["type"], "Span");
+    assert_eq!(teves["name"], "RoshChodesh");
+    assert_eq!(teves["day"], "2024-12-30T18:00:00Z");
+    assert_eq!(teves["end"], "2025-01-01T18:00:00Z");
+    assert_eq!(teves["category"], "rosh_chodesh");
+    // A Rosh Chodesh of one day isn't merged.
+    assert!(res.iter().any(|x| x["name"] == "RoshChodeshShvat"));
+    assert!(res.iter().all(|x| x["name"] != "RoshChodeshTeves2"));
+}
+
+#[test]
+fn ics_output() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("ics")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov")
+        .arg("--merge-spans");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(out.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(out.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(
+        out.matches("BEGIN:VEVENT").count(),
+        out.matches("END:VEVENT").count()
+    );
+    assert!(out.lines().all(|x| x.len() <= 76));
+    assert!(out.contains(
+        "DTSTART;VALUE=DATE:20250413\r\nDTEND;VALUE=DATE:20250421\r\nSUMMARY:Pesach\r\n"
+    ));
+    assert!(out.contains(
+        "DTSTART;VALUE=DATE:20241003\r\nDTEND;VALUE=DATE:20241004\r\nSUMMARY:1st day of Rosh Hashanah\r\n"
+    ));
+}
+
+#[test]
+fn ics_time_zones() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("ics")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,zmanim:candles")
+        .arg("--city")
+        .arg("NewYorkCity");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(out.matches("BEGIN:VTIMEZONE").count(), 1);
+    assert!(out.contains("TZID:America/New_York\r\n"));
+    // Daylight saving time ends in November, and starts again in March, at 2:00 local time.
+    assert!(out.contains(
+        "BEGIN:DAYLIGHT\r\nDTSTART:20240930T200000\r\nTZOFFSETFROM:-0400\r\nTZOFFSETTO:-0400\r\n"
+    ));
+    assert!(out.contains(
+        "BEGIN:STANDARD\r\nDTSTART:20241103T020000\r\nTZOFFSETFROM:-0400\r\nTZOFFSETTO:-0500\r\n"
+    ));
+    assert!(out.contains(
+        "BEGIN:DAYLIGHT\r\nDTSTART:20250309T020000\r\nTZOFFSETFROM:-0500\r\nTZOFFSETTO:-0400\r\n"
+    ));
+    // Candle lighting is at the local time, and Yom Tov is still a day.
+    assert!(out.contains("DTSTART;TZID=America/New_York:20250411T19"));
+    assert!(out.contains("DTSTART;TZID=America/New_York:20241206T16"));
+    assert!(out.contains("DTSTART;VALUE=DATE:20250413\r\n"));
+    assert!(!out.contains("Z\r\nSUMMARY"));
+}
+
+#[test]
+fn ics_time_zone_changes() {
+    // Candle lighting on the Friday before the clocks change in Chicago, and on the Friday after.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("ics")
+        .arg("list")
+        .arg("2025")
+        .arg("--gmonth")
+        .arg("3")
+        .arg("--show=zmanim:candles")
+        .arg("--city")
+        .arg("Chicago");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    let time_zone = &out[out.find("BEGIN:VTIMEZONE").unwrap()..out.find("END:VTIMEZONE").unwrap()];
+    assert_eq!(
+        time_zone,
+        "BEGIN:VTIMEZONE\r\nTZID:America/Chicago\r\n\
+         BEGIN:STANDARD\r\nDTSTART:20250305T180000\r\nTZOFFSETFROM:-0600\r\nTZOFFSETTO:-0600\r\nEND:STANDARD\r\n\
+         BEGIN:DAYLIGHT\r\nDTSTART:20250309T020000\r\nTZOFFSETFROM:-0600\r\nTZOFFSETTO:-0500\r\nEND:DAYLIGHT\r\n"
+    );
+    assert!(out.contains("DTSTART;TZID=America/Chicago:20250307T1731"));
+    assert!(out.contains("DTSTART;TZID=America/Chicago:20250314T1839"));
+}
+
+#[test]
+fn ics_uids_are_stable() {
+    let uids = |output_type: &str, args: &[&str]| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print").arg(output_type).arg("list").args(args);
+        String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+    };
+    let out = uids("ics", &["5785", "--show=yom-tov", "--city", "Chicago"]);
+    assert!(out.contains("UID:yomtov-pesach1-5785-nissan-15-chul-chicago@heca\r\n"));
+    // Listing a longer range doesn't change the UIDs of the events in it.
+    let longer = uids(
+        "ics",
+        &[
+            "5784",
+            "--years",
+            "3",
+            "--show=yom-tov",
+            "--city",
+            "Chicago",
+        ],
+    );
+    assert!(longer.contains("UID:yomtov-pesach1-5785-nissan-15-chul-chicago@heca\r\n"));
+
+    let res: Vec<serde_json::Value> = serde_json::from_str(&uids(
+        "json",
+        &["5785", "--show=all", "--location", "Israel"],
+    ))
+    .unwrap();
+    let mut all: Vec<&str> = res.iter().map(|x| x["uid"].as_str().unwrap()).collect();
+    assert!(all.contains(&"dafyomi-5785-tishrei-1-israel@heca"));
+    assert!(all.contains(&"minordays-lagbaomer-5785-iyar-18-israel@heca"));
+    let count = all.len();
+    all.sort_unstable();
+    all.dedup();
+    assert_eq!(all.len(), count);
+}
+
+#[test]
+fn presets() {
+    let types = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print").arg("json").arg("list").arg("5785");
+        cmd.args(args);
+        let res: Vec<serde_json::Value> =
+            serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap())
+                .unwrap();
+        let mut types: Vec<String> = res
+            .iter()
+            .map(|x| x["type"].as_str().unwrap().to_string())
+            .collect();
+        types.sort();
+        types.dedup();
+        (res, types)
+    };
+
+    let (israel, israel_types) = types(&["--preset", "israel"]);
+    assert_eq!(
+        israel_types,
+        vec![
+            "Chol",
+            "IsraeliHoliday",
+            "MinorDays",
+            "Shabbos",
+            "ShabbosMevarchim",
+            "YomTov"
+        ]
+    );
+    // Israel only has one day of Shavuos.
+    assert!(israel.iter().all(|x| x["name"] != "Shavuos2"));
+
+    let (diaspora, _) = types(&["--preset", "orthodox-diaspora"]);
+    assert!(diaspora.iter().any(|x| x["name"] == "Shavuos2"));
+    assert!(diaspora.iter().all(|x| x["type"] != "IsraeliHoliday"));
+
+    let (_, combined) = types(&["--preset", "daily-study", "--show", "shabbos"]);
+    assert_eq!(
+        combined,
+        vec![
+            "Chumash",
+            "DafYomi",
+            "Rambam1Chapter",
+            "Rambam3Chapters",
+            "Shabbos",
+            "Yerushalmi"
+        ]
+    );
+
+    let (_, all) = types(&["--events", "all"]);
+    assert_eq!(all.len(), 14);
+}
+
+#[test]
+fn backwards_year_ranges() {
+    let list = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print")
+            .arg("json")
+            .arg("list")
+            .args(args)
+            .arg("--show=yom-tov");
+        let res: Vec<Res> =
+            serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap())
+                .unwrap();
+        res.into_iter()
+            .filter(|x| x.name == "RoshHashanah1")
+            .map(|x| x.day)
+            .collect::<Vec<String>>()
+    };
+    let expected = vec![
+        "2022-09-25T18:00:00Z",
+        "2023-09-15T18:00:00Z",
+        "2024-10-02T18:00:00Z",
+    ];
+    assert_eq!(list(&["5785", "--years", "-3"]), expected);
+    assert_eq!(list(&["5783", "--years", "3"]), expected);
+    assert_eq!(list(&["5785", "--until", "5783"]), expected);
+    assert_eq!(list(&["5783", "--until", "5785"]), expected);
+    assert_eq!(
+        list(&["2024", "--years", "-2"]),
+        vec!["2023-09-15T18:00:00Z", "2024-10-02T18:00:00Z"]
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5")
+        .arg("--years=-10");
+    let res: Err =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stderr).unwrap()).unwrap();
+    assert_eq!(res.r#type, "InvalidYearRange");
+}
+
+#[test]
+fn missing_custom_holiday_warns() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/missing_custom_config.toml")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5783")
+        .arg("--years")
+        .arg("4")
+        .arg("--show=custom-holidays");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let warning: serde_json::Value =
+        serde_json::from_str(&String::from_utf8(output.stderr).unwrap()).unwrap();
+    assert_eq!(warning["warning"], "SkippedCustomHoliday");
+    assert_eq!(
+        warning["message"],
+        "30th of Cheshvan (30Cheshvan) was skipped in 5784, 5786, since its date doesn't exist in those years. Add an ifNotExists to the config file to list it on another date"
+    );
+    let res: Vec<Res> = serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert_eq!(res.iter().filter(|x| x.name == "30Cheshvan").count(), 2);
+    assert_eq!(res.iter().filter(|x| x.name == "YudShvat").count(), 4);
+}
+
+#[test]
+fn missing_custom_holiday_strict() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/missing_custom_config.toml")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5783")
+        .arg("--years")
+        .arg("4")
+        .arg("--show=custom-holidays")
+        .arg("--strict");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    let res: Err = serde_json::from_str(&String::from_utf8(output.stderr).unwrap()).unwrap();
+    assert_eq!(res.r#type, "MissingCustomHoliday");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/missing_custom_config.toml")
+        .arg("--deny-warnings")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5783")
+        .arg("--years")
+        .arg("4")
+        .arg("--show=custom-holidays");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let res: Err = serde_json::from_str(&String::from_utf8(output.stderr).unwrap()).unwrap();
+    assert_eq!(res.r#type, "MissingCustomHoliday");
+}
+
+#[test]
+fn conflicting_custom_holidays() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/conflicting_custom_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=custom-holidays");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Warning: line 3: YudShvat has the same id as line 2
+Warning: line 4: TenthOfShvat is on the same date as YudShvat (line 2)
+Warning: line 5: LagBaOmer is the id of a built-in event
+"
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/conflicting_custom_config.toml")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=custom-holidays")
+        .arg("--strict");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    let res: Err = serde_json::from_str(&String::from_utf8(output.stderr).unwrap()).unwrap();
+    assert_eq!(res.r#type, "ConfigError");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/conflicting_custom_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov");
+    assert_eq!(String::from_utf8(cmd.output().unwrap().stderr).unwrap(), "");
+}
+
+#[test]
+fn shabbos_conflicts() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/shabbos_conflicts_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--years")
+        .arg("2")
+        .arg("--show=custom-holidays")
+        .arg("--warn-shabbos-conflicts");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().ends_with(
+        "
+Custom days on Shabbos or Yom Tov:
+2024/12/28: Community Dinner lands on Shabbos
+2025/6/3: Siyum lands on 2nd day of Shavuos
+2026/5/23: Siyum lands on Shabbos and 2nd day of Shavuos
+"
+    ));
+
+    // 7 Sivan isn't Yom Tov in Israel.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/shabbos_conflicts_config.toml")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--location")
+        .arg("Israel")
+        .arg("--show=custom-holidays")
+        .arg("--warn-shabbos-conflicts");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let warning: serde_json::Value =
+        serde_json::from_str(&String::from_utf8(output.stderr).unwrap()).unwrap();
+    assert_eq!(warning["warning"], "ShabbosConflict");
+    assert_eq!(
+        warning["message"],
+        "2024/12/28: Community Dinner lands on Shabbos"
+    );
+    let res: Vec<Res> = serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert_eq!(res.len(), 3);
+}
+
+#[test]
+fn custom_holiday_year_range() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/custom_year_range_config.toml")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5783")
+        .arg("--years")
+        .arg("4")
+        .arg("--show=custom-holidays");
+    let output = cmd.output().unwrap();
+    // Custom holidays on the same date in different years don't conflict.
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "");
+    let res: Vec<Res> = serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    let days = |name: &str| {
+        res.iter()
+            .filter(|x| x.name == name)
+            .map(|x| x.day.as_str())
+            .collect::<Vec<&str>>()
+    };
+    assert_eq!(
+        days("Birthday"),
+        vec!["2022-11-28T18:00:00Z", "2023-11-17T18:00:00Z"]
+    );
+    assert_eq!(
+        days("Yahrzeit"),
+        vec!["2024-12-05T18:00:00Z", "2025-11-24T18:00:00Z"]
+    );
+    assert_eq!(
+        days("Siyum"),
+        vec!["2024-01-10T18:00:00Z", "2025-01-29T18:00:00Z"]
+    );
+}
+
+#[test]
+fn anniversary_count() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/anniversary_config.toml")
+        .arg("list")
+        .arg("5784")
+        .arg("--years")
+        .arg("2")
+        .arg("--show=custom-holidays");
+    cmd.assert().success().stdout(
+        "Night of 2023/11/17: Yahrzeit (16th yahrzeit)
+Night of 2024/1/19: Birthday (12th birthday)
+Night of 2024/12/5: Yahrzeit (17th yahrzeit)
+Night of 2025/2/7: Birthday (bar mitzvah - 13th birthday)
+Night of 2025/5/27: Wedding (1st anniversary)
+",
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/anniversary_config.toml")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=custom-holidays");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    let counts = res
+        .iter()
+        .map(|x| (x["name"].as_str().unwrap(), x["count"].as_u64().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        counts,
+        vec![("Yahrzeit", 17), ("Birthday", 13), ("Wedding", 1)]
+    );
+}
+
+#[test]
+fn night_of_only_for_evening_events() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,minor-holidays,chol");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    for expected in &[
+        "2024/10/6: Tzom Gedalia",
+        "2024/10/11: Erev Yom Kippur",
+        "Night of 2024/10/11: Yom Kippur. Candle lighting",
+        "2025/4/10: Taanis Bechoros",
+        "Night of 2025/8/2: Ninth of Av",
+    ] {
+        assert!(
+            stdout.lines().any(|line| line == *expected),
+            "{} not in {}",
+            expected,
+            stdout
+        );
+    }
+}
+
+#[test]
+fn single_month() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Elul")
+        .arg("--show=daf-yomi");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 29);
+    assert_eq!(lines[0], "2025/8/25: Avodah Zarah 68");
+    assert_eq!(lines[28], "2025/9/22: Zevachim 8");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("2024")
+        .arg("--gmonth")
+        .arg("12")
+        .arg("--show=shabbos");
+    cmd.assert().success().stdout(
+        "Night of 2024/12/6: Vayetzei. Candle lighting
+Night of 2024/12/13: Vayishlach. Candle lighting
+Night of 2024/12/20: Vayeshev. Candle lighting
+Night of 2024/12/27: Miketz. Candle lighting
+",
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list").arg("2024").arg("--month").arg("Kislev");
+    cmd.assert()
+        .failure()
+        .stderr("--month needs a Hebrew year. Use --gmonth for a Gregorian month\n");
+}
+
+#[test]
+fn only_shabbos() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Kislev")
+        .arg("--only-shabbos")
+        .arg("--city")
+        .arg("Chicago");
+    cmd.assert().success().stdout(
+        "Night of 2024/12/6: Vayetzei. Candle lighting 16:02. Havdalah 17:01
+Night of 2024/12/13: Vayishlach. Candle lighting 16:02. Havdalah 17:02
+Night of 2024/12/20: Vayeshev. Candle lighting 16:05. Havdalah 17:04
+Night of 2024/12/27: Miketz; 3rd day of Chanukah; Shabbos Mevarchim Teves: Molad is on Monday December 30, 17:33 and 16 Chalakim. Candle lighting 16:09. Havdalah 17:09
+",
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list").arg("5785").arg("--only-shabbos");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(stdout.lines().count(), 51);
+    for expected in &[
+        "Night of 2024/10/4: Haazinu; Shabbos Shuva. Candle lighting",
+        "Night of 2024/10/11: Yom Kippur. Candle lighting",
+        "Night of 2025/2/7: Beshalach; Shabbos Shira. Candle lighting",
+        "Night of 2025/3/7: Tetzaveh; Parshas Zachor. Candle lighting",
+    ] {
+        assert!(
+            stdout.lines().any(|line| line == *expected),
+            "{} not in {}",
+            expected,
+            stdout
+        );
+    }
+}
+
+#[test]
+fn havdalah_opinions() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Tishrei")
+        .arg("--show=yom-tov,shabbos")
+        .arg("--city")
+        .arg("Jerusalem")
+        .arg("--havdalah-opinion")
+        .arg("42,8.5,rabbeinu-tam");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    // The second day of Rosh Hashanah is followed by Shabbos, so it has no havdalah.
+    for expected in &[
+        "Night of 2024/10/3: 2nd day of Rosh Hashanah. Candle lighting",
+        "Night of 2024/10/4: Haazinu. Candle lighting 17:40. Havdalah 19:00 (42 minutes), 18:54 (8.5 degrees), 19:30 (Rabbeinu Tam)",
+    ] {
+        assert!(
+            stdout.lines().any(|line| line == *expected),
+            "{} not in {}",
+            expected,
+            stdout
+        );
+    }
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Tishrei")
+        .arg("--show=yom-tov")
+        .arg("--city")
+        .arg("Chicago")
+        .arg("--havdalah-opinion")
+        .arg("72");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    let yom_kippur = res.iter().find(|x| x["name"] == "YomKippur").unwrap();
+    assert_eq!(yom_kippur["havdalah"][0]["opinion"], "Minutes72");
+    assert_eq!(
+        yom_kippur["havdalah"][0]["time"],
+        "2024-10-12T19:24:51-05:00"
+    );
+}
+
+#[test]
+fn icons() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Tishrei")
+        .arg("--show=yom-tov,daf-yomi")
+        .arg("--city")
+        .arg("Jerusalem")
+        .arg("--icons");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    for expected in &[
+        "Night of 2024/10/2: 🎉 1st day of Rosh Hashanah. 🕯 Candle lighting 17:43",
+        "2024/10/3: 📖 Bava Batra 100",
+    ] {
+        assert!(
+            stdout.lines().any(|line| line == *expected),
+            "{} not in {}",
+            expected,
+            stdout
+        );
+    }
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("ics")
+        .arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Tishrei")
+        .arg("--city")
+        .arg("Jerusalem")
+        .arg("--icons");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.contains("SUMMARY:🎉🕯🍷 Yom Kippur\r\n"));
+}
+
+#[test]
+fn avos_ubanim() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list").arg("5785").arg("--show").arg("avos-ubanim");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 24);
+    assert_eq!(lines[0], "Night of 2024/10/26: Avos Ubanim");
+    // Pesach starts on Motzei Shabbos, so the program ends the week before.
+    assert_eq!(lines[23], "Night of 2025/4/5: Avos Ubanim");
+}
+
+#[test]
+fn self_test() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--language").arg("he_IL").arg("self-test");
+    cmd.assert().success().stdout(
+        "ok: 5781 in Israel (Purim Meshulash)
+ok: 5782 in Chutz La'aretz (leap year)
+ok: 5785 in Chutz La'aretz
+ok: 5785 Daf Yomi
+",
+    );
+}
+
+#[test]
+fn verify_anchors() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print").arg("json").arg("verify-anchors");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert!(!res.is_empty());
+    assert!(res.iter().all(|x| x["ok"] == true));
+    assert!(res
+        .iter()
+        .any(|x| x["anchor"] == "1 Tishrei 5784 is 2023-09-16" && x["got"] == "2023-09-16"));
+}
+
+#[test]
+fn binary_output_matches_json() {
+    let list = |print: &str| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print")
+            .arg(print)
+            .arg("list")
+            .arg("5785")
+            .arg("--city")
+            .arg("Chicago")
+            .arg("--show=yom-tov,shabbos,daf-yomi");
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        output.stdout
+    };
+    let json: serde_json::Value = serde_json::from_slice(&list("json")).unwrap();
+    let cbor: serde_json::Value = serde_cbor::from_slice(&list("cbor")).unwrap();
+    let msgpack: serde_json::Value = rmp_serde::from_slice(&list("msgpack")).unwrap();
+    assert!(json.as_array().unwrap().len() > 355);
+    assert_eq!(cbor, json);
+    assert_eq!(msgpack, json);
+}
+
+#[test]
+fn short_names() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--language=en,he")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,chol,daf-yomi")
+        .arg("--short-names");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Night of 2024/10/2: RH 1 | ר\"ה א'. Candle lighting\n"));
+    assert!(stdout.contains("Night of 2024/12/27: Chanukah 3 | חנוכה ג'. Candle lighting\n"));
+    assert!(stdout.contains("2024/10/3: B.B. 100 | "));
+    // Names without an abbreviation are printed in full.
+    assert!(stdout.contains(": Purim | פורים\n"));
+
+    // Special Shabbosim, zmanim, davening changes and merged months are abbreviated too.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--language=en,he")
+        .arg("list")
+        .arg("5784")
+        .arg("--show=chol,davening-changes,zmanim:sof-zman-shma")
+        .arg("--only-shabbos")
+        .arg("--merge-rosh-chodesh")
+        .arg("--city")
+        .arg("Chicago")
+        .arg("--short-names");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(
+        "Night of 2023/9/22: Haazinu; Sh. Shuva; Shma 9:40 | האזינו; שבת שובה; סזק\"ש 9:40. "
+    ));
+    assert!(stdout.contains(
+        "Night of 2023/10/6: Sh. Atzeres; Shma 9:45; Geshem | שמ\"ע; סזק\"ש 9:45; גשם. "
+    ));
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5784")
+        .arg("--show=chol")
+        .arg("--merge-rosh-chodesh")
+        .arg("--short-names");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout
+        .contains("Night of 2024/2/8 until night of 2024/2/10: Rosh Chodesh Adar I (2 days)\n"));
+}
+
+#[test]
+fn selected_fields() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print=json")
+        .arg("--language=en,he")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov")
+        .arg("--fields")
+        .arg("day,name.en,category,topic.masechta");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json[0],
+        serde_json::json!({
+            "day": "2024-10-02T18:00:00Z",
+            "name": {"en": "1st day of Rosh Hashanah"},
+            "category": "yom_tov",
+        })
+    );
+
+    // A field which no event has is a mistake, and the error lists the fields.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print=json")
+        .arg("list")
+        .arg("5785")
+        .arg("--fields")
+        .arg("day,nmae");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    let err: Err = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(err.r#type, "ArgUndefinedError");
+    assert!(err
+        .error
+        .starts_with("Unknown field \"nmae\". The fields are day, type, name,"));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn parquet_output() {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    let run = |print: &str| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print")
+            .arg(print)
+            .arg("list")
+            .arg("5785")
+            .arg("--city")
+            .arg("Chicago")
+            .arg("--show=yom-tov,shabbos");
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        output.stdout
+    };
+    let path = std::env::temp_dir().join(format!("heca-list-{}.parquet", std::process::id()));
+    std::fs::write(&path, run("parquet")).unwrap();
+    let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let metadata = reader.metadata().file_metadata();
+    let columns: Vec<&str> = metadata
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|x| x.name())
+        .collect();
+    assert_eq!(
+        columns,
+        [
+            "night",
+            "date",
+            "event",
+            "title",
+            "category",
+            "candle_lighting",
+            "havdalah",
+            "uid"
+        ]
+    );
+    let json: Vec<serde_json::Value> = serde_json::from_slice(&run("json")).unwrap();
+    assert_eq!(metadata.num_rows(), json.len() as i64);
+
+    let rows: Vec<_> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| {
+            row.unwrap()
+                .get_column_iter()
+                .map(|(_, field)| field.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let day = |uid: &str| {
+        rows.iter()
+            .find(|row| row[7] == Field::Str(uid.into()))
+            .unwrap()
+    };
+    // Candles are lit for the 7th day of Pesach, and havdalah is after the 8th. There's neither
+    // on Chol HaMoed.
+    let pesach7 = day("yomtov-pesach7-5785-nissan-21-chul-chicago@heca");
+    assert!(matches!(pesach7[5], Field::TimestampMillis(_)));
+    assert_eq!(pesach7[6], Field::Null);
+    let pesach8 = day("yomtov-pesach8-5785-nissan-22-chul-chicago@heca");
+    assert!(matches!(pesach8[6], Field::TimestampMillis(_)));
+    let pesach3 = day("yomtov-pesach3-5785-nissan-17-chul-chicago@heca");
+    assert_eq!(pesach3[5..7], [Field::Null, Field::Null]);
+}
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+pub struct Res {
+    day: String,
+    name: String,
+    r#type: String,
+}
+
+#[derive(Deserialize, Debug, Eq, PartialEq)]
+struct Err {
+    error: String,
+    r#type: String,
+}
+
+#[test]
+fn agricultural() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5782")
+        .arg("--location=Israel")
+        .arg("--show=agricultural");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Night of 2021/9/6: Start of the Shmita Year\n"));
+    assert!(stdout.contains("Night of 2022/1/16: New Year of Trees for Orlah and Maaser\n"));
+    assert!(stdout.contains("2022/4/15: Biur Maasros\n"));
+    assert!(stdout.contains("Night of 2022/6/4: Bikurim Begin\n"));
+    assert!(stdout.contains("2022/9/25: End of the Shmita Year (Loans Released)\n"));
+
+    // Maasros are only removed in the 4th and 7th years of the cycle.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5783")
+        .arg("--location=Israel")
+        .arg("--show=agricultural");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(!stdout.contains("Shmita"));
+    assert!(!stdout.contains("Maasros"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5782")
+        .arg("--location=Chul")
+        .arg("--show=agricultural");
+    assert!(!cmd.output().unwrap().status.success());
+}
+
+#[test]
+fn erev_pesach() {
+    // Erev Pesach 5785 is on Shabbos, so everything but the times moves back.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=minor-holidays")
+        .arg("--city=Jerusalem");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("2025/4/10: Taanis Bechoros. Starts 5:02, ends 19:41\n"));
+    assert!(stdout.contains("Night of 2025/4/10: Bedikas Chametz\n"));
+    assert!(stdout.contains("2025/4/11: Biur Chametz\n"));
+    assert!(stdout.contains("2025/4/12: Sof Zman Achilas Chametz 10:31\n"));
+    assert!(stdout.contains("2025/4/12: Sof Zman Biur Chametz 11:35\n"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5784")
+        .arg("--show=minor-holidays");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.contains("2024/4/22: Taanis Bechoros\n"));
+    assert!(stdout.contains("Night of 2024/4/21: Bedikas Chametz\n"));
+    assert!(stdout.contains("2024/4/22: Biur Chametz\n"));
+    // The times need a city.
+    assert!(!stdout.contains("Sof Zman"));
+}
+
+#[test]
+fn davening_changes() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,davening-changes");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Night of 2024/10/22: Hoshana Rabbah\n"));
+    assert!(stdout.contains("2024/10/24: Tefillas Geshem\n"));
+    assert!(stdout.contains("2025/4/13: Tefillas Tal\n"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print=json")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=yom-tov,davening-changes")
+        .arg("--category=davening_changes");
+    let res: Vec<Res> =
+        serde_json::from_str(&String::from_utf8(cmd.output().unwrap().stdout).unwrap()).unwrap();
+    let names: Vec<&str> = res.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(names, vec!["TefillasGeshem", "TefillasTal"]);
+}
+
+#[test]
+fn shabbos_modifier() {
+    // 20 Kislev 5785 is on Shabbos, so the Shabbos before and after it are a week away.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/shabbos_modifier_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=custom-holidays");
+    cmd.assert().success().stdout(
+        "Night of 2024/12/13: Aufruf
+Night of 2024/12/20: Wedding
+Night of 2024/12/27: Sheva Brachos
+Night of 2025/3/7: Kiddush
+",
+    );
+}
+
+#[test]
+fn wrong_modifier() {
+    let config = std::env::temp_dir().join("heca_wrong_modifier.toml");
+    std::fs::write(
+        &config,
+        r#"days = [{ date = "1 Kislev", title = "Day", json = "Day", modifier = "sunday_before" }]"#,
+    )
+    .unwrap();
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg(&config)
+        .arg("list")
+        .arg("5785")
+        .arg("--show=custom-holidays");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Wrong modifier sunday_before in Day"));
+}
+
+#[test]
+fn relative_custom_holidays() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/relative_custom_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--show=custom-holidays");
+    cmd.assert().success().stdout(
+        "Night of 2024/10/25: Isru Chag
+Night of 2025/3/13: Maos Chitim
+Night of 2025/5/2: Party
+",
+    );
+
+    // There's no Simchas Torah in Israel, so it's skipped.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/relative_custom_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--location=Israel")
+        .arg("--show=custom-holidays")
+        .arg("--strict");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains(
+        "Isru Chag (IsruChag) was skipped in 5785, since SimchasTorah isn't in those years"
+    ));
+}
+
+#[test]
+fn weekday_custom_holidays() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/weekday_custom_config.toml")
+        .arg("list")
+        .arg("5785")
+        .arg("--years")
+        .arg("2")
+        .arg("--show=custom-holidays");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Night of 2024/12/27: Last Shabbos of Kislev
+Night of 2025/8/30: Selichos Kumzitz
+Night of 2025/12/19: Last Shabbos of Kislev
+Night of 2026/8/15: Selichos Kumzitz
+"
+    );
+    // Adar never has a 5th Tuesday in these years.
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Fifth Tuesday of Adar (FifthTuesday) was skipped in 5785, 5786"));
+}
+
+#[test]
+fn ics_alarms() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("ics")
+        .arg("list")
+        .arg("2026")
+        .arg("--gmonth")
+        .arg("4")
+        .arg("--city")
+        .arg("NewYorkCity")
+        .arg("--show=yom-tov,daf-yomi,zmanim:candles")
+        .arg("--alarm")
+        .arg("candles:-18m")
+        .arg("--alarm")
+        .arg("daf-yomi:20:00")
+        .arg("--alarm")
+        .arg("yom-tov:-1d");
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(
+        out.matches("BEGIN:VALARM").count(),
+        out.matches("END:VALARM").count()
+    );
+    // Candle lighting on Erev Pesach is at 19:03, so the reminder is at 18:45.
+    assert!(out.contains("TRIGGER;VALUE=DATE-TIME:20260401T224551Z\r\n"));
+    assert!(out.contains("TRIGGER:PT20H\r\n"));
+    assert!(out.contains("TRIGGER:-P1D\r\n"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("2026")
+        .arg("--alarm")
+        .arg("daf-yomi:soon");
+    cmd.assert().failure();
+}
+
+#[test]
+fn routes() {
+    let zmanim = std::env::temp_dir().join(format!("heca_route_{}.ics", std::process::id()));
+    let study = std::env::temp_dir().join(format!("heca_route_{}.json", std::process::id()));
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("2026")
+        .arg("--gmonth")
+        .arg("4")
+        .arg("--city")
+        .arg("NewYorkCity")
+        .arg("--show=yom-tov,daf-yomi,zmanim:candles")
+        .arg("--route")
+        .arg(format!("zmanim=>{}", zmanim.display()))
+        .arg("--route")
+        .arg(format!("daily-study=>{}", study.display()));
+    let out = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(out.contains("1st day of Pesach"));
+    assert!(!out.contains("Menachot"));
+
+    let ics = std::fs::read_to_string(&zmanim).unwrap();
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.contains("SUMMARY:Candle lighting 19:03\r\n"));
+    assert!(!ics.contains("Pesach"));
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&std::fs::read_to_string(&study).unwrap()).unwrap();
+    assert_eq!(res.len(), 30);
+    assert!(res.iter().all(|x| x["category"] == "daily_study"));
+    std::fs::remove_file(zmanim).unwrap();
+    std::fs::remove_file(study).unwrap();
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("2026")
+        .arg("--route")
+        .arg("zmanim=>zmanim.doc");
+    cmd.assert().failure();
+}
+
+#[test]
+fn append_since_last_run() {
+    let state = std::env::temp_dir().join(format!("heca_append_{}.json", std::process::id()));
+    let list = |month: &str| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("list")
+            .arg("2026")
+            .arg("--gmonth")
+            .arg(month)
+            .arg("--show=yom-tov")
+            .arg("--append-since-last-run")
+            .arg(&state);
+        String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+    };
+    assert!(list("4").starts_with("Night of 2026/4/1: 1st day of Pesach"));
+    assert_eq!(
+        std::fs::read_to_string(&state).unwrap(),
+        "{\"lastDate\":\"2026-04-08\"}\n"
+    );
+    // Running it again prints nothing, and keeps the date.
+    assert_eq!(list("4"), "");
+    assert!(list("5").starts_with("Night of 2026/5/21: 1st day of Shavuos"));
+    assert_eq!(
+        std::fs::read_to_string(&state).unwrap(),
+        "{\"lastDate\":\"2026-05-22\"}\n"
+    );
+    std::fs::remove_file(state).unwrap();
+}
+
+#[test]
+fn meta_hash() {
+    let list = |output_type: &str, year: &str| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print")
+            .arg(output_type)
+            .arg("list")
+            .arg(year)
+            .arg("--show=yom-tov")
+            .arg("--meta");
+        String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+    };
+    let json: serde_json::Value = serde_json::from_str(&list("json", "5786")).unwrap();
+    let hash = json["meta"]["hash"].as_str().unwrap();
+    assert!(hash.starts_with("sha256:"));
+    assert_eq!(hash.len(), 7 + 64);
+    assert_eq!(json["meta"]["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(json["events"].as_array().unwrap().len(), 22);
+    assert_eq!(json["meta"]["arguments"][3], "5786");
+
+    // The hash is of the events, whatever format they're printed in.
+    let ics = list("ics", "5786").replace("\r\n ", "");
+    assert!(ics.contains(&format!("X-WR-HASH:{}\r\n", hash)));
+    assert!(ics.contains("X-WR-PARAMETERS:--print ics list 5786 --show=yom-tov --meta\r\n"));
+    let other: serde_json::Value = serde_json::from_str(&list("json", "5787")).unwrap();
+    assert_ne!(other["meta"]["hash"], hash);
+}
+
+#[test]
+fn yom_tov_observance() {
+    let names = |args: &[&str], kind: &str| -> Vec<String> {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["--config", "/dev/null", "--print", "json", "list", "5785"])
+            .arg("--show=yom-tov,shabbos")
+            .args(args);
+        let out = cmd.output().unwrap();
+        assert!(
+            out.status.success(),
+            "{}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        let json: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+        json.as_array()
+            .unwrap()
+            .iter()
+            .filter(|x| x["type"] == kind)
+            .map(|x| x["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+    // A traveler from Chutz La'aretz in Israel keeps two days of Yom Tov, and hears the parsha
+    // as it's read in Israel.
+    let traveler = ["--location", "Israel", "--yomtov-observance", "diaspora"];
+    assert_eq!(
+        names(&traveler, "YomTov"),
+        names(&["--location", "Chul"], "YomTov")
+    );
+    assert_eq!(
+        names(&traveler, "Shabbos"),
+        names(&["--location", "Israel"], "Shabbos")
+    );
+    let visitor = ["--location", "Chul", "--yomtov-observance", "israel"];
+    assert_eq!(
+        names(&visitor, "YomTov"),
+        names(&["--location", "Israel"], "YomTov")
+    );
+    assert_eq!(
+        names(&visitor, "Shabbos"),
+        names(&["--location", "Chul"], "Shabbos")
+    );
+    assert_ne!(names(&visitor, "YomTov"), names(&traveler, "YomTov"));
+}
+
+#[test]
+fn fast_time_ranges() {
+    let list = |output_type: &str| {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args([
+            "--config",
+            "/dev/null",
+            "--print",
+            output_type,
+            "list",
+            "5785",
+        ])
+        .args(["--show=chol", "--city", "NewYorkCity"]);
+        String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+    };
+    let json: serde_json::Value = serde_json::from_str(&list("json")).unwrap();
+    let day = |name: &str| {
+        json.as_array()
+            .unwrap()
+            .iter()
+            .find(|x| x["name"] == name)
+            .unwrap()
+            .clone()
+    };
+    // A minor fast is from dawn until nightfall, and Tisha B'Av from sunset the night before.
+    assert_eq!(
+        day("TzomGedalia")["timeRange"],
+        serde_json::json!({
+            "start": "2024-10-06T05:37:27-04:00",
+            "end": "2024-10-06T19:09:30-04:00",
+        })
+    );
+    let nine_av = &day("NineAv")["timeRange"];
+    assert!(nine_av["start"]
+        .as_str()
+        .unwrap()
+        .starts_with("2025-08-02T20:"));
+    assert!(nine_av["end"]
+        .as_str()
+        .unwrap()
+        .starts_with("2025-08-03T20:"));
+    assert!(day("Purim").get("timeRange").is_none());
+
+    assert!(list("pretty").contains("2024/10/6: Tzom Gedalia. Starts 5:37, ends 19:09\n"));
+    let ics = list("ics");
+    assert!(ics.contains(
+        "DTSTART;TZID=America/New_York:20241006T053727\r\n\
+         DTEND;TZID=America/New_York:20241006T190930\r\n\
+         SUMMARY:Tzom Gedalia\r\n"
+    ));
+}
+
+#[test]
+fn provenance() {
+    let sources = |year: &str, show: &str| -> Vec<(String, String)> {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["--config", "./tests/anniversary_config.toml"])
+            .args(["--print", "json", "list", year, "--provenance"])
+            .arg(format!("--show={}", show));
+        let json: serde_json::Value =
+            serde_json::from_slice(&cmd.output().unwrap().stdout).unwrap();
+        json.as_array()
+            .unwrap()
+            .iter()
+            .map(|x| {
+                (
+                    x["name"].as_str().unwrap().to_string(),
+                    x["source"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    };
+    let source = |year: &str, show: &str, name: &str| -> String {
+        sources(year, show)
+            .into_iter()
+            .find(|(x, _)| x == name)
+            .unwrap()
+            .1
+    };
+    // Rosh Hashanah 5786 was postponed by Molad Zaken, and 5788 by Lo ADU Rosh.
+    assert_eq!(
+        source("5786", "yom-tov", "RoshHashanah1"),
+        "dechiyah:molad-zaken"
+    );
+    assert_eq!(
+        source("5788", "yom-tov", "RoshHashanah1"),
+        "dechiyah:lo-adu-rosh"
+    );
+    assert_eq!(source("5785", "yom-tov", "RoshHashanah1"), "molad");
+    assert_eq!(source("5785", "yom-tov", "Pesach1"), "yom-tov:chul");
+    assert_eq!(
+        source("5785", "shabbos", "BeharBechukosai"),
+        "doubling:regular-year"
+    );
+    assert_eq!(source("5785", "shabbos", "Vayakhel"), "shabbos:chul");
+    assert_eq!(source("5785", "chol", "TzomGedalia"), "fast:postponed");
+    assert_eq!(source("5788", "chol", "TaanisEsther"), "fast:advanced");
+    assert_eq!(
+        source("5785", "custom-holidays", "Birthday"),
+        "custom:anniversary_config.toml#3"
+    );
+
+    // Without --provenance, there's no source.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.args(["--config", "/dev/null", "--print", "json", "list", "5785"]);
+    let json: serde_json::Value = serde_json::from_slice(&cmd.output().unwrap().stdout).unwrap();
+    assert!(json[0].get("source").is_none());
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.args(["--config", "/dev/null", "list", "5786", "--show=yom-tov"])
+        .arg("--provenance");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.starts_with(
+        "Night of 2025/9/22: 1st day of Rosh Hashanah. Candle lighting [dechiyah:molad-zaken]\n"
+    ));
 }
@@ -0,0 +1,75 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn merge() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Kislev")
+        .arg("--show=shabbos");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let base = std::env::temp_dir().join(format!("heca_merge_{}.json", std::process::id()));
+    std::fs::write(&base, output.stdout).unwrap();
+
+    // The family calendar adds a birthday, and overrides the title of Shabbos Vayishlach.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("merge")
+        .arg(&base)
+        .arg("./tests/family_calendar.json")
+        .arg(&base);
+    cmd.assert().success().stdout(
+        "2024/12/7: Vayetzei
+2024/12/11: Sarah's birthday
+2024/12/14: Vayishlach
+2024/12/21: Vayeshev
+2024/12/28: Miketz
+",
+    );
+
+    let ics = std::env::temp_dir().join(format!("heca_merge_{}.ics", std::process::id()));
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("merge")
+        .arg(&base)
+        .arg("./tests/family_calendar.json")
+        .arg("--out")
+        .arg(&ics);
+    cmd.assert().success();
+    let calendar = std::fs::read_to_string(&ics).unwrap();
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 5);
+    for expected in &[
+        "DTSTART;VALUE=DATE:20241214",
+        "SUMMARY:Vayetzei",
+        "SUMMARY:Vayishlach (Bar Mitzvah of David)",
+        "DESCRIPTION:Kiddush at the shul",
+        "UID:shabbos-vayetzei-5785-kislev-6-chul@heca",
+    ] {
+        assert!(
+            calendar.contains(expected),
+            "{} not in {}",
+            expected,
+            calendar
+        );
+    }
+
+    // With --provenance, the events which weren't listed with it are named by their calendar.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("merge")
+        .arg(&base)
+        .arg("./tests/family_calendar.json")
+        .arg("--provenance");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.contains("2024/12/11: Sarah's birthday [merge:family_calendar.json#1]\n"));
+    assert!(stdout.starts_with(&format!(
+        "2024/12/7: Vayetzei [merge:{}#1]\n",
+        base.file_name().unwrap().to_string_lossy()
+    )));
+
+    std::fs::remove_file(base).unwrap();
+    std::fs::remove_file(ics).unwrap();
+}
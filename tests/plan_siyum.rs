@@ -0,0 +1,60 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn plan_siyum() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("plan-siyum")
+        .arg("--masechta")
+        .arg("Kesubos")
+        .arg("--start-daf")
+        .arg("105")
+        .arg("--by")
+        .arg("5786-Nissan-1")
+        .arg("--days")
+        .arg("sun,thu")
+        .arg("--from")
+        .arg("2026-03-01");
+    cmd.assert().success().stdout(
+        "2026/3/1: Ketubot 105\n2026/3/5: Ketubot 106\n2026/3/8: Ketubot 107-108\n2026/3/12: Ketubot 109\n2026/3/15: Ketubot 110\n2026/3/19: Ketubot 111-112\n\nSiyum on Ketubot on 2026/3/19: 8 dapim in 6 sessions\n",
+    );
+
+    // Hebrew names of the masechta work too, and the JSON has the totals.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("plan-siyum")
+        .arg("--masechta")
+        .arg("כתובות")
+        .arg("--by")
+        .arg("2026-03-19")
+        .arg("--from")
+        .arg("2026-03-19");
+    let out: serde_json::Value = serde_json::from_slice(&cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(out["masechta"], "Ketubot");
+    assert_eq!(out["siyum"], "2026-03-19");
+    assert_eq!(out["dapim"], 111);
+    assert_eq!(out["sessions"], 1);
+    assert_eq!(out["events"][0]["units"].as_array().unwrap().len(), 111);
+}
+
+#[test]
+fn plan_siyum_no_days() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("plan-siyum")
+        .arg("--masechta")
+        .arg("Kesubos")
+        .arg("--by")
+        .arg("2026-03-19")
+        .arg("--days")
+        .arg("sat")
+        .arg("--from")
+        .arg("2026-03-16");
+    cmd.assert().failure();
+}
@@ -0,0 +1,145 @@
+use assert_cmd::Command;
+use heca::args::types::{Command as HecaCommand, DayVal, LimitsArgs, MainArgs, OutputType};
+use heca::args::types::{Language, PolarFallback, SolarAlgorithm, Transliteration};
+use heca::list::providers::MinorHolidays;
+use heca::list::{get_events, Return};
+use heca::prelude::Printable;
+
+fn main_args(output_type: OutputType) -> MainArgs {
+    MainArgs {
+        custom_days: None,
+        output_type,
+        language: Language::English,
+        second_language: None,
+        transliteration: Transliteration::Sephardi,
+        out: None,
+        ascii: false,
+        today: None,
+        command: HecaCommand::Limits(LimitsArgs),
+    }
+}
+
+#[test]
+fn print_to_writer() {
+    let minor_holidays = MinorHolidays {
+        city: None,
+        polar_fallback: PolarFallback::None,
+        solar_algorithm: SolarAlgorithm::Noaa,
+    };
+    let ret = Return {
+        list: get_events(5785, 5786, &[&minor_holidays]).unwrap(),
+        icons: false,
+        location: "chul".into(),
+        time_zone: None,
+        fields: None,
+        short_names: false,
+        shabbos_conflicts: None,
+        headers: None,
+        summary: None,
+        meta: false,
+        alarms: vec![],
+        candle_lighting_minutes: None,
+        provenance: None,
+    };
+
+    let mut out = vec![];
+    let written = ret.print(&main_args(OutputType::Pretty), &mut out).unwrap();
+    assert_eq!(written, out.len());
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.lines().any(|x| x == "2024/10/11: Erev Yom Kippur"));
+
+    let mut out = vec![];
+    let written = ret.print(&main_args(OutputType::ICS), &mut out).unwrap();
+    assert_eq!(written, out.len());
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(out.contains("SUMMARY:Erev Yom Kippur\r\n"));
+    assert!(out.contains("UID:minordays-erevyomkippur-5785-tishrei-9-chul@heca\r\n"));
+}
+
+#[test]
+fn out_file() {
+    let path = std::env::temp_dir().join(format!("heca-out-{}.json", std::process::id()));
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--out")
+        .arg(&path)
+        .arg("--print")
+        .arg("json")
+        .arg("limits");
+    cmd.assert().success().stdout("");
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "{\"hebrew\":{\"min\":3764,\"max\":265000},\"gregorian\":{\"min\":4,\"max\":261000}}\n"
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--out").arg("/nonexistent/heca.txt").arg("limits");
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::starts_with("Write error: Can't create"));
+}
+
+/// Lists printed with `--print json` are read back into the same days. Their UIDs are only
+/// printed by `list`.
+#[test]
+fn json_round_trip() {
+    let runs: &[&[&str]] = &[
+        &["list", "5785", "--show=all", "--city", "NewYorkCity"],
+        &[
+            "list",
+            "5785",
+            "--show=all,chumash",
+            "--reading-cycle",
+            "triennial",
+            "--city",
+            "Helsinki",
+            "--havdalah-opinion",
+            "42,8.5",
+        ],
+        &["list", "5785", "--merge-spans", "--show=all"],
+        &["list", "5785", "--merge-rosh-chodesh", "--show=all"],
+        &["list", "5785", "--only-shabbos", "--city", "Jerusalem"],
+        &[
+            "list",
+            "5785",
+            "--city",
+            "Helsinki",
+            "--show=shabbos,zmanim:candles,havdalah,tzeis",
+            "--havdalah-opinion",
+            "42,8.5",
+        ],
+        &["list", "5685", "--years", "2", "--show=daf-yomi"],
+        &[
+            "--config",
+            "./tests/anniversary_config.toml",
+            "list",
+            "5785",
+            "--show=custom-holidays",
+        ],
+    ];
+    for args in runs {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print").arg("json").args(*args);
+        let output = cmd.assert().success().get_output().stdout.clone();
+        let mut printed: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        for day in printed.as_array_mut().unwrap() {
+            let day = day.as_object_mut().unwrap();
+            assert!(day.remove("uid").is_some());
+            // The minutes are the city's, not the day's, like the UID.
+            day.remove("candleLightingMinutes");
+        }
+        let days: Vec<DayVal> = serde_json::from_value(printed.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&days).unwrap(), printed, "{:?}", args);
+    }
+}
+
+#[test]
+fn json_with_unknown_type_is_an_error() {
+    let day = r#"{"day":"2024-10-02T18:00:00Z","type":"Birthday","name":"RoshHashanah1"}"#;
+    let err = serde_json::from_str::<DayVal>(day).unwrap_err();
+    assert!(err.to_string().contains("Unknown type of event: Birthday"));
+    let day = r#"{"day":"2024-10-02T18:00:00Z","type":"DafYomi","topic":{"masechta":"Berakhot","daf":65}}"#;
+    let err = serde_json::from_str::<DayVal>(day).unwrap_err();
+    assert!(err.to_string().contains("Unknown daf: Berakhot 65"));
+}
@@ -0,0 +1,76 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn profile_replaces_settings() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/profiles_config.toml")
+        .arg("--profile")
+        .arg("shul")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Shvat");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = res
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "Bo",
+            "Beshalach",
+            "YudShvat",
+            "Kiddush",
+            "Yisro",
+            "Mishpatim"
+        ]
+    );
+    // The profile's city, in Israel.
+    assert_eq!(
+        res[0]["uid"],
+        "shabbos-bo-5785-shvat-3-israel-jerusalem@heca"
+    );
+}
+
+#[test]
+fn without_profile() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/profiles_config.toml")
+        .arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Shvat")
+        .arg("--show")
+        .arg("custom-holidays");
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("YudShvat"))
+        .stdout(predicates::str::contains("Kiddush").not());
+}
+
+#[test]
+fn unknown_profile() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("./tests/profiles_config.toml")
+        .arg("--profile")
+        .arg("office")
+        .arg("list")
+        .arg("5785");
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "There's no profile office. The profiles are shul, travel",
+    ));
+}
@@ -0,0 +1,28 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn slug() {
+    for (date, series, expected) in &[
+        ("2025-02-14", "parsha", "5785-yisro\n"),
+        ("2025-02-15", "parsha", "5785-yisro\n"),
+        ("2025-04-14", "parsha", "5785-7th-day-of-pesach\n"),
+        ("2025-04-28", "parsha", "5785-tazriya-metzorah\n"),
+        ("2020-01-05", "daf-yomi", "berachos-002a\n"),
+        ("2020-01-15", "daf-yomi", "berachos-012a\n"),
+    ] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("slug")
+            .arg("--date")
+            .arg(date)
+            .arg("--series")
+            .arg(series);
+        cmd.assert().success().stdout(*expected);
+    }
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("slug").arg("--date").arg("2025-02-30");
+    cmd.assert().failure();
+}
@@ -0,0 +1,90 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn stats() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("stats")
+        .arg("--from")
+        .arg("5700")
+        .arg("--to")
+        .arg("5800");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res["years"], 101);
+    // Rosh Hashanah is never on Sunday, Wednesday or Friday, and Pesach never on Monday,
+    // Wednesday or Friday.
+    for (holiday, never) in &[
+        ("roshHashanah", ["Sunday", "Wednesday", "Friday"]),
+        ("pesach", ["Monday", "Wednesday", "Friday"]),
+    ] {
+        let weekdays = res[holiday]["weekdays"].as_object().unwrap();
+        assert_eq!(
+            weekdays.values().map(|x| x.as_u64().unwrap()).sum::<u64>(),
+            101
+        );
+        for weekday in never {
+            assert_eq!(weekdays[*weekday], 0);
+        }
+    }
+    assert_eq!(
+        res["roshHashanah"]["earliest"],
+        serde_json::json!({"date": "09-05", "year": 5774})
+    );
+    assert_eq!(
+        res["pesach"]["latest"],
+        serde_json::json!({"date": "04-25", "year": 5727})
+    );
+    assert_eq!(res["lengths"]["355"], 29);
+    assert_eq!(res["yearTypes"].as_object().unwrap().len(), 14);
+}
+
+#[test]
+fn event_stats() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("stats")
+        .arg("--event")
+        .arg("yom-kippur")
+        .arg("--from")
+        .arg("5700")
+        .arg("--to")
+        .arg("5900")
+        .arg("--by")
+        .arg("weekday")
+        .arg("--csv");
+    cmd.assert().success().stdout(concat!(
+        "weekday,count,percent\n",
+        "Sunday,0,0.0\n",
+        "Monday,56,27.9\n",
+        "Tuesday,0,0.0\n",
+        "Wednesday,56,27.9\n",
+        "Thursday,21,10.4\n",
+        "Friday,0,0.0\n",
+        "Shabbos,68,33.8\n",
+    ));
+
+    // The first day of Chanukah is never on Tuesday.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("stats")
+        .arg("--event")
+        .arg("Chanukah1")
+        .arg("--from")
+        .arg("5700")
+        .arg("--to")
+        .arg("5799");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res["years"], 100);
+    assert_eq!(res["days"], 100);
+    assert_eq!(res["weekdays"]["Tuesday"], 0);
+}
@@ -0,0 +1,152 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn status_bar_text() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("status-bar")
+        .arg("--date")
+        .arg("2024-12-31");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "30 Kislev 5785 - RC Teves 1, Chanukah 6\n"
+    );
+}
+
+#[test]
+fn status_bar_waybar() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("status-bar")
+        .arg("--date")
+        .arg("2024-12-28")
+        .arg("--city")
+        .arg("Chicago");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res["text"], "27 Kislev 5785 - Chanukah 3, Miketz");
+    assert_eq!(res["class"], "shabbos");
+    assert_eq!(
+        res["tooltip"],
+        "Saturday 2024/12/28, 27 Kislev 5785\n\
+         Night of 2024/12/27: 3rd day of Chanukah. Candle lighting 16:09. Havdalah 17:09\n\
+         Night of 2024/12/27: Miketz. Candle lighting 16:09. Havdalah 17:09\n\
+         Night of 2024/12/28: 4th day of Chanukah"
+    );
+}
+
+#[test]
+fn status_bar_yom_tov() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("--print")
+        .arg("json")
+        .arg("status-bar")
+        .arg("--date")
+        .arg("2025-04-13");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res["text"], "15 Nissan 5785 - Pesach 1");
+    assert_eq!(res["class"], "yomtov");
+}
+
+#[test]
+fn status_bar_exec() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("today")
+        .arg("--date")
+        .arg("2024-12-31")
+        .arg("--exec")
+        .arg("rev");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "6 hakunahC ,1 seveT CR - 5875 velsiK 03\n"
+    );
+}
+
+#[test]
+fn status_bar_time_zone() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("status-bar")
+        .arg("--time-zone")
+        .arg("Nowhere/Special");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.env("TZ", "EST5")
+        .arg("--config")
+        .arg("/dev/null")
+        .arg("--deny-warnings")
+        .arg("status-bar");
+    let output = cmd.output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("system's time zone"));
+}
+
+#[test]
+fn status_bar_today() {
+    // At 20:00 in Jerusalem, after sunset, the next day has started; in New York, at noon, it
+    // hasn't.
+    for (city, today, text) in [
+        ("Jerusalem", "2025-03-11T20:00", "12 Adar 5785"),
+        ("NewYorkCity", "2025-03-11T20:00+02:00", "11 Adar 5785"),
+    ] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--config")
+            .arg("/dev/null")
+            .arg("status-bar")
+            .arg("--city")
+            .arg(city)
+            .arg("--today")
+            .arg(today);
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            format!("{}\n", text)
+        );
+    }
+}
+
+#[test]
+fn status_bar_heca_today() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.env("HECA_TODAY", "2024-12-31")
+        .arg("--config")
+        .arg("/dev/null")
+        .arg("status-bar")
+        .arg("--time-zone")
+        .arg("Europe/London");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "30 Kislev 5785 - RC Teves 1, Chanukah 6\n"
+    );
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.env("HECA_TODAY", "yesterday")
+        .arg("--config")
+        .arg("/dev/null")
+        .arg("status-bar");
+    cmd.assert().failure();
+}
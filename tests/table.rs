@@ -0,0 +1,47 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn table() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("table")
+        .arg("--from")
+        .arg("5784")
+        .arg("--to")
+        .arg("5786");
+    cmd.assert().success().stdout(concat!(
+        "Year  Rosh Hashanah  Days  Leap\n",
+        "5784  2023-09-16     383   yes\n",
+        "5785  2024-10-03     355   no\n",
+        "5786  2025-09-23     354   no\n",
+    ));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("table")
+        .arg("--from")
+        .arg("5700")
+        .arg("--to")
+        .arg("5800");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res.len(), 101);
+    assert_eq!(res[0]["roshHashanah"], "1939-09-14");
+    // 7 of every 19 years are leap years.
+    assert_eq!(
+        res.iter().take(95).filter(|x| x["leap"] == true).count(),
+        35
+    );
+    assert!(res
+        .iter()
+        .all(|x| [353, 354, 355, 383, 384, 385].contains(&x["length"].as_i64().unwrap())));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("table")
+        .arg("--from")
+        .arg("5786")
+        .arg("--to")
+        .arg("5785");
+    cmd.assert().failure();
+}
@@ -0,0 +1,24 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn json_version() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print").arg("json").arg("--version");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res["version"], env!("CARGO_PKG_VERSION"));
+    // Cargo features are only listed when heca is built with them.
+    let mut features = vec!["zmanim", "ics"];
+    if cfg!(feature = "profile") {
+        features.push("profile");
+    }
+    if cfg!(feature = "parquet") {
+        features.push("parquet");
+    }
+    assert_eq!(res["features"], serde_json::json!(features));
+    assert_eq!(res["schemaVersions"], serde_json::json!([1]));
+    assert_eq!(res["years"]["hebrew"]["min"], 3764);
+    assert!(res["gitHash"].is_string() || res["gitHash"].is_null());
+}
@@ -0,0 +1,173 @@
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+#[test]
+fn year_info() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("year-info").arg("5785");
+    cmd.assert().success().stdout(concat!(
+        "Year: 5785\n",
+        "Keviyah: השא\n",
+        "Length: 355 days\n",
+        "Leap year: no\n",
+        "Rosh Hashanah: Thursday 2024-10-03\n",
+        "Pesach: Sunday 2025-04-13\n",
+        "At-Bash:\n",
+        "1st day of Pesach (א\"ת): Tisha B'Av, Sunday 2025-08-03\n",
+        "2nd day of Pesach (ב\"ש): Shavuos, Monday 2025-06-02\n",
+        "3rd day of Pesach (ג\"ר): Rosh Hashanah, Tuesday 2025-09-23\n",
+        "4th day of Pesach (ד\"ק): Simchas Torah, Wednesday 2025-10-15\n",
+        "5th day of Pesach (ה\"צ): Yom Kippur, Thursday 2025-10-02\n",
+        "6th day of Pesach (ו\"פ): Purim, Friday 2025-03-14\n",
+    ));
+
+    for (year, keviyah, length) in &[(5784, "זחג", 383), (5786, "גכה", 354), (5782, "גכז", 384)]
+    {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print")
+            .arg("json")
+            .arg("year-info")
+            .arg(year.to_string());
+        let output = cmd.output().unwrap();
+        let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(res["keviyah"], *keviyah);
+        assert_eq!(res["length"], *length);
+        assert_eq!(res["leap"], *length > 355);
+        // Every holiday is on the day of the week of its day of Pesach.
+        let pesach =
+            chrono::NaiveDate::parse_from_str(res["pesach"].as_str().unwrap(), "%Y-%m-%d").unwrap();
+        for at_bash in res["atBash"].as_array().unwrap() {
+            let date =
+                chrono::NaiveDate::parse_from_str(at_bash["date"].as_str().unwrap(), "%Y-%m-%d")
+                    .unwrap();
+            let day = at_bash["pesachDay"].as_i64().unwrap();
+            assert_eq!((date - pesach).num_days().rem_euclid(7), day - 1);
+        }
+    }
+}
+
+#[test]
+fn explain() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("year-info").arg("5786").arg("--explain");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.ends_with(concat!(
+        "Explanation:\n",
+        "Molad of Tishrei: Monday, 18 hours and 187 chalakim (Monday 2025-09-22 12:10 and 7 Chalakim)\n",
+        "Molad Zaken: the molad is at noon or later, so Rosh Hashanah is postponed to the next day\n",
+        "Rosh Hashanah: Tuesday 2025-09-23\n",
+        "Next Rosh Hashanah: Saturday 2026-09-12, from the molad on Saturday, 2 hours and 1063 chalakim\n",
+        "Cheshvan and Kislev: a regular year has 354 days, with 29 in Cheshvan and 30 in Kislev; this year has 354 days, as many, so Cheshvan has 29 days and Kislev 30\n",
+    )));
+
+    // 5784 was a short leap year: its molad was on Friday, so Rosh Hashanah was on Shabbos.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("month-info")
+        .arg("5784")
+        .arg("Kislev")
+        .arg("--explain");
+    let output = cmd.output().unwrap();
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let explanation = &res["explanation"];
+    assert_eq!(explanation["roshHashanah"]["moladWeekday"], "Fri");
+    assert_eq!(
+        explanation["roshHashanah"]["dechiyos"],
+        serde_json::json!(["LoAdu"])
+    );
+    assert_eq!(explanation["length"], 383);
+    assert_eq!(explanation["regularLength"], 384);
+    assert_eq!(explanation["cheshvan"], 29);
+    assert_eq!(explanation["kislev"], 29);
+
+    // Without it, there's no explanation.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print").arg("json").arg("year-info").arg("5786");
+    let output = cmd.output().unwrap();
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(res.get("explanation").is_none());
+}
+
+#[test]
+fn month_info() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("month-info").arg("5784").arg("Adar1");
+    cmd.assert().success().stdout(concat!(
+        "Month: Adar Rishon 5784\n",
+        "Length: 30 days\n",
+        "Leap year: yes\n",
+        "Rosh Chodesh: Friday 2024-02-09, Saturday 2024-02-10\n",
+        "Molad: Friday 2024-02-09 21:29 and 5 Chalakim\n",
+        "Dates: Saturday 2024-02-10 to Sunday 2024-03-10\n",
+    ));
+
+    // The same molad as Shabbos Mevarchim. Kislev 5785 was full, so Rosh Chodesh Teves is two
+    // days.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("month-info")
+        .arg("5785")
+        .arg("teves");
+    let output = cmd.output().unwrap();
+    let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(res["month"], "Teves");
+    assert_eq!(res["length"], 29);
+    assert_eq!(
+        res["roshChodesh"],
+        serde_json::json!(["2024-12-31", "2025-01-01"])
+    );
+    assert_eq!(
+        res["molad"],
+        serde_json::json!({"date": "2024-12-30", "hour": 17, "minute": 33, "chalakim": 16})
+    );
+    assert_eq!(res["firstDay"], "2025-01-01");
+    assert_eq!(res["lastDay"], "2025-01-29");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("month-info").arg("5785").arg("Tishrei");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.contains("Rosh Chodesh: none (Rosh Hashanah)\n"));
+
+    // 5785 isn't a leap year.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("month-info").arg("5785").arg("Adar1");
+    cmd.assert().failure();
+}
+
+#[test]
+fn doubled() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.args(["year-info", "5785", "--doubled", "--location", "Chul"]);
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout.ends_with(concat!(
+        "Doubled parshiyos in Chutz La'aretz:\n",
+        "Vayakhel/Pikudei: separate; a regular year of 355 days which starts on Thursday has a Shabbos more before Pesach\n",
+        "Tazriya/Metzorah: together on Saturday 2025-05-03; a regular year has no Adar I, so there aren't enough Shabbosos to read them separately\n",
+        "Acharei Mos/Kedoshim: together on Saturday 2025-05-10; a regular year has no Adar I, so there aren't enough Shabbosos to read them separately\n",
+        "Behar/Bechukosai: together on Saturday 2025-05-24; a regular year has no Adar I, so there aren't enough Shabbosos to read them separately\n",
+        "Chukas/Balak: separate; the second day of Shavuos isn't on Shabbos, so there's a Shabbos for each\n",
+        "Matos/Maasei: together on Saturday 2025-07-26; they're read together so that Devarim is read on the Shabbos before Tisha B'Av\n",
+        "Nitzavim/Vayelech: separate; the next Rosh Hashanah is on Monday or Tuesday, so there are two Shabbosos between it and Sukkos, and Vayelech is read on the first and Haazinu on the second\n",
+    )));
+
+    // In 5782, the eighth day of Pesach was on Shabbos, and Israel read Matos and Maasei
+    // separately while Chutz La'aretz read them together.
+    for (location, doubled) in &[("Chul", true), ("Israel", false)] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.args(["--print", "json", "year-info", "5782", "--doubled"])
+            .args(["--location", location]);
+        let output = cmd.output().unwrap();
+        let res: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let matos = &res["doubled"]["parshiyos"][5];
+        assert_eq!(matos["parsha"], "MatosMaasei");
+        assert_eq!(matos["doubled"], *doubled);
+        if *doubled {
+            assert_eq!(matos["date"], "2022-07-30");
+            assert_eq!(matos["reason"], "DevarimBeforeTishaBav");
+        } else {
+            assert_eq!(matos["reason"], "EighthDayOfPesachOnShabbos");
+        }
+    }
+}
@@ -0,0 +1,480 @@
+use assert_cmd::prelude::*;
+use chrono::{DateTime, NaiveDate, Timelike};
+use std::collections::HashMap;
+use std::process::Command;
+
+#[test]
+fn zmanim_table() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--city")
+        .arg("Jerusalem")
+        .arg("--zmanim")
+        .arg("sunrise,sof-zman-shma,sunset")
+        .arg("--table")
+        .arg("csv");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 366);
+    assert_eq!(lines[0], "date,sunrise,sof-zman-shma,sunset");
+    assert_eq!(lines[1], "2025-01-01,06:39,09:11,16:46");
+    assert_eq!(lines[365], "2025-12-31,06:38,09:10,16:45");
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--city")
+        .arg("Jerusalem")
+        .arg("--zmanim")
+        .arg("alos,tzeis")
+        .arg("--table");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert_eq!(
+        stdout.lines().take(2).collect::<Vec<&str>>(),
+        vec![
+            "Date        Alos HaShachar  Tzeis HaKochavim",
+            "2025/1/1    5:21            17:26"
+        ]
+    );
+}
+
+#[test]
+fn zmanim_json() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--city")
+        .arg("Jerusalem")
+        .arg("--zmanim")
+        .arg("chatzos");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert_eq!(res.len(), 365);
+    assert_eq!(res[0]["date"], "2025-01-01");
+    assert!(res[0]["Chatzos"]
+        .as_str()
+        .unwrap()
+        .starts_with("2025-01-01T11:42"));
+}
+
+#[test]
+fn zmanim_needs_a_city() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.env_remove("HECA_CITY")
+        .arg("--config")
+        .arg("/dev/null")
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025");
+    cmd.assert().failure();
+}
+
+#[test]
+fn zmanim_polar_fallback() {
+    // The sun doesn't go 8.5 degrees below the horizon in Stockholm around the summer solstice.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--city")
+        .arg("Stockholm")
+        .arg("--zmanim")
+        .arg("sunset,tzeis");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert_eq!(res[171]["date"], "2025-06-21");
+    assert!(res[171]["Sunset"].is_string());
+    assert!(res[171]["TzeisHakochavim"].is_null());
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--city")
+        .arg("Stockholm")
+        .arg("--zmanim")
+        .arg("tzeis")
+        .arg("--polar-fallback")
+        .arg("midnight");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert!(res[171]["TzeisHakochavim"]
+        .as_str()
+        .unwrap()
+        .starts_with("2025-06-22T00:49"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--month")
+        .arg("Sivan")
+        .arg("--show=shabbos")
+        .arg("--city")
+        .arg("Stockholm")
+        .arg("--havdalah-opinion")
+        .arg("8.5");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout
+        .lines()
+        .any(|line| line.ends_with("Havdalah does not occur")));
+}
+
+#[test]
+fn chatzos_without_sunrise() {
+    // Tromsø has the midnight sun in June and the polar night in January, but the sun is still
+    // highest at chatzos.
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--coordinates")
+        .arg("69.65,18.96")
+        .arg("--time-zone")
+        .arg("Europe/Oslo")
+        .arg("--zmanim")
+        .arg("sunrise,chatzos");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> =
+        serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert!(res[0]["Sunrise"].is_null());
+    assert!(res[0]["Chatzos"]
+        .as_str()
+        .unwrap()
+        .starts_with("2025-01-01T11:47"));
+    assert!(res[171]["Sunrise"].is_null());
+    assert!(res[171]["Chatzos"]
+        .as_str()
+        .unwrap()
+        .starts_with("2025-06-21T12:46"));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("2025")
+        .arg("--gmonth")
+        .arg("6")
+        .arg("--coordinates")
+        .arg("69.65,18.96")
+        .arg("--time-zone")
+        .arg("Europe/Oslo")
+        .arg("--events")
+        .arg("zmanim:chatzos");
+    let stdout = String::from_utf8(cmd.output().unwrap().stdout).unwrap();
+    assert!(stdout
+        .lines()
+        .any(|line| line == "2025/6/21: Chatzos 12:46"));
+    assert!(!stdout.contains("does not occur"));
+}
+
+#[test]
+fn zmanim_in_list() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("2024")
+        .arg("--gmonth")
+        .arg("10")
+        .arg("--city")
+        .arg("Chicago")
+        .arg("--events")
+        .arg("zmanim:candles,havdalah,chatzos");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<&str>>();
+    // The same times as `heca zmanim` and the candle lighting printed with Shabbos.
+    for expected in &[
+        "2024/10/4: Chatzos 12:38",
+        "2024/10/4: Candle lighting 18:09",
+        "2024/10/5: Havdalah 19:06",
+        "2024/10/11: Candle lighting 17:57",
+        "2024/10/12: Havdalah 18:54",
+    ] {
+        assert!(lines.contains(expected), "{} not in {}", expected, stdout);
+    }
+    // Yom Kippur is on Shabbos, but havdalah is only listed once.
+    assert_eq!(
+        lines
+            .iter()
+            .filter(|x| x.starts_with("2024/10/12: "))
+            .count(),
+        2
+    );
+    // Like daily study, the month is filtered by the date the times are on.
+    let chatzos = lines
+        .iter()
+        .filter(|x| x.contains("Chatzos"))
+        .collect::<Vec<_>>();
+    assert_eq!(chatzos.len(), 31);
+    assert!(chatzos[0].starts_with("2024/10/1: "));
+    assert!(chatzos[30].starts_with("2024/10/31: "));
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--events")
+        .arg("zmanim:chatzos");
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--print")
+        .arg("json")
+        .arg("list")
+        .arg("5785")
+        .arg("--city")
+        .arg("Helsinki")
+        .arg("--show")
+        .arg("zmanim:havdalah")
+        .arg("--havdalah-opinion")
+        .arg("42,8.5");
+    let output = cmd.output().unwrap();
+    let res: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(res
+        .iter()
+        .all(|x| x["type"] == "Zman" && x["name"] == "Havdalah" && x["category"] == "zman"));
+    // In the summer in Helsinki, the sun doesn't go 8.5 degrees below the horizon.
+    assert!(res
+        .iter()
+        .any(|x| x["opinion"] == "Degrees8_5" && x["time"].is_null()));
+}
+
+#[test]
+fn zmanim_in_whole_year() {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("list")
+        .arg("5785")
+        .arg("--city")
+        .arg("Chicago")
+        .arg("--events")
+        .arg("zmanim:candles,chatzos");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<&str>>();
+    // Each month is generated on its own, but every day of the year is listed once, and candle
+    // lighting for Rosh Hashanah is listed on the day before the year starts.
+    assert_eq!(lines[0], "2024/10/2: Candle lighting 18:12");
+    let mut days = lines
+        .iter()
+        .filter(|x| x.contains("Chatzos"))
+        .map(|x| x.split(':').next().unwrap())
+        .collect::<Vec<&str>>();
+    assert_eq!(days.len(), 355);
+    days.dedup();
+    assert_eq!(days.len(), 355);
+}
+
+/// Compares sunset to the candle lighting times published by Hebcal, which are a fixed amount of
+/// minutes before sunset, to the minute. Hebcal uses the NOAA algorithm.
+fn check_sunset(algorithm: &str, city: &str, table: &str, minutes_before: i64, tolerance: i64) {
+    let published: HashMap<NaiveDate, i64> = table
+        .lines()
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            let (date, time) = x.split_once(" Candle lighting:").unwrap();
+            let date = NaiveDate::parse_from_str(date, "%m/%d/%Y").unwrap();
+            let (hour, minute) = time.trim().split_once(':').unwrap();
+            let minutes = (hour.parse::<i64>().unwrap() + 12) * 60 + minute.parse::<i64>().unwrap();
+            (date, minutes + minutes_before)
+        })
+        .collect();
+    let mut compared = 0;
+    for year in 2015..2025 {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print")
+            .arg("json")
+            .arg("zmanim")
+            .arg("--year")
+            .arg(year.to_string())
+            .arg("--city")
+            .arg(city)
+            .arg("--zmanim")
+            .arg("sunset")
+            .arg("--solar-algorithm")
+            .arg(algorithm);
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        let res: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+        for day in res {
+            let date =
+                NaiveDate::parse_from_str(day["date"].as_str().unwrap(), "%Y-%m-%d").unwrap();
+            let sunset = DateTime::parse_from_rfc3339(day["Sunset"].as_str().unwrap()).unwrap();
+            let sunset = (sunset.hour() * 60 + sunset.minute()) as i64;
+            match published.get(&date) {
+                // Candles lit after nightfall on the second night of Yom Tov aren't near sunset.
+                Some(published) if (sunset - published).abs() < 10 => {
+                    assert!(
+                        (sunset - published).abs() <= tolerance,
+                        "{} {} {}: {} != {}",
+                        algorithm,
+                        city,
+                        date,
+                        sunset,
+                        published
+                    );
+                    compared += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    assert!(compared > 500);
+}
+
+#[test]
+fn sunset_matches_published_tables() {
+    let new_york = include_str!("new_york_city_1945_100");
+    let jerusalem = include_str!("jerusalem_1945_100");
+    check_sunset("noaa", "NewYorkCity", new_york, 18, 1);
+    check_sunset("noaa", "Jerusalem", jerusalem, 40, 1);
+    // The almanac's algorithm is rougher.
+    check_sunset("usno", "NewYorkCity", new_york, 18, 2);
+    check_sunset("usno", "Jerusalem", jerusalem, 40, 2);
+}
+
+fn sunsets(args: &[&str]) -> String {
+    sunsets_with_config("/dev/null", args)
+}
+
+fn sunsets_with_config(config: &str, args: &[&str]) -> String {
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg(config)
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--zmanim")
+        .arg("sunset")
+        .args(args);
+    let output = cmd.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn zmanim_at_coordinates() {
+    let jerusalem = sunsets(&["--city", "Jerusalem"]);
+    // Jerusalem's time zone is that of the nearest built-in city.
+    assert_eq!(sunsets(&["--coordinates", "31.7683,35.2137"]), jerusalem);
+    assert_eq!(
+        sunsets(&["--coordinates", "31°46'6\"N 35°12'49\"E"]),
+        jerusalem
+    );
+    assert_eq!(
+        sunsets(&[
+            "--coordinates",
+            "31.7683 35.2137",
+            "--time-zone",
+            "Asia/Jerusalem"
+        ]),
+        jerusalem
+    );
+    // Southern latitudes and western longitudes start with a minus sign.
+    assert_eq!(
+        sunsets(&["--coordinates", "-33.8688,151.2093"]),
+        sunsets(&["--city", "Sydney"])
+    );
+    assert_eq!(
+        sunsets(&["--coordinates", "-34.6037,-58.3816"]),
+        sunsets(&["--city", "BuenosAires"])
+    );
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg("/dev/null")
+        .arg("list")
+        .arg("5785")
+        .arg("--show")
+        .arg("shabbos")
+        .arg("--coordinates")
+        .arg("-33.8688,151.2093");
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("Night of 2024/10/4: Haazinu. Candle lighting 17:43. Havdalah 18:42\n"));
+
+    for (args, error) in &[
+        (&["--coordinates", "31.7683"][..], "CannotParseCoordinates"),
+        (
+            &["--coordinates", "1,2", "--time-zone", "Middle/Earth"],
+            "InvalidTimeZone",
+        ),
+        (&["--address", "Atlantis"], "AddressNotFound"),
+    ] {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        cmd.arg("--print")
+            .arg("json")
+            .arg("zmanim")
+            .arg("--year")
+            .arg("2025")
+            .args(*args);
+        let output = cmd.output().unwrap();
+        assert!(!output.status.success());
+        assert!(String::from_utf8(output.stderr).unwrap().contains(error));
+    }
+}
+
+#[test]
+fn zmanim_at_address() {
+    // Each part of the address is looked up in the built-in cities in turn.
+    assert_eq!(
+        sunsets(&["--address", "Golders Green, london"]),
+        sunsets(&["--city", "London"])
+    );
+    assert_eq!(
+        sunsets(&["--address", "New York City, NY"]),
+        sunsets(&["--city", "NewYorkCity"])
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn zmanim_with_geocoder() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = std::env::temp_dir().join(format!("heca_geocoder_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let geocoder = dir.join("geocoder.sh");
+    std::fs::write(
+        &geocoder,
+        "#!/bin/sh\n[ \"$1\" = \"Kotel\" ] && printf '31.7767 35.2345\\nAsia/Jerusalem\\n'\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&geocoder, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let config = dir.join("config.toml");
+    std::fs::write(&config, format!("geocoder = {:?}\n", geocoder)).unwrap();
+    let config = config.to_str().unwrap();
+
+    assert_eq!(
+        sunsets_with_config(config, &["--address", "Kotel"]),
+        sunsets(&["--coordinates", "31.7767,35.2345"])
+    );
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    cmd.arg("--config")
+        .arg(config)
+        .arg("zmanim")
+        .arg("--year")
+        .arg("2025")
+        .arg("--address")
+        .arg("Jerusalem");
+    assert!(!cmd.output().unwrap().status.success());
+    std::fs::remove_dir_all(&dir).unwrap();
+}